@@ -0,0 +1,242 @@
+/* Generated by cbindgen; do not edit. */
+
+#ifndef TAGGY_H
+#define TAGGY_H
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+typedef struct TaggyTag TaggyTag;
+typedef struct TaggyFile TaggyFile;
+
+// The numeric fields of a tag which can be accessed through the C API.
+typedef enum TaggyNumberField {
+  TaggyNumberField_TrackNumber,
+  TaggyNumberField_TrackTotal,
+  TaggyNumberField_DiscNumber,
+  TaggyNumberField_DiscTotal,
+  TaggyNumberField_Year,
+  TaggyNumberField_Bpm,
+  TaggyNumberField_Rating,
+  TaggyNumberField_MovementNumber,
+  TaggyNumberField_MovementTotal,
+} TaggyNumberField;
+
+// The C counterpart of [TagType].
+typedef enum TaggyTagType {
+  TaggyTagType_Ape,
+  TaggyTagType_Id3v1,
+  TaggyTagType_Id3v2,
+  TaggyTagType_Mp4Ilst,
+  TaggyTagType_VorbisComments,
+  TaggyTagType_RiffInfo,
+  TaggyTagType_AiffText,
+  TaggyTagType_FilePrimaryType,
+  TaggyTagType_Other,
+} TaggyTagType;
+
+// The text fields of a tag which can be accessed through the C API.
+typedef enum TaggyTextField {
+  TaggyTextField_Title,
+  TaggyTextField_Artist,
+  TaggyTextField_Album,
+  TaggyTextField_AlbumArtist,
+  TaggyTextField_Producer,
+  TaggyTextField_RecordingDate,
+  TaggyTextField_OriginalReleaseDate,
+  TaggyTextField_Language,
+  TaggyTextField_Lyrics,
+  TaggyTextField_Genre,
+  TaggyTextField_Isrc,
+  TaggyTextField_Composer,
+  TaggyTextField_Conductor,
+  TaggyTextField_Remixer,
+  TaggyTextField_ArtistSort,
+  TaggyTextField_AlbumSort,
+  TaggyTextField_TitleSort,
+  TaggyTextField_AlbumArtistSort,
+  TaggyTextField_InitialKey,
+  TaggyTextField_Mood,
+  TaggyTextField_LyricsLanguage,
+  TaggyTextField_LyricsDescription,
+  TaggyTextField_MusicBrainzRecordingId,
+  TaggyTextField_MusicBrainzReleaseId,
+  TaggyTextField_MusicBrainzReleaseGroupId,
+  TaggyTextField_MusicBrainzArtistId,
+  TaggyTextField_MusicBrainzAlbumArtistId,
+  TaggyTextField_MusicBrainzTrackId,
+  TaggyTextField_AcoustidId,
+  TaggyTextField_AcoustidFingerprint,
+  TaggyTextField_Barcode,
+  TaggyTextField_CatalogNumber,
+  TaggyTextField_Label,
+  TaggyTextField_Copyright,
+  TaggyTextField_Grouping,
+  TaggyTextField_Subtitle,
+  TaggyTextField_Work,
+  TaggyTextField_Movement,
+  TaggyTextField_PodcastUrl,
+  TaggyTextField_EpisodeId,
+  TaggyTextField_Narrator,
+  TaggyTextField_Series,
+  TaggyTextField_SeriesPart,
+} TaggyTextField;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the message of the last error that happened on the calling thread, or `NULL`.
+//
+// The returned string is owned by taggy and stays valid until the next failing call.
+const char *taggy_last_error(void);
+
+// Returns the stable code of the last error that happened on the calling thread
+// (e.g. `file_not_found`, see [TaggyError::code]), or `NULL`.
+//
+// The returned string is owned by taggy and stays valid until the next failing call.
+const char *taggy_last_error_code(void);
+
+// Reads all the tags of the file at given `path`.
+//
+// Returns `NULL` on failure, see [taggy_last_error].
+//
+// # Safety
+// `path` must be a valid, nul-terminated UTF-8 string.
+TaggyFile *taggy_read_all(const char *path);
+
+// Reads only the primary tag of the file at given `path`.
+//
+// Returns `NULL` on failure, see [taggy_last_error].
+//
+// # Safety
+// `path` must be a valid, nul-terminated UTF-8 string.
+TaggyFile *taggy_read_primary(const char *path);
+
+// Reads any tag of the file at given `path`.
+//
+// Returns `NULL` on failure, see [taggy_last_error].
+//
+// # Safety
+// `path` must be a valid, nul-terminated UTF-8 string.
+TaggyFile *taggy_read_any(const char *path);
+
+// Releases a file returned by one of the `taggy_read_*` functions.
+//
+// # Safety
+// `file` must be `NULL` or a pointer returned by taggy which wasn't freed before.
+void taggy_file_free(TaggyFile *file);
+
+// Returns the number of tags the given `file` has.
+//
+// # Safety
+// `file` must be a valid pointer returned by taggy.
+size_t taggy_file_tags_count(const TaggyFile *file);
+
+// Returns the tag at `index` of the given `file`, or `NULL` if out of bounds.
+//
+// The returned tag is borrowed from `file` and must **not** be freed.
+//
+// # Safety
+// `file` must be a valid pointer returned by taggy.
+const TaggyTag *taggy_file_tag(const TaggyFile *file, size_t index);
+
+// Returns the duration in seconds of the given `file`, or `0` if unknown.
+//
+// # Safety
+// `file` must be a valid pointer returned by taggy.
+uint64_t taggy_file_duration_sec(const TaggyFile *file);
+
+// Returns the duration in milliseconds of the given `file`, or `0` if unknown.
+//
+// # Safety
+// `file` must be a valid pointer returned by taggy.
+uint64_t taggy_file_duration_ms(const TaggyFile *file);
+
+// Creates a new empty tag with the given `tag_type`.
+TaggyTag *taggy_tag_new(enum TaggyTagType tag_type);
+
+// Releases a tag created with [taggy_tag_new].
+//
+// # Safety
+// `tag` must be `NULL` or a pointer returned by [taggy_tag_new] which wasn't freed before.
+void taggy_tag_free(TaggyTag *tag);
+
+// Returns the type of the given `tag`.
+//
+// # Safety
+// `tag` must be a valid pointer returned by taggy.
+enum TaggyTagType taggy_tag_type(const TaggyTag *tag);
+
+// Returns a copy of the given text `field` of `tag`, or `NULL` if it isn't set.
+//
+// The returned string must be released with [taggy_string_free].
+//
+// # Safety
+// `tag` must be a valid pointer returned by taggy.
+char *taggy_tag_get_text(const TaggyTag *tag, enum TaggyTextField field);
+
+// Sets the given text `field` of `tag` to `value`, passing `NULL` clears the field.
+//
+// # Safety
+// `tag` must be a valid pointer returned by [taggy_tag_new] and `value` must be `NULL`
+// or a valid, nul-terminated UTF-8 string.
+void taggy_tag_set_text(TaggyTag *tag, enum TaggyTextField field, const char *value);
+
+// Writes the given numeric `field` of `tag` into `out`.
+//
+// Returns `false` if the field isn't set.
+//
+// # Safety
+// `tag` must be a valid pointer returned by taggy and `out` must be a valid pointer.
+bool taggy_tag_get_number(const TaggyTag *tag, enum TaggyNumberField field, uint32_t *out);
+
+// Sets the given numeric `field` of `tag` to `value`.
+//
+// # Safety
+// `tag` must be a valid pointer returned by [taggy_tag_new].
+void taggy_tag_set_number(TaggyTag *tag, enum TaggyNumberField field, uint32_t value);
+
+// Clears the given numeric `field` of `tag`.
+//
+// # Safety
+// `tag` must be a valid pointer returned by [taggy_tag_new].
+void taggy_tag_clear_number(TaggyTag *tag, enum TaggyNumberField field);
+
+// Writes `tag` as the primary tag of the file at given `path`.
+//
+// Returns `0` on success and `-1` on failure, see [taggy_last_error].
+//
+// # Safety
+// `path` must be a valid, nul-terminated UTF-8 string and `tag` a valid pointer returned by taggy.
+int32_t taggy_write_primary(const char *path, const TaggyTag *tag, bool keep_others);
+
+// Deletes all tags from the file at given `path`.
+//
+// Returns `0` on success and `-1` on failure, see [taggy_last_error].
+//
+// # Safety
+// `path` must be a valid, nul-terminated UTF-8 string.
+int32_t taggy_remove_all(const char *path);
+
+// Deletes the tag of the given `tag_type` from the file at given `path`.
+//
+// Returns `0` on success and `-1` on failure, see [taggy_last_error].
+//
+// # Safety
+// `path` must be a valid, nul-terminated UTF-8 string.
+int32_t taggy_remove_tag(const char *path, enum TaggyTagType tag_type);
+
+// Releases a string returned by taggy.
+//
+// # Safety
+// `value` must be `NULL` or a string returned by taggy which wasn't freed before.
+void taggy_string_free(char *value);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* TAGGY_H */
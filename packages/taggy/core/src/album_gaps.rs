@@ -0,0 +1,20 @@
+/// An album with missing or duplicated track numbers, see [find_gaps](crate::api::find_gaps).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct AlbumGaps {
+    /// The album name.
+    pub album: String,
+    /// The album artist, the files without one are grouped together.
+    pub album_artist: Option<String>,
+    /// The disc whose tracks are checked, `1` for the files without a `disc_number`.
+    pub disc_number: u32,
+    /// The expected number of tracks, the highest `track_total` of the files,
+    /// or their highest track number when none has a total.
+    pub track_total: u32,
+    /// The track numbers from `1` to the `track_total` no file has, sorted.
+    pub missing: Vec<u32>,
+    /// The track numbers more than one file has, sorted.
+    pub duplicated: Vec<u32>,
+    /// The paths of the files of the album disc, sorted.
+    pub files: Vec<String>,
+}
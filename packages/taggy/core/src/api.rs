@@ -0,0 +1,5058 @@
+#[cfg(not(target_arch = "wasm32"))]
+use crate::album_gaps::AlbumGaps;
+#[cfg(all(feature = "artwork", not(target_arch = "wasm32")))]
+use crate::artwork_optimization::{ArtworkOptions, ArtworkSavings};
+use crate::audio_info::AudioInfo;
+use crate::batch_result::BatchReadResult;
+use crate::chapter::Chapter;
+use crate::date_context::DateContext;
+use crate::disc_set::DiscSet;
+use crate::dj_markers::DjMarkers;
+use crate::effective_tag::EffectiveTag;
+use crate::error::TaggyError;
+use crate::featuring::{FeaturingChange, FeaturingStyle};
+use crate::field_class::FieldClass;
+use crate::field_value_count::FieldValueCount;
+use crate::item_key::ItemKey;
+use crate::limits::check_tag_sizes;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::lyrics_match::LyricsMatch;
+use crate::merge_suggestion::MergeSuggestion;
+#[cfg(feature = "artwork")]
+use crate::palette_color::PaletteColor;
+use crate::parse_config::ParseConfig;
+use crate::picture::{Picture, PictureChanges, PictureOrder, PictureType};
+use crate::raw_frame::RawFrame;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::recovery_report::RecoveryReport;
+use crate::replay_gain::ReplayGain;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::scan_result::ScanResult;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::scanner::{ScanOptions, SymlinkPolicy};
+use crate::synced_lyrics::SyncedLyrics;
+use crate::tag::{Tag, TagType};
+use crate::taggy_file::TaggyFile;
+use crate::utils::album_art::{largest_cover, set_cover};
+use crate::utils::artwork_rules::apply_artwork_rules;
+use crate::utils::buffer::{get_tagged_file_from_bytes, write_through_temp_file};
+use crate::utils::chapters::read_chapters_from;
+use crate::utils::date_added::date_added_from;
+use crate::utils::dates::normalize_dates;
+use crate::utils::duplicates::resolve_duplicates;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::featuring::relocate_featuring;
+use crate::utils::file_id::{generate_uuid, probe_file_type};
+use crate::utils::fuzzy::group_near_duplicates;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::histogram::count_field_values;
+use crate::utils::id3v2_frames::{
+    completed_id3v2, is_id3v2_unchanged, read_id3v2_frames_from, save_tags_with, Id3v2Frames,
+};
+use crate::utils::integrity::check_not_truncated;
+use crate::utils::isrc::{isrc_write_warning, warn_invalid_isrcs};
+use crate::utils::lofty_froms::*;
+use crate::utils::lofty_intos::get_pic_from_data;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::lyrics_search::match_lyrics;
+use crate::utils::merge::merge_tags;
+use crate::utils::opus_head::{read_output_gain, write_output_gain};
+use crate::utils::picture_blocks::{pictures_with_information, vorbis_comments_with_information};
+use crate::utils::picture_data::{restore_picture_data, strip_picture_data};
+use crate::utils::picture_meta;
+use crate::utils::picture_order::sort_pictures;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::pool::map_parallel;
+use crate::utils::protection::protect_existing_fields;
+use crate::utils::read_only::{open_read_only, probe_with_extension};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::release_year::year_from_path;
+use crate::utils::replay_gain::{db_to_q78, insert_replay_gain, q78_to_db, replay_gain_from};
+use crate::utils::sidecar;
+use crate::utils::snapshot::Snapshot;
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::staging::{commit_all, discard_all, StagedFile, TransactionDir};
+#[cfg(not(target_arch = "wasm32"))]
+use crate::utils::track_gaps::find_track_gaps;
+use crate::utils::truncation::truncate_fields;
+use crate::work_link::WorkLink;
+use crate::write_options::{LintProfile, UnsupportedTagPolicy, WriteOptions, WriteProfile};
+use crate::write_report::{TagWriteAction, TagWriteResult, WriteReport};
+use crate::write_request::WriteRequest;
+use crate::year_suggestion::YearSuggestion;
+use anyhow::anyhow;
+use lofty::{BoundTaggedFile, ItemValue, ParseOptions, TagItem, TaggedFile, TaggedFileExt};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Cursor, Read, Seek};
+use std::path::Path;
+
+/// Read all audio tags from the file at given `path`.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file is empty or truncated, so it can be told apart from a parsing failure
+pub fn read_all(path: String) -> anyhow::Result<TaggyFile> {
+    read_all_with_config(path, ParseConfig::default())
+}
+
+/// Same as [read_all], parsing the file with the given `config`.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file can't be parsed with the `config`
+pub fn read_all_with_config(path: String, config: ParseConfig) -> anyhow::Result<TaggyFile> {
+    let (mut reader, tagged) = open_tagged_file(&path, config)?;
+    let file = taggy_from_tagged(&tagged, &path);
+    Ok(complete_read(file, &tagged, &mut reader, config))
+}
+
+/// Read only the primary audio tag from the file at given `path`.
+///
+/// **Note**: If the primary tag does not exist,
+/// this will return a [TaggyFile] with no tags.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_primary(path: String) -> anyhow::Result<TaggyFile> {
+    read_primary_with_config(path, ParseConfig::default())
+}
+
+/// Same as [read_primary], parsing the file with the given `config`.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file can't be parsed with the `config`
+pub fn read_primary_with_config(path: String, config: ParseConfig) -> anyhow::Result<TaggyFile> {
+    let (mut reader, tagged) = open_tagged_file(&path, config)?;
+
+    let file = TaggyFile {
+        tags: get_primary_tag_from_tagged_file(&tagged),
+        ..taggy_from_tagged(&tagged, &path)
+    };
+    Ok(complete_read(file, &tagged, &mut reader, config))
+}
+
+/// Read any audio tag from the file at the given `path`.
+///
+/// **Note**: If the file has no tags,
+/// this will return a [TaggyFile] with an empty tags.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_any(path: String) -> anyhow::Result<TaggyFile> {
+    read_any_with_config(path, ParseConfig::default())
+}
+
+/// Same as [read_any], parsing the file with the given `config`.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file can't be parsed with the `config`
+pub fn read_any_with_config(path: String, config: ParseConfig) -> anyhow::Result<TaggyFile> {
+    let (mut reader, tagged) = open_tagged_file(&path, config)?;
+
+    let file = TaggyFile {
+        tags: get_any_tag_from_tagged_file(&tagged),
+        ..taggy_from_tagged(&tagged, &path)
+    };
+    Ok(complete_read(file, &tagged, &mut reader, config))
+}
+
+/// Read all audio tags from each file of the given `paths`, in a single call.
+///
+/// The files are read in parallel, and a file failing to be read doesn't fail the others,
+/// its [BatchReadResult] carries the error instead. The results are in the same order as the `paths`.
+///
+/// `concurrency` is the number of files read at the same time, `0` for one per CPU core.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_all_batch(paths: Vec<String>, concurrency: u32) -> Vec<BatchReadResult> {
+    map_parallel(&paths, concurrency, |path| {
+        BatchReadResult::new(path.clone(), read_all(path.clone()))
+    })
+}
+
+/// Read all audio tags from the audio files in the directory at given `dir` path,
+/// and in its subfolders when `recursive` is set.
+///
+/// Only the files with one of the given `extensions` (e.g. `mp3`, `flac`) are read,
+/// or the files of any supported format when `extensions` is empty.
+/// A file failing to be read doesn't fail the scan, its [BatchReadResult] carries the error instead.
+/// The hidden files and the artifacts of the operating systems, e.g. the `__MACOSX` folders,
+/// are skipped, see [scan_directory_with_options].
+///
+/// `concurrency` is the number of files read at the same time, `0` for one per CPU core.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    crate::scanner::scan_directory(&dir, recursive, &extensions, concurrency)
+}
+
+/// Same as [scan_directory], with all the [ScanOptions]: e.g. following the symbolic links,
+/// or reading the hidden and system files, which are skipped by default.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory_with_options(
+    dir: String,
+    options: ScanOptions,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    crate::scanner::scan_directory_with_options(&dir, &options)
+}
+
+/// Same as [scan_directory], but the symbolic links (and the junctions on Windows) are handled
+/// according to `symlinks`: skipped, followed with each file read once, or followed with each
+/// path read as a distinct entry.
+///
+/// When the links are followed, the links to one of their own parent folders are skipped so
+/// cycles don't hang the scan. The [TaggyFile]s `canonical_path` tells where a linked file actually is.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory_with_symlinks(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+    symlinks: SymlinkPolicy,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    crate::scanner::scan_directory_with_symlinks(
+        &dir,
+        recursive,
+        &extensions,
+        concurrency,
+        symlinks,
+    )
+}
+
+/// Same as [scan_directory], but the files which can't be read, e.g. corrupt or truncated ones,
+/// are collected into a separate quarantine list with the reason of each failure, so they can
+/// be reviewed afterwards. The subfolders which can't be listed are quarantined too.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory_with_quarantine(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+) -> anyhow::Result<ScanResult> {
+    crate::scanner::scan_directory_with_quarantine(&dir, recursive, &extensions, concurrency)
+}
+
+/// Counts the distinct values of the given `field` across the audio files in the directory
+/// at given `dir` path and its subfolders, e.g. for autocompletion or for spotting the
+/// different spellings of a genre.
+///
+/// The `field` is named after the [Tag] property, e.g. `genre` or `album_artist`, and each
+/// file counts with its effective value, see [read_effective]. The files which can't be read
+/// are skipped. The values are sorted by their count, the most common first.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+/// - the field doesn't exist
+#[cfg(not(target_arch = "wasm32"))]
+pub fn field_values(dir: String, field: String) -> anyhow::Result<Vec<FieldValueCount>> {
+    let tags: Vec<Tag> = scan_directory(dir, true, vec![], 0)?
+        .into_iter()
+        .filter_map(|result| result.file)
+        .map(|file| merge_tags(&file.tags, file.primary_tag_type).tag)
+        .collect();
+    count_field_values(&tags, &field)
+}
+
+/// Groups the near-duplicate `values` of a field, e.g. `Hip Hop`, `Hip-Hop` and `hiphop`,
+/// as returned by [field_values].
+///
+/// Case, spacing and punctuation are ignored, and values of at least 5 letters may also
+/// differ by one typo for every 5 letters. Each [MergeSuggestion] keeps the most common value
+/// of its group as the canonical one, so its `variants` can be replaced with it in a batch,
+/// e.g. with [write_transaction]. Only the groups with more than one value are returned.
+pub fn suggest_merges(values: Vec<FieldValueCount>) -> Vec<MergeSuggestion> {
+    group_near_duplicates(&values)
+}
+
+/// Finds the audio files in the directory at given `dir` path and its subfolders whose
+/// lyrics contain the `query`, e.g. to find a song from a line of it.
+///
+/// The case is ignored and any whitespace matches a line break. The lyrics are the effective
+/// ones of each file, see [read_effective], so the `USLT` frames and the `LYRICS` fields.
+/// The files which can't be read are skipped. The matches are sorted by path.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+/// - the query is blank
+#[cfg(not(target_arch = "wasm32"))]
+pub fn search_lyrics(dir: String, query: String) -> anyhow::Result<Vec<LyricsMatch>> {
+    if query.trim().is_empty() {
+        return Err(TaggyError::invalid_value("The lyrics search query is blank!").into());
+    }
+    let mut matches: Vec<LyricsMatch> = scan_directory(dir, true, vec![], 0)?
+        .into_iter()
+        .filter_map(|result| {
+            let file = result.file?;
+            let lyrics = merge_tags(&file.tags, file.primary_tag_type).tag.lyrics?;
+            match_lyrics(&result.path, &lyrics, &query)
+        })
+        .collect();
+    matches.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(matches)
+}
+
+/// Finds the albums in the directory at given `dir` path and its subfolders with missing
+/// or duplicated track numbers, e.g. to spot the incomplete albums of a library.
+///
+/// The files are grouped by album artist, album and disc, and each disc is expected to hold
+/// the tracks from `1` to its `track_total`, or to its highest track number when it has none.
+/// The tags are the effective ones of each file, see [read_effective]. The files which can't
+/// be read, or have no album or track number, are skipped.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn find_gaps(dir: String) -> anyhow::Result<Vec<AlbumGaps>> {
+    let files = scan_directory(dir, true, vec![], 0)?
+        .into_iter()
+        .filter_map(|result| {
+            let file = result.file?;
+            Some((
+                result.path,
+                merge_tags(&file.tags, file.primary_tag_type).tag,
+            ))
+        })
+        .collect();
+    Ok(find_track_gaps(files))
+}
+
+/// Read all audio tags from the file at the given `path`, merged into a single [EffectiveTag].
+///
+/// Each field takes its value from the primary tag when it has one, otherwise from the first
+/// other tag having it. The [EffectiveTag] `sources` tell which tag type each value came from,
+/// e.g. to show where conflicting values originate.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_effective(path: String) -> anyhow::Result<EffectiveTag> {
+    let file = read_all(path)?;
+    Ok(merge_tags(&file.tags, file.primary_tag_type))
+}
+
+/// Read all audio tags from the file at given `path`, overlaid with the tags of its sidecar
+/// (e.g. `track.flac.taggy.json`) if it has one.
+///
+/// A sidecar tag replaces the embedded tag of the same type.
+/// The sidecars are written instead of read-only files when writing with `sidecar_fallback`.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the sidecar is malformed
+pub fn read_all_with_sidecar(path: String) -> anyhow::Result<TaggyFile> {
+    let file = read_all(path.clone())?;
+    let sidecar_tags = sidecar::read_sidecar_tags(&path)?;
+    Ok(sidecar::overlay(file, sidecar_tags))
+}
+
+/// Read all audio tags from the given audio file `bytes`.
+///
+/// This doesn't require access to the file system, which makes it
+/// the reading path to use on the web (`wasm` feature).
+///
+/// Throws an **exception** when:
+/// - the file type can't be detected from the given bytes
+pub fn read_all_from_buffer(bytes: Vec<u8>) -> anyhow::Result<TaggyFile> {
+    let tagged = get_tagged_file_from_bytes(&bytes)?;
+    let file = taggy_from_tagged_with_size(&tagged, Some(bytes.len() as u64));
+    Ok(complete_read(
+        file,
+        &tagged,
+        &mut Cursor::new(&bytes),
+        ParseConfig::default(),
+    ))
+}
+
+/// Read only the primary audio tag from the given audio file `bytes`.
+///
+/// **Note**: If the primary tag does not exist,
+/// this will return a [TaggyFile] with no tags.
+///
+/// Throws an **exception** when:
+/// - the file type can't be detected from the given bytes
+pub fn read_primary_from_buffer(bytes: Vec<u8>) -> anyhow::Result<TaggyFile> {
+    let tagged = get_tagged_file_from_bytes(&bytes)?;
+
+    let file = TaggyFile {
+        tags: get_primary_tag_from_tagged_file(&tagged),
+        ..taggy_from_tagged_with_size(&tagged, Some(bytes.len() as u64))
+    };
+    Ok(complete_read(
+        file,
+        &tagged,
+        &mut Cursor::new(&bytes),
+        ParseConfig::default(),
+    ))
+}
+
+/// Read any audio tag from the given audio file `bytes`.
+///
+/// **Note**: If the file has no tags,
+/// this will return a [TaggyFile] with an empty tags.
+///
+/// Throws an **exception** when:
+/// - the file type can't be detected from the given bytes
+pub fn read_any_from_buffer(bytes: Vec<u8>) -> anyhow::Result<TaggyFile> {
+    let tagged = get_tagged_file_from_bytes(&bytes)?;
+
+    let file = TaggyFile {
+        tags: get_any_tag_from_tagged_file(&tagged),
+        ..taggy_from_tagged_with_size(&tagged, Some(bytes.len() as u64))
+    };
+    Ok(complete_read(
+        file,
+        &tagged,
+        &mut Cursor::new(&bytes),
+        ParseConfig::default(),
+    ))
+}
+
+/// Completes the `file` read by lofty from the `reader` of the audio file, for the path and the
+/// buffer reads alike: its chapters, the ID3v2 frames lofty doesn't keep, the duplicated fields
+/// and the invalid ISRC codes, then the parts of the `config` lofty doesn't handle.
+fn complete_read<R: Read + Seek>(
+    mut file: TaggyFile,
+    tagged: &TaggedFile,
+    reader: &mut R,
+    config: ParseConfig,
+) -> TaggyFile {
+    let file_type = tagged.file_type();
+    file.chapters = read_chapters_from(reader, file_type).unwrap_or_default();
+    read_id3v2_frames_from(&mut file.tags, reader, file_type);
+    resolve_duplicates(&mut file, tagged, reader, config.duplicate_policy);
+    warn_invalid_isrcs(&mut file);
+    file.date_added_ms = date_added_from(&file.tags);
+    apply_parse_config(file, config)
+}
+
+/// Applies the parts of the `config` lofty doesn't handle to the read `file`.
+fn apply_parse_config(mut file: TaggyFile, config: ParseConfig) -> TaggyFile {
+    if !config.read_picture_data {
+        strip_picture_data(&mut file.tags);
+    }
+    if let Some(context) = &config.date_context {
+        file.tags
+            .iter_mut()
+            .for_each(|tag| normalize_dates(tag, context));
+    }
+    file
+}
+
+/// A helper function to get a [`TaggedFile`] from the given path, parsed with the `config`.
+/// the returned file will be used for reading properties only.
+fn get_tagged_file(path: &str, config: ParseConfig) -> anyhow::Result<TaggedFile> {
+    Ok(open_tagged_file(path, config)?.1)
+}
+
+/// Same as [get_tagged_file], along with the opened file, for the reads going on with it.
+fn open_tagged_file(path: &str, config: ParseConfig) -> anyhow::Result<(File, TaggedFile)> {
+    check_not_truncated(path)?;
+    let mut file = open_read_only(path).map_err(|_| TaggyError::FileNotFound)?;
+    let tagged = probe_with_extension(BufReader::new(&mut file), Path::new(path))
+        .options(config.into())
+        .read()
+        .map_err(|e| anyhow!(e))?;
+    Ok((file, tagged))
+}
+
+/// Read the pictures of all the tags of the file at given `path`, the primary tag ones first,
+/// without the rest of the tags.
+///
+/// The same picture stored in several tags is only returned once.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_pictures(path: String) -> anyhow::Result<Vec<Picture>> {
+    let tagged = get_tagged_file(&path, ParseConfig::default())?;
+    Ok(get_pictures_from_tagged_file(&tagged))
+}
+
+/// Read the front cover of the file at given `path`, searching all of its tags,
+/// the primary one first.
+///
+/// When no picture is marked as the front cover, the first picture is returned instead,
+/// since some taggers don't set the picture type. Returns `None` when the file has no pictures.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_front_cover(path: String) -> anyhow::Result<Option<Picture>> {
+    read_primary_picture(path, PictureOrder::FrontCoverFirst)
+}
+
+/// Same as [read_pictures], with the pictures sorted in the given `order`.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_pictures_with_order(path: String, order: PictureOrder) -> anyhow::Result<Vec<Picture>> {
+    let mut pictures = read_pictures(path)?;
+    sort_pictures(&mut pictures, order);
+    Ok(pictures)
+}
+
+/// Read the first picture of the file at given `path` in the given `order`, e.g. the one
+/// to show as its cover. Returns `None` when the file has no pictures.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_primary_picture(path: String, order: PictureOrder) -> anyhow::Result<Option<Picture>> {
+    Ok(read_pictures_with_order(path, order)?.into_iter().next())
+}
+
+/// Writes the data of the first `picture_type` picture of the file at given `path`
+/// to the file at `out_path` as is, e.g. for filling a thumbnails cache without copying
+/// the picture through the bridge.
+///
+/// All the tags are searched, the primary one first. Returns `false` when the file
+/// has no picture of this type, in which case `out_path` isn't created.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - writing to `out_path` fails
+pub fn export_picture(
+    path: String,
+    out_path: String,
+    picture_type: PictureType,
+) -> anyhow::Result<bool> {
+    let config = ParseConfig {
+        read_properties: false,
+        ..Default::default()
+    };
+    let tagged = get_tagged_file(&path, config)?;
+    let picture_type: lofty::PictureType = picture_type.into();
+    let picture = tags_primary_first(&tagged)
+        .into_iter()
+        .flat_map(|t| t.pictures())
+        .find(|p| p.pic_type() == picture_type);
+    match picture {
+        Some(picture) => {
+            std::fs::write(&out_path, picture.data())?;
+            Ok(true)
+        }
+        None => Ok(false),
+    }
+}
+
+/// Read the value of the field with the given `key`, e.g. [ItemKey::Composer], from
+/// the file at given `path`, including the fields [Tag] doesn't model.
+///
+/// All the tags are searched, the primary one first. Returns `None` when no tag
+/// has a text value for this key.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_field(path: String, key: ItemKey) -> anyhow::Result<Option<String>> {
+    let config = ParseConfig {
+        read_properties: false,
+        ..Default::default()
+    };
+    let tagged = get_tagged_file(&path, config)?;
+    let key = key.to_lofty();
+    let value = tags_primary_first(&tagged)
+        .into_iter()
+        .flat_map(|t| t.get_items(&key))
+        .find_map(|item| match item.value() {
+            ItemValue::Text(text) | ItemValue::Locator(text) => Some(text.clone()),
+            ItemValue::Binary(_) => None,
+        });
+    Ok(value)
+}
+
+/// The type of the primary tag of the file at given `path`, e.g. [TagType::VorbisComments]
+/// for a FLAC file, which is the type to give the tags written with [write_primary].
+///
+/// Only the file header is read to tell its format.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file type is not supported
+pub fn read_primary_tag_type(path: String) -> anyhow::Result<TagType> {
+    Ok(probe_file_type(&path)?.primary_tag_type().into())
+}
+
+/// Read only the duration of the file at given `path` in **microseconds**.
+///
+/// This is much faster than [read_all] since only the audio headers are read
+/// (Xing/VBRI for MPEG, STREAMINFO for FLAC and mvhd for MP4) which makes it suitable
+/// for calculating the total duration of thousands of files. The headers are chosen by the
+/// detected file type, the other formats are read with their audio properties.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_duration(path: String) -> anyhow::Result<u64> {
+    crate::utils::duration::read_duration_micros(&path)
+}
+
+/// Read only the audio properties (duration, bitrate, sample rate, channels...)
+/// of the file at given `path`, without its tags.
+///
+/// This is faster than [read_all] and uses less memory, since the tags and especially their
+/// embedded pictures aren't parsed, which makes it suitable for a first pass over a library.
+/// For MPEG and FLAC files the tags are skipped entirely, other formats are read as usual.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file type is not supported
+pub fn read_properties(path: String) -> anyhow::Result<AudioInfo> {
+    crate::utils::properties::read_audio_info(&path)
+}
+
+/// Write all provided `tags` to the file at given `path`.
+///
+/// when `override_existent` is set to `true`, this will remove all existing tags.
+/// Otherwise, it will add or update any existing ones.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn write_all(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> anyhow::Result<TaggyFile> {
+    write_all_with_options(path, tags, override_existent, WriteOptions::default())
+}
+
+/// Write all provided `tags` to the given audio file `bytes`, and return the tagged bytes.
+///
+/// when `override_existent` is set to `true`, this will remove all existing tags.
+/// Otherwise, it will add or update any existing ones.
+///
+/// **Note**: the bytes are written to a short-lived file in the system temp directory while
+/// tagging, so unlike [read_all_from_buffer] this isn't available on the web.
+///
+/// Throws an **exception** when:
+/// - the file type can't be detected from the given bytes
+pub fn write_all_to_buffer(
+    bytes: Vec<u8>,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> anyhow::Result<Vec<u8>> {
+    write_through_temp_file(&bytes, |path| {
+        write_all(path, tags, override_existent).map(|_| ())
+    })
+}
+
+/// Same as [write_all], with the extra write `options`.
+///
+/// The issues which didn't prevent the write, e.g. a cut value, are added to the `warnings` of
+/// the returned file, see [write_all_with_report].
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - `options.protect_existing` is set and the `tags` would overwrite existing fields,
+///   unless `options.skip_protected` or `options.force` is set
+/// - a tag type isn't supported by the file and `options.unsupported_tag_policy` is `Error`
+/// - a tag would be larger than its limit in `options.max_tag_sizes`
+pub fn write_all_with_options(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+    options: WriteOptions,
+) -> anyhow::Result<TaggyFile> {
+    let report = write_all_with_report(path, tags, override_existent, options)?;
+    let mut file = report.file;
+    file.warnings.extend(report.warnings);
+    Ok(file)
+}
+
+/// The [WriteOptions] of the given device `profile`, to pass to [write_all_with_options]
+/// or [write_primary_with_options]. They can be adjusted before writing.
+pub fn write_options_for_profile(profile: WriteProfile) -> WriteOptions {
+    profile.options()
+}
+
+/// Same as [write_all_with_options], but returns a [WriteReport] telling which of
+/// the `tags` were inserted, replaced, skipped or converted, along with the issues
+/// which didn't prevent the write.
+///
+/// When the tags to write are the same as the existing ones, the file isn't saved
+/// at all and the report is `unchanged`.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - `options.protect_existing` is set and the `tags` would overwrite existing fields,
+///   unless `options.skip_protected` or `options.force` is set
+/// - a tag type isn't supported by the file and `options.unsupported_tag_policy` is `Error`
+/// - a tag would be larger than its limit in `options.max_tag_sizes`
+///
+/// When `options.sidecar_fallback` is set and the file is read-only,
+/// the tags are written to its sidecar instead, see [read_all_with_sidecar].
+pub fn write_all_with_report(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+    options: WriteOptions,
+) -> anyhow::Result<WriteReport> {
+    if options.sidecar_fallback && sidecar::is_read_only(&path) {
+        let tags = resolve_primary_tag_type(tags, probe_file_type(&path)?);
+        return write_all_to_sidecar(path, tags, override_existent, options);
+    }
+
+    let mut tagged_file = get_bound_tagged_file(&path)?;
+    let tags_before = tagged_file.tags().to_vec();
+    let tags: Vec<Tag> = resolve_primary_tag_type(tags, tagged_file.file_type())
+        .into_iter()
+        .map(|t| restore_picture_data(t, &tags_before))
+        .collect();
+
+    let tags = if options.protects_existing() {
+        let existing = taggy_from_bound_tagged(&tagged_file, &path).tags;
+        tags.into_iter()
+            .map(|t| protect_existing_fields(&existing, t, &options))
+            .collect::<anyhow::Result<Vec<_>>>()?
+    } else {
+        tags
+    };
+
+    if override_existent {
+        tagged_file.clear();
+    } else if options.clear_pictures {
+        remove_all_pictures(&mut tagged_file);
+    }
+
+    // add tags to file
+    let pictures = pictures_with_information(
+        tagged_file.file_type(),
+        tags.iter().flat_map(|t| &t.pictures),
+    );
+    let id3v2_frames = Id3v2Frames::to_write(&path, &tags)?;
+    let (tag_results, warnings) = insert_tags(&mut tagged_file, tags, &options)?;
+    check_tag_sizes(tagged_file.tags(), &options.max_tag_sizes)?;
+
+    let id3v2 = completed_id3v2(&tagged_file, &id3v2_frames)?;
+    let vorbis_comments = vorbis_comments_with_information(&path, &tagged_file, &pictures)?;
+    let unchanged = tags_unchanged(&tagged_file, &tags_before)
+        && id3v2
+            .as_ref()
+            .is_none_or(|tag| is_id3v2_unchanged(&path, tag))
+        && vorbis_comments.is_none();
+    if !unchanged {
+        save_tags_with(&mut tagged_file, &path, id3v2, vorbis_comments)?;
+    }
+
+    Ok(WriteReport {
+        file: taggy_from_bound_tagged(&tagged_file, &path),
+        tags: tag_results,
+        warnings,
+        unchanged,
+    })
+}
+
+/// The `tags` with the [TagType::FilePrimaryType] replaced by the primary tag type of the
+/// `file_type`.
+fn resolve_primary_tag_type(tags: Vec<Tag>, file_type: lofty::FileType) -> Vec<Tag> {
+    let primary_tag_type = TagType::from(file_type.primary_tag_type());
+    tags.into_iter()
+        .map(|tag| match tag.tag_type {
+            TagType::FilePrimaryType => Tag {
+                tag_type: primary_tag_type,
+                ..tag
+            },
+            _ => tag,
+        })
+        .collect()
+}
+
+/// Saves the `file` unless its tags are the same as `tags_before`,
+/// so no-op writes don't touch the file on disk.
+///
+/// Returns `true` when the file was left unchanged.
+fn save_if_changed(file: &mut BoundTaggedFile, tags_before: &[lofty::Tag]) -> anyhow::Result<bool> {
+    let unchanged = tags_unchanged(file, tags_before);
+    if !unchanged {
+        file.save().map_err(TaggyError::save_failed)?;
+    }
+    Ok(unchanged)
+}
+
+/// Whether the tags of the `file` are the same as `tags_before`.
+fn tags_unchanged(file: &BoundTaggedFile, tags_before: &[lofty::Tag]) -> bool {
+    let tags_after = file.tags();
+    tags_after.len() == tags_before.len()
+        && tags_before
+            .iter()
+            .all(|before| tags_after.iter().any(|after| same_tag(before, after)))
+}
+
+/// Whether the tags `a` and `b` have the same type, items and pictures,
+/// as lofty's tags can't be compared directly.
+fn same_tag(a: &lofty::Tag, b: &lofty::Tag) -> bool {
+    a.tag_type() == b.tag_type() && a.items().eq(b.items()) && a.pictures() == b.pictures()
+}
+
+/// Writes the `tags` to the sidecar of the read-only file at `path`.
+fn write_all_to_sidecar(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+    options: WriteOptions,
+) -> anyhow::Result<WriteReport> {
+    let tags = if options.protects_existing() {
+        let existing = read_all_with_sidecar(path.clone())?.tags;
+        tags.into_iter()
+            .map(|t| protect_existing_fields(&existing, t, &options))
+            .collect::<anyhow::Result<Vec<_>>>()?
+    } else {
+        tags
+    };
+    let tag_results = sidecar::write_sidecar_tags(&path, tags, override_existent)?;
+    Ok(WriteReport {
+        file: read_all_with_sidecar(path.clone())?,
+        tags: tag_results,
+        warnings: vec![format!(
+            "The file is read-only, the tags were written to {}",
+            sidecar::sidecar_path(&path).display()
+        )],
+        unchanged: false,
+    })
+}
+
+/// Inserts the given `tags` into the `file`, applying the `policy` to the tags
+/// whose type isn't supported by the file.
+///
+/// Returns what happened to each of the `tags`, and the warnings about the tags
+/// which were skipped or converted.
+fn insert_tags(
+    file: &mut BoundTaggedFile,
+    tags: Vec<Tag>,
+    options: &WriteOptions,
+) -> anyhow::Result<(Vec<TagWriteResult>, Vec<String>)> {
+    let primary_tag_type = TagType::from(file.primary_tag_type());
+    let mut supported: Vec<Tag> = vec![];
+    let mut converted: Vec<Tag> = vec![];
+    let mut results = vec![];
+    let mut warnings = vec![];
+    for tag in tags {
+        let lofty_tag_type = tag.tag_type.into();
+        if file.supports_tag_type(lofty_tag_type) {
+            let action = match file.tag(lofty_tag_type) {
+                Some(_) => TagWriteAction::Replaced,
+                None => TagWriteAction::Inserted,
+            };
+            results.push(TagWriteResult::new(
+                tag.tag_type,
+                Some(tag.tag_type),
+                action,
+            ));
+            supported.push(tag);
+            continue;
+        }
+        let message = format!(
+            "The tag type '{:?}' is not supported for the file type '{:?}'",
+            tag.tag_type,
+            file.file_type()
+        );
+        match options.unsupported_tag_policy {
+            UnsupportedTagPolicy::Skip => {
+                results.push(TagWriteResult::new(
+                    tag.tag_type,
+                    None,
+                    TagWriteAction::Skipped,
+                ));
+                warnings.push(message);
+            }
+            UnsupportedTagPolicy::Error => {
+                return Err(TaggyError::UnsupportedTagType {
+                    message: format!("{message}!"),
+                }
+                .into())
+            }
+            UnsupportedTagPolicy::ConvertToSupported => {
+                results.push(TagWriteResult::new(
+                    tag.tag_type,
+                    Some(primary_tag_type),
+                    TagWriteAction::Converted,
+                ));
+                warnings.push(format!(
+                    "{message}, it was converted to '{primary_tag_type:?}'"
+                ));
+                converted.push(tag);
+            }
+        }
+    }
+
+    if !converted.is_empty() {
+        // the primary tag written along, if any, takes precedence over the converted ones.
+        let (mut primary, others): (Vec<Tag>, Vec<Tag>) = supported
+            .into_iter()
+            .partition(|t| t.tag_type == primary_tag_type);
+        primary.extend(converted);
+        let merged = merge_tags(&primary, primary_tag_type).tag;
+        supported = others;
+        supported.push(merged);
+    }
+
+    if options.sync_id3v1
+        && file.supports_tag_type(lofty::TagType::Id3v1)
+        && !supported.iter().any(|t| t.tag_type == TagType::Id3v1)
+    {
+        if let Some(primary) = supported.iter().find(|t| t.tag_type == primary_tag_type) {
+            supported.push(Tag {
+                tag_type: TagType::Id3v1,
+                pictures: vec![],
+                custom_items: vec![],
+                ..primary.clone()
+            });
+        }
+    }
+
+    for mut tag in supported {
+        if let Some(context) = &options.date_context {
+            normalize_dates(&mut tag, context);
+        }
+        let (tag, artwork_warnings) = apply_artwork_rules(tag, options);
+        warnings.extend(artwork_warnings);
+        let (tag, truncation_warnings) = truncate_fields(tag, options.truncation);
+        warnings.extend(truncation_warnings);
+        warnings.extend(crate::utils::lint::lint_tag(&tag, options.lint_profile));
+        warnings.extend(isrc_write_warning(&tag));
+        file.insert_tag(tag.to_lofty());
+    }
+    Ok((results, warnings))
+}
+
+/// Removes the pictures of all the tags of the given `file`.
+fn remove_all_pictures(file: &mut BoundTaggedFile) {
+    remove_pictures_of_type(file, None);
+}
+
+/// Removes the pictures of the given `picture_type` from all the tags of the given `file`,
+/// all of them when `None`.
+fn remove_pictures_of_type(file: &mut BoundTaggedFile, picture_type: Option<PictureType>) {
+    let tag_types: Vec<lofty::TagType> = file.tags().iter().map(|t| t.tag_type()).collect();
+    for tag_type in tag_types {
+        if let Some(tag) = file.tag_mut(tag_type) {
+            match picture_type {
+                Some(picture_type) => tag.remove_picture_type(picture_type.into()),
+                None => {
+                    while !tag.pictures().is_empty() {
+                        tag.remove_picture(0);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Write the provided `tag` as the primary tag for the file at given `path`.
+///
+/// If `keep_others` is set to `false`, this will remove any existing tags from the file.
+///
+/// **Note**: the `tag_type` of the give tag will be overridden with the file primary tag type,
+/// so you can set it to any or use [TagType.FilePrimaryType].
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn write_primary(path: String, tag: Tag, keep_others: bool) -> anyhow::Result<TaggyFile> {
+    write_primary_with_options(path, tag, keep_others, WriteOptions::default())
+}
+
+/// Same as [write_primary], with the extra write `options`, applied as with
+/// [write_all_with_options].
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - `options.protect_existing` is set and the `tag` would overwrite existing fields,
+///   unless `options.skip_protected` or `options.force` is set
+/// - the tag would be larger than its limit in `options.max_tag_sizes`
+pub fn write_primary_with_options(
+    path: String,
+    tag: Tag,
+    keep_others: bool,
+    options: WriteOptions,
+) -> anyhow::Result<TaggyFile> {
+    let tag = Tag {
+        tag_type: TagType::FilePrimaryType,
+        ..tag
+    };
+    write_all_with_options(path, vec![tag], !keep_others, options)
+}
+
+/// Write the given `picture` as the `picture_type` picture of the primary tag of the file
+/// at given `path`, replacing the existing pictures of this type.
+///
+/// All the other fields and pictures of the file are left intact, including the ones
+/// taggy doesn't model, so there's no need to read and write back the whole tag.
+/// The primary tag is created when the file doesn't have one.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn write_picture(
+    path: String,
+    picture: Picture,
+    picture_type: PictureType,
+) -> anyhow::Result<TaggyFile> {
+    let mut tagged_file = get_bound_tagged_file(&path)?;
+    let tags_before = tagged_file.tags().to_vec();
+
+    let picture = Picture {
+        pic_type: picture_type,
+        ..picture
+    };
+    let pictures = pictures_with_information(tagged_file.file_type(), [&picture]);
+
+    let tag_type = tagged_file.primary_tag_type();
+    if tagged_file.primary_tag().is_none() {
+        tagged_file.insert_tag(lofty::Tag::new(tag_type));
+    }
+    if let Some(tag) = tagged_file.primary_tag_mut() {
+        tag.remove_picture_type(picture_type.into());
+        tag.push_picture(get_pic_from_data(&picture));
+    }
+    let vorbis_comments = vorbis_comments_with_information(&path, &tagged_file, &pictures)?;
+    if vorbis_comments.is_some() || !tags_unchanged(&tagged_file, &tags_before) {
+        save_tags_with(&mut tagged_file, &path, None, vorbis_comments)?;
+    }
+
+    Ok(taggy_from_bound_tagged(&tagged_file, &path))
+}
+
+/// Reads the image file at given `image_path` as a `picture_type` picture, e.g. to pass it
+/// to [write_all] along with other changes.
+///
+/// The mimetype, width, height and color depth are detected from the image data,
+/// so there's no need to fill them manually.
+///
+/// Throws an **exception** when:
+/// - image_path doesn't exists
+/// - the file isn't a PNG, JPEG, GIF, BMP or TIFF image
+pub fn picture_from_path(image_path: String, picture_type: PictureType) -> anyhow::Result<Picture> {
+    Picture::from_path(&image_path, picture_type)
+}
+
+/// Same as [write_picture], but the picture is read from the image file at given `image_path`,
+/// see [picture_from_path].
+///
+/// Throws an **exception** when:
+/// - path or image_path doesn't exists
+/// - the image file isn't a PNG, JPEG, GIF, BMP or TIFF image
+pub fn write_picture_from_path(
+    path: String,
+    image_path: String,
+    picture_type: PictureType,
+) -> anyhow::Result<TaggyFile> {
+    let picture = Picture::from_path(&image_path, picture_type)?;
+    write_picture(path, picture, picture_type)
+}
+
+/// Removes the pictures of the given `picture_type` from all the tags of the file
+/// at given `path`, or all of its pictures when `None`, then saves the file.
+///
+/// The other fields of the tags are left intact.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn remove_pictures(
+    path: String,
+    picture_type: Option<PictureType>,
+) -> anyhow::Result<TaggyFile> {
+    let mut tagged_file = get_bound_tagged_file(&path)?;
+    let tags_before = tagged_file.tags().to_vec();
+    remove_pictures_of_type(&mut tagged_file, picture_type);
+    save_if_changed(&mut tagged_file, &tags_before)?;
+    Ok(taggy_from_bound_tagged(&tagged_file, &path))
+}
+
+/// Applies the `changes` to the type, description or mimetype of the picture at `index`
+/// in the pictures of the file at given `path`, as returned by [read_pictures], without
+/// sending its data again, e.g. to mark as the front cover a picture written as `Other`.
+///
+/// The picture is changed in all the tags storing it, the other fields and pictures
+/// of the file are left intact.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file has no picture at `index`
+pub fn update_picture_meta(
+    path: String,
+    index: u32,
+    changes: PictureChanges,
+) -> anyhow::Result<TaggyFile> {
+    let mut tagged_file = get_bound_tagged_file(&path)?;
+    let tags_before = tagged_file.tags().to_vec();
+    picture_meta::update_picture_meta(&mut tagged_file, index as usize, &changes)?;
+    save_if_changed(&mut tagged_file, &tags_before)?;
+    Ok(taggy_from_bound_tagged(&tagged_file, &path))
+}
+
+/// Write the `value` of the field with the given `key` to the primary tag of the file
+/// at given `path`, or remove the field when `None`, e.g. for updating only the rating.
+///
+/// All the other fields and pictures of the file are left intact, so there's no need
+/// to read and write back the whole tag. The primary tag is created when the file
+/// doesn't have one.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the primary tag type doesn't support the `key`
+pub fn write_field(path: String, key: ItemKey, value: Option<String>) -> anyhow::Result<TaggyFile> {
+    let mut tagged_file = get_bound_tagged_file(&path)?;
+    let tags_before = tagged_file.tags().to_vec();
+
+    let tag_type = tagged_file.primary_tag_type();
+    if tagged_file.primary_tag().is_none() {
+        tagged_file.insert_tag(lofty::Tag::new(tag_type));
+    }
+    if let Some(tag) = tagged_file.primary_tag_mut() {
+        let lofty_key = key.to_lofty();
+        match value {
+            Some(value) => {
+                let value = if key.is_url() {
+                    ItemValue::Locator(value)
+                } else {
+                    ItemValue::Text(value)
+                };
+                if !tag.insert(TagItem::new(lofty_key, value)) {
+                    return Err(TaggyError::UnsupportedTagType {
+                        message: format!("The {key:?} field isn't supported by {tag_type:?}!"),
+                    }
+                    .into());
+                }
+            }
+            None => tag.remove_key(&lofty_key),
+        }
+    }
+    save_if_changed(&mut tagged_file, &tags_before)?;
+
+    Ok(taggy_from_bound_tagged(&tagged_file, &path))
+}
+
+/// Read the loudness normalization gains and peaks of the file at given `path`.
+///
+/// The gains are in dB relative to the ReplayGain reference of -18 LUFS: the `R128_TRACK_GAIN`
+/// and `R128_ALBUM_GAIN` comments of the Opus files, relative to -23 LUFS, are converted, and
+/// their header output gain is read as well. The other formats use the `REPLAYGAIN_*` fields.
+/// Each value is taken from the primary tag first.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the header of an Opus file is invalid
+pub fn read_replay_gain(path: String) -> anyhow::Result<ReplayGain> {
+    let config = ParseConfig {
+        read_properties: false,
+        ..Default::default()
+    };
+    let tagged = get_tagged_file(&path, config)?;
+    let is_opus = tagged.file_type() == lofty::FileType::Opus;
+    let gain = tags_primary_first(&tagged)
+        .into_iter()
+        .map(|tag| replay_gain_from(tag, is_opus))
+        .fold(ReplayGain::default(), |gain, other| ReplayGain {
+            track_gain_db: gain.track_gain_db.or(other.track_gain_db),
+            track_peak: gain.track_peak.or(other.track_peak),
+            album_gain_db: gain.album_gain_db.or(other.album_gain_db),
+            album_peak: gain.album_peak.or(other.album_peak),
+            opus_output_gain_db: None,
+        });
+    let opus_output_gain_db = if is_opus {
+        Some(q78_to_db(read_output_gain(&path)?))
+    } else {
+        None
+    };
+    Ok(ReplayGain {
+        opus_output_gain_db,
+        ..gain
+    })
+}
+
+/// Write the loudness normalization `gain` to the primary tag of the file at given `path`,
+/// see [read_replay_gain]. The gains and peaks which are `None` are removed.
+///
+/// The Opus files get `R128_*` comments, converted to their -23 LUFS reference, and no
+/// `REPLAYGAIN_*` field as required by RFC 7845, so their peaks aren't written. Their header
+/// output gain is written too unless `None`, it's ignored for the other formats.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the header of an Opus file is invalid
+pub fn write_replay_gain(path: String, gain: ReplayGain) -> anyhow::Result<()> {
+    let mut tagged_file = get_bound_tagged_file(&path)?;
+    let tags_before = tagged_file.tags().to_vec();
+    let is_opus = tagged_file.file_type() == lofty::FileType::Opus;
+
+    let tag_type = tagged_file.primary_tag_type();
+    if tagged_file.primary_tag().is_none() {
+        tagged_file.insert_tag(lofty::Tag::new(tag_type));
+    }
+    if let Some(tag) = tagged_file.primary_tag_mut() {
+        insert_replay_gain(tag, &gain, is_opus);
+    }
+    save_if_changed(&mut tagged_file, &tags_before)?;
+
+    if let (true, Some(output_gain_db)) = (is_opus, gain.opus_output_gain_db) {
+        write_output_gain(&path, db_to_q78(output_gain_db))?;
+    }
+    Ok(())
+}
+
+/// Applies all the write `requests`, or none of them.
+///
+/// The tags are first written to copies of the files, which replace the originals
+/// only when every write succeeded, so an album-wide edit can't be half-applied.
+/// A transaction interrupted by a crash is recovered with [recover].
+///
+/// Returns the written files in the same order as `requests`.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - any path doesn't exists
+/// - writing to any file fails, leaving all files unchanged
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_transaction(requests: Vec<WriteRequest>) -> anyhow::Result<Vec<TaggyFile>> {
+    write_staged(requests, |path, _| StagedFile::stage(path))
+}
+
+/// Same as [write_transaction], but the copies of the files are created in the folder
+/// at given `temp_dir` path instead of next to the files, e.g. the app cache folder for
+/// the Android scoped storage locations which don't allow creating other files.
+///
+/// The copies are then copied over the files, so the folder may be on another storage.
+/// A transaction interrupted by a crash is recovered by calling [recover] with `temp_dir`.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - temp_dir doesn't exists
+/// - any path doesn't exists
+/// - writing to any file fails, leaving all files unchanged
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_transaction_with_temp_dir(
+    requests: Vec<WriteRequest>,
+    temp_dir: String,
+) -> anyhow::Result<Vec<TaggyFile>> {
+    let paths: Vec<String> = requests.iter().map(|r| r.path.clone()).collect();
+    let transaction = TransactionDir::create(Path::new(&temp_dir), &paths)?;
+    let result = write_staged(requests, |path, index| transaction.stage(path, index));
+    transaction.close();
+    result
+}
+
+/// Writes the `requests` to the copies created by `stage` from each path and index,
+/// then commits them all.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_staged(
+    requests: Vec<WriteRequest>,
+    mut stage: impl FnMut(&str, usize) -> anyhow::Result<StagedFile>,
+) -> anyhow::Result<Vec<TaggyFile>> {
+    let mut staged_files = Vec::with_capacity(requests.len());
+    let mut written = Vec::with_capacity(requests.len());
+    for (index, request) in requests.into_iter().enumerate() {
+        let result = stage(&request.path, index).and_then(|staged| {
+            let path = staged.staged_path();
+            staged_files.push(staged);
+            write_all(path, request.tags, request.override_existent)
+        });
+        match result {
+            Ok(file) => written.push(file),
+            Err(e) => {
+                discard_all(&staged_files);
+                return Err(TaggyError::save_failed(format!(
+                    "Failed to write to {}: {}",
+                    request.path, e
+                ))
+                .into());
+            }
+        }
+    }
+    commit_all(&staged_files)?;
+    Ok(written)
+}
+
+/// Recovers the [write_transaction]s interrupted in the folder at given `dir` path,
+/// e.g. because the app was killed while committing them, and removes their leftover
+/// `.taggy-*` copies of the files.
+///
+/// Each interrupted transaction is completed when all its files were already replaced,
+/// and rolled back otherwise, including its files in other folders. The subfolders aren't
+/// recovered. Call it on startup, before writing to the folder, and with the `temp_dir`
+/// given to [write_transaction_with_temp_dir], if any.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn recover(dir: String) -> anyhow::Result<RecoveryReport> {
+    crate::utils::staging::recover(Path::new(&dir))
+}
+
+/// The [DateContext] of the given `locale`, e.g. `en-US` or `fr_FR.UTF-8`, with the user's
+/// `utc_offset_minutes`, to pass in the [ParseConfig] or [WriteOptions] `date_context`.
+///
+/// The numeric dates are read month first for the locales of the United States and a few
+/// other regions, day first otherwise.
+pub fn date_context_for_locale(locale: String, utc_offset_minutes: i32) -> DateContext {
+    DateContext::for_locale(&locale, utc_offset_minutes)
+}
+
+/// Validates the given `isrc` and returns its normalized 12 characters form,
+/// e.g. `us-rc1-76-07839` becomes `USRC17607839`.
+///
+/// The written ISRCs are normalized the same way, invalid ones are written as is
+/// with a warning.
+///
+/// Throws an **exception** when:
+/// - the code doesn't follow the `CC-XXX-YY-NNNNN` structure
+pub fn normalize_isrc(isrc: String) -> anyhow::Result<String> {
+    crate::utils::isrc::normalize_isrc(&isrc)
+}
+
+/// Generates a new random UUID, suitable for [write_file_id].
+pub fn generate_file_id() -> String {
+    generate_uuid()
+}
+
+/// Read the unique identifier stored by the application `scope` in the file at given `path`,
+/// `None` if the file has no identifier for this `scope`.
+///
+/// Storing an identifier in the file itself lets library databases keep track of files
+/// across renames and moves.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_file_id(path: String, scope: String) -> anyhow::Result<Option<String>> {
+    crate::utils::file_id::read_file_id(&path, &scope)
+}
+
+/// Write the unique identifier `id` of the application `scope` to the file at given `path`,
+/// replacing any previous identifier of this `scope`.
+///
+/// The `scope` should be unique to the application, e.g. `com.example.player`.
+/// It's stored in a `TXXX` frame for ID3v2 tags, in a freeform atom for MP4 files
+/// and in a `scope` named item otherwise.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn write_file_id(path: String, scope: String, id: String) -> anyhow::Result<()> {
+    crate::utils::file_id::write_file_id(&path, &scope, &id)
+}
+
+/// Returns the identifier of the application `scope` of the file at given `path`,
+/// after writing a newly generated one if the file doesn't have any.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn ensure_file_id(path: String, scope: String) -> anyhow::Result<String> {
+    if let Some(id) = read_file_id(path.clone(), scope.clone())? {
+        return Ok(id);
+    }
+    let id = generate_file_id();
+    write_file_id(path, scope, id.clone())?;
+    Ok(id)
+}
+
+/// Read the synchronized lyrics of the file at given `path`, i.e. the lyrics with the time
+/// each line is sung at, `None` if the file has none.
+///
+/// They're read from the ID3v2 `SYLT` frame, the frames timed in MPEG frames are skipped.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_synced_lyrics(path: String) -> anyhow::Result<Option<SyncedLyrics>> {
+    crate::utils::sylt::read_synced_lyrics(&path)
+}
+
+/// Write the synchronized `lyrics` to the file at given `path` as an ID3v2 `SYLT` frame,
+/// replacing the existing ones. Lyrics without lines remove them.
+///
+/// They're kept when the tags of the file are written afterwards.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file primary tag isn't an ID3v2 tag, e.g. for FLAC files
+/// - the language isn't a 3 letters ISO 639-2 code, e.g. `eng`
+pub fn write_synced_lyrics(path: String, lyrics: SyncedLyrics) -> anyhow::Result<()> {
+    crate::utils::sylt::write_synced_lyrics(&path, &lyrics)
+}
+
+/// Parse the `lrc` text, made of `[mm:ss.xx]` prefixed lines, to [SyncedLyrics],
+/// e.g. to import a `.lrc` file before writing it with [write_synced_lyrics].
+///
+/// The `[offset:...]` tag is applied to the timestamps, the other tags are ignored.
+///
+/// Throws an **exception** when:
+/// - the text has no timed line
+pub fn parse_lrc(lrc: String) -> anyhow::Result<SyncedLyrics> {
+    crate::utils::lrc::parse_lrc(&lrc)
+}
+
+/// Format the `lyrics` as LRC text, e.g. to export them to a `.lrc` file.
+pub fn to_lrc(lyrics: SyncedLyrics) -> String {
+    crate::utils::lrc::format_lrc(&lyrics)
+}
+
+/// Read the chapters of the file at given `path`, sorted by start time, e.g. of an audiobook
+/// or a podcast episode, none if the file has none.
+///
+/// They're read from the ID3v2 `CHAP` frames, and from the chapter track of the MP4 files,
+/// or else from their Nero `chpl` atom.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_chapters(path: String) -> anyhow::Result<Vec<Chapter>> {
+    crate::utils::chapters::read_chapters(&path)
+}
+
+/// Write the `chapters` to the file at given `path`, replacing the existing ones.
+/// No chapters remove them.
+///
+/// They're written as ID3v2 `CHAP` frames listed by a `CTOC` frame, kept when the tags of the
+/// file are written afterwards, and as a Nero `chpl` atom in MP4 files, which only keeps the
+/// start time and title of each chapter.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file isn't an MPEG, AIFF, WAV or MP4 file
+/// - a chapter ends before it starts
+/// - there's more than 255 chapters
+pub fn write_chapters(path: String, chapters: Vec<Chapter>) -> anyhow::Result<()> {
+    crate::utils::chapters::write_chapters(&path, &chapters)
+}
+
+/// Read the frames of the ID3v2 tag of the file at given `path` as they're stored, e.g. the
+/// `GEOB`, `PRIV` or proprietary frames [Tag] doesn't model, none if the file has no ID3v2 tag.
+///
+/// Unlike the other reads, the frames having the same ID are all returned, in their order.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_raw_frames(path: String) -> anyhow::Result<Vec<RawFrame>> {
+    Ok(crate::utils::raw_id3v2::read_raw_frames(&path)?.unwrap_or_default())
+}
+
+/// Write the `frame` to the ID3v2 tag of the file at given `path`, e.g. to store a binary blob
+/// in a `PRIV` frame, see [read_raw_frames].
+///
+/// It's added to the frames having the same ID, unless one of them holds the same data.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the file isn't an MPEG, AIFF or WAV file
+/// - the ID isn't a 4 characters ID3v2.4 one, e.g. `PRIV`
+/// - the flags tell the data is grouped, compressed, encrypted or unsynchronized
+pub fn write_raw_frame(path: String, frame: RawFrame) -> anyhow::Result<()> {
+    crate::utils::raw_id3v2::write_raw_frame(&path, &frame)
+}
+
+/// Reads the bytes of the tag of given `tag_type` of the file at given `path` exactly as
+/// they're stored, e.g. to copy a tag to another file with [inject_raw_tag].
+///
+/// Only the ID3v2, ID3v1 and APE tags are stored as a single block of the file, the
+/// [TagType::FilePrimaryType] is the ID3v2 one for the MPEG files.
+///
+/// Returns empty bytes when the file has no such tag.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the tag type isn't an ID3v2, ID3v1 or APE one, or the file can't store it
+pub fn dump_raw_tag(path: String, tag_type: TagType) -> anyhow::Result<Vec<u8>> {
+    crate::utils::raw_tag::dump_raw_tag(&path, tag_type)
+}
+
+/// Writes the given `bytes` as is to the file at given `path`, in place of its tag of the same
+/// type, see [dump_raw_tag].
+///
+/// This is an expert operation: the tag isn't modelled, only its size is checked, and the
+/// file is only replaced once its new tag is read back.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the bytes aren't a whole ID3v2, ID3v1 or APE tag
+/// - the file can't store a tag of this type
+/// - the file with the new tag can't be read back
+pub fn inject_raw_tag(path: String, bytes: Vec<u8>) -> anyhow::Result<()> {
+    crate::utils::raw_tag::inject_raw_tag(&path, &bytes)
+}
+
+/// Reads the hot cues, saved loops and track color Serato DJ stored in the ID3v2 tag of the file
+/// at given `path`.
+///
+/// Returns `None` when the file has no Serato DJ markers.
+///
+/// The frames of Serato DJ and Traktor are kept as they are when the tags are written.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the markers can't be decoded
+pub fn read_dj_markers(path: String) -> anyhow::Result<Option<DjMarkers>> {
+    crate::utils::dj::read_dj_markers(&path)
+}
+
+/// Finds the audio files of the album in the directory at given `dir` path, grouped by disc.
+///
+/// The files of the direct subfolders are included too, and the disc of each file comes
+/// from its `disc_number` tag, or from its folder name when named like `Disc 2` or `CD2`.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+pub fn detect_disc_set(dir: String) -> anyhow::Result<DiscSet> {
+    crate::utils::discs::detect_disc_set(&dir)
+}
+
+/// Writes consistent `disc_number`s and `disc_total`s to all the files of the album
+/// in the directory at given `dir` path, see [detect_disc_set].
+///
+/// When `fold_into_track_number` is set and the album has multiple discs, the disc number
+/// is also folded into the track numbers (track 5 of disc 2 becomes 205), so players that
+/// ignore disc numbers still sort the tracks in order.
+///
+/// Returns the detected [DiscSet].
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+/// - writing to any of the files fails
+pub fn fix_disc_numbers(dir: String, fold_into_track_number: bool) -> anyhow::Result<DiscSet> {
+    let disc_set = detect_disc_set(dir)?;
+    let disc_total = disc_set.disc_total();
+    let fold = fold_into_track_number && disc_set.is_multi_disc();
+    for disc in &disc_set.discs {
+        for path in &disc.files {
+            let old_tag = read_primary(path.clone())?
+                .first_tag()
+                .unwrap_or_else(|| Tag::new(TagType::FilePrimaryType));
+            let mut tag = Tag {
+                disc_number: Some(disc.number),
+                disc_total: Some(disc_total),
+                ..old_tag.clone()
+            };
+            if fold {
+                // numbers above 99 are already folded.
+                tag.track_number =
+                    tag.track_number
+                        .map(|n| if n < 100 { disc.number * 100 + n } else { n });
+            }
+            if tag != old_tag {
+                write_primary(path.clone(), tag, true)?;
+            }
+        }
+    }
+    Ok(disc_set)
+}
+
+/// Embeds the same front cover into all the files of the album in the directory at given
+/// `dir` path, see [detect_disc_set].
+///
+/// The cover is the given `source` picture, or when `None`, the largest front cover already
+/// embedded in any of the files (the largest picture of any type when there's no front cover).
+/// The other front covers of each file are replaced, and its other pictures are kept.
+///
+/// Returns the paths of the files which changed, the files already having the cover
+/// are left untouched. Nothing changes when there's no `source` and no embedded picture.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+/// - writing to any of the files fails
+pub fn unify_album_art(dir: String, source: Option<Picture>) -> anyhow::Result<Vec<String>> {
+    let paths: Vec<String> = detect_disc_set(dir)?
+        .discs
+        .into_iter()
+        .flat_map(|disc| disc.files)
+        .collect();
+    let mut tags = Vec::with_capacity(paths.len());
+    for path in &paths {
+        let tag = read_primary(path.clone())?
+            .first_tag()
+            .unwrap_or_else(|| Tag::new(TagType::FilePrimaryType));
+        tags.push(tag);
+    }
+    let cover = match source {
+        Some(source) => Picture {
+            pic_type: PictureType::CoverFront,
+            ..source
+        },
+        None => match largest_cover(&tags) {
+            Some(cover) => cover,
+            None => return Ok(vec![]),
+        },
+    };
+
+    let mut changed = vec![];
+    for (path, mut tag) in paths.into_iter().zip(tags) {
+        if set_cover(&mut tag, &cover) {
+            write_primary(path.clone(), tag, true)?;
+            changed.push(path);
+        }
+    }
+    Ok(changed)
+}
+
+/// Looks for the release year of the audio files missing one in the directory at given
+/// `dir` path and its subfolders, from their folder names like `Artist - Album (1997)`,
+/// then from their file names.
+///
+/// Nothing is written, the suggestions are meant to be reviewed first, then the accepted
+/// ones applied with [apply_years]. The files which can't be read are skipped.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn suggest_years(dir: String) -> anyhow::Result<Vec<YearSuggestion>> {
+    let mut suggestions: Vec<YearSuggestion> = scan_directory(dir, true, vec![], 0)?
+        .into_iter()
+        .filter_map(|result| {
+            let file = result.file?;
+            if merge_tags(&file.tags, file.primary_tag_type)
+                .tag
+                .year
+                .is_some()
+            {
+                return None;
+            }
+            let (year, source) = year_from_path(Path::new(&result.path))?;
+            Some(YearSuggestion {
+                path: result.path,
+                year,
+                source,
+            })
+        })
+        .collect();
+    suggestions.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(suggestions)
+}
+
+/// Writes the year of each of the given `suggestions` to the primary tag of its file,
+/// see [suggest_years].
+///
+/// The files are written with [write_transaction], so either all of them or none are.
+///
+/// Throws an **exception** when:
+/// - any of the paths doesn't exists
+/// - writing to any of the files fails, leaving all files unchanged
+pub fn apply_years(suggestions: Vec<YearSuggestion>) -> anyhow::Result<Vec<TaggyFile>> {
+    let mut requests = Vec::with_capacity(suggestions.len());
+    for suggestion in suggestions {
+        let file = read_primary(suggestion.path.clone())?;
+        let primary_tag_type = file.primary_tag_type;
+        let tag = file
+            .first_tag()
+            .unwrap_or_else(|| Tag::new(primary_tag_type));
+        requests.push(WriteRequest {
+            path: suggestion.path,
+            tags: vec![Tag {
+                year: Some(suggestion.year),
+                ..tag
+            }],
+            override_existent: false,
+        });
+    }
+    write_transaction(requests)
+}
+
+/// Looks for the audio files in the directory at given `dir` path and its subfolders whose
+/// featured artists aren't credited according to the `style`, e.g. `Song` by `A feat. X`
+/// for [FeaturingStyle::InTitle], since libraries mixing both break the search by artist.
+///
+/// The featured artists are introduced by `feat.`, `ft.` or `featuring`, with or without
+/// brackets. Nothing is written, the changes are meant to be previewed first, then the
+/// accepted ones applied with [apply_featuring]. The files which can't be read are skipped.
+///
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - dir doesn't exists
+#[cfg(not(target_arch = "wasm32"))]
+pub fn suggest_featuring(
+    dir: String,
+    style: FeaturingStyle,
+) -> anyhow::Result<Vec<FeaturingChange>> {
+    let mut changes: Vec<FeaturingChange> = scan_directory(dir, true, vec![], 0)?
+        .into_iter()
+        .filter_map(|result| {
+            let file = result.file?;
+            let tag = merge_tags(&file.tags, file.primary_tag_type).tag;
+            let (title, artist) = (tag.track_title?, tag.track_artist?);
+            let (track_title, track_artist) = relocate_featuring(&title, &artist, style)?;
+            Some(FeaturingChange {
+                path: result.path,
+                track_title,
+                track_artist,
+                previous_track_title: title,
+                previous_track_artist: artist,
+            })
+        })
+        .collect();
+    changes.sort_by(|a, b| a.path.cmp(&b.path));
+    Ok(changes)
+}
+
+/// Writes the title and artist of each of the given `changes` to the primary tag of its file,
+/// see [suggest_featuring].
+///
+/// The files are written with [write_transaction], so either all of them or none are.
+///
+/// Throws an **exception** when:
+/// - any of the paths doesn't exists
+/// - writing to any of the files fails, leaving all files unchanged
+pub fn apply_featuring(changes: Vec<FeaturingChange>) -> anyhow::Result<Vec<TaggyFile>> {
+    let mut requests = Vec::with_capacity(changes.len());
+    for change in changes {
+        let file = read_primary(change.path.clone())?;
+        let primary_tag_type = file.primary_tag_type;
+        let tag = file
+            .first_tag()
+            .unwrap_or_else(|| Tag::new(primary_tag_type));
+        requests.push(WriteRequest {
+            path: change.path,
+            tags: vec![Tag {
+                track_title: Some(change.track_title),
+                track_artist: Some(change.track_artist),
+                ..tag
+            }],
+            override_existent: false,
+        });
+    }
+    write_transaction(requests)
+}
+
+/// Marks the files at given `paths` as the consecutive movements of the continuous `work`,
+/// e.g. a symphony or a live set, so players can group them and play them without gaps.
+///
+/// The files are numbered in the order of the `paths`, and flagged for gapless playback.
+/// When `show_movement` is set, players should show the work and movement instead of the track title.
+///
+/// Throws an **exception** when:
+/// - any of the paths doesn't exists, in which case none of the files is written
+/// - writing to any of the files fails
+pub fn link_continuous_work(
+    paths: Vec<String>,
+    work: String,
+    show_movement: bool,
+) -> anyhow::Result<()> {
+    if paths.iter().any(|path| !Path::new(path).is_file()) {
+        return Err(TaggyError::FileNotFound.into());
+    }
+    let movement_total = paths.len() as u32;
+    for (index, path) in paths.iter().enumerate() {
+        let link = WorkLink {
+            work: work.clone(),
+            movement_number: Some(index as u32 + 1),
+            movement_total: Some(movement_total),
+            gapless: true,
+            show_movement,
+        };
+        crate::utils::work::write_work_link(path, &link)?;
+    }
+    Ok(())
+}
+
+/// Read the place of the file at given `path` in a continuous work,
+/// see [link_continuous_work].
+///
+/// Returns `None` when the file doesn't belong to a work.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn read_work_link(path: String) -> anyhow::Result<Option<WorkLink>> {
+    crate::utils::work::read_work_link(&path)
+}
+
+/// Checks the text fields of the given `tag` against the rules of the `profile`,
+/// e.g. before writing it.
+///
+/// Returns a warning for each issue, an empty list when the tag is fine.
+pub fn lint_tag(tag: Tag, profile: LintProfile) -> Vec<String> {
+    crate::utils::lint::lint_tag(&tag, profile)
+}
+
+/// Take a snapshot of the complete metadata of the file at given `path`, as JSON.
+///
+/// Unlike [read_all], the snapshot keeps every item of every tag, including the ones
+/// taggy doesn't model, so it can be used for lossless metadata backups with [restore].
+/// The pictures are stored once by the hash of their data.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn snapshot(path: String) -> anyhow::Result<String> {
+    let tagged = get_tagged_file(&path, ParseConfig::default())?;
+    Snapshot::from_tags(tagged.tags()).to_json()
+}
+
+/// Replace all the tags of the file at given `path` with the ones of the given `snapshot`,
+/// taken with [snapshot].
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the snapshot is malformed or of a newer version
+pub fn restore(path: String, snapshot: String) -> anyhow::Result<TaggyFile> {
+    // parse the whole snapshot first, so a malformed one leaves the file untouched.
+    let tags = Snapshot::from_json(&snapshot)?.to_tags()?;
+    remove_all(path.clone())?;
+    let mut tagged_file = get_bound_tagged_file(&path)?;
+    for tag in tags {
+        tagged_file.insert_tag(tag);
+    }
+    tagged_file.save().map_err(TaggyError::save_failed)?;
+    Ok(taggy_from_bound_tagged(&tagged_file, &path))
+}
+
+/// Delete all tags from file at given `path`.
+///
+/// The tags are stripped from the file entirely, so other tools
+/// won't see any leftover tag structure either.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn remove_all(path: String) -> anyhow::Result<()> {
+    let tagged = get_tagged_file(&path, ParseConfig::default())?;
+    let mut file = open_for_writing(&path)?;
+    for tag in tagged.tags() {
+        strip_tag(&mut file, tag.tag_type())
+            .map_err(|_| TaggyError::save_failed("Failed to remove file tags"))?;
+    }
+    Ok(())
+}
+
+/// Deletes the `tag` with `TagType` equals to `tag_type` from file at the given `path`.
+///
+/// If the file doesn't have any tag with the given `tag_type`,
+/// **no** errors will be returned.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+pub fn remove_tag(path: String, tag_type: TagType) -> anyhow::Result<()> {
+    let tagged = get_tagged_file(&path, ParseConfig::default())?;
+    let lofty_tag_type = tag_type.into();
+    if tagged.tag(lofty_tag_type).is_none() {
+        // There's no need for touching the file cuz it wouldn't be changed
+        // since the tag doesn't exist
+        return Ok(());
+    }
+    let mut file = open_for_writing(&path)?;
+    strip_tag(&mut file, lofty_tag_type)
+}
+
+/// Converts the tag of the given `from` tag type of the file at given `path` to a tag of the
+/// given `to` tag type, e.g. to move a legacy library from APEv2 to ID3v2 tags.
+///
+/// The fields lofty can map to the `to` tag type are written to it, including the pictures and
+/// custom items, over the fields of the existing `to` tag, if any. When `strip_source` is set to
+/// `true`, the `from` tag is removed afterwards.
+///
+/// Converting an ID3v2 tag to ID3v2 writes it again as ID3v2.4, keeping all its frames,
+/// e.g. to upgrade the ID3v2.3 tags.
+///
+/// If the file doesn't have any tag with the given `from` tag type, it's left untouched.
+///
+/// Returns the file as it is afterwards.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the `to` tag type isn't supported by the file type
+pub fn convert_tag(
+    path: String,
+    from: TagType,
+    to: TagType,
+    strip_source: bool,
+) -> anyhow::Result<TaggyFile> {
+    let file = read_all(path.clone())?;
+    let resolve = |tag_type| match tag_type {
+        TagType::FilePrimaryType => file.primary_tag_type,
+        tag_type => tag_type,
+    };
+    let (from, to) = (resolve(from), resolve(to));
+    let Some(source) = file.tags.iter().find(|t| t.tag_type == from) else {
+        return Ok(file);
+    };
+    if from == to {
+        if from == TagType::Id3v2 {
+            crate::utils::raw_id3v2::rewrite_id3v2(&path)?;
+        }
+        return read_all(path);
+    }
+
+    let converted = Tag {
+        tag_type: to,
+        ..source.clone()
+    };
+    let tag = match file.tags.iter().find(|t| t.tag_type == to) {
+        // the converted fields take precedence.
+        Some(existing) => merge_tags(&[converted, existing.clone()], to).tag,
+        None => converted,
+    };
+    let options = WriteOptions {
+        unsupported_tag_policy: UnsupportedTagPolicy::Error,
+        ..WriteOptions::default()
+    };
+    let converted_file = write_all_with_options(path.clone(), vec![tag], false, options)?;
+    if !strip_source {
+        return Ok(converted_file);
+    }
+    remove_tag(path.clone(), from)?;
+    read_all(path)
+}
+
+/// Removes the tag of the given `tag_type` from the `file` on disk.
+///
+/// Unlike removing the tag from a [BoundTaggedFile] and saving it, which leaves the
+/// tag on disk untouched, this strips the tag data from the file.
+fn strip_tag(file: &mut File, tag_type: lofty::TagType) -> anyhow::Result<()> {
+    file.rewind()?;
+    tag_type.remove_from(file).map_err(|e| anyhow!(e))
+}
+
+/// Removes the fields of the given `classes` from all the tags of the files at given `paths`,
+/// e.g. the comments, lyrics and private data before sharing a library, and returns the files
+/// as they are afterwards.
+///
+/// The other fields are left intact, and the files without such fields aren't written.
+///
+/// Throws an **exception** when:
+/// - any of the paths doesn't exists, before any file is changed
+pub fn strip_fields(
+    paths: Vec<String>,
+    classes: Vec<FieldClass>,
+) -> anyhow::Result<Vec<TaggyFile>> {
+    if paths.iter().any(|path| !Path::new(path).is_file()) {
+        return Err(TaggyError::FileNotFound.into());
+    }
+    let mut files = Vec::with_capacity(paths.len());
+    for path in paths {
+        crate::utils::strip_fields::strip_fields(&path, &classes)?;
+        files.push(read_all(path)?);
+    }
+    Ok(files)
+}
+
+/// Computes the waveform of the audio track of the file at given `path`.
+///
+/// Returns `buckets` peak values normalized to the `0.0..=1.0` range, suitable for
+/// drawing waveforms or seek bars.
+///
+/// **Note**: this decodes the whole audio stream, so it's much slower than reading tags.
+/// Only available with the `decode` feature.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the audio track can't be decoded
+#[cfg(feature = "decode")]
+pub fn compute_waveform(path: String, buckets: u32) -> anyhow::Result<Vec<f32>> {
+    crate::waveform::compute_peaks(&path, buckets as usize)
+}
+
+/// Computes the `k` dominant colors of the cover of the file at given `path`, most common
+/// first, e.g. for theming a player UI after it.
+///
+/// The cover is the first picture in the [PictureOrder::FrontCoverFirst] order. Returns an
+/// empty list when the file has no pictures, and fewer than `k` colors when the cover has
+/// less distinct colors.
+///
+/// **Note**: this decodes the cover, so it's slower than reading tags.
+/// Only available with the `artwork` feature.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the cover can't be decoded
+#[cfg(feature = "artwork")]
+pub fn artwork_palette(path: String, k: u32) -> anyhow::Result<Vec<PaletteColor>> {
+    match read_primary_picture(path, PictureOrder::FrontCoverFirst)? {
+        Some(cover) => crate::palette::dominant_colors(&cover.pic_data, k as usize),
+        None => Ok(vec![]),
+    }
+}
+
+/// Encodes the cover of the file at given `path` as a [BlurHash](https://blurha.sh) string,
+/// e.g. for showing a placeholder in lists before the cover itself is loaded.
+///
+/// The cover is the first picture in the [PictureOrder::FrontCoverFirst] order.
+/// Returns `None` when the file has no pictures.
+///
+/// **Note**: this decodes the cover, so it's slower than reading tags.
+/// Only available with the `artwork` feature.
+///
+/// Throws an **exception** when:
+/// - path doesn't exists
+/// - the cover can't be decoded
+#[cfg(feature = "artwork")]
+pub fn artwork_blurhash(path: String) -> anyhow::Result<Option<String>> {
+    read_primary_picture(path, PictureOrder::FrontCoverFirst)?
+        .map(|cover| crate::blurhash::blurhash(&cover.pic_data))
+        .transpose()
+}
+
+/// Re-encodes the embedded pictures of each file of the given `paths` as JPEGs of the
+/// `options` quality, downscaled to their `max_size`, e.g. to reclaim the space of needlessly
+/// large covers in a library.
+///
+/// A picture is only replaced when it gets smaller, and the pictures using transparency are
+/// kept. The files are optimized in parallel, and a file failing to be optimized doesn't fail
+/// the others, its [ArtworkSavings] carries the error instead and it's left untouched.
+/// The results are in the same order as the `paths`.
+///
+/// **Note**: this decodes the pictures, so it's slower than reading tags.
+/// Only available with the `artwork` feature.
+/// Not available in the `wasm32` builds, which only read in-memory buffers.
+///
+/// Throws an **exception** when:
+/// - the `options` quality isn't between `1` and `100`
+#[cfg(all(feature = "artwork", not(target_arch = "wasm32")))]
+pub fn optimize_artwork(
+    paths: Vec<String>,
+    options: ArtworkOptions,
+) -> anyhow::Result<Vec<ArtworkSavings>> {
+    if !(1..=100).contains(&options.quality) {
+        return Err(TaggyError::invalid_value(format!(
+            "The JPEG quality {} isn't between 1 and 100!",
+            options.quality
+        ))
+        .into());
+    }
+    Ok(map_parallel(&paths, options.concurrency, |path| {
+        crate::utils::artwork_optimizer::optimize_file(path, &options).unwrap_or_else(|e| {
+            ArtworkSavings {
+                path: path.clone(),
+                pictures_optimized: 0,
+                bytes_before: 0,
+                bytes_after: 0,
+                error: Some(e.to_string()),
+            }
+        })
+    }))
+}
+
+/// Read all audio tags of each audio file inside the `.zip` archive at given `zip_path`,
+/// e.g. to preview a purchased album before unpacking it.
+///
+/// The entries are read one at a time, without extracting the archive, and the `path` of each
+/// result is the entry name, e.g. `Album/01 Intro.mp3`. An entry which can't be read, e.g. an
+/// encrypted one or one larger than 1 GiB once decompressed, only fails its own result.
+///
+/// Only available with the `zip` feature.
+///
+/// Throws an **exception** when:
+/// - zip_path doesn't exists
+/// - the file isn't a zip archive
+#[cfg(feature = "zip")]
+pub fn read_zip_entries(zip_path: String) -> anyhow::Result<Vec<BatchReadResult>> {
+    crate::zip_archive::read_zip_entries(&zip_path, None)
+}
+
+/// Write all provided `tags` to the `entry_name` audio file inside the `.zip` archive at given
+/// `zip_path`, see [read_zip_entries].
+///
+/// when `override_existent` is set to `true`, this will remove all existing tags.
+/// Otherwise, it will add or update any existing ones.
+///
+/// **Note**: the archive is rebuilt next to it with the other entries copied as they are, and
+/// only replaces it once the tagged entry is read back, so it needs as much free space.
+/// Only available with the `zip` feature.
+///
+/// Throws an **exception** when:
+/// - zip_path doesn't exists, or the archive has no `entry_name` entry
+/// - the file isn't a zip archive
+/// - the entry is encrypted, or its file type can't be detected
+/// - the entry is larger than 1 GiB once decompressed
+#[cfg(feature = "zip")]
+pub fn write_zip_entry(
+    zip_path: String,
+    entry_name: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> anyhow::Result<()> {
+    crate::zip_archive::write_zip_entry(&zip_path, &entry_name, tags, override_existent, None)
+}
+
+/// A helper function to get a [`BoundTaggedFile`] from the given path
+/// which can be used to read an write tags to the file on disk directly.
+fn get_bound_tagged_file(path: &String) -> anyhow::Result<BoundTaggedFile> {
+    check_not_truncated(path)?;
+    let file = open_for_writing(path)?;
+
+    match BoundTaggedFile::read_from(file, ParseOptions::new()) {
+        Ok(file) => Ok(file),
+        Err(e) => Err(anyhow!(e)),
+    }
+}
+
+/// Opens the file at the given `path` for reading *and* writing.
+fn open_for_writing(path: &str) -> anyhow::Result<File> {
+    Ok(OpenOptions::new().read(true).write(true).open(path)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::{ClientOptions, TaggyClient};
+    use crate::comment::Comment;
+    use crate::custom_item::CustomItem;
+    use crate::dj_markers::{CuePoint, DjLoop};
+    use crate::duplicate_policy::DuplicatePolicy;
+    use crate::hooks::{OperationStats, TaggyHooks};
+    use crate::limits::LimitError;
+    use crate::parse_config::ParsingMode;
+    use crate::picture::{MimeType, Picture, PictureStorage, PictureType};
+    use crate::scanner::ScanEvent;
+    use crate::synced_lyrics::SyncedLine;
+    use crate::tag_date::TagDate;
+    use crate::write_options::{TagSizeLimit, TruncationStrategy};
+    use base64::Engine;
+    use std::fs::{copy, remove_file};
+    use std::sync::{Arc, Mutex};
+    use std::{env, panic};
+
+    #[test]
+    fn reading_non_existing_file_is_an_error() {
+        let result = read_all(get_fake_path());
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn reading_empty_or_truncated_file_is_a_specific_error() {
+        let path = env::temp_dir().join(format!("taggy-truncated-{}.mp3", generate_uuid()));
+        let path_str = path.to_str().unwrap().to_string();
+
+        std::fs::write(&path, []).unwrap();
+        let error = read_all(path_str.clone()).unwrap_err();
+        assert_eq!(
+            TaggyError::from(&error),
+            TaggyError::EmptyOrTruncated {
+                expected_len: None,
+                actual_len: 0,
+            }
+        );
+
+        // the ID3v2 tag of the sample is longer than that.
+        let bytes = std::fs::read(get_audio_sample_file_path()).unwrap();
+        std::fs::write(&path, &bytes[..20]).unwrap();
+        let error = read_all(path_str).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(matches!(
+            TaggyError::from(&error),
+            TaggyError::EmptyOrTruncated {
+                expected_len: Some(_),
+                actual_len: 20,
+            }
+        ));
+    }
+
+    #[test]
+    fn reading_existing_file_is_ok() {
+        let result = read_all(get_audio_sample_file_path());
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn reading_file_with_no_tags_should_return_with_empty_tags() {
+        let path = get_no_tags_sample_file_path();
+        let taggy = read_all(path).unwrap();
+        assert!(taggy.tags.is_empty());
+    }
+
+    #[test]
+    fn reading_duration_matches_audio_properties() {
+        let path = get_audio_sample_file_path();
+        let duration_sec = read_all(path.clone()).unwrap().audio.duration_sec.unwrap();
+        let duration_micros = read_duration(path).expect("Failed to read duration");
+        assert!((duration_micros / 1_000_000).abs_diff(duration_sec) <= 1);
+    }
+
+    #[test]
+    fn reading_duration_of_non_mpeg_files_ignores_frame_syncs() {
+        // one second of 16 bits mono PCM at 44.1 kHz, made of MPEG frame headers.
+        let data: Vec<u8> = [0xFF, 0xFB, 0x90, 0x00].repeat(44100 / 2);
+        let mut bytes = b"RIFF".to_vec();
+        bytes.extend((36 + data.len() as u32).to_le_bytes());
+        bytes.extend(b"WAVEfmt ");
+        bytes.extend(16u32.to_le_bytes());
+        bytes.extend(1u16.to_le_bytes());
+        bytes.extend(1u16.to_le_bytes());
+        bytes.extend(44100u32.to_le_bytes());
+        bytes.extend((44100u32 * 2).to_le_bytes());
+        bytes.extend(2u16.to_le_bytes());
+        bytes.extend(16u16.to_le_bytes());
+        bytes.extend(b"data");
+        bytes.extend((data.len() as u32).to_le_bytes());
+        bytes.extend(data);
+        let path = env::temp_dir().join(format!("taggy-duration-{}.wav", generate_uuid()));
+        std::fs::write(&path, bytes).unwrap();
+
+        let duration_micros = read_duration(path.to_str().unwrap().to_string());
+        remove_file(&path).unwrap();
+        assert!(duration_micros.unwrap().abs_diff(1_000_000) < 10_000);
+    }
+
+    #[test]
+    fn durations_are_consistent() {
+        let audio = read_all(get_audio_sample_file_path()).unwrap().audio;
+        let duration_ms = audio.duration_ms.unwrap();
+        assert_eq!(duration_ms / 1000, audio.duration_sec.unwrap());
+
+        let duration = audio.duration.unwrap();
+        let total_ms = ((duration.hours as u64 * 60 + duration.minutes as u64) * 60
+            + duration.seconds as u64)
+            * 1000
+            + duration.millis as u64;
+        assert_eq!(total_ms, duration_ms);
+
+        // the sample is an MP3, which has a fixed frame length.
+        assert!(audio.frame_length.is_some());
+        assert!(audio.seek_granularity_us.unwrap() > 0);
+    }
+
+    #[test]
+    fn reading_without_properties_keeps_the_tags() {
+        let path = get_audio_sample_file_path();
+        let config = ParseConfig {
+            read_properties: false,
+            parsing_mode: ParsingMode::Strict,
+            ..Default::default()
+        };
+        let taggy = read_all_with_config(path.clone(), config).unwrap();
+        assert_eq!(taggy.tags, read_all(path).unwrap().tags);
+        assert_eq!(taggy.audio.duration_sec, Some(0));
+    }
+
+    #[test]
+    fn reading_properties_matches_reading_all() {
+        let path = get_audio_sample_file_path();
+        let expected = read_all(path.clone()).unwrap().audio;
+        let audio = read_properties(path).unwrap();
+        assert_eq!(audio.duration_sec, expected.duration_sec);
+        assert_eq!(audio.sample_rate, expected.sample_rate);
+        assert_eq!(audio.channels, expected.channels);
+
+        assert!(read_properties(get_fake_path()).is_err());
+    }
+
+    #[test]
+    fn reading_without_picture_data_keeps_it_on_write() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let tag = Tag {
+                pictures: vec![get_pic_from_asset()],
+                ..Tag::new(TagType::Id3v2)
+            };
+            write_all(path.clone(), vec![tag], true).unwrap();
+
+            let config = ParseConfig {
+                read_picture_data: false,
+                ..Default::default()
+            };
+            let lite_tag = read_primary_with_config(path.clone(), config)
+                .unwrap()
+                .first_tag()
+                .unwrap();
+            let picture = &lite_tag.pictures[0];
+            assert!(picture.pic_data.is_empty());
+            assert_eq!(
+                picture.data_size,
+                Some(get_pic_from_asset().pic_data.len() as u32)
+            );
+
+            let renamed = Tag {
+                track_title: Some("Lite".to_string()),
+                ..lite_tag
+            };
+            let written = write_primary(path, renamed, true)
+                .unwrap()
+                .first_tag()
+                .unwrap();
+            assert_eq!(written.pictures[0].pic_data, get_pic_from_asset().pic_data);
+        });
+    }
+
+    #[test]
+    fn reading_pictures_searches_all_tags() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            assert!(read_front_cover(path.clone()).unwrap().is_none());
+
+            let back_cover = Picture {
+                pic_type: PictureType::CoverBack,
+                ..get_pic_from_asset()
+            };
+            let tags = vec![
+                Tag {
+                    pictures: vec![back_cover],
+                    ..Tag::new(TagType::Id3v2)
+                },
+                Tag {
+                    pictures: vec![get_pic_from_asset()],
+                    ..Tag::new(TagType::Ape)
+                },
+            ];
+            write_all(path.clone(), tags, true).unwrap();
+
+            let pictures = read_pictures(path.clone()).unwrap();
+            assert_eq!(pictures.len(), 2);
+            assert_eq!(pictures[0].pic_type, PictureType::CoverBack);
+            let cover = read_front_cover(path).unwrap().unwrap();
+            assert_eq!(cover.pic_type, PictureType::CoverFront);
+        });
+    }
+
+    #[test]
+    fn exporting_a_picture_writes_its_data() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let out_path = env::temp_dir().join(format!("taggy-cover-{}.jpg", generate_uuid()));
+            let out = out_path.to_str().unwrap().to_string();
+            let exported = export_picture(path.clone(), out.clone(), PictureType::CoverFront);
+            assert!(!exported.unwrap());
+            assert!(!out_path.exists());
+
+            write_picture(path.clone(), get_pic_from_asset(), PictureType::CoverFront).unwrap();
+            assert!(export_picture(path, out, PictureType::CoverFront).unwrap());
+            let data = std::fs::read(&out_path).unwrap();
+            remove_file(&out_path).unwrap();
+            assert_eq!(data, get_pic_from_asset().pic_data);
+        });
+    }
+
+    #[test]
+    fn a_picture_read_from_a_path_has_its_info_detected() {
+        let picture = picture_from_path(get_image_path(), PictureType::CoverBack).unwrap();
+        assert_eq!(picture.pic_type, PictureType::CoverBack);
+        assert_eq!(picture.mime_type, Some(MimeType::Jpeg));
+        assert!(picture.width.is_some_and(|w| w > 0));
+        assert!(picture.height.is_some_and(|h| h > 0));
+        assert_eq!(picture.pic_data, get_pic_from_asset().pic_data);
+
+        let not_an_image = picture_from_path(get_audio_sample_file_path(), PictureType::Other);
+        let error = not_an_image.unwrap_err();
+        assert_eq!(TaggyError::from(&error).code(), "invalid_value");
+        assert!(picture_from_path(get_fake_path(), PictureType::Other).is_err());
+    }
+
+    #[test]
+    fn writing_a_picture_from_a_path() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            write_picture_from_path(path.clone(), get_image_path(), PictureType::CoverFront)
+                .unwrap();
+            let cover = read_front_cover(path).unwrap().unwrap();
+            assert_eq!(cover.mime_type, Some(MimeType::Jpeg));
+            assert_eq!(cover.pic_data, get_pic_from_asset().pic_data);
+        });
+    }
+
+    #[test]
+    fn the_missing_mime_type_of_a_picture_is_detected() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let picture = Picture {
+                mime_type: None,
+                ..get_pic_from_asset()
+            };
+            write_picture(path.clone(), picture, PictureType::CoverFront).unwrap();
+            let cover = read_front_cover(path).unwrap().unwrap();
+            assert_eq!(cover.mime_type, Some(MimeType::Jpeg));
+        });
+    }
+
+    #[test]
+    fn dates_are_normalized_with_the_date_context() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                recording_date: Some("03/04/2001".to_string()),
+                original_release_date: Some("2001-03-04T23:30:00Z".to_string()),
+                ..Tag::new(TagType::Id3v2)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let read_tag = |context| {
+                let config = ParseConfig {
+                    date_context: Some(context),
+                    ..Default::default()
+                };
+                read_primary_with_config(path.clone(), config)
+                    .unwrap()
+                    .first_tag()
+                    .unwrap()
+            };
+            let us = read_tag(date_context_for_locale("en-US".to_string(), -300));
+            assert_eq!(us.recording_date.as_deref(), Some("2001-03-04"));
+            assert_eq!(
+                us.original_release_date.as_deref(),
+                Some("2001-03-04T18:30:00")
+            );
+            let fr = read_tag(date_context_for_locale("fr_FR.UTF-8".to_string(), 120));
+            assert_eq!(fr.recording_date.as_deref(), Some("2001-04-03"));
+            assert_eq!(
+                fr.original_release_date.as_deref(),
+                Some("2001-03-05T01:30:00")
+            );
+
+            // the dates are left as they are without a context.
+            let tag = read_primary(path.clone()).unwrap().first_tag().unwrap();
+            assert_eq!(tag.recording_date.as_deref(), Some("03/04/2001"));
+        });
+    }
+
+    #[test]
+    fn picture_description_and_resolution_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let picture = Picture {
+                description: Some("Front cover".to_string()),
+                ..get_pic_from_asset()
+            };
+            write_picture(path.clone(), picture, PictureType::CoverFront).unwrap();
+
+            let cover = read_front_cover(path).unwrap().unwrap();
+            assert_eq!(cover.description.as_deref(), Some("Front cover"));
+            assert!(cover.width.is_some_and(|w| w > 0));
+            assert!(cover.height.is_some_and(|h| h > 0));
+            assert!(cover.color_depth.is_some());
+        });
+    }
+
+    #[test]
+    fn pictures_report_how_they_are_stored() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            assert_eq!(get_pic_from_asset().storage, None);
+            write_picture(path.clone(), get_pic_from_asset(), PictureType::CoverFront).unwrap();
+
+            let cover = read_front_cover(path).unwrap().unwrap();
+            assert_eq!(cover.storage, Some(PictureStorage::Id3v2Frame));
+        });
+    }
+
+    #[test]
+    fn pictures_are_ordered_by_the_given_policy() {
+        let small_cover = Picture {
+            pic_type: PictureType::CoverBack,
+            width: Some(10),
+            height: Some(10),
+            ..get_pic_from_asset()
+        };
+        let tag = Tag {
+            pictures: vec![
+                Picture {
+                    pic_type: PictureType::Artist,
+                    ..get_pic_from_asset()
+                },
+                small_cover.clone(),
+                get_pic_from_asset(),
+            ],
+            ..Tag::new(TagType::Id3v2)
+        };
+        let first_type = |order| tag.primary_picture(order).unwrap().pic_type;
+        assert_eq!(first_type(PictureOrder::AsStored), PictureType::Artist);
+        assert_eq!(
+            first_type(PictureOrder::FrontCoverFirst),
+            PictureType::CoverFront
+        );
+        assert_eq!(first_type(PictureOrder::ByType), PictureType::CoverFront);
+        assert_eq!(first_type(PictureOrder::BySize), PictureType::Artist);
+        assert!(Tag::new(TagType::Id3v2)
+            .primary_picture(PictureOrder::default())
+            .is_none());
+
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            write_primary(path.clone(), tag, false).unwrap();
+            let types: Vec<PictureType> =
+                read_pictures_with_order(path.clone(), PictureOrder::ByType)
+                    .unwrap()
+                    .into_iter()
+                    .map(|p| p.pic_type)
+                    .collect();
+            assert_eq!(
+                types,
+                [
+                    PictureType::CoverFront,
+                    PictureType::CoverBack,
+                    PictureType::Artist
+                ]
+            );
+            let primary = read_primary_picture(path, PictureOrder::FrontCoverFirst).unwrap();
+            assert_eq!(primary.unwrap().pic_type, PictureType::CoverFront);
+        });
+    }
+
+    #[test]
+    fn a_single_field_is_written_and_read_by_its_key() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let before = read_primary(path.clone()).unwrap().primary_tag().unwrap();
+            assert_eq!(read_field(path.clone(), ItemKey::Composer).unwrap(), None);
+
+            write_field(path.clone(), ItemKey::Composer, Some("Bach".to_string())).unwrap();
+            assert_eq!(
+                read_field(path.clone(), ItemKey::Composer).unwrap(),
+                Some("Bach".to_string())
+            );
+            let after = read_primary(path.clone()).unwrap().primary_tag().unwrap();
+            assert_eq!(after.track_title, before.track_title);
+            assert_eq!(after.pictures.len(), before.pictures.len());
+
+            write_field(path.clone(), ItemKey::Composer, None).unwrap();
+            assert_eq!(read_field(path, ItemKey::Composer).unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn custom_items_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("Title".to_string()),
+                custom_items: vec![
+                    CustomItem::new("MY_APP_PLAYCOUNT", "12"),
+                    CustomItem::new("MY_APP_RATING", "4"),
+                ],
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            let written = write_primary(path.clone(), tag, false).unwrap();
+            assert_eq!(written.primary_tag().unwrap().custom_items.len(), 2);
+
+            let tag = read_primary(path.clone()).unwrap().primary_tag().unwrap();
+            assert_eq!(tag.track_title, Some("Title".to_string()));
+            assert!(tag
+                .custom_items
+                .contains(&CustomItem::new("MY_APP_PLAYCOUNT", "12")));
+            assert!(tag
+                .custom_items
+                .contains(&CustomItem::new("MY_APP_RATING", "4")));
+        });
+    }
+
+    #[cfg(feature = "capi")]
+    #[test]
+    fn c_api_round_trip() {
+        use crate::capi::*;
+        use std::ffi::{CStr, CString};
+
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| unsafe {
+            let c_path = CString::new(path).unwrap();
+            let tag = taggy_tag_new(TaggyTagType::FilePrimaryType);
+            let title = CString::new("Title").unwrap();
+            taggy_tag_set_text(tag, TaggyTextField::Title, title.as_ptr());
+            taggy_tag_set_number(tag, TaggyNumberField::TrackNumber, 3);
+            assert_eq!(taggy_write_primary(c_path.as_ptr(), tag, false), 0);
+            taggy_tag_free(tag);
+
+            let file = taggy_read_primary(c_path.as_ptr());
+            assert!(!file.is_null());
+            assert_eq!(taggy_file_tags_count(file), 1);
+            let tag = taggy_file_tag(file, 0);
+            let text = taggy_tag_get_text(tag, TaggyTextField::Title);
+            assert_eq!(CStr::from_ptr(text).to_str(), Ok("Title"));
+            taggy_string_free(text);
+            let mut track_number = 0;
+            assert!(taggy_tag_get_number(
+                tag,
+                TaggyNumberField::TrackNumber,
+                &mut track_number
+            ));
+            assert_eq!(track_number, 3);
+            assert!(taggy_file_tag(file, 1).is_null());
+            taggy_file_free(file);
+
+            let missing = CString::new(get_fake_path()).unwrap();
+            assert!(taggy_read_all(missing.as_ptr()).is_null());
+            let code = CStr::from_ptr(taggy_last_error_code());
+            assert_eq!(code.to_str(), Ok("file_not_found"));
+            assert_eq!(taggy_remove_all(std::ptr::null()), -1);
+        });
+    }
+
+    #[test]
+    fn comments_with_descriptions_coexist() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let normalization = Comment {
+                language: Some("eng".to_string()),
+                description: Some("iTunNORM".to_string()),
+                text: " 00000A2B 00000A2B 00003C4D".to_string(),
+            };
+            let tag = Tag {
+                comments: vec![Comment::new("Great track"), normalization.clone()],
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let mut tag = read_primary(path.clone()).unwrap().primary_tag().unwrap();
+            assert_eq!(tag.comments.len(), 2);
+            assert!(tag.comments.contains(&normalization));
+            assert!(tag.comments.contains(&Comment::new("Great track")));
+
+            // editing the user comment keeps the other one.
+            for comment in tag.comments.iter_mut() {
+                if comment.description.is_none() {
+                    comment.text = "Even better live".to_string();
+                }
+            }
+            write_primary(path.clone(), tag, false).unwrap();
+            let tag = read_primary(path).unwrap().primary_tag().unwrap();
+            assert!(tag.comments.contains(&normalization));
+            assert!(tag.comments.contains(&Comment::new("Even better live")));
+        });
+    }
+
+    #[test]
+    fn only_the_comments_without_description_are_kept_by_vorbis_comments() {
+        let tag = Tag {
+            comments: vec![
+                Comment::new("First"),
+                Comment::new("Second"),
+                Comment {
+                    description: Some("iTunNORM".to_string()),
+                    ..Comment::new("00000A2B")
+                },
+            ],
+            ..Tag::new(TagType::VorbisComments)
+        };
+
+        let read = Tag::from(&tag.to_lofty());
+        assert_eq!(
+            read.comments,
+            vec![Comment::new("First"), Comment::new("Second")]
+        );
+    }
+
+    #[test]
+    fn files_report_when_they_were_added_and_modified() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                custom_items: vec![CustomItem::new("Date Added", "2021-06-15 22:30:00+02:00")],
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let file = read_all(path.clone()).unwrap();
+            assert_eq!(file.date_added_ms, Some(1623789000000));
+            assert_eq!(file.added_at_ms(), Some(1623789000000));
+            assert!(file.modified_at_ms.is_some_and(|ms| ms > 1623789000000));
+
+            let bytes = std::fs::read(&path).unwrap();
+            let file = read_all_from_buffer(bytes).unwrap();
+            assert_eq!(file.modified_at_ms, None);
+        });
+    }
+
+    #[cfg(feature = "decode")]
+    #[test]
+    fn the_waveform_has_the_requested_buckets() {
+        let path = get_audio_sample_file_path();
+
+        let waveform = compute_waveform(path.clone(), 100).unwrap();
+        assert_eq!(waveform.len(), 100);
+        assert!(waveform.iter().all(|peak| (0.0..=1.0).contains(peak)));
+        // the peaks are normalized to the loudest one.
+        assert!(waveform.iter().any(|peak| *peak == 1.0));
+
+        assert!(compute_waveform(path, 0).unwrap().is_empty());
+        assert!(compute_waveform(get_fake_path(), 100).is_err());
+    }
+
+    #[cfg(feature = "artwork")]
+    #[test]
+    fn the_artwork_palette_has_the_dominant_colors() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            assert!(artwork_palette(path.clone(), 4).unwrap().is_empty());
+
+            write_picture(path.clone(), get_pic_from_asset(), PictureType::CoverFront).unwrap();
+            let palette = artwork_palette(path.clone(), 4).unwrap();
+            assert!(!palette.is_empty() && palette.len() <= 4);
+            assert!(palette
+                .windows(2)
+                .all(|w| w[0].proportion >= w[1].proportion));
+            let total: f32 = palette.iter().map(|c| c.proportion).sum();
+            assert!((total - 1.0).abs() < 0.001);
+            assert_eq!(artwork_palette(path.clone(), 4).unwrap(), palette);
+            assert!(artwork_palette(path, 0).unwrap().is_empty());
+        });
+    }
+
+    #[cfg(feature = "artwork")]
+    #[test]
+    fn the_cover_is_encoded_as_a_blurhash() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            assert_eq!(artwork_blurhash(path.clone()).unwrap(), None);
+
+            write_picture(path.clone(), get_pic_from_asset(), PictureType::CoverFront).unwrap();
+            let hash = artwork_blurhash(path.clone()).unwrap().unwrap();
+            // the size flag of 4x3 components, then 1 + 4 + 2 * 11 characters.
+            assert!(hash.starts_with('L'));
+            assert_eq!(hash.len(), 28);
+            assert_eq!(artwork_blurhash(path).unwrap(), Some(hash));
+        });
+    }
+
+    #[cfg(feature = "artwork")]
+    #[test]
+    fn the_artwork_is_optimized() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            write_picture(path.clone(), get_pic_from_asset(), PictureType::CoverFront).unwrap();
+            let options = ArtworkOptions {
+                quality: 50,
+                max_size: Some(100),
+                ..ArtworkOptions::default()
+            };
+
+            let dry_run = ArtworkOptions {
+                dry_run: true,
+                ..options.clone()
+            };
+            let savings = optimize_artwork(vec![path.clone()], dry_run).unwrap();
+            assert_eq!(savings[0].pictures_optimized, 1);
+            assert!(savings[0].bytes_saved() > 0);
+            assert_eq!(
+                read_front_cover(path.clone()).unwrap().unwrap().pic_data,
+                get_pic_from_asset().pic_data
+            );
+
+            let savings =
+                optimize_artwork(vec![path.clone(), get_fake_path()], options.clone()).unwrap();
+            assert_eq!(savings[0].error, None);
+            assert!(savings[1].error.is_some());
+            let cover = read_front_cover(path.clone()).unwrap().unwrap();
+            assert_eq!(cover.pic_data.len() as u64, savings[0].bytes_after);
+            let (width, height) = crate::utils::artwork_rules::picture_size(&cover).unwrap();
+            assert!(width.max(height) <= 100);
+
+            let undecodable = Picture {
+                pic_data: vec![0xFF, 0xD8, 0xFF, 0xE0, 0, 1, 2, 3],
+                ..get_pic_from_asset()
+            };
+            write_picture(path.clone(), undecodable.clone(), PictureType::CoverBack).unwrap();
+            let savings = optimize_artwork(vec![path.clone()], options).unwrap();
+            assert!(savings[0].error.as_ref().unwrap().starts_with("1 picture"));
+            let file = read_primary(path.clone()).unwrap();
+            let back = file.tags[0]
+                .pictures
+                .iter()
+                .find(|p| p.pic_type == PictureType::CoverBack)
+                .unwrap();
+            assert_eq!(back.pic_data, undecodable.pic_data);
+
+            let invalid = ArtworkOptions {
+                quality: 0,
+                ..ArtworkOptions::default()
+            };
+            let error = optimize_artwork(vec![path], invalid).unwrap_err();
+            assert_eq!(TaggyError::from(&error).code(), "invalid_value");
+        });
+    }
+
+    #[cfg(feature = "zip")]
+    #[test]
+    fn the_audio_files_of_a_zip_archive_are_read_and_written() {
+        use std::io::{Read, Write};
+        use zip::write::FileOptions;
+        use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+        let path = env::temp_dir().join(format!("taggy-album-{}.zip", generate_uuid()));
+        let zip_path = path.to_str().unwrap().to_string();
+        let mut writer = ZipWriter::new(File::create(&path).unwrap());
+        let stored = FileOptions::default().compression_method(CompressionMethod::Stored);
+        writer.start_file("Album/01.mp3", stored).unwrap();
+        writer
+            .write_all(&std::fs::read(get_audio_sample_file_path()).unwrap())
+            .unwrap();
+        writer
+            .start_file("Album/notes.txt", FileOptions::default())
+            .unwrap();
+        writer.write_all(b"Liner notes").unwrap();
+        writer.finish().unwrap();
+
+        let results = read_zip_entries(zip_path.clone()).unwrap();
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].path, "Album/01.mp3");
+        assert!(results[0].file.is_some());
+
+        let tag = Tag {
+            track_title: Some("Zipped".to_string()),
+            ..Tag::new(TagType::FilePrimaryType)
+        };
+        write_zip_entry(
+            zip_path.clone(),
+            "Album/01.mp3".to_string(),
+            vec![tag],
+            false,
+        )
+        .unwrap();
+        let file = read_zip_entries(zip_path.clone()).unwrap()[0]
+            .file
+            .clone()
+            .unwrap();
+        assert_eq!(
+            file.primary_tag().unwrap().track_title.as_deref(),
+            Some("Zipped")
+        );
+        let mut archive = ZipArchive::new(File::open(&path).unwrap()).unwrap();
+        let mut notes = String::new();
+        archive
+            .by_name("Album/notes.txt")
+            .unwrap()
+            .read_to_string(&mut notes)
+            .unwrap();
+        assert_eq!(notes, "Liner notes");
+
+        let client = TaggyClient::new(ClientOptions {
+            max_file_size: Some(1024),
+            ..ClientOptions::default()
+        });
+        let results = client.read_zip_entries(zip_path.clone()).unwrap();
+        assert!(results[0].file.is_none());
+        assert!(results[0]
+            .error
+            .as_ref()
+            .unwrap()
+            .contains("exceeds the limit"));
+        let error = client
+            .write_zip_entry(zip_path.clone(), "Album/01.mp3".to_string(), vec![], false)
+            .unwrap_err();
+        assert!(error.downcast_ref::<LimitError>().is_some());
+
+        let error = write_zip_entry(zip_path, "Missing.mp3".to_string(), vec![], false);
+        assert_eq!(
+            TaggyError::from(&error.unwrap_err()).code(),
+            "file_not_found"
+        );
+        remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn multiple_values_of_a_field_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let values = |v: &[&str]| v.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+            let tag = Tag {
+                artists: values(&["Artist A", "Artist B"]),
+                genres: values(&["Rock", "Pop"]),
+                composers: values(&["Composer A", "Composer B"]),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let tag = read_primary(path.clone()).unwrap().primary_tag().unwrap();
+            assert_eq!(tag.track_artist.as_deref(), Some("Artist A"));
+            assert_eq!(tag.artists, values(&["Artist A", "Artist B"]));
+            assert_eq!(tag.genre.as_deref(), Some("Rock"));
+            assert_eq!(tag.genres, values(&["Rock", "Pop"]));
+            assert_eq!(tag.composers, values(&["Composer A", "Composer B"]));
+
+            // changing the single value replaces all of them.
+            let tag = Tag {
+                track_artist: Some("Artist C".to_string()),
+                ..tag
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+            let tag = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(tag.artists, values(&["Artist C"]));
+            assert_eq!(tag.genres, values(&["Rock", "Pop"]));
+        });
+    }
+
+    #[test]
+    fn credits_and_sort_fields_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag::builder()
+                .with_tag_type(TagType::FilePrimaryType)
+                .with_artist("The Beatles")
+                .with_composer("John Lennon")
+                .with_conductor("George Martin")
+                .with_remixer("Giles Martin")
+                .with_artist_sort("Beatles, The")
+                .with_album_sort("Abbey Road")
+                .with_title_sort("Come Together")
+                .with_album_artist_sort("Beatles, The")
+                .create();
+            write_primary(path.clone(), tag.clone(), false).unwrap();
+
+            let read = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(read.composer, tag.composer);
+            assert_eq!(read.composers, vec!["John Lennon".to_string()]);
+            assert_eq!(read.conductor, tag.conductor);
+            assert_eq!(read.remixer, tag.remixer);
+            assert_eq!(read.artist_sort, tag.artist_sort);
+            assert_eq!(read.album_sort, tag.album_sort);
+            assert_eq!(read.title_sort, tag.title_sort);
+            assert_eq!(read.album_artist_sort, tag.album_artist_sort);
+        });
+    }
+
+    #[test]
+    fn dj_fields_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                bpm: Some(128),
+                initial_key: Some("8A".to_string()),
+                mood: Some("Energetic".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let tag = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(tag.bpm, Some(128));
+            assert_eq!(tag.initial_key.as_deref(), Some("8A"));
+            assert_eq!(tag.mood.as_deref(), Some("Energetic"));
+        });
+    }
+
+    #[test]
+    fn lyrics_language_and_description_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                lyrics: Some("First line\nSecond line".to_string()),
+                lyrics_language: Some("ENG".to_string()),
+                lyrics_description: Some("Live version".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let tag = read_primary(path.clone()).unwrap().primary_tag().unwrap();
+            assert_eq!(tag.lyrics.as_deref(), Some("First line\nSecond line"));
+            assert_eq!(tag.lyrics_language.as_deref(), Some("eng"));
+            assert_eq!(tag.lyrics_description.as_deref(), Some("Live version"));
+
+            let invalid = Tag {
+                lyrics_language: Some("english".to_string()),
+                ..tag
+            };
+            let error = write_primary(path, invalid, false).unwrap_err();
+            assert_eq!(TaggyError::from(&error).code(), "invalid_value");
+        });
+    }
+
+    #[test]
+    fn synced_lyrics_round_trip_and_survive_tag_writes() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let lyrics = SyncedLyrics {
+                language: Some("eng".to_string()),
+                description: Some("Karaoke".to_string()),
+                lines: vec![
+                    SyncedLine {
+                        timestamp_ms: 1500,
+                        text: "First line".to_string(),
+                    },
+                    SyncedLine {
+                        timestamp_ms: 4250,
+                        text: "Second line".to_string(),
+                    },
+                ],
+            };
+            write_synced_lyrics(path.clone(), lyrics.clone()).unwrap();
+            assert_eq!(
+                read_synced_lyrics(path.clone()).unwrap(),
+                Some(lyrics.clone())
+            );
+
+            let tag = Tag {
+                track_title: Some("A title".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+            assert_eq!(read_synced_lyrics(path.clone()).unwrap(), Some(lyrics));
+
+            write_synced_lyrics(path.clone(), SyncedLyrics::default()).unwrap();
+            assert_eq!(read_synced_lyrics(path).unwrap(), None);
+        });
+    }
+
+    #[test]
+    fn duplicate_frames_are_read_with_the_policy() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            // an ID3v2.3 tag holding two latin1 `TIT2` frames.
+            let mut frames = vec![];
+            for title in ["First", "Second"] {
+                frames.extend(b"TIT2");
+                frames.extend((title.len() as u32 + 1).to_be_bytes());
+                frames.extend([0, 0, 0]);
+                frames.extend(title.as_bytes());
+            }
+            let mut bytes = b"ID3\x03\x00\x00\x00\x00\x00".to_vec();
+            bytes.push(frames.len() as u8);
+            bytes.extend(frames);
+            bytes.extend(std::fs::read(&path).unwrap());
+            std::fs::write(&path, bytes).unwrap();
+
+            let read = |duplicate_policy| {
+                let config = ParseConfig {
+                    duplicate_policy,
+                    ..Default::default()
+                };
+                read_primary_with_config(path.clone(), config).unwrap()
+            };
+            let title = |file: TaggyFile| file.primary_tag().unwrap().track_title;
+
+            let file = read(DuplicatePolicy::First);
+            assert_eq!(file.warnings.len(), 1);
+            assert!(file.warnings[0].contains("'TIT2'"));
+            assert_eq!(title(file).as_deref(), Some("First"));
+            assert_eq!(
+                title(read(DuplicatePolicy::Last)).as_deref(),
+                Some("Second")
+            );
+            assert_eq!(
+                title(read(DuplicatePolicy::Concatenate)).as_deref(),
+                Some("First; Second")
+            );
+            let file = read(DuplicatePolicy::ReportAll);
+            assert!(file.warnings[0].ends_with("'First', 'Second'"));
+        });
+    }
+
+    #[test]
+    fn raw_frames_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let frames = vec![
+                RawFrame {
+                    id: "PRIV".to_string(),
+                    flags: 0,
+                    data: b"com.example\0first".to_vec(),
+                },
+                RawFrame {
+                    id: "PRIV".to_string(),
+                    flags: 0x1000,
+                    data: b"com.example\0second".to_vec(),
+                },
+            ];
+            for frame in &frames {
+                write_raw_frame(path.clone(), frame.clone()).unwrap();
+            }
+            // already in the tag.
+            write_raw_frame(path.clone(), frames[0].clone()).unwrap();
+
+            let read: Vec<RawFrame> = read_raw_frames(path.clone())
+                .unwrap()
+                .into_iter()
+                .filter(|f| f.id == "PRIV")
+                .collect();
+            assert_eq!(read, frames);
+
+            let invalid = RawFrame {
+                id: "PRIVATE".to_string(),
+                ..frames[0].clone()
+            };
+            let error = write_raw_frame(path, invalid).unwrap_err();
+            assert_eq!(TaggyError::from(&error).code(), "invalid_value");
+        });
+    }
+
+    #[test]
+    fn tags_are_converted_to_another_tag_type() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let source = read_all(path.clone()).unwrap();
+            let id3v2 = source
+                .tags
+                .iter()
+                .find(|t| t.tag_type == TagType::Id3v2)
+                .unwrap();
+
+            let file = convert_tag(path.clone(), TagType::Id3v2, TagType::Ape, true).unwrap();
+            assert!(file.tags.iter().all(|t| t.tag_type != TagType::Id3v2));
+            let ape = file
+                .tags
+                .iter()
+                .find(|t| t.tag_type == TagType::Ape)
+                .unwrap();
+            assert_eq!(ape.track_title, id3v2.track_title);
+            assert_eq!(ape.album, id3v2.album);
+            assert_eq!(ape.pictures.len(), id3v2.pictures.len());
+
+            // there's no ID3v2 tag anymore.
+            let unchanged = convert_tag(path.clone(), TagType::Id3v2, TagType::Ape, true).unwrap();
+            assert_eq!(unchanged.tags, file.tags);
+
+            let error =
+                convert_tag(path, TagType::Ape, TagType::VorbisComments, false).unwrap_err();
+            assert_eq!(TaggyError::from(&error).code(), "unsupported_tag_type");
+        });
+    }
+
+    #[test]
+    fn raw_tags_are_moved_between_files() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            assert!(dump_raw_tag(path.clone(), TagType::Id3v2)
+                .unwrap()
+                .is_empty());
+
+            let source = get_audio_sample_file_path();
+            let bytes = dump_raw_tag(source.clone(), TagType::FilePrimaryType).unwrap();
+            assert!(bytes.starts_with(b"ID3"));
+            inject_raw_tag(path.clone(), bytes.clone()).unwrap();
+            assert_eq!(dump_raw_tag(path.clone(), TagType::Id3v2).unwrap(), bytes);
+            assert_eq!(
+                read_primary(path.clone())
+                    .unwrap()
+                    .first_tag()
+                    .unwrap()
+                    .track_title,
+                read_primary(source)
+                    .unwrap()
+                    .first_tag()
+                    .unwrap()
+                    .track_title
+            );
+
+            let error =
+                inject_raw_tag(path.clone(), bytes[..bytes.len() - 1].to_vec()).unwrap_err();
+            assert_eq!(TaggyError::from(&error).code(), "invalid_value");
+            let error = dump_raw_tag(path, TagType::VorbisComments).unwrap_err();
+            assert_eq!(TaggyError::from(&error).code(), "unsupported_tag_type");
+        });
+    }
+
+    #[test]
+    fn serato_markers_are_read_and_survive_tag_writes() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let entry = |name: &str, data: &[u8]| {
+                [
+                    name.as_bytes(),
+                    &[0],
+                    &(data.len() as u32).to_be_bytes(),
+                    data,
+                ]
+                .concat()
+            };
+            let payload = [
+                vec![1, 1],
+                entry("COLOR", &[0, 0xFF, 0x99, 0xFF]),
+                entry(
+                    "CUE",
+                    &[
+                        &[0, 1][..],
+                        &1500u32.to_be_bytes(),
+                        &[0, 0xCC, 0, 0, 0, 0],
+                        b"Drop\0",
+                    ]
+                    .concat(),
+                ),
+                entry(
+                    "LOOP",
+                    &[
+                        &[0, 0][..],
+                        &1000u32.to_be_bytes(),
+                        &5000u32.to_be_bytes(),
+                        &[0xFF; 4],
+                        &[0, 0x27, 0xAA, 0xE1, 0, 1],
+                        b"\0",
+                    ]
+                    .concat(),
+                ),
+                entry("BPMLOCK", &[1]),
+                vec![0],
+            ]
+            .concat();
+            let encoded = base64::engine::general_purpose::STANDARD_NO_PAD.encode(payload);
+            let frame = RawFrame {
+                id: "GEOB".to_string(),
+                flags: 0,
+                data: [
+                    &b"\0application/octet-stream\0\0Serato Markers2\0\x01\x01"[..],
+                    encoded.as_bytes(),
+                    &[0],
+                ]
+                .concat(),
+            };
+            write_raw_frame(path.clone(), frame).unwrap();
+            let markers = DjMarkers {
+                cues: vec![CuePoint {
+                    index: 1,
+                    position_ms: 1500,
+                    color: 0xCC0000,
+                    name: Some("Drop".to_string()),
+                }],
+                loops: vec![DjLoop {
+                    index: 0,
+                    start_ms: 1000,
+                    end_ms: 5000,
+                    name: None,
+                    is_locked: true,
+                }],
+                track_color: Some(0xFF99FF),
+                is_bpm_locked: true,
+            };
+            assert_eq!(
+                read_dj_markers(path.clone()).unwrap(),
+                Some(markers.clone())
+            );
+
+            let tag = Tag {
+                track_title: Some("A title".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+            assert_eq!(read_dj_markers(path).unwrap(), Some(markers));
+        });
+    }
+
+    #[test]
+    fn chapters_round_trip_and_survive_tag_writes() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let chapters = vec![
+                Chapter {
+                    start_ms: 0,
+                    end_ms: 1500,
+                    title: Some("Intro".to_string()),
+                    url: Some("https://example.com/intro".to_string()),
+                    picture: None,
+                },
+                Chapter {
+                    start_ms: 1500,
+                    end_ms: 3000,
+                    title: Some("Chapitre deux".to_string()),
+                    url: None,
+                    picture: None,
+                },
+            ];
+            // written in any order, read by start time.
+            let reversed = chapters.iter().rev().cloned().collect();
+            write_chapters(path.clone(), reversed).unwrap();
+            assert_eq!(read_chapters(path.clone()).unwrap(), chapters);
+
+            let tag = Tag {
+                track_title: Some("A title".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+            assert_eq!(read_primary(path.clone()).unwrap().chapters, chapters);
+
+            let invalid = Chapter {
+                start_ms: 2000,
+                end_ms: 1000,
+                ..chapters[0].clone()
+            };
+            let error = write_chapters(path.clone(), vec![invalid]).unwrap_err();
+            assert_eq!(TaggyError::from(&error).code(), "invalid_value");
+
+            write_chapters(path.clone(), vec![]).unwrap();
+            assert!(read_chapters(path).unwrap().is_empty());
+        });
+    }
+
+    #[test]
+    fn lrc_text_is_parsed_and_formatted() {
+        let lrc = "[ar:Artist]\n[offset:+500]\n[00:12.34][01:02.00]Chorus\n[00:05.5]Intro\n";
+        let lyrics = parse_lrc(lrc.to_string()).unwrap();
+        let lines: Vec<(u32, &str)> = lyrics
+            .lines
+            .iter()
+            .map(|l| (l.timestamp_ms, l.text.as_str()))
+            .collect();
+        assert_eq!(
+            lines,
+            vec![(5000, "Intro"), (11840, "Chorus"), (61500, "Chorus")]
+        );
+        assert_eq!(
+            to_lrc(lyrics),
+            "[00:05.00]Intro\n[00:11.84]Chorus\n[01:01.50]Chorus\n"
+        );
+
+        assert!(parse_lrc("Just some text".to_string()).is_err());
+    }
+
+    #[test]
+    fn unsynced_lyrics_comments_are_read_as_lyrics() {
+        let mut vorbis = lofty::Tag::new(lofty::TagType::VorbisComments);
+        vorbis.push_unchecked(TagItem::new(
+            lofty::ItemKey::Unknown("UNSYNCEDLYRICS".to_string()),
+            ItemValue::Text("Some lyrics".to_string()),
+        ));
+
+        let tag = Tag::from(&vorbis);
+        assert_eq!(tag.lyrics.as_deref(), Some("Some lyrics"));
+        assert!(tag.custom_items.is_empty());
+    }
+
+    #[test]
+    fn musicbrainz_identifiers_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                musicbrainz_recording_id: Some("b1a9c0e9-d987-4042-ae91-78d6a3267d69".to_string()),
+                musicbrainz_release_id: Some("5b11f4ce-a62d-471e-81fc-a69a8278c7da".to_string()),
+                musicbrainz_track_id: Some("a3c7a7d6-6ff5-3fd6-a5ab-6b36d2d0c4a2".to_string()),
+                acoustid_fingerprint: Some("AQADtEmUJEkS".repeat(30)),
+                custom_items: vec![CustomItem::new("MY_APP_PLAYCOUNT", "3")],
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag.clone(), false).unwrap();
+
+            let read = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(read.musicbrainz_recording_id, tag.musicbrainz_recording_id);
+            assert_eq!(read.musicbrainz_release_id, tag.musicbrainz_release_id);
+            assert_eq!(read.musicbrainz_track_id, tag.musicbrainz_track_id);
+            assert_eq!(read.acoustid_fingerprint, tag.acoustid_fingerprint);
+            assert_eq!(read.musicbrainz_artist_id, None);
+            // the identifiers aren't read as custom items too.
+            assert_eq!(read.custom_items, tag.custom_items);
+        });
+    }
+
+    #[test]
+    fn release_fields_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                barcode: Some("0602537518357".to_string()),
+                catalog_number: Some("B0019297-02".to_string()),
+                label: Some("Some Label".to_string()),
+                copyright: Some("℗ 2013 Some Label".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag.clone(), false).unwrap();
+
+            let read = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(read.barcode, tag.barcode);
+            assert_eq!(read.catalog_number, tag.catalog_number);
+            assert_eq!(read.label, tag.label);
+            assert_eq!(read.copyright, tag.copyright);
+            assert!(read.custom_items.is_empty());
+        });
+    }
+
+    #[test]
+    fn structured_dates_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let release_date = TagDate {
+                year: 2001,
+                month: Some(3),
+                day: Some(4),
+            };
+            let original_date = TagDate {
+                month: Some(6),
+                ..TagDate::year(1999)
+            };
+            let tag = Tag {
+                release_date: Some(release_date),
+                original_date: Some(original_date),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let read = read_primary(path.clone()).unwrap().primary_tag().unwrap();
+            assert_eq!(read.release_date, Some(release_date));
+            assert_eq!(read.recording_date.as_deref(), Some("2001-03-04"));
+            assert_eq!(read.year, Some(2001));
+            assert_eq!(read.original_date, Some(original_date));
+            assert_eq!(read.original_release_date.as_deref(), Some("1999-06"));
+
+            // rewriting the year keeps the month and day of the date.
+            let tag = Tag {
+                year: Some(2001),
+                ..read
+            };
+            write_primary(path.clone(), tag, true).unwrap();
+            let read = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(read.release_date, Some(release_date));
+        });
+    }
+
+    #[test]
+    fn grouping_subtitle_and_compilation_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                grouping: Some("Piano Sonatas".to_string()),
+                subtitle: Some("Live at Wembley".to_string()),
+                is_compilation: Some(true),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag.clone(), false).unwrap();
+
+            let read = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(read.grouping, tag.grouping);
+            assert_eq!(read.subtitle, tag.subtitle);
+            assert_eq!(read.is_compilation, Some(true));
+        });
+    }
+
+    #[test]
+    fn podcast_and_audiobook_fields_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                is_podcast: Some(true),
+                podcast_url: Some("https://example.com/feed.xml".to_string()),
+                episode_id: Some("episode-42".to_string()),
+                narrator: Some("Jefferson Mays".to_string()),
+                series: Some("The Expanse".to_string()),
+                series_part: Some("1".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag.clone(), false).unwrap();
+
+            let read = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(read.is_podcast, Some(true));
+            assert_eq!(read.podcast_url, tag.podcast_url);
+            assert_eq!(read.episode_id, tag.episode_id);
+            assert_eq!(read.narrator, tag.narrator);
+            assert_eq!(read.series, tag.series);
+            assert_eq!(read.series_part, tag.series_part);
+            // the audiobook `TXXX` frames aren't custom items.
+            assert!(read.custom_items.is_empty());
+        });
+    }
+
+    #[test]
+    fn builder_for_file_uses_the_primary_tag_type() {
+        let tag = Tag::builder()
+            .for_file(get_no_tags_sample_file_path())
+            .unwrap()
+            .create();
+        assert_eq!(tag.tag_type, TagType::Id3v2);
+
+        assert!(Tag::builder().for_file(get_fake_path()).is_err());
+    }
+
+    #[test]
+    fn work_and_movement_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                work: Some("Symphony No. 9".to_string()),
+                movement: Some("Allegro ma non troppo".to_string()),
+                movement_number: Some(1),
+                movement_total: Some(4),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag.clone(), false).unwrap();
+
+            let read = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(read.work, tag.work);
+            assert_eq!(read.movement, tag.movement);
+            assert_eq!(read.movement_number, Some(1));
+            assert_eq!(read.movement_total, Some(4));
+            // the work `TXXX` frame isn't a custom item.
+            assert!(read.custom_items.is_empty());
+        });
+    }
+
+    #[test]
+    fn structured_dates_are_read_from_vorbis_comments() {
+        let mut vorbis = lofty::Tag::new(lofty::TagType::VorbisComments);
+        vorbis.insert_text(
+            lofty::ItemKey::RecordingDate,
+            "2001-03-04T21:30:00".to_string(),
+        );
+        vorbis.insert_text(lofty::ItemKey::OriginalReleaseDate, "1999".to_string());
+
+        let tag = Tag::from(&vorbis);
+        assert_eq!(
+            tag.release_date,
+            Some(TagDate {
+                year: 2001,
+                month: Some(3),
+                day: Some(4),
+            })
+        );
+        assert_eq!(tag.original_date, Some(TagDate::year(1999)));
+        assert_eq!(
+            tag.to_lofty().get_string(&lofty::ItemKey::RecordingDate),
+            Some("2001-03-04T21:30:00")
+        );
+    }
+
+    #[test]
+    fn track_and_disc_numbers_round_trip() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_number: Some(5),
+                track_total: Some(12),
+                disc_number: Some(2),
+                disc_total: Some(3),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let tag = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!((tag.track_number, tag.track_total), (Some(5), Some(12)));
+            assert_eq!((tag.disc_number, tag.disc_total), (Some(2), Some(3)));
+        });
+    }
+
+    #[test]
+    fn number_pairs_of_vorbis_comments_are_split() {
+        let mut vorbis = lofty::Tag::new(lofty::TagType::VorbisComments);
+        vorbis.insert_text(lofty::ItemKey::TrackNumber, "05/12".to_string());
+        vorbis.insert_text(lofty::ItemKey::DiscNumber, "2".to_string());
+        vorbis.push_unchecked(TagItem::new(
+            lofty::ItemKey::Unknown("TOTALDISCS".to_string()),
+            ItemValue::Text("3".to_string()),
+        ));
+
+        let tag = Tag::from(&vorbis);
+        assert_eq!((tag.track_number, tag.track_total), (Some(5), Some(12)));
+        assert_eq!((tag.disc_number, tag.disc_total), (Some(2), Some(3)));
+    }
+
+    #[test]
+    fn rating_round_trips_through_popularimeter() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag::builder()
+                .with_tag_type(TagType::FilePrimaryType)
+                .with_rating_stars(3.5)
+                .create();
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let tag = read_primary(path).unwrap().primary_tag().unwrap();
+            assert_eq!(tag.rating, Some(70));
+            assert_eq!(tag.rating_stars(), Some(3.5));
+        });
+    }
+
+    #[test]
+    fn player_ratings_are_normalized() {
+        let mut id3v2 = lofty::Tag::new(lofty::TagType::Id3v2);
+        let mut popm = b"MusicBee\0".to_vec();
+        popm.extend_from_slice(&[196, 0, 0, 0, 3]);
+        id3v2.push_unchecked(TagItem::new(
+            lofty::ItemKey::Popularimeter,
+            ItemValue::Binary(popm),
+        ));
+        assert_eq!(Tag::from(&id3v2).rating, Some(80));
+
+        let mut vorbis = lofty::Tag::new(lofty::TagType::VorbisComments);
+        vorbis.push_unchecked(TagItem::new(
+            lofty::ItemKey::Unknown("RATING".to_string()),
+            ItemValue::Text("3".to_string()),
+        ));
+        let tag = Tag::from(&vorbis);
+        assert_eq!(tag.rating, Some(60));
+        assert!(tag.custom_items.is_empty());
+    }
+
+    #[test]
+    fn replay_gain_round_trips() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let gain = ReplayGain {
+                track_gain_db: Some(-6.48),
+                track_peak: Some(0.988525),
+                album_gain_db: Some(-7.1),
+                album_peak: None,
+                opus_output_gain_db: Some(3.0),
+            };
+            write_replay_gain(path.clone(), gain).unwrap();
+
+            let gain = read_replay_gain(path).unwrap();
+            assert_eq!(gain.track_gain_db, Some(-6.48));
+            assert_eq!(gain.track_peak, Some(0.988525));
+            assert_eq!(gain.album_gain_db, Some(-7.1));
+            assert_eq!(gain.album_peak, None);
+            // only the Opus files have a header gain.
+            assert_eq!(gain.opus_output_gain_db, None);
+        });
+    }
+
+    #[test]
+    fn r128_gains_are_relative_to_minus_23_lufs() {
+        let mut vorbis = lofty::Tag::new(lofty::TagType::VorbisComments);
+        vorbis.push_unchecked(TagItem::new(
+            lofty::ItemKey::Unknown("R128_TRACK_GAIN".to_string()),
+            ItemValue::Text("-2560".to_string()),
+        ));
+        assert_eq!(replay_gain_from(&vorbis, true).track_gain_db, Some(-5.0));
+
+        let gain = ReplayGain {
+            track_gain_db: Some(-8.0),
+            track_peak: Some(0.9),
+            ..ReplayGain::default()
+        };
+        insert_replay_gain(&mut vorbis, &gain, true);
+        let r128 = lofty::ItemKey::Unknown("R128_TRACK_GAIN".to_string());
+        assert_eq!(vorbis.get_string(&r128), Some("-3328"));
+        assert_eq!(
+            vorbis.get_string(&lofty::ItemKey::ReplayGainTrackPeak),
+            None
+        );
+    }
+
+    #[test]
+    fn the_opus_header_gain_is_written_with_a_valid_crc() {
+        let mut page = b"OggS\0\x02".to_vec();
+        page.extend_from_slice(&[0; 8]); // granule position
+        page.extend_from_slice(&1u32.to_le_bytes()); // serial number
+        page.extend_from_slice(&[0; 8]); // sequence number and CRC
+        page.extend_from_slice(&[1, 19]); // one segment of 19 bytes
+        page.extend_from_slice(b"OpusHead");
+        page.extend_from_slice(&[1, 2, 0x38, 0x01, 0x80, 0xbb, 0, 0, 0, 0, 0]);
+        let path = env::temp_dir().join(format!("taggy-opus-{}.opus", generate_uuid()));
+        std::fs::write(&path, &page).unwrap();
+        let path_str = path.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            assert_eq!(read_output_gain(&path_str).unwrap(), 0);
+            write_output_gain(&path_str, db_to_q78(-1.5)).unwrap();
+            assert_eq!(q78_to_db(read_output_gain(&path_str).unwrap()), -1.5);
+
+            let mut written = std::fs::read(&path_str).unwrap();
+            let crc = u32::from_le_bytes(written[22..26].try_into().unwrap());
+            written[22..26].fill(0);
+            assert_eq!(crate::utils::opus_head::ogg_crc(&written), crc);
+        });
+        std::fs::remove_file(&path).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn client_sees_its_own_writes() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let client = TaggyClient::default();
+            let old_tag = client
+                .read_primary(path.clone())
+                .unwrap()
+                .first_tag()
+                .unwrap();
+            let new_tag = Tag::builder().with_tag_type(old_tag.tag_type).create();
+            client
+                .write_primary(path.clone(), new_tag.clone(), false)
+                .unwrap();
+            let tag_after_write = client.read_primary(path).unwrap().first_tag().unwrap();
+            assert_eq!(tag_after_write, new_tag);
+        });
+    }
+
+    #[test]
+    fn client_is_shared_between_threads() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let client = Arc::new(TaggyClient::default());
+            let tag_type = client.read_primary(path.clone()).unwrap().primary_tag_type;
+            let handles: Vec<_> = (0..4)
+                .map(|i| {
+                    let client = client.clone();
+                    let path = path.clone();
+                    std::thread::spawn(move || {
+                        let tag = Tag {
+                            track_title: Some(format!("Title {i}")),
+                            ..Tag::new(tag_type)
+                        };
+                        client.write_primary(path.clone(), tag, false).unwrap();
+                        client.read_primary(path).unwrap()
+                    })
+                })
+                .collect();
+            for handle in handles {
+                let file = handle.join().unwrap();
+                let title = file.primary_tag().unwrap().track_title.unwrap();
+                assert!(title.starts_with("Title "));
+            }
+        });
+    }
+
+    #[test]
+    fn client_cache_reports_stats_and_respects_its_size_limit() {
+        let client = TaggyClient::default();
+        client.read_all(get_audio_sample_file_path()).unwrap();
+        client.read_primary(get_audio_sample_file_path()).unwrap();
+        let stats = client.cache_stats();
+        assert_eq!((stats.entries, stats.hits, stats.misses), (1, 1, 1));
+        assert!(stats.bytes > 0);
+        client.clear_cache();
+        assert_eq!(client.cache_stats().bytes, 0);
+
+        let client = TaggyClient::new(ClientOptions {
+            cache_max_bytes: Some(1),
+            ..ClientOptions::default()
+        });
+        client.read_all(get_audio_sample_file_path()).unwrap();
+        client.read_all(get_audio_sample_file_path()).unwrap();
+        let stats = client.cache_stats();
+        assert_eq!((stats.entries, stats.hits, stats.misses), (0, 0, 2));
+    }
+
+    #[test]
+    fn client_rejects_files_larger_than_the_limit() {
+        let client = TaggyClient::new(ClientOptions {
+            max_file_size: Some(1),
+            ..ClientOptions::default()
+        });
+        let error = client.read_all(get_audio_sample_file_path()).unwrap_err();
+        assert!(matches!(
+            error.downcast_ref::<LimitError>(),
+            Some(LimitError::FileTooLarge { .. })
+        ));
+    }
+
+    #[test]
+    fn read_only_client_refuses_writes() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let client = TaggyClient::new(ClientOptions {
+                read_only: true,
+                ..ClientOptions::default()
+            });
+            let before = client.read_all(path.clone()).unwrap();
+            let error = client
+                .write_primary(path.clone(), Tag::new(TagType::FilePrimaryType), false)
+                .unwrap_err();
+            assert_eq!(TaggyError::from(&error), TaggyError::PermissionDenied);
+            assert!(client.remove_all(path.clone()).is_err());
+            assert_eq!(read_all(path).unwrap().tags, before.tags);
+        });
+    }
+
+    #[test]
+    fn client_throttles_uncached_reads() {
+        let client = TaggyClient::new(ClientOptions {
+            cache_capacity: 0,
+            max_files_per_sec: Some(20),
+            ..ClientOptions::default()
+        });
+        let start = std::time::Instant::now();
+        for _ in 0..3 {
+            client.read_all(get_audio_sample_file_path()).unwrap();
+        }
+        // the first read starts right away, the two others wait 50ms each.
+        assert!(start.elapsed() >= std::time::Duration::from_millis(100));
+    }
+
+    #[test]
+    fn client_hooks_report_cached_reads() {
+        #[derive(Default)]
+        struct Recorder(Mutex<Vec<OperationStats>>);
+        impl TaggyHooks for Recorder {
+            fn on_read_end(&self, stats: &OperationStats) {
+                self.0.lock().unwrap().push(stats.clone());
+            }
+        }
+
+        let recorder = Arc::new(Recorder::default());
+        let client = TaggyClient::default();
+        client.set_hooks(Some(recorder.clone()));
+        client.read_primary(get_audio_sample_file_path()).unwrap();
+        client.read_all(get_audio_sample_file_path()).unwrap();
+
+        let reads = recorder.0.lock().unwrap();
+        assert_eq!(reads.len(), 2);
+        assert!(!reads[0].from_cache);
+        assert!(reads[1].from_cache);
+    }
+
+    #[test]
+    fn failed_transaction_leaves_files_unchanged() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let tags_before = read_all(path.clone()).unwrap().tags;
+            let requests = vec![
+                WriteRequest {
+                    path: path.clone(),
+                    tags: vec![],
+                    override_existent: true,
+                },
+                WriteRequest {
+                    path: get_fake_path(),
+                    tags: vec![],
+                    override_existent: true,
+                },
+            ];
+            assert!(write_transaction(requests).is_err());
+            assert_eq!(read_all(path).unwrap().tags, tags_before);
+        });
+    }
+
+    #[test]
+    fn recover_rolls_back_interrupted_transactions() {
+        let dir = env::temp_dir().join(format!("taggy-recover-{}", generate_uuid()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = std::fs::canonicalize(dir).unwrap();
+        let (first, second) = (dir.join("first.mp3"), dir.join("second.mp3"));
+        let journal = serde_json::json!({ "files": [first, second] }).to_string();
+        // the first file was committed, the second one wasn't yet.
+        std::fs::write(&first, "new").unwrap();
+        std::fs::write(dir.join(".taggy-backup-first.mp3"), "old").unwrap();
+        std::fs::write(&second, "old").unwrap();
+        std::fs::write(dir.join(".taggy-staged-second.mp3"), "new").unwrap();
+        std::fs::write(dir.join(".taggy-journal-first.mp3"), &journal).unwrap();
+        std::fs::write(dir.join(".taggy-journal-second.mp3"), &journal).unwrap();
+        // a write interrupted before its commit.
+        std::fs::write(dir.join("third.mp3"), "old").unwrap();
+        std::fs::write(dir.join(".taggy-staged-third.mp3"), "new").unwrap();
+
+        let report = recover(dir.to_string_lossy().to_string()).unwrap();
+        assert_eq!(
+            report.rolled_back,
+            vec![first.to_string_lossy().to_string()]
+        );
+        assert!(report.completed.is_empty());
+        assert_eq!(report.discarded.len(), 1);
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "old");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "old");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 3);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn recover_completes_committed_transactions() {
+        let dir = env::temp_dir().join(format!("taggy-recover-{}", generate_uuid()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let dir = std::fs::canonicalize(dir).unwrap();
+        let file = dir.join("track.mp3");
+        // committed, but interrupted before removing the backup.
+        std::fs::write(&file, "new").unwrap();
+        std::fs::write(dir.join(".taggy-backup-track.mp3"), "old").unwrap();
+        let journal = serde_json::json!({ "files": [file] }).to_string();
+        std::fs::write(dir.join(".taggy-journal-track.mp3"), journal).unwrap();
+
+        let report = recover(dir.to_string_lossy().to_string()).unwrap();
+        assert_eq!(report.completed, vec![file.to_string_lossy().to_string()]);
+        assert_eq!(std::fs::read_to_string(&file).unwrap(), "new");
+        assert_eq!(std::fs::read_dir(&dir).unwrap().count(), 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn transaction_stages_files_in_temp_dir() {
+        let temp_dir = env::temp_dir().join(format!("taggy-temp-{}", generate_uuid()));
+        std::fs::create_dir_all(&temp_dir).unwrap();
+        let result = panic::catch_unwind(|| {
+            with_duplicate_file(get_audio_sample_file_path(), |path| {
+                let requests = vec![WriteRequest {
+                    path: path.clone(),
+                    tags: vec![Tag {
+                        track_title: Some("Staged Elsewhere".to_string()),
+                        ..Tag::new(TagType::FilePrimaryType)
+                    }],
+                    override_existent: true,
+                }];
+                let temp = temp_dir.to_string_lossy().to_string();
+                write_transaction_with_temp_dir(requests, temp).unwrap();
+
+                let tag = read_primary(path.clone()).unwrap().first_tag().unwrap();
+                assert_eq!(tag.track_title, Some("Staged Elsewhere".to_string()));
+                assert_eq!(std::fs::read_dir(&temp_dir).unwrap().count(), 0);
+                let dir = Path::new(&path).parent().unwrap();
+                assert!(std::fs::read_dir(dir).unwrap().all(|e| !e
+                    .unwrap()
+                    .file_name()
+                    .to_string_lossy()
+                    .starts_with(".taggy-")));
+            });
+        });
+        let _ = std::fs::remove_dir_all(&temp_dir);
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn recover_rolls_back_transactions_staged_in_temp_dir() {
+        let dir = env::temp_dir().join(format!("taggy-recover-{}", generate_uuid()));
+        let transaction = dir.join("cache").join(".taggy-transaction-interrupted");
+        std::fs::create_dir_all(&transaction).unwrap();
+        let dir = std::fs::canonicalize(dir).unwrap();
+        let (first, second) = (dir.join("first.mp3"), dir.join("second.mp3"));
+        let journal = serde_json::json!({ "files": [first, second] }).to_string();
+        std::fs::write(transaction.join("journal.json"), journal).unwrap();
+        // the first file was committed, the second one wasn't yet.
+        std::fs::write(&first, "new").unwrap();
+        std::fs::write(transaction.join("0.backup"), "old").unwrap();
+        std::fs::write(&second, "old").unwrap();
+        std::fs::write(transaction.join("1.staged"), "new").unwrap();
+
+        let cache = dir.join("cache").to_string_lossy().to_string();
+        let report = recover(cache.clone()).unwrap();
+        assert_eq!(
+            report.rolled_back,
+            vec![first.to_string_lossy().to_string()]
+        );
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "old");
+        assert_eq!(std::fs::read_to_string(&second).unwrap(), "old");
+        assert_eq!(std::fs::read_dir(cache).unwrap().count(), 0);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn protected_write_does_not_overwrite_existing_fields() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("Original".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let new_tag = Tag {
+                track_title: Some("Replaced".to_string()),
+                album: Some("Filled".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            let protect = WriteOptions {
+                protect_existing: true,
+                ..WriteOptions::default()
+            };
+            let result =
+                write_primary_with_options(path.clone(), new_tag.clone(), true, protect.clone());
+            assert!(result.is_err());
+
+            let skip = WriteOptions {
+                skip_protected: true,
+                ..protect
+            };
+            write_primary_with_options(path.clone(), new_tag, true, skip).unwrap();
+            let tag_after_write = read_primary(path).unwrap().first_tag().unwrap();
+            assert_eq!(tag_after_write.track_title.as_deref(), Some("Original"));
+            assert_eq!(tag_after_write.album.as_deref(), Some("Filled"));
+        });
+    }
+
+    #[test]
+    fn effective_tag_prefers_the_primary_tag() {
+        let tags = vec![
+            Tag {
+                track_title: Some("From ID3v1".to_string()),
+                album: Some("Only in ID3v1".to_string()),
+                ..Tag::new(TagType::Id3v1)
+            },
+            Tag {
+                track_title: Some("From ID3v2".to_string()),
+                ..Tag::new(TagType::Id3v2)
+            },
+        ];
+        let effective = merge_tags(&tags, TagType::Id3v2);
+        assert_eq!(effective.tag.track_title.as_deref(), Some("From ID3v2"));
+        assert_eq!(effective.tag.album.as_deref(), Some("Only in ID3v1"));
+        assert_eq!(effective.source_of("track_title"), Some(TagType::Id3v2));
+        assert_eq!(effective.source_of("album"), Some(TagType::Id3v1));
+        assert_eq!(effective.source_of("genre"), None);
+    }
+
+    #[test]
+    fn generated_file_ids_are_unique_v4_uuids() {
+        let id = generate_file_id();
+        assert_eq!(id.len(), 36);
+        assert_eq!(&id[14..15], "4");
+        assert_ne!(id, generate_file_id());
+    }
+
+    #[test]
+    fn it_keeps_the_file_id_once_written() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let scope = String::from("com.example.taggy");
+            let id = ensure_file_id(path.clone(), scope.clone()).unwrap();
+            assert_eq!(
+                read_file_id(path.clone(), scope.clone()).unwrap(),
+                Some(id.clone())
+            );
+            assert_eq!(ensure_file_id(path, scope).unwrap(), id);
+        });
+    }
+
+    #[test]
+    fn it_normalizes_isrc_codes() {
+        let isrc = normalize_isrc(String::from("us-rc1-76-07839")).unwrap();
+        assert_eq!(isrc, "USRC17607839");
+        assert!(normalize_isrc(String::from("US-RC1-76-0783")).is_err());
+        assert!(normalize_isrc(String::from("1S-RC1-76-07839")).is_err());
+    }
+
+    #[test]
+    fn invalid_isrc_codes_are_reported() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                isrc: Some("not an isrc".to_string()),
+                ..Tag::new(TagType::Id3v2)
+            };
+
+            let report = write_all_with_report(
+                path.clone(),
+                vec![tag.clone()],
+                false,
+                WriteOptions::default(),
+            )
+            .unwrap();
+            assert_eq!(report.warnings.len(), 1);
+            assert!(report.warnings[0].contains("'not an isrc'"));
+            let file =
+                write_all_with_options(path.clone(), vec![tag], true, WriteOptions::default())
+                    .unwrap();
+            assert!(file.warnings.iter().any(|w| w.contains("ISRC")));
+
+            let file = read_all(path).unwrap();
+            assert_eq!(file.warnings.len(), 1);
+            assert!(file.warnings[0].contains("'not an isrc'"));
+            assert_eq!(
+                file.primary_tag().unwrap().isrc.as_deref(),
+                Some("not an isrc")
+            );
+        });
+    }
+
+    #[test]
+    fn it_detects_the_discs_of_a_folder() {
+        let sample = get_audio_sample_file_path();
+        let dir = Path::new(&sample)
+            .parent()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+        let disc_set = detect_disc_set(dir).unwrap();
+        assert!(!disc_set.discs.is_empty());
+    }
+
+    #[test]
+    fn it_unifies_the_album_art() {
+        let dir = env::temp_dir().join(format!("taggy-album-{}", generate_uuid()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for name in ["01.mp3", "02.mp3"] {
+            copy(get_audio_sample_file_path(), dir.join(name)).unwrap();
+        }
+        let dir_path = dir.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            let changed = unify_album_art(dir_path.clone(), Some(get_pic_from_asset())).unwrap();
+            assert_eq!(changed.len(), 2);
+            for path in &changed {
+                let tag = read_primary(path.clone()).unwrap().first_tag().unwrap();
+                let covers: Vec<&Picture> = tag
+                    .pictures
+                    .iter()
+                    .filter(|p| p.pic_type == PictureType::CoverFront)
+                    .collect();
+                assert_eq!(covers.len(), 1);
+                assert_eq!(covers[0].pic_data, get_pic_from_asset().pic_data);
+            }
+            // the files already have the cover now.
+            assert!(unify_album_art(dir_path.clone(), None).unwrap().is_empty());
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn it_backfills_the_year_from_the_folder_name() {
+        let dir = env::temp_dir().join(format!("taggy-years-{}", generate_uuid()));
+        let album_dir = dir.join("Artist - Album (1997)");
+        std::fs::create_dir_all(&album_dir).unwrap();
+        copy(get_no_tags_sample_file_path(), album_dir.join("01.mp3")).unwrap();
+        let dir_path = dir.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            let suggestions = suggest_years(dir_path.clone()).unwrap();
+            assert_eq!(suggestions.len(), 1);
+            assert_eq!(suggestions[0].year, 1997);
+            assert_eq!(suggestions[0].source, "Artist - Album (1997)");
+
+            let files = apply_years(suggestions).unwrap();
+            assert_eq!(files[0].clone().first_tag().unwrap().year, Some(1997));
+            assert!(suggest_years(dir_path.clone()).unwrap().is_empty());
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn it_moves_the_featured_artists_to_the_title() {
+        let dir = env::temp_dir().join(format!("taggy-featuring-{}", generate_uuid()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("01.mp3");
+        copy(get_no_tags_sample_file_path(), &path).unwrap();
+        let tag = Tag {
+            track_title: Some("Song".to_string()),
+            track_artist: Some("Artist ft. Guest".to_string()),
+            ..Tag::new(TagType::FilePrimaryType)
+        };
+        write_primary(path.to_str().unwrap().to_string(), tag, false).unwrap();
+        let dir_path = dir.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            let changes = suggest_featuring(dir_path.clone(), FeaturingStyle::InTitle).unwrap();
+            assert_eq!(changes.len(), 1);
+            assert_eq!(changes[0].track_title, "Song (feat. Guest)");
+            assert_eq!(changes[0].track_artist, "Artist");
+            assert_eq!(changes[0].previous_track_artist, "Artist ft. Guest");
+
+            let files = apply_featuring(changes).unwrap();
+            let tag = files[0].clone().first_tag().unwrap();
+            assert_eq!(tag.track_title.as_deref(), Some("Song (feat. Guest)"));
+            assert_eq!(tag.track_artist.as_deref(), Some("Artist"));
+            assert!(suggest_featuring(dir_path.clone(), FeaturingStyle::InTitle)
+                .unwrap()
+                .is_empty());
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn featured_artists_are_moved_to_the_artist() {
+        let moved = |title, artist| relocate_featuring(title, artist, FeaturingStyle::InArtist);
+        assert_eq!(
+            moved("Song [Feat. Guest] - Remix", "Artist"),
+            Some(("Song - Remix".to_string(), "Artist feat. Guest".to_string()))
+        );
+        assert_eq!(
+            moved("Song featuring Guest", "Artist feat. Guest"),
+            Some(("Song".to_string(), "Artist feat. Guest".to_string()))
+        );
+        assert_eq!(moved("Feat. Song", "Artist"), None);
+        assert_eq!(moved("Aftermath", "Artist"), None);
+    }
+
+    #[test]
+    fn writing_a_picture_keeps_the_other_fields() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("Cover".to_string()),
+                pictures: vec![get_pic_from_asset()],
+                ..Tag::new(TagType::Id3v2)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            for _ in 0..2 {
+                write_picture(path.clone(), get_pic_from_asset(), PictureType::CoverBack).unwrap();
+            }
+
+            let tag = read_primary(path).unwrap().first_tag().unwrap();
+            assert_eq!(tag.track_title, Some("Cover".to_string()));
+            let types: Vec<PictureType> = tag.pictures.iter().map(|p| p.pic_type).collect();
+            assert_eq!(types, vec![PictureType::CoverFront, PictureType::CoverBack]);
+        });
+    }
+
+    #[test]
+    fn removing_pictures_of_a_type_keeps_the_others() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let back_cover = Picture {
+                pic_type: PictureType::CoverBack,
+                ..get_pic_from_asset()
+            };
+            let tag = Tag {
+                track_title: Some("Covers".to_string()),
+                pictures: vec![get_pic_from_asset(), back_cover],
+                ..Tag::new(TagType::Id3v2)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let file = remove_pictures(path.clone(), Some(PictureType::CoverBack)).unwrap();
+            let tag = file.first_tag().unwrap();
+            assert_eq!(tag.track_title, Some("Covers".to_string()));
+            let types: Vec<PictureType> = tag.pictures.iter().map(|p| p.pic_type).collect();
+            assert_eq!(types, vec![PictureType::CoverFront]);
+
+            let file = remove_pictures(path, None).unwrap();
+            assert!(file.first_tag().unwrap().pictures.is_empty());
+        });
+    }
+
+    #[test]
+    fn updating_picture_meta_keeps_its_data() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let picture = Picture {
+                pic_type: PictureType::Other,
+                description: Some("Scan".to_string()),
+                ..get_pic_from_asset()
+            };
+            let tag = Tag {
+                track_title: Some("Covers".to_string()),
+                pictures: vec![picture.clone()],
+                ..Tag::new(TagType::Id3v2)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let changes = PictureChanges {
+                pic_type: Some(PictureType::CoverFront),
+                description: Some("Front".to_string()),
+                mime_type: None,
+            };
+            let file = update_picture_meta(path.clone(), 0, changes.clone()).unwrap();
+            let tag = file.first_tag().unwrap();
+            assert_eq!(tag.track_title, Some("Covers".to_string()));
+            assert_eq!(tag.pictures.len(), 1);
+            assert_eq!(tag.pictures[0].pic_type, PictureType::CoverFront);
+            assert_eq!(tag.pictures[0].description, Some("Front".to_string()));
+            assert_eq!(tag.pictures[0].pic_data, picture.pic_data);
+            assert_eq!(tag.pictures[0].mime_type, picture.mime_type);
+
+            assert!(update_picture_meta(path, 1, changes).is_err());
+        });
+    }
+
+    #[test]
+    fn it_applies_the_unsupported_tag_policy() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let vorbis_tag = Tag {
+                track_title: Some("Converted".to_string()),
+                ..Tag::new(TagType::VorbisComments)
+            };
+            let options = |policy| WriteOptions {
+                unsupported_tag_policy: policy,
+                ..WriteOptions::default()
+            };
+
+            let report = write_all_with_report(
+                path.clone(),
+                vec![vorbis_tag.clone()],
+                false,
+                options(UnsupportedTagPolicy::Skip),
+            )
+            .unwrap();
+            assert_eq!(report.warnings.len(), 1);
+            assert_eq!(report.tags[0].action, TagWriteAction::Skipped);
+
+            let result = write_all_with_report(
+                path.clone(),
+                vec![vorbis_tag.clone()],
+                false,
+                options(UnsupportedTagPolicy::Error),
+            );
+            assert!(result.is_err());
+
+            let report = write_all_with_report(
+                path.clone(),
+                vec![vorbis_tag],
+                false,
+                options(UnsupportedTagPolicy::ConvertToSupported),
+            )
+            .unwrap();
+            assert_eq!(report.tags[0].action, TagWriteAction::Converted);
+            let primary_tag = report.file.primary_tag().unwrap();
+            assert_eq!(primary_tag.track_title.as_deref(), Some("Converted"));
+        });
+    }
+
+    #[test]
+    fn it_skips_saving_unchanged_tags() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("Title".to_string()),
+                lyrics: Some("Some lyrics".to_string()),
+                lyrics_language: Some("eng".to_string()),
+                custom_items: vec![CustomItem::new("MY_APP_RATING", "4")],
+                pictures: vec![get_pic_from_asset()],
+                ..Tag::new(TagType::Id3v2)
+            };
+            let write = || {
+                write_all_with_report(
+                    path.clone(),
+                    vec![tag.clone()],
+                    false,
+                    WriteOptions::default(),
+                )
+                .unwrap()
+            };
+            assert!(!write().unchanged);
+            let bytes = std::fs::read(&path).unwrap();
+            let modified = std::fs::metadata(&path).unwrap().modified().unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(20));
+
+            assert!(write().unchanged);
+            write_primary(path.clone(), tag.clone(), true).unwrap();
+            write_picture(path.clone(), get_pic_from_asset(), PictureType::CoverFront).unwrap();
+
+            assert_eq!(std::fs::read(&path).unwrap(), bytes);
+            let metadata = std::fs::metadata(&path).unwrap();
+            assert_eq!(metadata.modified().unwrap(), modified);
+        });
+    }
+
+    #[test]
+    fn it_restores_a_snapshot() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let tags_before = read_all(path.clone()).unwrap().tags;
+            let snapshot = snapshot(path.clone()).expect("Failed to take a snapshot");
+            remove_all(path.clone()).unwrap();
+            let taggy = restore(path, snapshot).expect("Failed to restore the snapshot");
+            assert_eq!(taggy.tags, tags_before);
+        });
+    }
+
+    #[test]
+    fn sidecar_tags_overlay_the_embedded_ones() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let primary_tag_type = read_all(path.clone()).unwrap().primary_tag_type;
+            let tag = Tag {
+                track_title: Some("From the sidecar".to_string()),
+                ..Tag::new(primary_tag_type)
+            };
+            sidecar::write_sidecar_tags(&path, vec![tag], false).unwrap();
+            let taggy = read_all_with_sidecar(path.clone()).unwrap();
+            remove_file(sidecar::sidecar_path(&path)).unwrap();
+
+            let primary_tag = taggy.primary_tag().unwrap();
+            assert_eq!(primary_tag.track_title.as_deref(), Some("From the sidecar"));
+        });
+    }
+
+    #[test]
+    fn reading_from_buffer_matches_reading_from_path() {
+        let path = get_audio_sample_file_path();
+        let bytes = std::fs::read(&path).unwrap();
+
+        let primary = read_primary_from_buffer(bytes.clone()).unwrap();
+        assert_eq!(primary.tags, read_primary(path.clone()).unwrap().tags);
+        let any = read_any_from_buffer(bytes).unwrap();
+        assert_eq!(any.tags, read_any(path).unwrap().tags);
+    }
+
+    #[test]
+    fn writing_tags_to_buffer_returns_the_tagged_bytes() {
+        let bytes = std::fs::read(get_no_tags_sample_file_path()).unwrap();
+        let tag = Tag {
+            track_title: Some("In memory".to_string()),
+            ..Tag::new(TagType::Id3v2)
+        };
+
+        let tagged_bytes = write_all_to_buffer(bytes, vec![tag], false).unwrap();
+
+        let taggy = read_all_from_buffer(tagged_bytes).unwrap();
+        let id3v2 = taggy.tags.iter().find(|t| t.tag_type == TagType::Id3v2);
+        assert_eq!(
+            id3v2.and_then(|t| t.track_title.as_deref()),
+            Some("In memory")
+        );
+    }
+
+    #[test]
+    fn reading_from_buffer_keeps_the_frames_read_from_path() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            // an ID3v2.3 tag holding a `TXXX` custom item and two latin1 `TIT2` frames.
+            let mut frames = vec![];
+            for (id, content) in [
+                ("TXXX", &b"MY_APP_PLAYCOUNT\x0012"[..]),
+                ("TIT2", b"First"),
+                ("TIT2", b"Second"),
+            ] {
+                frames.extend(id.as_bytes());
+                frames.extend((content.len() as u32 + 1).to_be_bytes());
+                frames.extend([0, 0, 0]);
+                frames.extend(content);
+            }
+            let mut bytes = b"ID3\x03\x00\x00\x00\x00\x00".to_vec();
+            bytes.push(frames.len() as u8);
+            bytes.extend(frames);
+            bytes.extend(std::fs::read(&path).unwrap());
+            std::fs::write(&path, &bytes).unwrap();
+
+            let file = read_all_from_buffer(bytes).unwrap();
+            let id3v2 = file
+                .tags
+                .iter()
+                .find(|t| t.tag_type == TagType::Id3v2)
+                .unwrap();
+            assert!(id3v2
+                .custom_items
+                .contains(&CustomItem::new("MY_APP_PLAYCOUNT", "12")));
+            assert_eq!(id3v2.track_title.as_deref(), Some("First"));
+            assert_eq!(file.warnings.len(), 1);
+
+            let from_path = read_all(path).unwrap();
+            assert_eq!(file.tags, from_path.tags);
+            assert_eq!(file.warnings, from_path.warnings);
+        });
+    }
+
+    #[test]
+    fn batch_read_reports_each_file() {
+        let results = read_all_batch(vec![get_audio_sample_file_path(), get_fake_path()], 2);
+
+        assert_eq!(results.len(), 2);
+        assert!(results[0].file.is_some() && results[0].error.is_none());
+        assert!(results[1].file.is_none() && results[1].error.is_some());
+        assert_eq!(results[1].path, get_fake_path());
+    }
+
+    #[test]
+    fn it_links_files_as_a_continuous_work() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            link_continuous_work(vec![path.clone()], "Symphony No. 9".to_string(), true).unwrap();
+
+            let link = read_work_link(path).unwrap().unwrap();
+            assert_eq!(link.work, "Symphony No. 9");
+            assert!(link.gapless);
+            assert!(link.show_movement);
+        });
+    }
+
+    #[test]
+    fn scanning_a_directory_reads_its_audio_files() {
+        let sample = get_audio_sample_file_path();
+        let dir = Path::new(&sample)
+            .parent()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let results = scan_directory(dir, false, vec!["MP3".to_string()], 0).unwrap();
+
+        assert!(results
+            .iter()
+            .any(|r| Path::new(&r.path) == Path::new(&sample)));
+        assert!(results
+            .iter()
+            .all(|r| r.path.to_lowercase().ends_with(".mp3")));
+        assert!(scan_directory(get_fake_path(), true, vec![], 0).is_err());
+    }
+
+    #[test]
+    fn unreadable_files_are_quarantined_by_scans() {
+        let dir = env::temp_dir().join(format!("taggy-quarantine-{}", generate_uuid()));
+        std::fs::create_dir_all(&dir).unwrap();
+        copy(get_audio_sample_file_path(), dir.join("good.mp3")).unwrap();
+        std::fs::write(dir.join("empty.mp3"), []).unwrap();
+        std::fs::write(dir.join("garbage.mp3"), b"not an audio file at all").unwrap();
+        let dir_path = dir.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            let result =
+                scan_directory_with_quarantine(dir_path.clone(), false, vec![], 0).unwrap();
+            assert_eq!(result.files.len(), 1);
+            assert!(result.files[0].path.ends_with("good.mp3"));
+            assert_eq!(result.quarantine.len(), 2);
+            assert!(result.quarantine[0].path.ends_with("empty.mp3"));
+            assert_eq!(result.quarantine[0].code, "empty_or_truncated");
+            assert!(result.quarantine[1].path.ends_with("garbage.mp3"));
+            assert!(!result.quarantine[1].reason.is_empty());
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn scans_skip_hidden_and_system_files() {
+        let dir = env::temp_dir().join(format!("taggy-hidden-{}", generate_uuid()));
+        std::fs::create_dir_all(dir.join("__MACOSX")).unwrap();
+        std::fs::create_dir_all(dir.join("$RECYCLE.BIN")).unwrap();
+        copy(get_audio_sample_file_path(), dir.join("01.mp3")).unwrap();
+        copy(get_audio_sample_file_path(), dir.join(".02.mp3")).unwrap();
+        copy(
+            get_audio_sample_file_path(),
+            dir.join("__MACOSX").join("01.mp3"),
+        )
+        .unwrap();
+        copy(
+            get_audio_sample_file_path(),
+            dir.join("$RECYCLE.BIN").join("03.mp3"),
+        )
+        .unwrap();
+        std::fs::write(dir.join("._01.mp3"), b"AppleDouble resource fork").unwrap();
+        let dir_path = dir.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            let results = scan_directory(dir_path.clone(), true, vec![], 0).unwrap();
+            assert_eq!(results.len(), 1);
+            assert!(results[0].path.ends_with("01.mp3"));
+
+            let options = ScanOptions {
+                skip_system_files: false,
+                ..Default::default()
+            };
+            let results = scan_directory_with_options(dir_path.clone(), options).unwrap();
+            assert_eq!(results.len(), 3);
+
+            let options = ScanOptions {
+                skip_hidden: false,
+                skip_system_files: false,
+                ..Default::default()
+            };
+            let results = scan_directory_with_options(dir_path.clone(), options).unwrap();
+            assert_eq!(results.len(), 5);
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn scans_handle_symbolic_links() {
+        use std::os::unix::fs::symlink;
+
+        let dir = env::temp_dir().join(format!("taggy-symlinks-{}", generate_uuid()));
+        let album_dir = dir.join("album");
+        std::fs::create_dir_all(&album_dir).unwrap();
+        copy(get_audio_sample_file_path(), album_dir.join("01.mp3")).unwrap();
+        symlink(&album_dir, dir.join("linked album")).unwrap();
+        // a cycle back to the scanned folder.
+        symlink(&dir, album_dir.join("loop")).unwrap();
+        let dir_path = dir.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            let scan = |symlinks| {
+                scan_directory_with_symlinks(dir_path.clone(), true, vec![], 0, symlinks).unwrap()
+            };
+            assert_eq!(scan(SymlinkPolicy::Skip).len(), 1);
+            assert_eq!(scan(SymlinkPolicy::Follow).len(), 1);
+
+            let results = scan(SymlinkPolicy::Distinct);
+            assert_eq!(results.len(), 2);
+            assert!(results[1].path.contains("linked album"));
+            let canonical_paths: Vec<Option<String>> = results
+                .into_iter()
+                .map(|r| r.file.unwrap().canonical_path)
+                .collect();
+            assert_eq!(canonical_paths[0], canonical_paths[1]);
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn too_long_values_are_truncated_with_a_warning() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("A title much longer than thirty characters".to_string()),
+                ..Tag::new(TagType::Id3v1)
+            };
+            let options = WriteOptions {
+                truncation: TruncationStrategy::WordBoundary,
+                ..WriteOptions::default()
+            };
+
+            let report = write_all_with_report(path, vec![tag], false, options).unwrap();
+
+            assert_eq!(report.warnings.len(), 1);
+            let id3v1 = report
+                .file
+                .tags
+                .iter()
+                .find(|t| t.tag_type == TagType::Id3v1);
+            assert_eq!(
+                id3v1.and_then(|t| t.track_title.as_deref()),
+                Some("A title much longer than")
+            );
+        });
+    }
+
+    #[test]
+    fn long_values_are_kept_by_default() {
+        let tag = Tag {
+            track_title: Some("A".repeat(300)),
+            ..Tag::new(TagType::Mp4Ilst)
+        };
+
+        let (kept, warnings) = crate::utils::truncation::truncate_fields(
+            tag.clone(),
+            WriteOptions::default().truncation,
+        );
+        assert_eq!(kept, tag);
+        assert!(warnings.is_empty());
+
+        let (cut, warnings) =
+            crate::utils::truncation::truncate_fields(tag, TruncationStrategy::HardCut);
+        assert_eq!(cut.track_title.map(|t| t.len()), Some(255));
+        assert_eq!(warnings.len(), 1);
+    }
+
+    #[test]
+    fn writing_a_tag_larger_than_its_limit_fails() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("A title".to_string()),
+                pictures: vec![get_pic_from_asset()],
+                ..Tag::new(TagType::Id3v2)
+            };
+            let limit = |max_size| WriteOptions {
+                max_tag_sizes: vec![TagSizeLimit {
+                    tag_type: TagType::Id3v2,
+                    max_size,
+                }],
+                ..WriteOptions::default()
+            };
+
+            let error = write_all_with_options(path.clone(), vec![tag.clone()], false, limit(1024))
+                .unwrap_err();
+            assert!(matches!(
+                error.downcast_ref::<LimitError>(),
+                Some(LimitError::TagTooLarge {
+                    tag_type: TagType::Id3v2,
+                    max_size: 1024,
+                    ..
+                })
+            ));
+            assert!(read_primary(path.clone()).unwrap().primary_tag().is_none());
+
+            write_all_with_options(path, vec![tag], false, limit(10 * 1024 * 1024)).unwrap();
+        });
+    }
+
+    #[test]
+    fn car_play_profile_warns_about_emojis() {
+        let tag = Tag {
+            track_title: Some("Road trip \u{1F697}".to_string()),
+            ..Tag::new(TagType::Id3v2)
+        };
+
+        assert!(lint_tag(tag.clone(), LintProfile::Standard).is_empty());
+        assert_eq!(lint_tag(tag.clone(), LintProfile::CarPlaySafe).len(), 1);
+        assert!(lint_tag(tag, LintProfile::Off).is_empty());
+    }
+
+    #[test]
+    fn streamed_scan_reports_files_and_progress() {
+        let sample = get_audio_sample_file_path();
+        let dir = Path::new(&sample)
+            .parent()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let mut files = 0;
+        let mut last_progress = None;
+        crate::scanner::scan_directory_stream(&dir, false, &[], 0, |event| match event {
+            ScanEvent::File(_) => files += 1,
+            ScanEvent::Progress { done, total } => last_progress = Some((done, total)),
+        })
+        .unwrap();
+
+        assert!(files > 0);
+        assert_eq!(last_progress, Some((files, files)));
+    }
+
+    #[test]
+    fn old_car_stereo_profile_syncs_id3v1_and_drops_large_pictures() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("Highway".to_string()),
+                pictures: vec![Picture {
+                    width: Some(1000),
+                    height: Some(1000),
+                    ..get_pic_from_asset()
+                }],
+                ..Tag::new(TagType::Id3v2)
+            };
+            let options = write_options_for_profile(WriteProfile::OldCarStereo);
+
+            let report = write_all_with_report(path, vec![tag], false, options).unwrap();
+
+            let id3v1 = report
+                .file
+                .tags
+                .iter()
+                .find(|t| t.tag_type == TagType::Id3v1);
+            assert_eq!(
+                id3v1.and_then(|t| t.track_title.as_deref()),
+                Some("Highway")
+            );
+            let id3v2 = report
+                .file
+                .tags
+                .iter()
+                .find(|t| t.tag_type == TagType::Id3v2);
+            assert!(id3v2.unwrap().pictures.is_empty());
+            assert_eq!(report.warnings.len(), 1);
+        });
+    }
+
+    #[test]
+    fn archival_profile_keeps_long_values() {
+        let options = write_options_for_profile(WriteProfile::Archival);
+        assert_eq!(options.truncation, TruncationStrategy::Off);
+
+        let tag = Tag {
+            album: Some("A".repeat(300)),
+            ..Tag::new(TagType::Mp4Ilst)
+        };
+        let (kept, warnings) =
+            crate::utils::truncation::truncate_fields(tag.clone(), options.truncation);
+        assert_eq!(kept, tag);
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn write_primary_applies_all_the_options() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("A title much longer than thirty characters".to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            let options = WriteOptions {
+                truncation: TruncationStrategy::WordBoundary,
+                sync_id3v1: true,
+                ..WriteOptions::default()
+            };
+
+            let file = write_primary_with_options(path, tag, false, options).unwrap();
+
+            let id3v1 = file.tags.iter().find(|t| t.tag_type == TagType::Id3v1);
+            assert_eq!(
+                id3v1.and_then(|t| t.track_title.as_deref()),
+                Some("A title much longer than")
+            );
+            assert_eq!(
+                file.primary_tag().unwrap().track_title.as_deref(),
+                Some("A title much longer than thirty characters")
+            );
+        });
+    }
+
+    #[test]
+    fn errors_have_a_structured_kind() {
+        let error = read_all(get_fake_path()).unwrap_err();
+        assert_eq!(TaggyError::from(&error), TaggyError::FileNotFound);
+        assert_eq!(TaggyError::from(&error).code(), "file_not_found");
+
+        let error = normalize_isrc(String::from("not an isrc")).unwrap_err();
+        assert!(matches!(
+            TaggyError::from(&error),
+            TaggyError::InvalidValue { .. }
+        ));
+    }
+
+    #[test]
+    fn it_counts_the_values_of_a_field() {
+        let sample = get_audio_sample_file_path();
+        let dir = Path::new(&sample)
+            .parent()
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let values = field_values(dir.clone(), "track_title".to_string()).unwrap();
+
+        assert!(values.windows(2).all(|w| w[0].count >= w[1].count));
+        assert!(field_values(dir, "not_a_field".to_string()).is_err());
+    }
+
+    #[test]
+    fn it_finds_the_songs_by_their_lyrics() {
+        let dir = env::temp_dir().join(format!("taggy-lyrics-{}", generate_uuid()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, lyrics) in [
+            (
+                "01.mp3",
+                "Verse one\nHello darkness, my old friend\nI've come to talk",
+            ),
+            ("02.mp3", "Nothing to see here"),
+        ] {
+            let path = dir.join(name);
+            copy(get_no_tags_sample_file_path(), &path).unwrap();
+            let tag = Tag {
+                lyrics: Some(lyrics.to_string()),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.to_str().unwrap().to_string(), tag, false).unwrap();
+        }
+        let dir_path = dir.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            let matches = search_lyrics(dir_path.clone(), "DARKNESS, my".to_string()).unwrap();
+            assert_eq!(matches.len(), 1);
+            assert!(matches[0].path.ends_with("01.mp3"));
+            assert_eq!(matches[0].snippet, "Hello darkness, my old friend");
+            assert_eq!(matches[0].snippet_offset, 6);
+            assert_eq!(matches[0].snippet_length, 12);
+            assert_eq!(matches[0].offsets, vec![16]);
+
+            // the line breaks match the spaces.
+            let matches = search_lyrics(dir_path.clone(), "one hello".to_string()).unwrap();
+            assert_eq!(
+                matches[0].snippet,
+                "Verse one\nHello darkness, my old friend"
+            );
+            assert!(search_lyrics(dir_path.clone(), " ".to_string()).is_err());
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn it_finds_the_missing_and_duplicated_tracks_of_albums() {
+        let dir = env::temp_dir().join(format!("taggy-gaps-{}", generate_uuid()));
+        std::fs::create_dir_all(&dir).unwrap();
+        for (name, album, track_number) in [
+            ("01.mp3", "Gappy", 1),
+            ("03.mp3", "Gappy", 3),
+            ("03 (copy).mp3", "Gappy", 3),
+            ("a1.mp3", "Complete", 1),
+            ("a2.mp3", "Complete", 2),
+        ] {
+            let path = dir.join(name);
+            copy(get_no_tags_sample_file_path(), &path).unwrap();
+            let tag = Tag {
+                album: Some(album.to_string()),
+                track_number: Some(track_number),
+                track_total: (album == "Gappy").then_some(5),
+                ..Tag::new(TagType::FilePrimaryType)
+            };
+            write_primary(path.to_str().unwrap().to_string(), tag, false).unwrap();
+        }
+        let dir_path = dir.to_str().unwrap().to_string();
+
+        let result = panic::catch_unwind(|| {
+            let gaps = find_gaps(dir_path.clone()).unwrap();
+            assert_eq!(gaps.len(), 1);
+            assert_eq!(gaps[0].album, "Gappy");
+            assert_eq!(gaps[0].disc_number, 1);
+            assert_eq!(gaps[0].track_total, 5);
+            assert_eq!(gaps[0].missing, vec![2, 4, 5]);
+            assert_eq!(gaps[0].duplicated, vec![3]);
+            assert_eq!(gaps[0].files.len(), 3);
+        });
+        std::fs::remove_dir_all(&dir).unwrap();
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+
+    #[test]
+    fn it_suggests_merging_near_duplicate_values() {
+        let count = |value: &str, count| FieldValueCount {
+            value: value.to_string(),
+            count,
+        };
+        let suggestions = suggest_merges(vec![
+            count("Hip-Hop", 2),
+            count("Hip Hop", 5),
+            count("hiphop", 1),
+            count("Electronica", 3),
+            count("Electornica", 1),
+            count("Pop", 4),
+            count("Rap", 4),
+        ]);
+        assert_eq!(
+            suggestions,
+            vec![
+                MergeSuggestion {
+                    canonical: "Hip Hop".to_string(),
+                    variants: vec!["Hip-Hop".to_string(), "hiphop".to_string()],
+                    count: 8,
+                },
+                MergeSuggestion {
+                    canonical: "Electronica".to_string(),
+                    variants: vec!["Electornica".to_string()],
+                    count: 4,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn writing_tags_to_non_existing_file_is_an_error() {
+        let result = write_all(get_fake_path(), vec![], true);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn it_updates_file_primary_tag() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let old_tag = read_primary(path.clone()).unwrap().first_tag().unwrap();
+            let new_tag = Tag::builder().with_tag_type(old_tag.tag_type).create();
+            let result = write_primary(path.clone(), new_tag.clone(), false);
+            let tag_after_write = read_primary(path.clone()).unwrap().first_tag().unwrap();
+            assert!(result.is_ok());
+            assert_eq!(tag_after_write, new_tag);
+        });
+    }
+
+    #[test]
+    fn it_updates_a_file_with_no_tags_after_writing() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            // `TagType::Id3v2` is the primary tag type for the file so the equality assertion passes for
+            // this property.
+            let tag = Tag::builder().with_tag_type(TagType::Id3v2).create();
+            // act
+            let result = write_primary(path.clone(), tag.clone(), true);
+            // assert
+            let created_tag = result.unwrap().tags.first().unwrap().clone();
+            assert_eq!(created_tag, tag);
+        });
+    }
+
+    #[test]
+    fn it_adds_image_to_tag() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let pic = get_pic_from_asset();
+            let tag = Tag::builder().with_pictures(vec![pic.clone()]).create();
+            // act
+            let taggy = write_primary(path.clone(), tag.clone(), false)
+                .expect("Failed to write primary tag");
+            let tag = taggy.primary_tag().unwrap();
+            let added_picture = tag.pictures.first().unwrap();
+            // assert
+            assert_eq!(added_picture.pic_data, pic.pic_data);
+        });
+    }
+
+    #[test]
+    fn it_clears_existing_pictures_while_writing() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag::builder()
+                .with_pictures(vec![get_pic_from_asset()])
+                .create();
+            write_primary(path.clone(), tag, false).expect("Failed to write primary tag");
+            let options = WriteOptions {
+                clear_pictures: true,
+                ..WriteOptions::default()
+            };
+            // act
+            let taggy = write_all_with_options(path.clone(), vec![], false, options)
+                .expect("Failed to write tags");
+            // assert
+            assert!(taggy.tags.iter().all(|t| t.pictures.is_empty()));
+        });
+    }
+
+    #[test]
+    fn it_removes_a_specific_tag_from_file() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let taggy = read_primary(path.clone()).expect("Failed to read primary tag");
+            let tag = taggy.primary_tag();
+            // first assert a tag exists
+            assert!(tag.as_ref().is_some());
+            // act
+            let remove_result = remove_tag(path.clone(), tag.unwrap().tag_type);
+            // if remove_result.
+            // assert
+            assert!(remove_result.is_ok());
+            let taggy_after = read_primary(path.clone()).expect("Failed to read primary tag");
+            assert!(taggy_after.primary_tag().is_none());
+        });
+    }
+    #[test]
+    fn it_removes_all_tags_from_file() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            let taggy = read_primary(path.clone()).expect("Failed to read primary tag");
+            let tag = taggy.primary_tag();
+            // first assert a tag exists
+            assert!(tag.as_ref().is_some());
+            // act
+            let remove_result = remove_all(path.clone());
+            // if remove_result.
+            // assert
+            assert!(remove_result.is_ok());
+            let taggy_after = read_all(path.clone()).expect("Failed to read primary tag");
+            assert!(taggy_after.tags.is_empty());
+        });
+    }
+    #[test]
+    fn it_removes_only_specified_tag_from_file() {
+        with_duplicate_file(get_audio_sample_file_path(), |path| {
+            // setup
+            let new_tag = Tag::builder().with_tag_type(TagType::Ape).create();
+            let taggy = write_all(path.clone(), vec![new_tag.clone()], false)
+                .expect("Failed to write a new tag");
+            assert_eq!(taggy.tags.len(), 2);
+            // act
+            let remove_result = remove_tag(path.clone(), new_tag.tag_type);
+            // assert
+            assert!(remove_result.is_ok());
+            let taggy_after_remove = read_all(path).expect("Failed to read tags");
+            assert_eq!(taggy_after_remove.tags.len(), 1);
+        });
+    }
+
+    #[test]
+    fn strip_fields_removes_only_the_given_classes() {
+        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
+            let tag = Tag {
+                track_title: Some("Shared".to_string()),
+                lyrics: Some("Some lyrics".to_string()),
+                rating: Some(80),
+                comments: vec![Comment::new("A private note")],
+                ..Tag::new(TagType::Id3v2)
+            };
+            write_primary(path.clone(), tag, false).unwrap();
+
+            let classes = vec![FieldClass::Comments, FieldClass::Lyrics];
+            let files = strip_fields(vec![path.clone()], classes.clone()).unwrap();
+            let tag = files[0].clone().first_tag().unwrap();
+            assert_eq!(tag.track_title, Some("Shared".to_string()));
+            assert_eq!(tag.rating, Some(80));
+            assert_eq!(tag.lyrics, None);
+            assert!(tag.comments.is_empty());
+
+            assert!(strip_fields(vec![path, get_fake_path()], classes).is_err());
+        });
+    }
+
+    /*
+     * Helper Functions
+     */
+    fn get_pic_from_asset() -> Picture {
+        let bytes = std::fs::read(get_image_path()).expect("Failed to read image bytes");
+        Picture {
+            pic_type: PictureType::CoverFront,
+            pic_data: bytes,
+            mime_type: Some(MimeType::Jpeg),
+            width: None,
+            height: None,
+            color_depth: None,
+            num_colors: None,
+            data_size: None,
+            description: None,
+            storage: None,
+        }
+    }
+
+    /// Creates a copy of the file at given `path` and place it in the same directory.
+    /// Then calls [op] with the duplicated file path.
+    ///
+    /// The duplicated file will be **automatically removed** when [op] is finished.
+    fn with_duplicate_file(path: String, op: impl FnOnce(String) + panic::UnwindSafe) {
+        let path = Path::new(&path);
+        let extension = path.extension().and_then(|e| e.to_str()).unwrap_or("mp3");
+        // unique so the tests running in parallel never share a copy.
+        let file_name = format!("test_copy_{}.{extension}", generate_uuid());
+        let copy_path = path.with_file_name(file_name);
+        copy(path, copy_path.as_path()).expect("Failed to copy the test sample file");
+        let duplicate_path = copy_path.to_str().unwrap().to_string();
+
+        // Execute the operation within a Result context
+        let result = panic::catch_unwind(|| op(duplicate_path.clone()));
+
+        // Remove the duplicate file regardless of the operation result
+        remove_duplicate(&duplicate_path);
+
+        // Check if the operation panicked and Re-throw the error
+        if let Err(panic) = result {
+            panic::resume_unwind(panic);
+        }
+    }
+    fn remove_duplicate(path: &str) {
+        remove_file(path).expect("Failed to remove test asset");
+    }
+    fn get_image_path() -> String {
+        let current_dir = env::current_dir().expect("Failed to get current directory");
+        current_dir
+            .parent()
+            .unwrap()
+            .join("test_samples\\image.jpg")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn get_audio_sample_file_path() -> String {
+        let current_dir = env::current_dir().expect("Failed to get current directory");
+        current_dir
+            .parent()
+            .unwrap()
+            .join("test_samples\\sample.mp3")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn get_no_tags_sample_file_path() -> String {
+        let current_dir = env::current_dir().expect("Failed to get current directory");
+        current_dir
+            .parent()
+            .unwrap()
+            .join("test_samples\\no_tags.mp3")
+            .to_str()
+            .unwrap()
+            .to_string()
+    }
+
+    fn get_fake_path() -> String {
+        String::from("fake/path/file.mp3")
+    }
+}
@@ -0,0 +1,50 @@
+/// How [optimize_artwork](crate::api::optimize_artwork) re-encodes the embedded pictures.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ArtworkOptions {
+    /// The JPEG quality of the re-encoded pictures, from `1` to `100`, `85` by default.
+    pub quality: u8,
+    /// The largest width or height of the pictures in pixels, the larger ones are downscaled to
+    /// it, keeping their aspect ratio.
+    pub max_size: Option<u32>,
+    /// Compute the savings without writing the files.
+    pub dry_run: bool,
+    /// The number of files optimized at the same time, `0` for one per CPU core.
+    pub concurrency: u32,
+}
+
+impl Default for ArtworkOptions {
+    fn default() -> Self {
+        Self {
+            quality: 85,
+            max_size: None,
+            dry_run: false,
+            concurrency: 0,
+        }
+    }
+}
+
+/// The outcome of optimizing the pictures of a single file,
+/// see [optimize_artwork](crate::api::optimize_artwork).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ArtworkSavings {
+    /// The path of the file, as it was requested.
+    pub path: String,
+    /// The number of pictures replaced with a smaller re-encoded one.
+    pub pictures_optimized: u32,
+    /// The size of the data of all the pictures of the file before, in bytes.
+    pub bytes_before: u64,
+    /// The size of the data of all the pictures of the file after, in bytes.
+    pub bytes_after: u64,
+    /// Why the file couldn't be optimized, it's left untouched then, or how many of its pictures
+    /// couldn't be decoded, the others are still optimized.
+    pub error: Option<String>,
+}
+
+impl ArtworkSavings {
+    /// The number of bytes the pictures of the file take less.
+    pub fn bytes_saved(&self) -> u64 {
+        self.bytes_before.saturating_sub(self.bytes_after)
+    }
+}
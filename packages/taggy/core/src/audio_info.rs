@@ -0,0 +1,68 @@
+use std::time::Duration;
+
+/// The information of an audio track
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct AudioInfo {
+    /// The duration in seconds.
+    pub duration_sec: Option<u64>,
+    /// The duration in milliseconds.
+    pub duration_ms: Option<u64>,
+    /// The duration split into hours, minutes, seconds and milliseconds.
+    pub duration: Option<AudioDuration>,
+    pub overall_bitrate: Option<u32>,
+    pub audio_bitrate: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub bit_depth: Option<u8>,
+    pub channels: Option<u8>,
+    pub channel_mask: Option<u32>,
+    /// The number of samples per audio frame, for the formats where it's fixed
+    /// (`1` for uncompressed PCM).
+    pub frame_length: Option<u32>,
+    /// The duration of an audio frame in microseconds, the finest position players can
+    /// seek to without decoding, when the `frame_length` is known.
+    pub seek_granularity_us: Option<u32>,
+}
+impl Default for AudioInfo {
+    fn default() -> Self {
+        Self {
+            duration_sec: None,
+            duration_ms: None,
+            duration: None,
+            overall_bitrate: None,
+            audio_bitrate: None,
+            sample_rate: None,
+            bit_depth: None,
+            channels: None,
+            channel_mask: None,
+            frame_length: None,
+            seek_granularity_us: None,
+        }
+    }
+}
+
+/// A duration split into its components, so it can be shown as e.g. `1:02:03.456`
+/// without any conversion.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct AudioDuration {
+    pub hours: u32,
+    /// `0` to `59`.
+    pub minutes: u32,
+    /// `0` to `59`.
+    pub seconds: u32,
+    /// `0` to `999`.
+    pub millis: u32,
+}
+
+impl From<Duration> for AudioDuration {
+    fn from(value: Duration) -> Self {
+        let secs = value.as_secs();
+        Self {
+            hours: (secs / 3600) as u32,
+            minutes: (secs / 60 % 60) as u32,
+            seconds: (secs % 60) as u32,
+            millis: value.subsec_millis(),
+        }
+    }
+}
@@ -0,0 +1,33 @@
+use crate::taggy_file::TaggyFile;
+
+/// The outcome of reading a single file of a batch, see [read_all_batch](crate::api::read_all_batch).
+///
+/// Exactly one of `file` and `error` is set.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct BatchReadResult {
+    /// The path of the file, as it was requested, or its name in the archive for
+    /// `read_zip_entries`.
+    pub path: String,
+    /// The file tags, when it was read successfully.
+    pub file: Option<TaggyFile>,
+    /// Why the file couldn't be read.
+    pub error: Option<String>,
+}
+
+impl BatchReadResult {
+    pub(crate) fn new(path: String, result: anyhow::Result<TaggyFile>) -> Self {
+        match result {
+            Ok(file) => Self {
+                path,
+                file: Some(file),
+                error: None,
+            },
+            Err(e) => Self {
+                path,
+                file: None,
+                error: Some(e.to_string()),
+            },
+        }
+    }
+}
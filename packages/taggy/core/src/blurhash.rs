@@ -0,0 +1,98 @@
+use crate::utils::images::decode_thumbnail;
+use image::RgbImage;
+use std::f32::consts::PI;
+
+/// The size the pictures are downscaled to before being encoded,
+/// the hash only keeps their low frequencies anyway.
+const SAMPLE_SIZE: u32 = 32;
+
+/// The number of horizontal and vertical components, the usual 4x3 of the BlurHash examples.
+const X_COMPONENTS: usize = 4;
+const Y_COMPONENTS: usize = 3;
+
+const BASE83_CHARS: &[u8] =
+    b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz#$%*+,-.:;=?@[]^_{|}~";
+
+/// Decodes the picture `data` and encodes it as a [BlurHash](https://blurha.sh),
+/// e.g. `LEHV6nWB2yk8pyo0adR*.7kCMdnj`.
+pub(crate) fn blurhash(data: &[u8]) -> anyhow::Result<String> {
+    Ok(encode(&decode_thumbnail(data, SAMPLE_SIZE)?))
+}
+
+fn encode(image: &RgbImage) -> String {
+    let factors: Vec<[f32; 3]> = (0..Y_COMPONENTS)
+        .flat_map(|j| (0..X_COMPONENTS).map(move |i| (i, j)))
+        .map(|(i, j)| factor(image, i, j))
+        .collect();
+    let (dc, ac) = factors.split_first().unwrap();
+
+    let mut hash = String::with_capacity(4 + 2 * factors.len());
+    let size_flag = (X_COMPONENTS - 1) + (Y_COMPONENTS - 1) * 9;
+    push_base83(&mut hash, size_flag as u32, 1);
+
+    let actual_max = ac
+        .iter()
+        .flatten()
+        .fold(0.0_f32, |max, value| max.max(value.abs()));
+    let quantised_max = (actual_max * 166.0 - 0.5).floor().clamp(0.0, 82.0) as u32;
+    let max_value = (quantised_max + 1) as f32 / 166.0;
+    push_base83(&mut hash, quantised_max, 1);
+
+    let [r, g, b] = dc.map(linear_to_srgb);
+    push_base83(&mut hash, (r << 16) + (g << 8) + b, 4);
+    for component in ac {
+        let [r, g, b] = component.map(|value| {
+            let quantised = (sign_pow(value / max_value, 0.5) * 9.0 + 9.5).floor();
+            quantised.clamp(0.0, 18.0) as u32
+        });
+        push_base83(&mut hash, r * 19 * 19 + g * 19 + b, 2);
+    }
+    hash
+}
+
+/// The weight of the `(i, j)` cosine component in the linear colors of the `image`.
+fn factor(image: &RgbImage, i: usize, j: usize) -> [f32; 3] {
+    let (width, height) = image.dimensions();
+    let normalisation = if i == 0 && j == 0 { 1.0 } else { 2.0 };
+    let mut sum = [0.0; 3];
+    for (x, y, pixel) in image.enumerate_pixels() {
+        let basis = normalisation
+            * (PI * i as f32 * x as f32 / width as f32).cos()
+            * (PI * j as f32 * y as f32 / height as f32).cos();
+        for (sum, channel) in sum.iter_mut().zip(pixel.0) {
+            *sum += basis * srgb_to_linear(channel);
+        }
+    }
+    let scale = 1.0 / (width * height) as f32;
+    sum.map(|value| value * scale)
+}
+
+fn srgb_to_linear(value: u8) -> f32 {
+    let v = value as f32 / 255.0;
+    if v <= 0.04045 {
+        v / 12.92
+    } else {
+        ((v + 0.055) / 1.055).powf(2.4)
+    }
+}
+
+fn linear_to_srgb(value: f32) -> u32 {
+    let v = value.clamp(0.0, 1.0);
+    let srgb = if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    };
+    (srgb * 255.0 + 0.5) as u32
+}
+
+fn sign_pow(value: f32, exp: f32) -> f32 {
+    value.abs().powf(exp).copysign(value)
+}
+
+fn push_base83(hash: &mut String, value: u32, length: u32) {
+    for i in 1..=length {
+        let digit = (value / 83_u32.pow(length - i)) % 83;
+        hash.push(BASE83_CHARS[digit as usize] as char);
+    }
+}
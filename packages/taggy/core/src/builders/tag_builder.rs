@@ -0,0 +1,777 @@
+use crate::api::read_primary_tag_type;
+use crate::comment::Comment;
+use crate::custom_item::CustomItem;
+use crate::picture::Picture;
+use crate::tag::{Tag, TagType};
+use crate::tag_date::TagDate;
+use crate::utils::dates::{parse_tag_date, release_date_of};
+use fake::{
+    faker::lorem::en::Sentences, faker::lorem::en::Word, faker::name::en::Name,
+    faker::time::en::Date, Fake,
+};
+use rand::Rng;
+
+/// responsible for creating a [`Tag`] instance
+#[derive(Debug)]
+pub struct TagBuilder {
+    tag_type: Option<TagType>,
+    pictures: Vec<Picture>,
+    // Track General Info
+    track_title: Option<String>,
+
+    track_artist: Option<String>,
+
+    album: Option<String>,
+
+    album_artist: Option<String>,
+
+    producer: Option<String>,
+    /// The number of the track on its disk.
+    track_number: Option<u32>,
+    /// The disk total track count
+    track_total: Option<u32>,
+
+    disc_number: Option<u32>,
+
+    disc_total: Option<u32>,
+
+    year: Option<u32>,
+
+    recording_date: Option<String>,
+
+    original_release_date: Option<String>,
+
+    release_date: Option<TagDate>,
+
+    original_date: Option<TagDate>,
+
+    language: Option<String>,
+
+    lyrics: Option<String>,
+
+    genre: Option<String>,
+
+    /// The International Standard Recording Code, e.g. `USRC17607839`.
+    isrc: Option<String>,
+
+    composer: Option<String>,
+
+    conductor: Option<String>,
+
+    remixer: Option<String>,
+
+    artist_sort: Option<String>,
+
+    album_sort: Option<String>,
+
+    title_sort: Option<String>,
+
+    album_artist_sort: Option<String>,
+
+    bpm: Option<u32>,
+
+    initial_key: Option<String>,
+
+    mood: Option<String>,
+
+    rating: Option<u32>,
+
+    lyrics_language: Option<String>,
+
+    lyrics_description: Option<String>,
+
+    musicbrainz_recording_id: Option<String>,
+
+    musicbrainz_release_id: Option<String>,
+
+    musicbrainz_release_group_id: Option<String>,
+
+    musicbrainz_artist_id: Option<String>,
+
+    musicbrainz_album_artist_id: Option<String>,
+
+    musicbrainz_track_id: Option<String>,
+
+    acoustid_id: Option<String>,
+
+    acoustid_fingerprint: Option<String>,
+
+    barcode: Option<String>,
+
+    catalog_number: Option<String>,
+
+    label: Option<String>,
+
+    copyright: Option<String>,
+
+    grouping: Option<String>,
+
+    subtitle: Option<String>,
+
+    work: Option<String>,
+
+    movement: Option<String>,
+
+    movement_number: Option<u32>,
+
+    movement_total: Option<u32>,
+
+    is_compilation: Option<bool>,
+
+    is_podcast: Option<bool>,
+
+    podcast_url: Option<String>,
+
+    episode_id: Option<String>,
+
+    narrator: Option<String>,
+
+    series: Option<String>,
+
+    series_part: Option<String>,
+
+    custom_items: Vec<CustomItem>,
+
+    artists: Vec<String>,
+
+    genres: Vec<String>,
+
+    composers: Vec<String>,
+
+    comments: Vec<Comment>,
+}
+
+impl TagBuilder {
+    pub fn new() -> Self {
+        Self {
+            tag_type: None,
+            pictures: vec![],
+            track_title: None,
+            track_artist: None,
+            album: None,
+            album_artist: None,
+            producer: None,
+            track_number: None,
+            track_total: None,
+            disc_number: None,
+            disc_total: None,
+            year: None,
+            recording_date: None,
+            original_release_date: None,
+            release_date: None,
+            original_date: None,
+            language: None,
+            lyrics: None,
+            genre: None,
+            isrc: None,
+            composer: None,
+            conductor: None,
+            remixer: None,
+            artist_sort: None,
+            album_sort: None,
+            title_sort: None,
+            album_artist_sort: None,
+            bpm: None,
+            initial_key: None,
+            mood: None,
+            rating: None,
+            lyrics_language: None,
+            lyrics_description: None,
+            musicbrainz_recording_id: None,
+            musicbrainz_release_id: None,
+            musicbrainz_release_group_id: None,
+            musicbrainz_artist_id: None,
+            musicbrainz_album_artist_id: None,
+            musicbrainz_track_id: None,
+            acoustid_id: None,
+            acoustid_fingerprint: None,
+            barcode: None,
+            catalog_number: None,
+            label: None,
+            copyright: None,
+            grouping: None,
+            subtitle: None,
+            work: None,
+            movement: None,
+            movement_number: None,
+            movement_total: None,
+            is_compilation: None,
+            is_podcast: None,
+            podcast_url: None,
+            episode_id: None,
+            narrator: None,
+            series: None,
+            series_part: None,
+            custom_items: vec![],
+            artists: vec![],
+            genres: vec![],
+            composers: vec![],
+            comments: vec![],
+        }
+    }
+    pub fn with_tag_type(self, tag_type: impl Into<TagType>) -> Self {
+        Self {
+            tag_type: Some(tag_type.into()),
+            ..self
+        }
+    }
+
+    /// Sets the tag type to the one of the primary tag of the file at `path`, see
+    /// [read_primary_tag_type](crate::api::read_primary_tag_type), so the tag is written
+    /// where players look for it, e.g. to the Vorbis comments of a FLAC file.
+    pub fn for_file(self, path: impl Into<String>) -> anyhow::Result<Self> {
+        Ok(self.with_tag_type(read_primary_tag_type(path.into())?))
+    }
+
+    pub fn with_title(self, title: impl Into<String>) -> Self {
+        Self {
+            track_title: Some(title.into()),
+            ..self
+        }
+    }
+    pub fn with_album(self, album: impl Into<String>) -> Self {
+        Self {
+            album: Some(album.into()),
+            ..self
+        }
+    }
+    pub fn with_artist(self, artist: impl Into<String>) -> Self {
+        Self {
+            track_artist: Some(artist.into()),
+            ..self
+        }
+    }
+    pub fn with_album_artist(self, album_artist: impl Into<String>) -> Self {
+        Self {
+            album_artist: Some(album_artist.into()),
+            ..self
+        }
+    }
+    pub fn with_track_number(self, track_number: impl Into<u32>) -> Self {
+        Self {
+            track_number: Some(track_number.into()),
+            ..self
+        }
+    }
+    pub fn with_track_total(self, track_total: impl Into<u32>) -> Self {
+        Self {
+            track_total: Some(track_total.into()),
+            ..self
+        }
+    }
+    pub fn with_disk_number(self, disc_number: impl Into<u32>) -> Self {
+        Self {
+            disc_number: Some(disc_number.into()),
+            ..self
+        }
+    }
+    pub fn with_disk_total(self, disc_total: impl Into<u32>) -> Self {
+        Self {
+            disc_total: Some(disc_total.into()),
+            ..self
+        }
+    }
+    pub fn with_recording_date(self, recording_date: impl Into<String>) -> Self {
+        Self {
+            recording_date: Some(recording_date.into()),
+            ..self
+        }
+    }
+    pub fn with_release_date(self, release_date: impl Into<String>) -> Self {
+        Self {
+            original_release_date: Some(release_date.into()),
+            ..self
+        }
+    }
+    pub fn with_release_tag_date(self, release_date: TagDate) -> Self {
+        Self {
+            release_date: Some(release_date),
+            ..self
+        }
+    }
+    pub fn with_original_tag_date(self, original_date: TagDate) -> Self {
+        Self {
+            original_date: Some(original_date),
+            ..self
+        }
+    }
+    pub fn with_year(self, year: impl Into<u32>) -> Self {
+        Self {
+            year: Some(year.into()),
+            ..self
+        }
+    }
+    pub fn with_language(self, language: impl Into<String>) -> Self {
+        Self {
+            language: Some(language.into()),
+            ..self
+        }
+    }
+    pub fn with_genre(self, genre: impl Into<String>) -> Self {
+        Self {
+            genre: Some(genre.into()),
+            ..self
+        }
+    }
+    pub fn with_producer(self, producer: impl Into<String>) -> Self {
+        Self {
+            producer: Some(producer.into()),
+            ..self
+        }
+    }
+    pub fn with_lyrics(self, lyrics: impl Into<String>) -> Self {
+        Self {
+            lyrics: Some(lyrics.into()),
+            ..self
+        }
+    }
+    pub fn with_pictures(self, pictures: impl Into<Vec<Picture>>) -> TagBuilder {
+        TagBuilder {
+            pictures: pictures.into(),
+            ..self
+        }
+    }
+
+    pub fn with_isrc(self, isrc: impl Into<String>) -> Self {
+        Self {
+            isrc: Some(isrc.into()),
+            ..self
+        }
+    }
+
+    pub fn with_composer(self, composer: impl Into<String>) -> Self {
+        Self {
+            composer: Some(composer.into()),
+            ..self
+        }
+    }
+
+    pub fn with_conductor(self, conductor: impl Into<String>) -> Self {
+        Self {
+            conductor: Some(conductor.into()),
+            ..self
+        }
+    }
+
+    pub fn with_remixer(self, remixer: impl Into<String>) -> Self {
+        Self {
+            remixer: Some(remixer.into()),
+            ..self
+        }
+    }
+
+    pub fn with_artist_sort(self, artist_sort: impl Into<String>) -> Self {
+        Self {
+            artist_sort: Some(artist_sort.into()),
+            ..self
+        }
+    }
+
+    pub fn with_album_sort(self, album_sort: impl Into<String>) -> Self {
+        Self {
+            album_sort: Some(album_sort.into()),
+            ..self
+        }
+    }
+
+    pub fn with_title_sort(self, title_sort: impl Into<String>) -> Self {
+        Self {
+            title_sort: Some(title_sort.into()),
+            ..self
+        }
+    }
+
+    pub fn with_album_artist_sort(self, album_artist_sort: impl Into<String>) -> Self {
+        Self {
+            album_artist_sort: Some(album_artist_sort.into()),
+            ..self
+        }
+    }
+
+    pub fn with_bpm(self, bpm: impl Into<u32>) -> Self {
+        Self {
+            bpm: Some(bpm.into()),
+            ..self
+        }
+    }
+
+    pub fn with_initial_key(self, initial_key: impl Into<String>) -> Self {
+        Self {
+            initial_key: Some(initial_key.into()),
+            ..self
+        }
+    }
+
+    pub fn with_mood(self, mood: impl Into<String>) -> Self {
+        Self {
+            mood: Some(mood.into()),
+            ..self
+        }
+    }
+
+    /// Sets the rating from 0 to 100, the higher ones being capped to 100.
+    pub fn with_rating(self, rating: impl Into<u32>) -> Self {
+        Self {
+            rating: Some(rating.into().min(100)),
+            ..self
+        }
+    }
+
+    /// Sets the rating from 0 to 5 stars, rounded to the nearest half star.
+    pub fn with_rating_stars(self, stars: f32) -> Self {
+        let half_stars = (stars.clamp(0.0, 5.0) * 2.0).round() as u32;
+        Self {
+            rating: Some(half_stars * 10),
+            ..self
+        }
+    }
+
+    pub fn with_lyrics_language(self, lyrics_language: impl Into<String>) -> Self {
+        Self {
+            lyrics_language: Some(lyrics_language.into()),
+            ..self
+        }
+    }
+
+    pub fn with_lyrics_description(self, lyrics_description: impl Into<String>) -> Self {
+        Self {
+            lyrics_description: Some(lyrics_description.into()),
+            ..self
+        }
+    }
+
+    pub fn with_musicbrainz_recording_id(
+        self,
+        musicbrainz_recording_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            musicbrainz_recording_id: Some(musicbrainz_recording_id.into()),
+            ..self
+        }
+    }
+
+    pub fn with_musicbrainz_release_id(self, musicbrainz_release_id: impl Into<String>) -> Self {
+        Self {
+            musicbrainz_release_id: Some(musicbrainz_release_id.into()),
+            ..self
+        }
+    }
+
+    pub fn with_musicbrainz_release_group_id(
+        self,
+        musicbrainz_release_group_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            musicbrainz_release_group_id: Some(musicbrainz_release_group_id.into()),
+            ..self
+        }
+    }
+
+    pub fn with_musicbrainz_artist_id(self, musicbrainz_artist_id: impl Into<String>) -> Self {
+        Self {
+            musicbrainz_artist_id: Some(musicbrainz_artist_id.into()),
+            ..self
+        }
+    }
+
+    pub fn with_musicbrainz_album_artist_id(
+        self,
+        musicbrainz_album_artist_id: impl Into<String>,
+    ) -> Self {
+        Self {
+            musicbrainz_album_artist_id: Some(musicbrainz_album_artist_id.into()),
+            ..self
+        }
+    }
+
+    pub fn with_musicbrainz_track_id(self, musicbrainz_track_id: impl Into<String>) -> Self {
+        Self {
+            musicbrainz_track_id: Some(musicbrainz_track_id.into()),
+            ..self
+        }
+    }
+
+    pub fn with_acoustid_id(self, acoustid_id: impl Into<String>) -> Self {
+        Self {
+            acoustid_id: Some(acoustid_id.into()),
+            ..self
+        }
+    }
+
+    pub fn with_acoustid_fingerprint(self, acoustid_fingerprint: impl Into<String>) -> Self {
+        Self {
+            acoustid_fingerprint: Some(acoustid_fingerprint.into()),
+            ..self
+        }
+    }
+
+    pub fn with_barcode(self, barcode: impl Into<String>) -> Self {
+        Self {
+            barcode: Some(barcode.into()),
+            ..self
+        }
+    }
+
+    pub fn with_catalog_number(self, catalog_number: impl Into<String>) -> Self {
+        Self {
+            catalog_number: Some(catalog_number.into()),
+            ..self
+        }
+    }
+
+    pub fn with_label(self, label: impl Into<String>) -> Self {
+        Self {
+            label: Some(label.into()),
+            ..self
+        }
+    }
+
+    pub fn with_copyright(self, copyright: impl Into<String>) -> Self {
+        Self {
+            copyright: Some(copyright.into()),
+            ..self
+        }
+    }
+
+    pub fn with_grouping(self, grouping: impl Into<String>) -> Self {
+        Self {
+            grouping: Some(grouping.into()),
+            ..self
+        }
+    }
+
+    pub fn with_subtitle(self, subtitle: impl Into<String>) -> Self {
+        Self {
+            subtitle: Some(subtitle.into()),
+            ..self
+        }
+    }
+
+    pub fn with_work(self, work: impl Into<String>) -> Self {
+        Self {
+            work: Some(work.into()),
+            ..self
+        }
+    }
+
+    pub fn with_movement(self, movement: impl Into<String>) -> Self {
+        Self {
+            movement: Some(movement.into()),
+            ..self
+        }
+    }
+
+    pub fn with_movement_number(self, movement_number: impl Into<u32>) -> Self {
+        Self {
+            movement_number: Some(movement_number.into()),
+            ..self
+        }
+    }
+
+    pub fn with_movement_total(self, movement_total: impl Into<u32>) -> Self {
+        Self {
+            movement_total: Some(movement_total.into()),
+            ..self
+        }
+    }
+
+    pub fn with_compilation(self, is_compilation: bool) -> Self {
+        Self {
+            is_compilation: Some(is_compilation),
+            ..self
+        }
+    }
+
+    pub fn with_podcast(self, is_podcast: bool) -> Self {
+        Self {
+            is_podcast: Some(is_podcast),
+            ..self
+        }
+    }
+
+    pub fn with_podcast_url(self, podcast_url: impl Into<String>) -> Self {
+        Self {
+            podcast_url: Some(podcast_url.into()),
+            ..self
+        }
+    }
+
+    pub fn with_episode_id(self, episode_id: impl Into<String>) -> Self {
+        Self {
+            episode_id: Some(episode_id.into()),
+            ..self
+        }
+    }
+
+    pub fn with_narrator(self, narrator: impl Into<String>) -> Self {
+        Self {
+            narrator: Some(narrator.into()),
+            ..self
+        }
+    }
+
+    pub fn with_series(self, series: impl Into<String>) -> Self {
+        Self {
+            series: Some(series.into()),
+            ..self
+        }
+    }
+
+    pub fn with_series_part(self, series_part: impl Into<String>) -> Self {
+        Self {
+            series_part: Some(series_part.into()),
+            ..self
+        }
+    }
+
+    pub fn with_artists(self, artists: Vec<String>) -> Self {
+        Self { artists, ..self }
+    }
+
+    pub fn with_genres(self, genres: Vec<String>) -> Self {
+        Self { genres, ..self }
+    }
+
+    pub fn with_composers(self, composers: Vec<String>) -> Self {
+        Self { composers, ..self }
+    }
+
+    pub fn with_custom_item(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.custom_items.push(CustomItem::new(key, value));
+        self
+    }
+
+    pub fn with_comment(mut self, comment: Comment) -> Self {
+        self.comments.push(comment);
+        self
+    }
+
+    pub fn create(self) -> Tag {
+        let recording_date = self
+            .recording_date
+            .or_else(|| self.release_date.map(|date| date.to_string()))
+            .or(Some(Date().fake()));
+        let original_release_date = self
+            .original_release_date
+            .or_else(|| self.original_date.map(|date| date.to_string()))
+            .or(Some(Date().fake()));
+        // This is similar to how [`lofty::Tag`] sets the year.
+        // it also helps to keep a consistent data between our [Tag] and lofty's.
+        let year = self
+            .year
+            .or_else(|| try_parse_year(&recording_date.clone().unwrap()));
+        let track_artist = self
+            .track_artist
+            .or(self.artists.first().cloned())
+            .or(Some(Name().fake()));
+        let genre = self
+            .genre
+            .or(self.genres.first().cloned())
+            .or(Some(Word().fake::<String>().into()));
+        let composer = self.composer.or(self.composers.first().cloned());
+        Tag {
+            tag_type: self.tag_type.or(Some(TagType::random())).unwrap(),
+            pictures: self.pictures,
+            track_title: self.track_title.or(Some(Word().fake())),
+            track_artist: track_artist.clone(),
+            album: self.album.or(Some(Name().fake())),
+            album_artist: self.album_artist.or(Some(Name().fake::<String>().into())),
+            // don't auto generate a producer if not specified.
+            // the reason is due to writing a producer tag value to "ID3v2" tags.
+            producer: self.producer,
+            track_number: self.track_number.or(Some(get_random_int(None))),
+            track_total: self.track_total.or(Some(get_random_int(None))),
+            disc_number: self.disc_number.or(Some(get_random_int(None))),
+            disc_total: self.disc_total.or(Some(get_random_int(None))),
+            year,
+            // the structured dates are read back from the text ones.
+            release_date: self
+                .release_date
+                .or_else(|| release_date_of(recording_date.as_deref(), year)),
+            original_date: self
+                .original_date
+                .or_else(|| original_release_date.as_deref().and_then(parse_tag_date)),
+            recording_date,
+            original_release_date,
+            language: self.language.or(Some(Word().fake())),
+            lyrics: self
+                .lyrics
+                .or(Some(Sentences(1..4).fake::<Vec<String>>().join(" "))),
+            genre: genre.clone(),
+            isrc: self.isrc,
+            composer: composer.clone(),
+            conductor: self.conductor,
+            remixer: self.remixer,
+            artist_sort: self.artist_sort,
+            album_sort: self.album_sort,
+            title_sort: self.title_sort,
+            album_artist_sort: self.album_artist_sort,
+            bpm: self.bpm,
+            initial_key: self.initial_key,
+            mood: self.mood,
+            rating: self.rating,
+            lyrics_language: self.lyrics_language,
+            lyrics_description: self.lyrics_description,
+            musicbrainz_recording_id: self.musicbrainz_recording_id,
+            musicbrainz_release_id: self.musicbrainz_release_id,
+            musicbrainz_release_group_id: self.musicbrainz_release_group_id,
+            musicbrainz_artist_id: self.musicbrainz_artist_id,
+            musicbrainz_album_artist_id: self.musicbrainz_album_artist_id,
+            musicbrainz_track_id: self.musicbrainz_track_id,
+            acoustid_id: self.acoustid_id,
+            acoustid_fingerprint: self.acoustid_fingerprint,
+            barcode: self.barcode,
+            catalog_number: self.catalog_number,
+            label: self.label,
+            copyright: self.copyright,
+            grouping: self.grouping,
+            subtitle: self.subtitle,
+            work: self.work,
+            movement: self.movement,
+            movement_number: self.movement_number,
+            movement_total: self.movement_total,
+            is_compilation: self.is_compilation,
+            is_podcast: self.is_podcast,
+            podcast_url: self.podcast_url,
+            episode_id: self.episode_id,
+            narrator: self.narrator,
+            series: self.series,
+            series_part: self.series_part,
+            custom_items: self.custom_items,
+            // the single values are read back as the first of the multiple ones.
+            artists: if self.artists.is_empty() {
+                track_artist.into_iter().collect()
+            } else {
+                self.artists
+            },
+            genres: if self.genres.is_empty() {
+                genre.into_iter().collect()
+            } else {
+                self.genres
+            },
+            composers: if self.composers.is_empty() {
+                composer.into_iter().collect()
+            } else {
+                self.composers
+            },
+            comments: self.comments,
+        }
+    }
+}
+
+fn get_random_int(max: Option<u32>) -> u32 {
+    let max = max.or(Some(10)).unwrap();
+    rand::thread_rng().gen_range(0..=max)
+}
+fn try_parse_year(input: &str) -> Option<u32> {
+    let (num_digits, year) = input
+        .chars()
+        .skip_while(|c| c.is_whitespace())
+        .take_while(char::is_ascii_digit)
+        .take(4)
+        .fold((0usize, 0u32), |(num_digits, year), c| {
+            let decimal_digit = c.to_digit(10).expect("decimal digit");
+            (num_digits + 1, year * 10 + decimal_digit)
+        });
+    (num_digits == 4).then_some(year)
+}
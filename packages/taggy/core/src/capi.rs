@@ -0,0 +1,633 @@
+//! A C API over taggy's core functions for non-Flutter consumers.
+//!
+//! The `include/taggy.h` header is generated from this module with `cbindgen`,
+//! see `scripts/generate-c-header.sh`.
+//!
+//! **Memory**: every `TaggyFile`/`TaggyTag` returned by a `taggy_*` function is owned by the caller
+//! and must be released with the matching `*_free` function, strings returned by
+//! [taggy_tag_get_text] must be released with [taggy_string_free].
+//!
+//! **Panics**: a panic never unwinds into the caller, the function returns its failure value
+//! instead (`NULL`, `-1`, `false` or `0`) and [taggy_last_error] tells about it.
+use crate::api;
+use crate::error::TaggyError;
+use crate::tag::{Tag, TagType};
+use crate::taggy_file::TaggyFile;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> = RefCell::new(None);
+    static LAST_ERROR_CODE: RefCell<Option<CString>> = RefCell::new(None);
+}
+
+/// The C counterpart of [TagType].
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub enum TaggyTagType {
+    Ape,
+    Id3v1,
+    Id3v2,
+    Mp4Ilst,
+    VorbisComments,
+    RiffInfo,
+    AiffText,
+    FilePrimaryType,
+    Other,
+}
+
+/// The text fields of a tag which can be accessed through the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub enum TaggyTextField {
+    Title,
+    Artist,
+    Album,
+    AlbumArtist,
+    Producer,
+    RecordingDate,
+    OriginalReleaseDate,
+    Language,
+    Lyrics,
+    Genre,
+    Isrc,
+    Composer,
+    Conductor,
+    Remixer,
+    ArtistSort,
+    AlbumSort,
+    TitleSort,
+    AlbumArtistSort,
+    InitialKey,
+    Mood,
+    LyricsLanguage,
+    LyricsDescription,
+    MusicBrainzRecordingId,
+    MusicBrainzReleaseId,
+    MusicBrainzReleaseGroupId,
+    MusicBrainzArtistId,
+    MusicBrainzAlbumArtistId,
+    MusicBrainzTrackId,
+    AcoustidId,
+    AcoustidFingerprint,
+    Barcode,
+    CatalogNumber,
+    Label,
+    Copyright,
+    Grouping,
+    Subtitle,
+    Work,
+    Movement,
+    PodcastUrl,
+    EpisodeId,
+    Narrator,
+    Series,
+    SeriesPart,
+}
+
+/// The numeric fields of a tag which can be accessed through the C API.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub enum TaggyNumberField {
+    TrackNumber,
+    TrackTotal,
+    DiscNumber,
+    DiscTotal,
+    Year,
+    Bpm,
+    Rating,
+    MovementNumber,
+    MovementTotal,
+}
+
+/// Returns the message of the last error that happened on the calling thread, or `NULL`.
+///
+/// The returned string is owned by taggy and stays valid until the next failing call.
+#[no_mangle]
+pub extern "C" fn taggy_last_error() -> *const c_char {
+    catch_panic(ptr::null(), || {
+        LAST_ERROR.with(|e| e.borrow().as_ref().map_or(ptr::null(), |msg| msg.as_ptr()))
+    })
+}
+
+/// Returns the stable code of the last error that happened on the calling thread
+/// (e.g. `file_not_found`, see [TaggyError::code]), or `NULL`.
+///
+/// The returned string is owned by taggy and stays valid until the next failing call.
+#[no_mangle]
+pub extern "C" fn taggy_last_error_code() -> *const c_char {
+    catch_panic(ptr::null(), || {
+        LAST_ERROR_CODE.with(|e| {
+            e.borrow()
+                .as_ref()
+                .map_or(ptr::null(), |code| code.as_ptr())
+        })
+    })
+}
+
+/// Reads all the tags of the file at given `path`.
+///
+/// Returns `NULL` on failure, see [taggy_last_error].
+///
+/// # Safety
+/// `path` must be a valid, nul-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_read_all(path: *const c_char) -> *mut TaggyFile {
+    catch_panic(ptr::null_mut(), || read_with(path, api::read_all))
+}
+
+/// Reads only the primary tag of the file at given `path`.
+///
+/// Returns `NULL` on failure, see [taggy_last_error].
+///
+/// # Safety
+/// `path` must be a valid, nul-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_read_primary(path: *const c_char) -> *mut TaggyFile {
+    catch_panic(ptr::null_mut(), || read_with(path, api::read_primary))
+}
+
+/// Reads any tag of the file at given `path`.
+///
+/// Returns `NULL` on failure, see [taggy_last_error].
+///
+/// # Safety
+/// `path` must be a valid, nul-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_read_any(path: *const c_char) -> *mut TaggyFile {
+    catch_panic(ptr::null_mut(), || read_with(path, api::read_any))
+}
+
+/// Releases a file returned by one of the `taggy_read_*` functions.
+///
+/// # Safety
+/// `file` must be `NULL` or a pointer returned by taggy which wasn't freed before.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_file_free(file: *mut TaggyFile) {
+    catch_panic((), || {
+        if !file.is_null() {
+            drop(Box::from_raw(file));
+        }
+    })
+}
+
+/// Returns the number of tags the given `file` has.
+///
+/// # Safety
+/// `file` must be a valid pointer returned by taggy.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_file_tags_count(file: *const TaggyFile) -> usize {
+    catch_panic(0, || file.as_ref().map_or(0, |f| f.tags.len()))
+}
+
+/// Returns the tag at `index` of the given `file`, or `NULL` if out of bounds.
+///
+/// The returned tag is borrowed from `file` and must **not** be freed.
+///
+/// # Safety
+/// `file` must be a valid pointer returned by taggy.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_file_tag(file: *const TaggyFile, index: usize) -> *const Tag {
+    catch_panic(ptr::null(), || {
+        file.as_ref()
+            .and_then(|f| f.tags.get(index))
+            .map_or(ptr::null(), |t| t as *const Tag)
+    })
+}
+
+/// Returns the duration in seconds of the given `file`, or `0` if unknown.
+///
+/// # Safety
+/// `file` must be a valid pointer returned by taggy.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_file_duration_sec(file: *const TaggyFile) -> u64 {
+    catch_panic(0, || {
+        file.as_ref()
+            .and_then(|f| f.audio.duration_sec)
+            .unwrap_or_default()
+    })
+}
+
+/// Returns the duration in milliseconds of the given `file`, or `0` if unknown.
+///
+/// # Safety
+/// `file` must be a valid pointer returned by taggy.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_file_duration_ms(file: *const TaggyFile) -> u64 {
+    catch_panic(0, || {
+        file.as_ref()
+            .and_then(|f| f.audio.duration_ms)
+            .unwrap_or_default()
+    })
+}
+
+/// Creates a new empty tag with the given `tag_type`.
+#[no_mangle]
+pub extern "C" fn taggy_tag_new(tag_type: TaggyTagType) -> *mut Tag {
+    catch_panic(ptr::null_mut(), || {
+        Box::into_raw(Box::new(Tag::new(tag_type.into())))
+    })
+}
+
+/// Releases a tag created with [taggy_tag_new].
+///
+/// # Safety
+/// `tag` must be `NULL` or a pointer returned by [taggy_tag_new] which wasn't freed before.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_tag_free(tag: *mut Tag) {
+    catch_panic((), || {
+        if !tag.is_null() {
+            drop(Box::from_raw(tag));
+        }
+    })
+}
+
+/// Returns the type of the given `tag`.
+///
+/// # Safety
+/// `tag` must be a valid pointer returned by taggy.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_tag_type(tag: *const Tag) -> TaggyTagType {
+    catch_panic(TaggyTagType::Other, || {
+        tag.as_ref()
+            .map_or(TaggyTagType::Other, |t| TaggyTagType::from(t.tag_type))
+    })
+}
+
+/// Returns a copy of the given text `field` of `tag`, or `NULL` if it isn't set.
+///
+/// The returned string must be released with [taggy_string_free].
+///
+/// # Safety
+/// `tag` must be a valid pointer returned by taggy.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_tag_get_text(tag: *const Tag, field: TaggyTextField) -> *mut c_char {
+    catch_panic(ptr::null_mut(), || {
+        let Some(tag) = tag.as_ref() else {
+            return ptr::null_mut();
+        };
+        text_field(tag, field)
+            .as_ref()
+            .and_then(|value| CString::new(value.as_str()).ok())
+            .map_or(ptr::null_mut(), CString::into_raw)
+    })
+}
+
+/// Sets the given text `field` of `tag` to `value`, passing `NULL` clears the field.
+///
+/// # Safety
+/// `tag` must be a valid pointer returned by [taggy_tag_new] and `value` must be `NULL`
+/// or a valid, nul-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_tag_set_text(
+    tag: *mut Tag,
+    field: TaggyTextField,
+    value: *const c_char,
+) {
+    catch_panic((), || {
+        let Some(tag) = tag.as_mut() else {
+            return;
+        };
+        *text_field_mut(tag, field) = to_string(value);
+    })
+}
+
+/// Writes the given numeric `field` of `tag` into `out`.
+///
+/// Returns `false` if the field isn't set.
+///
+/// # Safety
+/// `tag` must be a valid pointer returned by taggy and `out` must be a valid pointer.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_tag_get_number(
+    tag: *const Tag,
+    field: TaggyNumberField,
+    out: *mut u32,
+) -> bool {
+    catch_panic(false, || {
+        match tag.as_ref().and_then(|t| *number_field(t, field)) {
+            Some(value) if !out.is_null() => {
+                *out = value;
+                true
+            }
+            _ => false,
+        }
+    })
+}
+
+/// Sets the given numeric `field` of `tag` to `value`.
+///
+/// # Safety
+/// `tag` must be a valid pointer returned by [taggy_tag_new].
+#[no_mangle]
+pub unsafe extern "C" fn taggy_tag_set_number(tag: *mut Tag, field: TaggyNumberField, value: u32) {
+    catch_panic((), || {
+        if let Some(tag) = tag.as_mut() {
+            *number_field_mut(tag, field) = Some(value);
+        }
+    })
+}
+
+/// Clears the given numeric `field` of `tag`.
+///
+/// # Safety
+/// `tag` must be a valid pointer returned by [taggy_tag_new].
+#[no_mangle]
+pub unsafe extern "C" fn taggy_tag_clear_number(tag: *mut Tag, field: TaggyNumberField) {
+    catch_panic((), || {
+        if let Some(tag) = tag.as_mut() {
+            *number_field_mut(tag, field) = None;
+        }
+    })
+}
+
+/// Writes `tag` as the primary tag of the file at given `path`.
+///
+/// Returns `0` on success and `-1` on failure, see [taggy_last_error].
+///
+/// # Safety
+/// `path` must be a valid, nul-terminated UTF-8 string and `tag` a valid pointer returned by taggy.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_write_primary(
+    path: *const c_char,
+    tag: *const Tag,
+    keep_others: bool,
+) -> i32 {
+    catch_panic(-1, || {
+        let (Some(path), Some(tag)) = (to_string(path), tag.as_ref()) else {
+            return invalid_argument();
+        };
+        to_status(api::write_primary(path, tag.clone(), keep_others).map(|_| ()))
+    })
+}
+
+/// Deletes all tags from the file at given `path`.
+///
+/// Returns `0` on success and `-1` on failure, see [taggy_last_error].
+///
+/// # Safety
+/// `path` must be a valid, nul-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_remove_all(path: *const c_char) -> i32 {
+    catch_panic(-1, || {
+        let Some(path) = to_string(path) else {
+            return invalid_argument();
+        };
+        to_status(api::remove_all(path))
+    })
+}
+
+/// Deletes the tag of the given `tag_type` from the file at given `path`.
+///
+/// Returns `0` on success and `-1` on failure, see [taggy_last_error].
+///
+/// # Safety
+/// `path` must be a valid, nul-terminated UTF-8 string.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_remove_tag(path: *const c_char, tag_type: TaggyTagType) -> i32 {
+    catch_panic(-1, || {
+        let Some(path) = to_string(path) else {
+            return invalid_argument();
+        };
+        to_status(api::remove_tag(path, tag_type.into()))
+    })
+}
+
+/// Releases a string returned by taggy.
+///
+/// # Safety
+/// `value` must be `NULL` or a string returned by taggy which wasn't freed before.
+#[no_mangle]
+pub unsafe extern "C" fn taggy_string_free(value: *mut c_char) {
+    catch_panic((), || {
+        if !value.is_null() {
+            drop(CString::from_raw(value));
+        }
+    })
+}
+
+impl From<TaggyTagType> for TagType {
+    fn from(value: TaggyTagType) -> Self {
+        match value {
+            TaggyTagType::Ape => TagType::Ape,
+            TaggyTagType::Id3v1 => TagType::Id3v1,
+            TaggyTagType::Id3v2 => TagType::Id3v2,
+            TaggyTagType::Mp4Ilst => TagType::Mp4Ilst,
+            TaggyTagType::VorbisComments => TagType::VorbisComments,
+            TaggyTagType::RiffInfo => TagType::RiffInfo,
+            TaggyTagType::AiffText => TagType::AiffText,
+            TaggyTagType::FilePrimaryType => TagType::FilePrimaryType,
+            TaggyTagType::Other => TagType::Other,
+        }
+    }
+}
+
+impl From<TagType> for TaggyTagType {
+    fn from(value: TagType) -> Self {
+        match value {
+            TagType::Ape => TaggyTagType::Ape,
+            TagType::Id3v1 => TaggyTagType::Id3v1,
+            TagType::Id3v2 => TaggyTagType::Id3v2,
+            TagType::Mp4Ilst => TaggyTagType::Mp4Ilst,
+            TagType::VorbisComments => TaggyTagType::VorbisComments,
+            TagType::RiffInfo => TaggyTagType::RiffInfo,
+            TagType::AiffText => TaggyTagType::AiffText,
+            TagType::FilePrimaryType => TaggyTagType::FilePrimaryType,
+            TagType::Other => TaggyTagType::Other,
+        }
+    }
+}
+
+/*
+ * Helper Functions
+ */
+
+/// Runs `f`, returning the `failure` value with the panic as the last error when it panics,
+/// since unwinding into the C caller is undefined behavior.
+fn catch_panic<T>(failure: T, f: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|message| message.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_default();
+        set_last_error(TaggyError::Other {
+            message: format!("taggy panicked: {message}"),
+        });
+        failure
+    })
+}
+
+unsafe fn read_with(
+    path: *const c_char,
+    read: fn(String) -> anyhow::Result<TaggyFile>,
+) -> *mut TaggyFile {
+    let Some(path) = to_string(path) else {
+        invalid_argument();
+        return ptr::null_mut();
+    };
+    match read(path) {
+        Ok(file) => Box::into_raw(Box::new(file)),
+        Err(e) => {
+            set_last_error(TaggyError::from(&e));
+            ptr::null_mut()
+        }
+    }
+}
+
+unsafe fn to_string(value: *const c_char) -> Option<String> {
+    if value.is_null() {
+        return None;
+    }
+    CStr::from_ptr(value).to_str().ok().map(str::to_string)
+}
+
+fn to_status(result: anyhow::Result<()>) -> i32 {
+    match result {
+        Ok(_) => 0,
+        Err(e) => {
+            set_last_error(TaggyError::from(&e));
+            -1
+        }
+    }
+}
+
+fn invalid_argument() -> i32 {
+    set_last_error(TaggyError::invalid_value(
+        "Invalid argument: expected a non-null UTF-8 string",
+    ));
+    -1
+}
+
+fn set_last_error(error: TaggyError) {
+    let message = CString::new(error.to_string().replace('\0', "")).unwrap_or_default();
+    LAST_ERROR.with(|e| *e.borrow_mut() = Some(message));
+    let code = CString::new(error.code()).unwrap_or_default();
+    LAST_ERROR_CODE.with(|e| *e.borrow_mut() = Some(code));
+}
+
+fn text_field(tag: &Tag, field: TaggyTextField) -> &Option<String> {
+    match field {
+        TaggyTextField::Title => &tag.track_title,
+        TaggyTextField::Artist => &tag.track_artist,
+        TaggyTextField::Album => &tag.album,
+        TaggyTextField::AlbumArtist => &tag.album_artist,
+        TaggyTextField::Producer => &tag.producer,
+        TaggyTextField::RecordingDate => &tag.recording_date,
+        TaggyTextField::OriginalReleaseDate => &tag.original_release_date,
+        TaggyTextField::Language => &tag.language,
+        TaggyTextField::Lyrics => &tag.lyrics,
+        TaggyTextField::Genre => &tag.genre,
+        TaggyTextField::Isrc => &tag.isrc,
+        TaggyTextField::Composer => &tag.composer,
+        TaggyTextField::Conductor => &tag.conductor,
+        TaggyTextField::Remixer => &tag.remixer,
+        TaggyTextField::ArtistSort => &tag.artist_sort,
+        TaggyTextField::AlbumSort => &tag.album_sort,
+        TaggyTextField::TitleSort => &tag.title_sort,
+        TaggyTextField::AlbumArtistSort => &tag.album_artist_sort,
+        TaggyTextField::InitialKey => &tag.initial_key,
+        TaggyTextField::Mood => &tag.mood,
+        TaggyTextField::LyricsLanguage => &tag.lyrics_language,
+        TaggyTextField::LyricsDescription => &tag.lyrics_description,
+        TaggyTextField::MusicBrainzRecordingId => &tag.musicbrainz_recording_id,
+        TaggyTextField::MusicBrainzReleaseId => &tag.musicbrainz_release_id,
+        TaggyTextField::MusicBrainzReleaseGroupId => &tag.musicbrainz_release_group_id,
+        TaggyTextField::MusicBrainzArtistId => &tag.musicbrainz_artist_id,
+        TaggyTextField::MusicBrainzAlbumArtistId => &tag.musicbrainz_album_artist_id,
+        TaggyTextField::MusicBrainzTrackId => &tag.musicbrainz_track_id,
+        TaggyTextField::AcoustidId => &tag.acoustid_id,
+        TaggyTextField::AcoustidFingerprint => &tag.acoustid_fingerprint,
+        TaggyTextField::Barcode => &tag.barcode,
+        TaggyTextField::CatalogNumber => &tag.catalog_number,
+        TaggyTextField::Label => &tag.label,
+        TaggyTextField::Copyright => &tag.copyright,
+        TaggyTextField::Grouping => &tag.grouping,
+        TaggyTextField::Subtitle => &tag.subtitle,
+        TaggyTextField::Work => &tag.work,
+        TaggyTextField::Movement => &tag.movement,
+        TaggyTextField::PodcastUrl => &tag.podcast_url,
+        TaggyTextField::EpisodeId => &tag.episode_id,
+        TaggyTextField::Narrator => &tag.narrator,
+        TaggyTextField::Series => &tag.series,
+        TaggyTextField::SeriesPart => &tag.series_part,
+    }
+}
+
+fn text_field_mut(tag: &mut Tag, field: TaggyTextField) -> &mut Option<String> {
+    match field {
+        TaggyTextField::Title => &mut tag.track_title,
+        TaggyTextField::Artist => &mut tag.track_artist,
+        TaggyTextField::Album => &mut tag.album,
+        TaggyTextField::AlbumArtist => &mut tag.album_artist,
+        TaggyTextField::Producer => &mut tag.producer,
+        TaggyTextField::RecordingDate => &mut tag.recording_date,
+        TaggyTextField::OriginalReleaseDate => &mut tag.original_release_date,
+        TaggyTextField::Language => &mut tag.language,
+        TaggyTextField::Lyrics => &mut tag.lyrics,
+        TaggyTextField::Genre => &mut tag.genre,
+        TaggyTextField::Isrc => &mut tag.isrc,
+        TaggyTextField::Composer => &mut tag.composer,
+        TaggyTextField::Conductor => &mut tag.conductor,
+        TaggyTextField::Remixer => &mut tag.remixer,
+        TaggyTextField::ArtistSort => &mut tag.artist_sort,
+        TaggyTextField::AlbumSort => &mut tag.album_sort,
+        TaggyTextField::TitleSort => &mut tag.title_sort,
+        TaggyTextField::AlbumArtistSort => &mut tag.album_artist_sort,
+        TaggyTextField::InitialKey => &mut tag.initial_key,
+        TaggyTextField::Mood => &mut tag.mood,
+        TaggyTextField::LyricsLanguage => &mut tag.lyrics_language,
+        TaggyTextField::LyricsDescription => &mut tag.lyrics_description,
+        TaggyTextField::MusicBrainzRecordingId => &mut tag.musicbrainz_recording_id,
+        TaggyTextField::MusicBrainzReleaseId => &mut tag.musicbrainz_release_id,
+        TaggyTextField::MusicBrainzReleaseGroupId => &mut tag.musicbrainz_release_group_id,
+        TaggyTextField::MusicBrainzArtistId => &mut tag.musicbrainz_artist_id,
+        TaggyTextField::MusicBrainzAlbumArtistId => &mut tag.musicbrainz_album_artist_id,
+        TaggyTextField::MusicBrainzTrackId => &mut tag.musicbrainz_track_id,
+        TaggyTextField::AcoustidId => &mut tag.acoustid_id,
+        TaggyTextField::AcoustidFingerprint => &mut tag.acoustid_fingerprint,
+        TaggyTextField::Barcode => &mut tag.barcode,
+        TaggyTextField::CatalogNumber => &mut tag.catalog_number,
+        TaggyTextField::Label => &mut tag.label,
+        TaggyTextField::Copyright => &mut tag.copyright,
+        TaggyTextField::Grouping => &mut tag.grouping,
+        TaggyTextField::Subtitle => &mut tag.subtitle,
+        TaggyTextField::Work => &mut tag.work,
+        TaggyTextField::Movement => &mut tag.movement,
+        TaggyTextField::PodcastUrl => &mut tag.podcast_url,
+        TaggyTextField::EpisodeId => &mut tag.episode_id,
+        TaggyTextField::Narrator => &mut tag.narrator,
+        TaggyTextField::Series => &mut tag.series,
+        TaggyTextField::SeriesPart => &mut tag.series_part,
+    }
+}
+
+fn number_field(tag: &Tag, field: TaggyNumberField) -> &Option<u32> {
+    match field {
+        TaggyNumberField::TrackNumber => &tag.track_number,
+        TaggyNumberField::TrackTotal => &tag.track_total,
+        TaggyNumberField::DiscNumber => &tag.disc_number,
+        TaggyNumberField::DiscTotal => &tag.disc_total,
+        TaggyNumberField::Year => &tag.year,
+        TaggyNumberField::Bpm => &tag.bpm,
+        TaggyNumberField::Rating => &tag.rating,
+        TaggyNumberField::MovementNumber => &tag.movement_number,
+        TaggyNumberField::MovementTotal => &tag.movement_total,
+    }
+}
+
+fn number_field_mut(tag: &mut Tag, field: TaggyNumberField) -> &mut Option<u32> {
+    match field {
+        TaggyNumberField::TrackNumber => &mut tag.track_number,
+        TaggyNumberField::TrackTotal => &mut tag.track_total,
+        TaggyNumberField::DiscNumber => &mut tag.disc_number,
+        TaggyNumberField::DiscTotal => &mut tag.disc_total,
+        TaggyNumberField::Year => &mut tag.year,
+        TaggyNumberField::Bpm => &mut tag.bpm,
+        TaggyNumberField::Rating => &mut tag.rating,
+        TaggyNumberField::MovementNumber => &mut tag.movement_number,
+        TaggyNumberField::MovementTotal => &mut tag.movement_total,
+    }
+}
@@ -0,0 +1,25 @@
+use crate::picture::Picture;
+
+/// A chapter of an audiobook or podcast episode, see [read_chapters](crate::api::read_chapters)
+/// and [write_chapters](crate::api::write_chapters).
+///
+/// Stored in the ID3v2 `CHAP` frames, listed by a `CTOC` one, and in the chapter track or the
+/// Nero `chpl` atom of MP4 files.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Chapter {
+    /// The time the chapter starts at, in milliseconds from the start of the track.
+    pub start_ms: u32,
+    /// The time the chapter ends at, in milliseconds from the start of the track.
+    pub end_ms: u32,
+    /// The title of the chapter, e.g. `Chapter 1: The Boy Who Lived`.
+    pub title: Option<String>,
+    /// A link to more about the chapter, e.g. the show notes of a podcast episode.
+    ///
+    /// Only stored in ID3v2 tags.
+    pub url: Option<String>,
+    /// The picture shown during the chapter, e.g. a slide.
+    ///
+    /// Only stored in ID3v2 tags.
+    pub picture: Option<Picture>,
+}
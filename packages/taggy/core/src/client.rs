@@ -0,0 +1,468 @@
+use crate::api;
+#[cfg(feature = "zip")]
+use crate::batch_result::BatchReadResult;
+use crate::error::TaggyError;
+use crate::hooks::{Stopwatch, TaggyHooks};
+use crate::limits::{check_file_size, with_timeout, Throttle};
+use crate::tag::{Tag, TagType};
+use crate::taggy_file::TaggyFile;
+use crate::utils::path_locks::{with_read_lock, with_write_lock};
+use crate::utils::tag_fields::for_each_text_field;
+use std::collections::{HashMap, VecDeque};
+use std::fmt::{Debug, Formatter};
+use std::fs;
+use std::sync::{Arc, Mutex, MutexGuard, PoisonError, RwLock};
+use std::time::SystemTime;
+
+/// The options a [TaggyClient] is created with.
+#[derive(Debug, Clone)]
+pub struct ClientOptions {
+    /// The maximum number of parsed files to keep in memory, `0` disables caching.
+    pub cache_capacity: u32,
+    /// The maximum estimated memory taken by the cached files, mostly by their pictures,
+    /// in bytes. The least recently used files are dropped beyond it, `None` for no limit.
+    pub cache_max_bytes: Option<u64>,
+    /// Files larger than this many bytes are rejected with [LimitError::FileTooLarge](crate::limits::LimitError::FileTooLarge),
+    /// `None` for no limit.
+    pub max_file_size: Option<u64>,
+    /// Reads taking longer than this many milliseconds fail with [LimitError::TimedOut](crate::limits::LimitError::TimedOut),
+    /// `None` for no limit.
+    pub timeout_ms: Option<u64>,
+    /// The maximum number of files read from or written to disk per second, `None` for no limit.
+    ///
+    /// Reads served from the cache aren't throttled.
+    pub max_files_per_sec: Option<u32>,
+    /// The maximum number of file bytes read from or written to disk per second, `None` for no limit.
+    ///
+    /// A file is accounted with its whole size, even though only its tags are usually read.
+    pub max_bytes_per_sec: Option<u64>,
+    /// Refuse all the writes with [TaggyError::PermissionDenied], before opening the files.
+    ///
+    /// The reads never request write access either way, this guarantees the files
+    /// are never opened for writing, e.g. on media where that triggers sync clients.
+    pub read_only: bool,
+}
+
+impl Default for ClientOptions {
+    fn default() -> Self {
+        Self {
+            cache_capacity: 32,
+            cache_max_bytes: Some(64 * 1024 * 1024),
+            max_file_size: None,
+            timeout_ms: None,
+            max_files_per_sec: None,
+            max_bytes_per_sec: None,
+            read_only: false,
+        }
+    }
+}
+
+/// The state of the cache of a [TaggyClient], see [TaggyClient::cache_stats].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CacheStats {
+    /// The number of cached files.
+    pub entries: u32,
+    /// The estimated memory taken by the cached files, in bytes.
+    pub bytes: u64,
+    /// The number of reads served from the cache.
+    pub hits: u64,
+    /// The number of reads which had to parse the file, because it wasn't cached or changed.
+    pub misses: u64,
+    /// The number of files dropped to stay within the cache limits.
+    pub evictions: u64,
+}
+
+/// A handle for reading and writing tags with a set of [ClientOptions].
+///
+/// Unlike the free functions in [api], a client remembers the files it parsed recently,
+/// so consecutive reads of the same file (e.g. `read_primary` then `read_all`) are served from memory.
+/// A cached file is parsed again once its modification time or size changes, and the least
+/// recently used files are dropped beyond the `cache_capacity` and `cache_max_bytes` limits.
+///
+/// The limits of the [ClientOptions] are applied to every operation, when one is exceeded
+/// the returned error wraps a [LimitError](crate::limits::LimitError).
+///
+/// The IO rate limits of the [ClientOptions] (`max_files_per_sec`, `max_bytes_per_sec`) make the
+/// operations wait instead of failing, which is handy for background library maintenance
+/// that shouldn't disturb the playback.
+///
+/// Register [TaggyHooks] with [TaggyClient::set_hooks] to observe the client operations.
+///
+/// A client is `Send` and `Sync`: share a single client between the threads, or the isolates
+/// of a Flutter app, rather than creating one for each, which would parse and cache the same
+/// files again. The reads and writes of a file through any client of the process are
+/// serialized, so a background isolate writing a file never races with another reading it,
+/// and a panic in one operation, e.g. in a hook, doesn't make the client unusable.
+pub struct TaggyClient {
+    options: ClientOptions,
+    cache: Mutex<ParseCache>,
+    throttle: Throttle,
+    hooks: RwLock<Option<Arc<dyn TaggyHooks>>>,
+}
+
+impl TaggyClient {
+    pub fn new(options: ClientOptions) -> Self {
+        Self {
+            cache: Mutex::new(ParseCache::new(
+                options.cache_capacity as usize,
+                options.cache_max_bytes,
+            )),
+            throttle: Throttle::new(options.max_files_per_sec, options.max_bytes_per_sec),
+            options,
+            hooks: RwLock::new(None),
+        }
+    }
+
+    pub fn options(&self) -> &ClientOptions {
+        &self.options
+    }
+
+    /// Replaces the hooks called by this client, `None` removes them.
+    pub fn set_hooks(&self, hooks: Option<Arc<dyn TaggyHooks>>) {
+        *self.hooks.write().unwrap_or_else(PoisonError::into_inner) = hooks;
+    }
+
+    /// Same as [api::read_all].
+    pub fn read_all(&self, path: String) -> anyhow::Result<TaggyFile> {
+        self.read_cached(path)
+    }
+
+    /// Same as [api::read_primary].
+    pub fn read_primary(&self, path: String) -> anyhow::Result<TaggyFile> {
+        let file = self.read_cached(path)?;
+        let primary_tag_type = file.primary_tag_type;
+        Ok(retain_tags(file, |tags| {
+            tags.into_iter()
+                .filter(|t| t.tag_type == primary_tag_type)
+                .take(1)
+                .collect()
+        }))
+    }
+
+    /// Same as [api::read_any].
+    pub fn read_any(&self, path: String) -> anyhow::Result<TaggyFile> {
+        let file = self.read_cached(path)?;
+        Ok(retain_tags(file, |tags| tags.into_iter().take(1).collect()))
+    }
+
+    /// Same as [api::write_all].
+    pub fn write_all(
+        &self,
+        path: String,
+        tags: Vec<Tag>,
+        override_existent: bool,
+    ) -> anyhow::Result<TaggyFile> {
+        self.observe_write(&path, || {
+            check_file_size(&path, self.options.max_file_size)?;
+            api::write_all(path.clone(), tags, override_existent)
+        })
+    }
+
+    /// Same as [api::write_primary].
+    pub fn write_primary(
+        &self,
+        path: String,
+        tag: Tag,
+        keep_others: bool,
+    ) -> anyhow::Result<TaggyFile> {
+        self.observe_write(&path, || {
+            check_file_size(&path, self.options.max_file_size)?;
+            api::write_primary(path.clone(), tag, keep_others)
+        })
+    }
+
+    /// Same as [api::read_zip_entries], the entries larger than `max_file_size` once
+    /// decompressed failing their own result.
+    #[cfg(feature = "zip")]
+    pub fn read_zip_entries(&self, zip_path: String) -> anyhow::Result<Vec<BatchReadResult>> {
+        self.throttle.wait(&zip_path);
+        with_read_lock(&zip_path, || {
+            crate::zip_archive::read_zip_entries(&zip_path, self.options.max_file_size)
+        })
+    }
+
+    /// Same as [api::write_zip_entry], failing when the entry is larger than `max_file_size`
+    /// once decompressed.
+    #[cfg(feature = "zip")]
+    pub fn write_zip_entry(
+        &self,
+        zip_path: String,
+        entry_name: String,
+        tags: Vec<Tag>,
+        override_existent: bool,
+    ) -> anyhow::Result<()> {
+        self.observe_write(&zip_path, || {
+            crate::zip_archive::write_zip_entry(
+                &zip_path,
+                &entry_name,
+                tags,
+                override_existent,
+                self.options.max_file_size,
+            )
+        })
+    }
+
+    /// Same as [api::remove_all].
+    pub fn remove_all(&self, path: String) -> anyhow::Result<()> {
+        self.observe_write(&path, || api::remove_all(path.clone()))
+    }
+
+    /// Same as [api::remove_tag].
+    pub fn remove_tag(&self, path: String, tag_type: TagType) -> anyhow::Result<()> {
+        self.observe_write(&path, || api::remove_tag(path.clone(), tag_type))
+    }
+
+    /// Drops all the cached files.
+    pub fn clear_cache(&self) {
+        self.cache().clear();
+    }
+
+    /// The number and size of the cached files, and how often the cache was used since
+    /// the client was created.
+    pub fn cache_stats(&self) -> CacheStats {
+        self.cache().stats()
+    }
+
+    fn hooks(&self) -> Option<Arc<dyn TaggyHooks>> {
+        self.hooks
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    /// The cache, even when another thread panicked while holding it: the cached files
+    /// are only replaced whole, so they're never left half updated.
+    fn cache(&self) -> MutexGuard<'_, ParseCache> {
+        self.cache.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn read_cached(&self, path: String) -> anyhow::Result<TaggyFile> {
+        let hooks = self.hooks();
+        if let Some(hooks) = &hooks {
+            hooks.on_read_start(&path);
+        }
+        let stopwatch = Stopwatch::start(&path);
+        let result = self.read_through_cache(&path);
+        if let Some(hooks) = &hooks {
+            match &result {
+                Ok((_, from_cache)) => hooks.on_read_end(&stopwatch.stop(*from_cache)),
+                Err(e) => hooks.on_error(&path, e),
+            }
+        }
+        result.map(|(file, _)| file)
+    }
+
+    /// Returns the file at `path` and whether it was served from the cache.
+    fn read_through_cache(&self, path: &str) -> anyhow::Result<(TaggyFile, bool)> {
+        let stamp = FileStamp::of(path);
+        if let Some(stamp) = &stamp {
+            if let Some(file) = self.cache().get(path, stamp) {
+                return Ok((file, true));
+            }
+        }
+        check_file_size(path, self.options.max_file_size)?;
+        self.throttle.wait(path);
+        let file = with_read_lock(path, || {
+            let path = path.to_string();
+            with_timeout(self.options.timeout_ms, move || api::read_all(path))
+        })?;
+        if let Some(stamp) = stamp {
+            self.cache().insert(path.to_string(), stamp, file.clone());
+        }
+        Ok((file, false))
+    }
+
+    /// Runs the write `op` on the file at `path`, after dropping the file from the cache.
+    fn observe_write<T>(
+        &self,
+        path: &str,
+        op: impl FnOnce() -> anyhow::Result<T>,
+    ) -> anyhow::Result<T> {
+        if self.options.read_only {
+            return Err(TaggyError::PermissionDenied.into());
+        }
+        self.invalidate(path);
+        self.throttle.wait(path);
+        let stopwatch = Stopwatch::start(path);
+        let result = with_write_lock(path, op);
+        if let Some(hooks) = self.hooks() {
+            match &result {
+                Ok(_) => hooks.on_write(&stopwatch.stop(false)),
+                Err(e) => hooks.on_error(path, e),
+            }
+        }
+        result
+    }
+
+    fn invalidate(&self, path: &str) {
+        self.cache().remove(path);
+    }
+}
+
+impl Debug for TaggyClient {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaggyClient")
+            .field("options", &self.options)
+            .field("cache", &self.cache)
+            .field("has_hooks", &self.hooks().is_some())
+            .finish()
+    }
+}
+
+// the clients are shared between threads, e.g. by the Flutter isolates through their handle.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<TaggyClient>();
+};
+
+impl Default for TaggyClient {
+    fn default() -> Self {
+        Self::new(ClientOptions::default())
+    }
+}
+
+fn retain_tags(file: TaggyFile, op: impl FnOnce(Vec<Tag>) -> Vec<Tag>) -> TaggyFile {
+    TaggyFile {
+        tags: op(file.tags),
+        ..file
+    }
+}
+
+/// Identifies a version of a file on disk.
+#[derive(Debug, Clone, PartialEq)]
+struct FileStamp {
+    modified: Option<SystemTime>,
+    len: u64,
+}
+
+impl FileStamp {
+    fn of(path: &str) -> Option<Self> {
+        let meta = fs::metadata(path).ok()?;
+        Some(Self {
+            modified: meta.modified().ok(),
+            len: meta.len(),
+        })
+    }
+}
+
+/// The approximate memory taken by the `file`: its pictures and text values,
+/// the fixed size fields aside.
+fn estimated_size(file: &TaggyFile) -> u64 {
+    let mut size = 0;
+    for tag in &file.tags {
+        macro_rules! add_len {
+            ($field:ident) => {
+                size += tag.$field.as_ref().map_or(0, String::len);
+            };
+        }
+        for_each_text_field!(add_len);
+        size += tag.pictures.iter().map(|p| p.pic_data.len()).sum::<usize>();
+        size += tag
+            .custom_items
+            .iter()
+            .map(|i| i.key.len() + i.value.len())
+            .sum::<usize>();
+        size += tag.comments.iter().map(|c| c.text.len()).sum::<usize>();
+    }
+    size as u64
+}
+
+/// A least-recently-used cache of parsed files, bounded by their number and estimated size.
+#[derive(Debug)]
+struct ParseCache {
+    capacity: usize,
+    max_bytes: Option<u64>,
+    entries: HashMap<String, CacheEntry>,
+    /// The cached paths, the most recently used is at the back.
+    order: VecDeque<String>,
+    stats: CacheStats,
+}
+
+#[derive(Debug)]
+struct CacheEntry {
+    stamp: FileStamp,
+    file: TaggyFile,
+    size: u64,
+}
+
+impl ParseCache {
+    fn new(capacity: usize, max_bytes: Option<u64>) -> Self {
+        Self {
+            capacity,
+            max_bytes,
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            stats: CacheStats::default(),
+        }
+    }
+
+    fn get(&mut self, path: &str, stamp: &FileStamp) -> Option<TaggyFile> {
+        let file = match self.entries.get(path) {
+            Some(entry) if &entry.stamp == stamp => entry.file.clone(),
+            Some(_) => {
+                self.remove(path);
+                self.stats.misses += 1;
+                return None;
+            }
+            None => {
+                self.stats.misses += 1;
+                return None;
+            }
+        };
+        self.stats.hits += 1;
+        self.touch(path);
+        Some(file)
+    }
+
+    fn insert(&mut self, path: String, stamp: FileStamp, file: TaggyFile) {
+        let size = estimated_size(&file);
+        if self.capacity == 0 || self.max_bytes.is_some_and(|max| size > max) {
+            return;
+        }
+        self.remove(&path);
+        while self.order.len() >= self.capacity
+            || self
+                .max_bytes
+                .is_some_and(|max| self.stats.bytes + size > max)
+        {
+            let Some(oldest) = self.order.pop_front() else {
+                break;
+            };
+            if let Some(entry) = self.entries.remove(&oldest) {
+                self.stats.bytes -= entry.size;
+                self.stats.evictions += 1;
+            }
+        }
+        self.order.push_back(path.clone());
+        self.entries.insert(path, CacheEntry { stamp, file, size });
+        self.stats.bytes += size;
+    }
+
+    fn remove(&mut self, path: &str) {
+        if let Some(entry) = self.entries.remove(path) {
+            self.stats.bytes -= entry.size;
+            self.order.retain(|p| p != path);
+        }
+    }
+
+    fn touch(&mut self, path: &str) {
+        if let Some(index) = self.order.iter().position(|p| p == path) {
+            if let Some(p) = self.order.remove(index) {
+                self.order.push_back(p);
+            }
+        }
+    }
+
+    fn clear(&mut self) {
+        self.entries.clear();
+        self.order.clear();
+        self.stats.bytes = 0;
+    }
+
+    fn stats(&self) -> CacheStats {
+        CacheStats {
+            entries: self.entries.len() as u32,
+            ..self.stats.clone()
+        }
+    }
+}
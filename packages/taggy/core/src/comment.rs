@@ -0,0 +1,26 @@
+/// A comment of a tag, e.g. the user comment or the `iTunNORM` one iTunes writes,
+/// see [Tag::comments](crate::tag::Tag::comments).
+///
+/// Only ID3v2 stores the `language` and `description` of its `COMM` frames, so several comments
+/// can coexist. The other tags only keep the comments without a description, as `COMMENT`
+/// Vorbis comments, `Comment` APE items and `©cmt` MP4 atoms.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Comment {
+    /// The ISO 639-2 language, e.g. `eng`, `None` when it's unknown.
+    pub language: Option<String>,
+    /// Tells the comments apart, e.g. `iTunNORM`, `None` for the user comment.
+    pub description: Option<String>,
+    pub text: String,
+}
+
+impl Comment {
+    /// A user comment, without language nor description.
+    pub fn new(text: impl Into<String>) -> Self {
+        Self {
+            language: None,
+            description: None,
+            text: text.into(),
+        }
+    }
+}
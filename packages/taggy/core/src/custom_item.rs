@@ -0,0 +1,20 @@
+/// An application specific field of a tag, e.g. `MY_APP_PLAYCOUNT`,
+/// see [Tag::custom_items](crate::tag::Tag::custom_items).
+///
+/// It's stored as a `TXXX` frame by ID3v2, a Vorbis comment, a `----:com.apple.iTunes:<key>`
+/// freeform atom by MP4 and an item by APE.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CustomItem {
+    pub key: String,
+    pub value: String,
+}
+
+impl CustomItem {
+    pub fn new(key: impl Into<String>, value: impl Into<String>) -> Self {
+        Self {
+            key: key.into(),
+            value: value.into(),
+        }
+    }
+}
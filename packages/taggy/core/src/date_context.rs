@@ -0,0 +1,56 @@
+/// The user's locale and time zone settings, used to interpret the dates of the tags consistently,
+/// see [ParseConfig](crate::parse_config::ParseConfig) and
+/// [WriteOptions](crate::write_options::WriteOptions)'s `date_context`.
+///
+/// With a context, the `recording_date` and `original_release_date` are normalized to
+/// ISO 8601 (e.g. `2001-03-04` or `2001-03-04T21:30:00`):
+/// - the ambiguous numeric dates of legacy tags, like `03/04/2001`, are read in the `date_order`
+/// - the timestamps with a time zone, like `2001-03-04T21:30:00Z`, are converted to the
+///   `utc_offset_minutes` local time
+///
+/// The values which aren't recognized as dates are left as they are.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DateContext {
+    /// The order of the day and the month in the numeric dates.
+    pub date_order: DateOrder,
+    /// The user's offset from UTC in minutes, e.g. `120` for UTC+2.
+    pub utc_offset_minutes: i32,
+}
+
+/// The order of the day and the month in numeric dates, see [DateContext].
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum DateOrder {
+    /// e.g. `31/12/2001`, used by most locales.
+    #[default]
+    DayMonth,
+    /// e.g. `12/31/2001`, used in the United States.
+    MonthDay,
+}
+
+/// The regions writing the month before the day.
+const MONTH_DAY_REGIONS: [&str; 6] = ["US", "PH", "FM", "MH", "PW", "AS"];
+
+impl DateContext {
+    /// The context of the given `locale`, as a BCP 47 tag (e.g. `en-US`)
+    /// or a POSIX locale (e.g. `en_US.UTF-8`), with the `utc_offset_minutes`.
+    pub fn for_locale(locale: &str, utc_offset_minutes: i32) -> Self {
+        let region = locale
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .split(['-', '_'])
+            .skip(1)
+            .find(|part| part.len() == 2 && part.chars().all(|c| c.is_ascii_alphabetic()))
+            .map(|region| region.to_ascii_uppercase());
+        let date_order = match region {
+            Some(region) if MONTH_DAY_REGIONS.contains(&region.as_str()) => DateOrder::MonthDay,
+            _ => DateOrder::DayMonth,
+        };
+        Self {
+            date_order,
+            utc_offset_minutes,
+        }
+    }
+}
@@ -0,0 +1,29 @@
+/// The discs of an album found in a folder, see [detect_disc_set](crate::api::detect_disc_set).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DiscSet {
+    /// The discs sorted by their number.
+    pub discs: Vec<Disc>,
+}
+
+impl DiscSet {
+    /// Whether the album spans more than one disc.
+    pub fn is_multi_disc(&self) -> bool {
+        self.discs.len() > 1
+    }
+
+    /// The highest disc number of the set.
+    pub fn disc_total(&self) -> u32 {
+        self.discs.iter().map(|d| d.number).max().unwrap_or(0)
+    }
+}
+
+/// A single disc of a [DiscSet].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Disc {
+    /// The disc number, from the `disc_number` tag or the `Disc N` folder name.
+    pub number: u32,
+    /// The paths of the audio files of this disc, sorted.
+    pub files: Vec<String>,
+}
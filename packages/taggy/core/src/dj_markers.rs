@@ -0,0 +1,42 @@
+/// The cue points, loops and color a DJ software set on a track, e.g. in Serato DJ,
+/// see [read_dj_markers](crate::api::read_dj_markers).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DjMarkers {
+    /// The hot cues, sorted by index.
+    pub cues: Vec<CuePoint>,
+    /// The saved loops, sorted by index.
+    pub loops: Vec<DjLoop>,
+    /// The color of the track in the library, as `0xRRGGBB`.
+    pub track_color: Option<u32>,
+    /// Whether the BPM of the track is locked, so it isn't analyzed again.
+    pub is_bpm_locked: bool,
+}
+
+/// A hot cue of [DjMarkers].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct CuePoint {
+    /// The pad the cue is on, starting at `0`.
+    pub index: u8,
+    /// The time of the cue, in milliseconds from the start of the track.
+    pub position_ms: u32,
+    /// The color of the cue, as `0xRRGGBB`.
+    pub color: u32,
+    pub name: Option<String>,
+}
+
+/// A saved loop of [DjMarkers].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct DjLoop {
+    /// The slot the loop is in, starting at `0`.
+    pub index: u8,
+    /// The time the loop starts at, in milliseconds from the start of the track.
+    pub start_ms: u32,
+    /// The time the loop ends at, in milliseconds from the start of the track.
+    pub end_ms: u32,
+    pub name: Option<String>,
+    /// Whether the loop can't be moved or deleted.
+    pub is_locked: bool,
+}
@@ -0,0 +1,19 @@
+/// Which value is read for a field a tag holds several times, e.g. two ID3v2 `TIT2` frames or
+/// two `TITLE` Vorbis comments, see [ParseConfig](crate::parse_config::ParseConfig).
+///
+/// Whatever the policy, each duplicated field is reported in the
+/// [warnings](crate::taggy_file::TaggyFile::warnings) of the read file, so it can be cleaned up.
+/// The fields holding several values by design, e.g. the artists, aren't concerned.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum DuplicatePolicy {
+    /// Read the first value.
+    #[default]
+    First,
+    /// Read the last value.
+    Last,
+    /// Read all the values, joined with `; `.
+    Concatenate,
+    /// Read the first value, and list all the values in the warnings.
+    ReportAll,
+}
@@ -0,0 +1,33 @@
+use crate::tag::{Tag, TagType};
+
+/// The values of all the tags of a file merged into a single tag,
+/// see [read_effective](crate::api::read_effective).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct EffectiveTag {
+    /// The merged values, its `tag_type` is the file primary tag type.
+    pub tag: Tag,
+    /// Which tag each of the `tag` fields with a value came from.
+    pub sources: Vec<FieldSource>,
+}
+
+impl EffectiveTag {
+    /// Returns the type of the tag the given `field` came from,
+    /// or `None` if the field has no value.
+    pub fn source_of(&self, field: &str) -> Option<TagType> {
+        self.sources
+            .iter()
+            .find(|s| s.field == field)
+            .map(|s| s.tag_type)
+    }
+}
+
+/// The origin of a field value of an [EffectiveTag].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FieldSource {
+    /// The name of the field, same as the [Tag] property, e.g. `track_title`.
+    pub field: String,
+    /// The type of the tag the value was read from.
+    pub tag_type: TagType,
+}
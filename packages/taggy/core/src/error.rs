@@ -0,0 +1,155 @@
+use crate::limits::LimitError;
+use lofty::error::{ErrorKind, LoftyError};
+use std::fmt::{Display, Formatter};
+use std::io;
+
+/// The kinds of errors taggy fails with.
+///
+/// The API functions return an `anyhow::Error` wrapping a [TaggyError] for the errors raised by
+/// taggy itself. Use [TaggyError::from] to get the kind of any returned error, including the ones
+/// coming from the underlying parsers, then branch on it or on its stable [TaggyError::code].
+#[derive(Debug, Clone, PartialEq)]
+pub enum TaggyError {
+    /// The file path doesn't exist.
+    FileNotFound,
+    /// The directory path doesn't exist.
+    DirectoryNotFound,
+    /// The file can't be opened with the needed permissions, e.g. for writing a read-only file.
+    PermissionDenied,
+    /// The file type can't be detected, or isn't supported.
+    UnsupportedFormat,
+    /// The file is empty, or shorter than its headers declare, e.g. an interrupted copy.
+    ///
+    /// `expected_len` is the length declared by the headers, when it could be read.
+    EmptyOrTruncated {
+        expected_len: Option<u64>,
+        actual_len: u64,
+    },
+    /// The tag type isn't supported by the file type.
+    UnsupportedTagType { message: String },
+    /// The file tags can't be parsed.
+    CorruptTag { message: String },
+    /// A given value is invalid, e.g. a malformed ISRC or snapshot.
+    InvalidValue { message: String },
+    /// The operation exceeded one of the client limits, see [LimitError].
+    LimitExceeded { message: String },
+    /// Reading or writing the file failed.
+    Io { message: String },
+    /// Saving the tags to the file failed.
+    SaveFailed { message: String },
+    /// Any other error.
+    Other { message: String },
+}
+
+impl TaggyError {
+    /// A stable identifier of the error kind, e.g. `file_not_found`, which doesn't change
+    /// with the error message.
+    pub fn code(&self) -> &'static str {
+        match self {
+            TaggyError::FileNotFound => "file_not_found",
+            TaggyError::DirectoryNotFound => "directory_not_found",
+            TaggyError::PermissionDenied => "permission_denied",
+            TaggyError::UnsupportedFormat => "unsupported_format",
+            TaggyError::EmptyOrTruncated { .. } => "empty_or_truncated",
+            TaggyError::UnsupportedTagType { .. } => "unsupported_tag_type",
+            TaggyError::CorruptTag { .. } => "corrupt_tag",
+            TaggyError::InvalidValue { .. } => "invalid_value",
+            TaggyError::LimitExceeded { .. } => "limit_exceeded",
+            TaggyError::Io { .. } => "io",
+            TaggyError::SaveFailed { .. } => "save_failed",
+            TaggyError::Other { .. } => "other",
+        }
+    }
+
+    pub(crate) fn invalid_value(message: impl Into<String>) -> Self {
+        TaggyError::InvalidValue {
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn save_failed(message: impl Display) -> Self {
+        TaggyError::SaveFailed {
+            message: message.to_string(),
+        }
+    }
+}
+
+impl Display for TaggyError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaggyError::FileNotFound => write!(f, "The file path does not exist!"),
+            TaggyError::DirectoryNotFound => write!(f, "The directory path does not exist!"),
+            TaggyError::PermissionDenied => write!(f, "Permission denied to access the file!"),
+            TaggyError::UnsupportedFormat => write!(f, "The file type is not supported!"),
+            TaggyError::EmptyOrTruncated { actual_len: 0, .. } => write!(f, "The file is empty!"),
+            TaggyError::EmptyOrTruncated {
+                expected_len: Some(expected_len),
+                actual_len,
+            } => write!(
+                f,
+                "The file is truncated, it has {actual_len} bytes out of at least {expected_len}!"
+            ),
+            TaggyError::EmptyOrTruncated { actual_len, .. } => {
+                write!(f, "The file is truncated, it has only {actual_len} bytes!")
+            }
+            TaggyError::UnsupportedTagType { message }
+            | TaggyError::CorruptTag { message }
+            | TaggyError::InvalidValue { message }
+            | TaggyError::LimitExceeded { message }
+            | TaggyError::Io { message }
+            | TaggyError::SaveFailed { message }
+            | TaggyError::Other { message } => write!(f, "{message}"),
+        }
+    }
+}
+
+impl std::error::Error for TaggyError {}
+
+impl From<&anyhow::Error> for TaggyError {
+    fn from(error: &anyhow::Error) -> Self {
+        if let Some(e) = error.downcast_ref::<TaggyError>() {
+            return e.clone();
+        }
+        if let Some(e) = error.downcast_ref::<LimitError>() {
+            return TaggyError::LimitExceeded {
+                message: e.to_string(),
+            };
+        }
+        if let Some(e) = error.downcast_ref::<LoftyError>() {
+            return TaggyError::from(e);
+        }
+        if let Some(e) = error.downcast_ref::<io::Error>() {
+            return TaggyError::from(e);
+        }
+        TaggyError::Other {
+            message: error.to_string(),
+        }
+    }
+}
+
+impl From<&LoftyError> for TaggyError {
+    fn from(error: &LoftyError) -> Self {
+        match error.kind() {
+            ErrorKind::UnknownFormat => TaggyError::UnsupportedFormat,
+            ErrorKind::UnsupportedTag => TaggyError::UnsupportedTagType {
+                message: error.to_string(),
+            },
+            ErrorKind::Io(e) => TaggyError::from(e),
+            _ => TaggyError::CorruptTag {
+                message: error.to_string(),
+            },
+        }
+    }
+}
+
+impl From<&io::Error> for TaggyError {
+    fn from(error: &io::Error) -> Self {
+        match error.kind() {
+            io::ErrorKind::NotFound => TaggyError::FileNotFound,
+            io::ErrorKind::PermissionDenied => TaggyError::PermissionDenied,
+            _ => TaggyError::Io {
+                message: error.to_string(),
+            },
+        }
+    }
+}
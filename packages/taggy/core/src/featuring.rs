@@ -0,0 +1,27 @@
+/// Where the featured artists of a track are credited, e.g. `Song (feat. X)` by `A`,
+/// or `Song` by `A feat. X`, see [suggest_featuring](crate::api::suggest_featuring).
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum FeaturingStyle {
+    /// In the track title, e.g. `Song (feat. X)`.
+    InTitle,
+    /// In the track artist, e.g. `A feat. X`.
+    InArtist,
+}
+
+/// The new title and artist of a file whose featured artists are moved,
+/// see [suggest_featuring](crate::api::suggest_featuring).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FeaturingChange {
+    /// The path of the file.
+    pub path: String,
+    /// The title to write, e.g. `Song (feat. X)`.
+    pub track_title: String,
+    /// The artist to write, e.g. `A`.
+    pub track_artist: String,
+    /// The title in the file, e.g. `Song`.
+    pub previous_track_title: String,
+    /// The artist in the file, e.g. `A feat. X`.
+    pub previous_track_artist: String,
+}
@@ -0,0 +1,21 @@
+/// The groups of fields removed by [strip_fields](crate::api::strip_fields),
+/// e.g. to sanitize a library before sharing it.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum FieldClass {
+    /// The comments, i.e. the ID3v2 `COMM` frames, the MP4 `©cmt` atom
+    /// and the `COMMENT` Vorbis comments.
+    Comments,
+    /// The unsynchronized and synchronized lyrics, i.e. the ID3v2 `USLT` and `SYLT` frames,
+    /// the MP4 `©lyr` atom and the `LYRICS` and `UNSYNCEDLYRICS` Vorbis comments.
+    Lyrics,
+    /// The ratings and play counters, i.e. the ID3v2 `POPM` and `PCNT` frames, the MP4 `rate`
+    /// atom and the `RATING` Vorbis comment.
+    Ratings,
+    /// The data applications store for themselves, i.e. the ID3v2 `PRIV` and `GEOB` frames,
+    /// e.g. the Serato cue points, and the binary items of the other tags.
+    PrivateData,
+    /// The links, i.e. the ID3v2 `W***` frames, e.g. `WXXX`, and the URL fields of the
+    /// other tags.
+    Urls,
+}
@@ -0,0 +1,9 @@
+/// How many files have a given value of a field, see [field_values](crate::api::field_values).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct FieldValueCount {
+    /// The value, as written in the files.
+    pub value: String,
+    /// The number of files having the value.
+    pub count: u32,
+}
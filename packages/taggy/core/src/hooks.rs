@@ -0,0 +1,57 @@
+use std::fs;
+use std::time::Instant;
+
+/// Timing and size of a finished operation, passed to the [TaggyHooks].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OperationStats {
+    /// The path of the file the operation was applied to.
+    pub path: String,
+    /// The wall-clock time the operation took, in microseconds.
+    pub elapsed_micros: u64,
+    /// The size of the file after the operation, if it could be read.
+    pub bytes: Option<u64>,
+    /// Whether the result was served from the client cache without reading the file.
+    pub from_cache: bool,
+}
+
+/// Callbacks a host application can register on a [TaggyClient](crate::client::TaggyClient)
+/// to collect its own metrics, e.g. for a performance dashboard.
+///
+/// All methods do nothing by default, so only the needed ones have to be implemented.
+/// They're called synchronously on the thread running the operation, so they should return quickly.
+///
+/// **Note**: taggy never sends these anywhere by itself.
+pub trait TaggyHooks: Send + Sync {
+    /// Called before a file is read.
+    fn on_read_start(&self, _path: &str) {}
+    /// Called after a file was read successfully.
+    fn on_read_end(&self, _stats: &OperationStats) {}
+    /// Called after tags were written to, or removed from, a file successfully.
+    fn on_write(&self, _stats: &OperationStats) {}
+    /// Called when reading or writing a file fails.
+    fn on_error(&self, _path: &str, _error: &anyhow::Error) {}
+}
+
+/// Measures an operation on the file at `path`.
+pub(crate) struct Stopwatch<'a> {
+    path: &'a str,
+    start: Instant,
+}
+
+impl<'a> Stopwatch<'a> {
+    pub(crate) fn start(path: &'a str) -> Self {
+        Self {
+            path,
+            start: Instant::now(),
+        }
+    }
+
+    pub(crate) fn stop(&self, from_cache: bool) -> OperationStats {
+        OperationStats {
+            path: self.path.to_string(),
+            elapsed_micros: self.start.elapsed().as_micros() as u64,
+            bytes: fs::metadata(self.path).map(|meta| meta.len()).ok(),
+            from_cache,
+        }
+    }
+}
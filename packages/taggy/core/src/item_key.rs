@@ -0,0 +1,264 @@
+use crate::utils::replay_gain::{R128_ALBUM_GAIN_KEY, R128_TRACK_GAIN_KEY};
+
+/// The key of a single field of a tag, see [read_field](crate::api::read_field)
+/// and [write_field](crate::api::write_field).
+///
+/// Covers the fields [Tag](crate::tag::Tag) doesn't model, each is mapped to the native key of
+/// the tag format, e.g. [ItemKey::Composer] is the `TCOM` frame of ID3v2 and the `COMPOSER`
+/// Vorbis comment. Not all the keys are supported by all the formats.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ItemKey {
+    /// The album title.
+    AlbumTitle,
+    /// The subtitle of the disc set.
+    SetSubtitle,
+    /// The TV show name.
+    ShowName,
+    /// The content group, e.g. a work of classical music.
+    ContentGroup,
+    /// The track title.
+    TrackTitle,
+    /// The track subtitle.
+    TrackSubtitle,
+    /// The title of the original album of a cover.
+    OriginalAlbumTitle,
+    /// The artist of the original song of a cover.
+    OriginalArtist,
+    /// The lyricist of the original song of a cover.
+    OriginalLyricist,
+    /// The album title used for sorting.
+    AlbumTitleSortOrder,
+    /// The album artist used for sorting.
+    AlbumArtistSortOrder,
+    /// The track title used for sorting.
+    TrackTitleSortOrder,
+    /// The track artist used for sorting.
+    TrackArtistSortOrder,
+    /// The TV show name used for sorting.
+    ShowNameSortOrder,
+    /// The composer used for sorting.
+    ComposerSortOrder,
+    /// The album artist.
+    AlbumArtist,
+    /// The track artist.
+    TrackArtist,
+    Arranger,
+    Writer,
+    Composer,
+    Conductor,
+    Director,
+    Engineer,
+    Lyricist,
+    MixDj,
+    MixEngineer,
+    MusicianCredits,
+    Performer,
+    Producer,
+    Publisher,
+    Label,
+    InternetRadioStationName,
+    InternetRadioStationOwner,
+    Remixer,
+    DiscNumber,
+    DiscTotal,
+    TrackNumber,
+    TrackTotal,
+    /// The rating, as stored by the format, e.g. the ID3v2 `POPM` frame.
+    Popularimeter,
+    ParentalAdvisory,
+    RecordingDate,
+    Year,
+    OriginalReleaseDate,
+    /// The International Standard Recording Code.
+    Isrc,
+    Barcode,
+    CatalogNumber,
+    Work,
+    Movement,
+    MovementNumber,
+    MovementTotal,
+    MusicBrainzRecordingId,
+    MusicBrainzTrackId,
+    MusicBrainzReleaseId,
+    MusicBrainzReleaseGroupId,
+    MusicBrainzArtistId,
+    MusicBrainzReleaseArtistId,
+    MusicBrainzWorkId,
+    /// Whether the album is a compilation, `1` or `0`.
+    FlagCompilation,
+    /// Whether the file is a podcast episode, `1` or `0`.
+    FlagPodcast,
+    FileType,
+    FileOwner,
+    TaggingTime,
+    Length,
+    OriginalFileName,
+    OriginalMediaType,
+    EncodedBy,
+    EncoderSoftware,
+    EncoderSettings,
+    EncodingTime,
+    ReplayGainAlbumGain,
+    ReplayGainAlbumPeak,
+    ReplayGainTrackGain,
+    ReplayGainTrackPeak,
+    /// The track gain of the Opus files, a Q7.8 number relative to -23 LUFS, e.g. `-2560`,
+    /// see [read_replay_gain](crate::api::read_replay_gain) for the gain in dB.
+    R128TrackGain,
+    /// The album gain of the Opus files, stored like [ItemKey::R128TrackGain].
+    R128AlbumGain,
+    AudioFileUrl,
+    AudioSourceUrl,
+    CommercialInformationUrl,
+    CopyrightUrl,
+    TrackArtistUrl,
+    RadioStationUrl,
+    PaymentUrl,
+    PublisherUrl,
+    Genre,
+    InitialKey,
+    Color,
+    Mood,
+    Bpm,
+    CopyrightMessage,
+    License,
+    PodcastDescription,
+    PodcastSeriesCategory,
+    PodcastURL,
+    PodcastReleaseDate,
+    PodcastGlobalUniqueID,
+    PodcastKeywords,
+    Comment,
+    Description,
+    Language,
+    Script,
+    Lyrics,
+    AppleXid,
+    AppleId3v2ContentGroup,
+}
+
+impl ItemKey {
+    pub(crate) fn to_lofty(self) -> lofty::ItemKey {
+        match self {
+            ItemKey::AlbumTitle => lofty::ItemKey::AlbumTitle,
+            ItemKey::SetSubtitle => lofty::ItemKey::SetSubtitle,
+            ItemKey::ShowName => lofty::ItemKey::ShowName,
+            ItemKey::ContentGroup => lofty::ItemKey::ContentGroup,
+            ItemKey::TrackTitle => lofty::ItemKey::TrackTitle,
+            ItemKey::TrackSubtitle => lofty::ItemKey::TrackSubtitle,
+            ItemKey::OriginalAlbumTitle => lofty::ItemKey::OriginalAlbumTitle,
+            ItemKey::OriginalArtist => lofty::ItemKey::OriginalArtist,
+            ItemKey::OriginalLyricist => lofty::ItemKey::OriginalLyricist,
+            ItemKey::AlbumTitleSortOrder => lofty::ItemKey::AlbumTitleSortOrder,
+            ItemKey::AlbumArtistSortOrder => lofty::ItemKey::AlbumArtistSortOrder,
+            ItemKey::TrackTitleSortOrder => lofty::ItemKey::TrackTitleSortOrder,
+            ItemKey::TrackArtistSortOrder => lofty::ItemKey::TrackArtistSortOrder,
+            ItemKey::ShowNameSortOrder => lofty::ItemKey::ShowNameSortOrder,
+            ItemKey::ComposerSortOrder => lofty::ItemKey::ComposerSortOrder,
+            ItemKey::AlbumArtist => lofty::ItemKey::AlbumArtist,
+            ItemKey::TrackArtist => lofty::ItemKey::TrackArtist,
+            ItemKey::Arranger => lofty::ItemKey::Arranger,
+            ItemKey::Writer => lofty::ItemKey::Writer,
+            ItemKey::Composer => lofty::ItemKey::Composer,
+            ItemKey::Conductor => lofty::ItemKey::Conductor,
+            ItemKey::Director => lofty::ItemKey::Director,
+            ItemKey::Engineer => lofty::ItemKey::Engineer,
+            ItemKey::Lyricist => lofty::ItemKey::Lyricist,
+            ItemKey::MixDj => lofty::ItemKey::MixDj,
+            ItemKey::MixEngineer => lofty::ItemKey::MixEngineer,
+            ItemKey::MusicianCredits => lofty::ItemKey::MusicianCredits,
+            ItemKey::Performer => lofty::ItemKey::Performer,
+            ItemKey::Producer => lofty::ItemKey::Producer,
+            ItemKey::Publisher => lofty::ItemKey::Publisher,
+            ItemKey::Label => lofty::ItemKey::Label,
+            ItemKey::InternetRadioStationName => lofty::ItemKey::InternetRadioStationName,
+            ItemKey::InternetRadioStationOwner => lofty::ItemKey::InternetRadioStationOwner,
+            ItemKey::Remixer => lofty::ItemKey::Remixer,
+            ItemKey::DiscNumber => lofty::ItemKey::DiscNumber,
+            ItemKey::DiscTotal => lofty::ItemKey::DiscTotal,
+            ItemKey::TrackNumber => lofty::ItemKey::TrackNumber,
+            ItemKey::TrackTotal => lofty::ItemKey::TrackTotal,
+            ItemKey::Popularimeter => lofty::ItemKey::Popularimeter,
+            ItemKey::ParentalAdvisory => lofty::ItemKey::ParentalAdvisory,
+            ItemKey::RecordingDate => lofty::ItemKey::RecordingDate,
+            ItemKey::Year => lofty::ItemKey::Year,
+            ItemKey::OriginalReleaseDate => lofty::ItemKey::OriginalReleaseDate,
+            ItemKey::Isrc => lofty::ItemKey::Isrc,
+            ItemKey::Barcode => lofty::ItemKey::Barcode,
+            ItemKey::CatalogNumber => lofty::ItemKey::CatalogNumber,
+            ItemKey::Work => lofty::ItemKey::Work,
+            ItemKey::Movement => lofty::ItemKey::Movement,
+            ItemKey::MovementNumber => lofty::ItemKey::MovementNumber,
+            ItemKey::MovementTotal => lofty::ItemKey::MovementTotal,
+            ItemKey::MusicBrainzRecordingId => lofty::ItemKey::MusicBrainzRecordingId,
+            ItemKey::MusicBrainzTrackId => lofty::ItemKey::MusicBrainzTrackId,
+            ItemKey::MusicBrainzReleaseId => lofty::ItemKey::MusicBrainzReleaseId,
+            ItemKey::MusicBrainzReleaseGroupId => lofty::ItemKey::MusicBrainzReleaseGroupId,
+            ItemKey::MusicBrainzArtistId => lofty::ItemKey::MusicBrainzArtistId,
+            ItemKey::MusicBrainzReleaseArtistId => lofty::ItemKey::MusicBrainzReleaseArtistId,
+            ItemKey::MusicBrainzWorkId => lofty::ItemKey::MusicBrainzWorkId,
+            ItemKey::FlagCompilation => lofty::ItemKey::FlagCompilation,
+            ItemKey::FlagPodcast => lofty::ItemKey::FlagPodcast,
+            ItemKey::FileType => lofty::ItemKey::FileType,
+            ItemKey::FileOwner => lofty::ItemKey::FileOwner,
+            ItemKey::TaggingTime => lofty::ItemKey::TaggingTime,
+            ItemKey::Length => lofty::ItemKey::Length,
+            ItemKey::OriginalFileName => lofty::ItemKey::OriginalFileName,
+            ItemKey::OriginalMediaType => lofty::ItemKey::OriginalMediaType,
+            ItemKey::EncodedBy => lofty::ItemKey::EncodedBy,
+            ItemKey::EncoderSoftware => lofty::ItemKey::EncoderSoftware,
+            ItemKey::EncoderSettings => lofty::ItemKey::EncoderSettings,
+            ItemKey::EncodingTime => lofty::ItemKey::EncodingTime,
+            ItemKey::ReplayGainAlbumGain => lofty::ItemKey::ReplayGainAlbumGain,
+            ItemKey::ReplayGainAlbumPeak => lofty::ItemKey::ReplayGainAlbumPeak,
+            ItemKey::ReplayGainTrackGain => lofty::ItemKey::ReplayGainTrackGain,
+            ItemKey::ReplayGainTrackPeak => lofty::ItemKey::ReplayGainTrackPeak,
+            ItemKey::R128TrackGain => lofty::ItemKey::Unknown(R128_TRACK_GAIN_KEY.to_string()),
+            ItemKey::R128AlbumGain => lofty::ItemKey::Unknown(R128_ALBUM_GAIN_KEY.to_string()),
+            ItemKey::AudioFileUrl => lofty::ItemKey::AudioFileUrl,
+            ItemKey::AudioSourceUrl => lofty::ItemKey::AudioSourceUrl,
+            ItemKey::CommercialInformationUrl => lofty::ItemKey::CommercialInformationUrl,
+            ItemKey::CopyrightUrl => lofty::ItemKey::CopyrightUrl,
+            ItemKey::TrackArtistUrl => lofty::ItemKey::TrackArtistUrl,
+            ItemKey::RadioStationUrl => lofty::ItemKey::RadioStationUrl,
+            ItemKey::PaymentUrl => lofty::ItemKey::PaymentUrl,
+            ItemKey::PublisherUrl => lofty::ItemKey::PublisherUrl,
+            ItemKey::Genre => lofty::ItemKey::Genre,
+            ItemKey::InitialKey => lofty::ItemKey::InitialKey,
+            ItemKey::Color => lofty::ItemKey::Color,
+            ItemKey::Mood => lofty::ItemKey::Mood,
+            ItemKey::Bpm => lofty::ItemKey::Bpm,
+            ItemKey::CopyrightMessage => lofty::ItemKey::CopyrightMessage,
+            ItemKey::License => lofty::ItemKey::License,
+            ItemKey::PodcastDescription => lofty::ItemKey::PodcastDescription,
+            ItemKey::PodcastSeriesCategory => lofty::ItemKey::PodcastSeriesCategory,
+            ItemKey::PodcastURL => lofty::ItemKey::PodcastURL,
+            ItemKey::PodcastReleaseDate => lofty::ItemKey::PodcastReleaseDate,
+            ItemKey::PodcastGlobalUniqueID => lofty::ItemKey::PodcastGlobalUniqueID,
+            ItemKey::PodcastKeywords => lofty::ItemKey::PodcastKeywords,
+            ItemKey::Comment => lofty::ItemKey::Comment,
+            ItemKey::Description => lofty::ItemKey::Description,
+            ItemKey::Language => lofty::ItemKey::Language,
+            ItemKey::Script => lofty::ItemKey::Script,
+            ItemKey::Lyrics => lofty::ItemKey::Lyrics,
+            ItemKey::AppleXid => lofty::ItemKey::AppleXid,
+            ItemKey::AppleId3v2ContentGroup => lofty::ItemKey::AppleId3v2ContentGroup,
+        }
+    }
+
+    /// Whether the values of this key are URLs, stored as links by some formats.
+    pub(crate) fn is_url(&self) -> bool {
+        matches!(
+            self,
+            ItemKey::AudioFileUrl
+                | ItemKey::AudioSourceUrl
+                | ItemKey::CommercialInformationUrl
+                | ItemKey::CopyrightUrl
+                | ItemKey::TrackArtistUrl
+                | ItemKey::RadioStationUrl
+                | ItemKey::PaymentUrl
+                | ItemKey::PublisherUrl
+        )
+    }
+}
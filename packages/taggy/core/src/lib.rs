@@ -0,0 +1,63 @@
+//! The engine behind the taggy Dart package, free of any Flutter dependencies.
+//!
+//! Use the functions of [api] to read and write audio tags.
+pub mod album_gaps;
+pub mod api;
+pub mod artwork_optimization;
+pub mod audio_info;
+pub mod batch_result;
+#[cfg(feature = "artwork")]
+mod blurhash;
+pub mod builders;
+#[cfg(feature = "capi")]
+pub mod capi;
+pub mod chapter;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod client;
+pub mod comment;
+pub mod custom_item;
+pub mod date_context;
+pub mod disc_set;
+pub mod dj_markers;
+pub mod duplicate_policy;
+pub mod effective_tag;
+pub mod error;
+pub mod featuring;
+pub mod field_class;
+pub mod field_value_count;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod hooks;
+pub mod item_key;
+pub mod limits;
+pub mod lyrics_match;
+pub mod merge_suggestion;
+#[cfg(feature = "artwork")]
+mod palette;
+pub mod palette_color;
+pub mod parse_config;
+pub mod picture;
+pub mod raw_frame;
+pub mod recovery_report;
+pub mod replay_gain;
+pub mod scan_result;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod scanner;
+pub mod synced_lyrics;
+pub mod tag;
+pub mod tag_date;
+pub mod taggy_file;
+#[cfg(feature = "uniffi")]
+mod uniffi_api;
+pub(crate) mod utils;
+#[cfg(feature = "decode")]
+mod waveform;
+pub mod work_link;
+pub mod write_options;
+pub mod write_report;
+pub mod write_request;
+pub mod year_suggestion;
+#[cfg(feature = "zip")]
+mod zip_archive;
+
+#[cfg(feature = "uniffi")]
+uniffi::setup_scaffolding!();
@@ -0,0 +1,193 @@
+use crate::tag::TagType;
+use crate::write_options::TagSizeLimit;
+#[cfg(not(target_arch = "wasm32"))]
+use anyhow::anyhow;
+use lofty::TagExt;
+use std::fmt::{Display, Formatter};
+#[cfg(not(target_arch = "wasm32"))]
+use std::fs;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{self, RecvTimeoutError};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Mutex;
+#[cfg(not(target_arch = "wasm32"))]
+use std::thread;
+#[cfg(not(target_arch = "wasm32"))]
+use std::time::{Duration, Instant};
+
+/// The error returned when an operation exceeds one of the [ClientOptions](crate::client::ClientOptions)
+/// limits, or a written tag exceeds its [TagSizeLimit].
+///
+/// It's wrapped in the returned `anyhow::Error`, use `downcast_ref::<LimitError>()` to tell it
+/// apart from parsing errors, e.g. to skip the file and continue scanning.
+#[derive(Debug, Clone, PartialEq)]
+pub enum LimitError {
+    /// The file is larger than `max_file_size`.
+    FileTooLarge { size: u64, max_file_size: u64 },
+    /// The operation didn't finish within `timeout_ms`.
+    TimedOut { timeout_ms: u64 },
+    /// The encoded `tag_type` tag is larger than the `max_size` of its [TagSizeLimit].
+    TagTooLarge {
+        tag_type: TagType,
+        size: u64,
+        max_size: u64,
+    },
+}
+
+impl Display for LimitError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            LimitError::FileTooLarge {
+                size,
+                max_file_size,
+            } => write!(
+                f,
+                "The file size ({size} bytes) exceeds the limit of {max_file_size} bytes!"
+            ),
+            LimitError::TimedOut { timeout_ms } => {
+                write!(f, "The operation didn't finish within {timeout_ms}ms!")
+            }
+            LimitError::TagTooLarge {
+                tag_type,
+                size,
+                max_size,
+            } => write!(
+                f,
+                "The {tag_type:?} tag size ({size} bytes) exceeds the limit of {max_size} bytes!"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for LimitError {}
+
+/// Fails with [LimitError::FileTooLarge] when the file at `path` is larger than `max_file_size`.
+///
+/// Missing files are left for the operation itself to report.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn check_file_size(path: &str, max_file_size: Option<u64>) -> anyhow::Result<()> {
+    let Some(max_file_size) = max_file_size else {
+        return Ok(());
+    };
+    match fs::metadata(path) {
+        Ok(meta) if meta.len() > max_file_size => Err(LimitError::FileTooLarge {
+            size: meta.len(),
+            max_file_size,
+        }
+        .into()),
+        _ => Ok(()),
+    }
+}
+
+/// Fails with [LimitError::TagTooLarge] when one of the `tags` is larger than the lowest
+/// of the `limits` of its type, the file being left untouched.
+///
+/// The size is the one of the tag encoded as it would be saved, headers and pictures included.
+pub(crate) fn check_tag_sizes(tags: &[lofty::Tag], limits: &[TagSizeLimit]) -> anyhow::Result<()> {
+    for tag in tags {
+        let tag_type = TagType::from(tag.tag_type());
+        let Some(max_size) = limits
+            .iter()
+            .filter(|limit| limit.tag_type == tag_type)
+            .map(|limit| limit.max_size)
+            .min()
+        else {
+            continue;
+        };
+        let mut encoded = vec![];
+        tag.dump_to(&mut encoded)?;
+        let size = encoded.len() as u64;
+        if size > max_size {
+            return Err(LimitError::TagTooLarge {
+                tag_type,
+                size,
+                max_size,
+            }
+            .into());
+        }
+    }
+    Ok(())
+}
+
+/// Runs `op` on a separate thread and fails with [LimitError::TimedOut] when it doesn't
+/// return within `timeout_ms`.
+///
+/// **Note**: the thread can't be interrupted, a timed out `op` keeps running in the background
+/// and its result is discarded. So this should only wrap operations without side effects, like reads.
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) fn with_timeout<T, F>(timeout_ms: Option<u64>, op: F) -> anyhow::Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> anyhow::Result<T> + Send + 'static,
+{
+    let Some(timeout_ms) = timeout_ms else {
+        return op();
+    };
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        // the receiver is gone when the operation timed out.
+        let _ = sender.send(op());
+    });
+    match receiver.recv_timeout(Duration::from_millis(timeout_ms)) {
+        Ok(result) => result,
+        Err(RecvTimeoutError::Timeout) => Err(LimitError::TimedOut { timeout_ms }.into()),
+        Err(RecvTimeoutError::Disconnected) => Err(anyhow!("The operation panicked!")),
+    }
+}
+
+/// Spaces out file operations so they stay under `max_files_per_sec` and `max_bytes_per_sec`,
+/// e.g. to keep a background library scan from saturating the disk.
+///
+/// Each operation waits until the previous ones have "paid" for their files and bytes,
+/// so short bursts aren't allowed.
+#[cfg(not(target_arch = "wasm32"))]
+#[derive(Debug)]
+pub(crate) struct Throttle {
+    max_files_per_sec: Option<u32>,
+    max_bytes_per_sec: Option<u64>,
+    /// When the next operation may start.
+    next_start: Mutex<Option<Instant>>,
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+impl Throttle {
+    pub(crate) fn new(max_files_per_sec: Option<u32>, max_bytes_per_sec: Option<u64>) -> Self {
+        Self {
+            max_files_per_sec: max_files_per_sec.filter(|&max| max > 0),
+            max_bytes_per_sec: max_bytes_per_sec.filter(|&max| max > 0),
+            next_start: Mutex::new(None),
+        }
+    }
+
+    /// Blocks until an operation on the file at `path` is allowed to start.
+    pub(crate) fn wait(&self, path: &str) {
+        if self.max_files_per_sec.is_none() && self.max_bytes_per_sec.is_none() {
+            return;
+        }
+        let bytes = fs::metadata(path).map(|meta| meta.len()).unwrap_or(0);
+        let cost = self.cost_of(bytes);
+
+        let start = {
+            let mut next_start = self.next_start.lock().unwrap();
+            let now = Instant::now();
+            let start = next_start.map_or(now, |next| next.max(now));
+            *next_start = Some(start + cost);
+            start
+        };
+        let now = Instant::now();
+        if start > now {
+            thread::sleep(start - now);
+        }
+    }
+
+    /// How long an operation on a file of `bytes` holds the throttle.
+    fn cost_of(&self, bytes: u64) -> Duration {
+        let per_file = self.max_files_per_sec.map_or(Duration::ZERO, |max| {
+            Duration::from_secs_f64(1.0 / max as f64)
+        });
+        let per_bytes = self.max_bytes_per_sec.map_or(Duration::ZERO, |max| {
+            Duration::from_secs_f64(bytes as f64 / max as f64)
+        });
+        per_file.max(per_bytes)
+    }
+}
@@ -0,0 +1,17 @@
+/// A file whose lyrics contain the query of [search_lyrics](crate::api::search_lyrics).
+///
+/// The offsets and lengths are in UTF-16 code units, like the indexes of the Dart strings.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct LyricsMatch {
+    /// The path of the file.
+    pub path: String,
+    /// The lines of the lyrics holding the first occurrence of the query.
+    pub snippet: String,
+    /// Where the first occurrence starts in the `snippet`.
+    pub snippet_offset: u32,
+    /// The length of the first occurrence in the `snippet`.
+    pub snippet_length: u32,
+    /// Where each occurrence starts in the whole lyrics.
+    pub offsets: Vec<u32>,
+}
@@ -0,0 +1,11 @@
+/// A group of near-duplicate values of a field, see [suggest_merges](crate::api::suggest_merges).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct MergeSuggestion {
+    /// The value to keep, the most common one of the group.
+    pub canonical: String,
+    /// The other values of the group, to be replaced with the `canonical` one.
+    pub variants: Vec<String>,
+    /// The number of files having any value of the group.
+    pub count: u32,
+}
@@ -0,0 +1,103 @@
+use crate::palette_color::PaletteColor;
+use crate::utils::images::decode_thumbnail;
+
+/// The size the pictures are downscaled to before their colors are clustered.
+///
+/// The dominant colors survive the downscaling, and clustering 4096 pixels at most
+/// keeps it fast regardless of the picture resolution.
+const SAMPLE_SIZE: u32 = 64;
+
+/// The maximum number of k-means iterations, most pictures converge way before.
+const MAX_ITERATIONS: usize = 16;
+
+type Rgb = [f32; 3];
+
+/// Decodes the picture `data` and returns its `k` dominant colors, most common first.
+///
+/// Fewer colors are returned when the picture has less than `k` distinct colors.
+pub(crate) fn dominant_colors(data: &[u8], k: usize) -> anyhow::Result<Vec<PaletteColor>> {
+    if k == 0 {
+        return Ok(vec![]);
+    }
+    let pixels: Vec<Rgb> = decode_thumbnail(data, SAMPLE_SIZE)?
+        .pixels()
+        .map(|p| p.0.map(f32::from))
+        .collect();
+    Ok(cluster(&pixels, k))
+}
+
+/// Groups the `pixels` into `k` clusters at most with k-means, and returns their mean colors.
+fn cluster(pixels: &[Rgb], k: usize) -> Vec<PaletteColor> {
+    let mut centers = initial_centers(pixels, k);
+    let mut assignments = vec![0; pixels.len()];
+    let mut counts = vec![0usize; centers.len()];
+    for iteration in 0..MAX_ITERATIONS {
+        let mut changed = false;
+        for (pixel, assignment) in pixels.iter().zip(assignments.iter_mut()) {
+            let nearest = nearest_center(pixel, &centers);
+            changed |= nearest != *assignment;
+            *assignment = nearest;
+        }
+        let mut sums = vec![[0.0; 3]; centers.len()];
+        counts = vec![0; centers.len()];
+        for (pixel, &assignment) in pixels.iter().zip(&assignments) {
+            for (sum, value) in sums[assignment].iter_mut().zip(pixel) {
+                *sum += value;
+            }
+            counts[assignment] += 1;
+        }
+        for ((center, sum), &count) in centers.iter_mut().zip(&sums).zip(&counts) {
+            if count > 0 {
+                *center = sum.map(|s| s / count as f32);
+            }
+        }
+        if !changed && iteration > 0 {
+            break;
+        }
+    }
+
+    let mut colors: Vec<(Rgb, usize)> = centers
+        .into_iter()
+        .zip(counts)
+        .filter(|(_, count)| *count > 0)
+        .collect();
+    colors.sort_by(|a, b| b.1.cmp(&a.1));
+    colors
+        .into_iter()
+        .map(|([red, green, blue], count)| PaletteColor {
+            red: red.round() as u8,
+            green: green.round() as u8,
+            blue: blue.round() as u8,
+            proportion: count as f32 / pixels.len() as f32,
+        })
+        .collect()
+}
+
+/// Picks `k` distinct pixels spread over the luminance range as the initial centers,
+/// so the palette of a picture is always the same.
+fn initial_centers(pixels: &[Rgb], k: usize) -> Vec<Rgb> {
+    let mut distinct = pixels.to_vec();
+    distinct.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    distinct.dedup();
+    distinct.sort_by(|a, b| luminance(a).total_cmp(&luminance(b)));
+    let k = k.min(distinct.len());
+    (0..k)
+        .map(|i| distinct[(2 * i + 1) * distinct.len() / (2 * k)])
+        .collect()
+}
+
+fn nearest_center(pixel: &Rgb, centers: &[Rgb]) -> usize {
+    centers
+        .iter()
+        .enumerate()
+        .min_by(|(_, a), (_, b)| distance(pixel, a).total_cmp(&distance(pixel, b)))
+        .map_or(0, |(i, _)| i)
+}
+
+fn distance(a: &Rgb, b: &Rgb) -> f32 {
+    a.iter().zip(b).map(|(x, y)| (x - y) * (x - y)).sum()
+}
+
+fn luminance([red, green, blue]: &Rgb) -> f32 {
+    0.2126 * red + 0.7152 * green + 0.0722 * blue
+}
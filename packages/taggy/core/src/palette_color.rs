@@ -0,0 +1,10 @@
+/// One of the dominant colors of a picture, see [artwork_palette](crate::api::artwork_palette).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PaletteColor {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    /// The share of the picture pixels closest to this color, from `0.0` to `1.0`.
+    pub proportion: f32,
+}
@@ -0,0 +1,53 @@
+use crate::date_context::DateContext;
+use crate::duplicate_policy::DuplicatePolicy;
+
+/// How the files are parsed, see [read_all_with_config](crate::api::read_all_with_config).
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ParseConfig {
+    /// The maximum number of bytes to skip while looking for the tags or the audio stream,
+    /// e.g. for files with a large junk prefix. Defaults to `1024`.
+    pub max_junk_bytes: u32,
+    /// Read the audio properties (duration, bitrate...), which takes extra time.
+    ///
+    /// When disabled, the [AudioInfo](crate::audio_info::AudioInfo) of the read file is zeroed.
+    pub read_properties: bool,
+    /// How to handle the invalid parts of the file.
+    pub parsing_mode: ParsingMode,
+    /// Return the pictures data along with their metadata (type, mime type, size...).
+    ///
+    /// When disabled, the [Picture](crate::picture::Picture)s `pic_data` is empty, which keeps
+    /// large embedded artwork out of memory and off the FFI boundary when only the tags are needed.
+    /// Writing such pictures back keeps the data already in the file.
+    pub read_picture_data: bool,
+    /// Normalize the dates of the read tags according to the user's locale and time zone,
+    /// see [DateContext]. `None` to return the dates as they are written.
+    pub date_context: Option<DateContext>,
+    /// Which value is read for the fields a tag holds several times, e.g. two ID3v2 `TIT2`
+    /// frames. Defaults to the first one.
+    pub duplicate_policy: DuplicatePolicy,
+}
+
+impl Default for ParseConfig {
+    fn default() -> Self {
+        Self {
+            max_junk_bytes: 1024,
+            read_properties: true,
+            parsing_mode: ParsingMode::default(),
+            read_picture_data: true,
+            date_context: None,
+            duplicate_policy: DuplicatePolicy::default(),
+        }
+    }
+}
+
+/// How to handle the invalid parts of a file, see [ParseConfig].
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum ParsingMode {
+    /// Fail on any invalid part.
+    Strict,
+    /// Skip the invalid parts when possible, and only fail when the file can't be read at all.
+    #[default]
+    BestAttempt,
+}
@@ -0,0 +1,186 @@
+use crate::error::TaggyError;
+use crate::tag::TagType;
+use crate::utils::read_only::open_read_only;
+use std::fmt::Debug;
+use std::io::Read;
+
+/// Gives information about a tag's picture.
+///
+/// The `width`, `height`, `color_depth` and `num_colors` are read from the image header, and
+/// written to the formats storing them along with the picture (i.e. FLAC picture blocks).
+#[derive(Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Picture {
+    pub pic_type: PictureType,
+    /// The picture's data
+    pub pic_data: Vec<u8>,
+    /// The picture's mimetype
+    pub mime_type: Option<MimeType>,
+    /// The picture's width in pixels
+    pub width: Option<u32>,
+    /// The picture's height in pixels
+    pub height: Option<u32>,
+    /// The picture's color depth in bits per pixel
+    pub color_depth: Option<u32>,
+    /// The number of colors used
+    pub num_colors: Option<u32>,
+    /// The size of the picture's data in bytes, set on read even when the data itself
+    /// was skipped, see [ParseConfig](crate::parse_config::ParseConfig)'s `read_picture_data`.
+    pub data_size: Option<u32>,
+    /// The picture's description, e.g. `Front cover`.
+    pub description: Option<String>,
+    /// How the picture is stored in the file it was read from, `None` for the pictures
+    /// which weren't read from a file. Ignored on write, where it follows the file format.
+    pub storage: Option<PictureStorage>,
+}
+impl Picture {
+    /// Reads the image file at `path` as a `pic_type` picture.
+    ///
+    /// The mimetype is detected from the image data, and the width, height,
+    /// color depth and number of colors are extracted from it when the format allows it.
+    pub fn from_path(path: &str, pic_type: PictureType) -> anyhow::Result<Self> {
+        let mut data = vec![];
+        open_read_only(path)?.read_to_end(&mut data)?;
+        let mut picture = lofty::Picture::from_reader(&mut data.as_slice()).map_err(|_| {
+            TaggyError::invalid_value(format!("'{path}' is not a supported image!"))
+        })?;
+        picture.set_pic_type(pic_type.into());
+        Ok(Picture::from(&picture))
+    }
+}
+/// The `storage` is left out, so a picture read from a file equals the one it was written from.
+impl PartialEq for Picture {
+    fn eq(&self, other: &Self) -> bool {
+        self.pic_type == other.pic_type
+            && self.pic_data == other.pic_data
+            && self.mime_type == other.mime_type
+            && self.width == other.width
+            && self.height == other.height
+            && self.color_depth == other.color_depth
+            && self.num_colors == other.num_colors
+            && self.data_size == other.data_size
+            && self.description == other.description
+    }
+}
+impl Debug for Picture {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Picture")
+            .field("pic_type", &self.pic_type)
+            .field("pic_data", &self.pic_data.len())
+            .field("mime_type", &self.mime_type)
+            .field("width", &self.width)
+            .field("height", &self.height)
+            .field("color_depth", &self.color_depth)
+            .field("num_colors", &self.num_colors)
+            .field("data_size", &self.data_size)
+            .field("description", &self.description)
+            .field("storage", &self.storage)
+            .finish()
+    }
+}
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum PictureType {
+    Other,
+    Icon,
+    OtherIcon,
+    CoverFront,
+    CoverBack,
+    Leaflet,
+    Media,
+    LeadArtist,
+    Artist,
+    Conductor,
+    Band,
+    Composer,
+    Lyricist,
+    RecordingLocation,
+    DuringRecording,
+    DuringPerformance,
+    ScreenCapture,
+    BrightFish,
+    Illustration,
+    BandLogo,
+    PublisherLogo,
+    Undefined,
+}
+
+/// The changes to the type, description and mimetype of a picture already in a file,
+/// see [update_picture_meta](crate::api::update_picture_meta). The `None` fields are left as they are.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct PictureChanges {
+    pub pic_type: Option<PictureType>,
+    /// The new description, an empty one removes it.
+    pub description: Option<String>,
+    /// The new mimetype, e.g. for a PNG marked as JPEG. The picture data isn't converted.
+    pub mime_type: Option<MimeType>,
+}
+
+/// Which picture comes first when a file has several, e.g. to pick the one shown as the cover,
+/// see [Tag::primary_picture](crate::tag::Tag::primary_picture).
+///
+/// The pictures which are equal for the order keep their stored order.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum PictureOrder {
+    /// The order the pictures are stored in.
+    AsStored,
+    /// The front covers first.
+    #[default]
+    FrontCoverFirst,
+    /// The front covers, then the back covers, then the other types in their ID3v2 order.
+    ByType,
+    /// The largest pictures first, by pixel count then by data size.
+    BySize,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum MimeType {
+    /// PNG image
+    Png,
+    /// JPEG image
+    Jpeg,
+    /// TIFF image
+    Tiff,
+    /// BMP image
+    Bmp,
+    /// GIF image
+    Gif,
+    /// Unknown mimetype
+    Unknown,
+    /// No mimetype
+    None,
+}
+
+/// How a picture is stored in a file, see [Picture::storage].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum PictureStorage {
+    /// An ID3v2 `APIC` frame.
+    Id3v2Frame,
+    /// A FLAC `PICTURE` metadata block.
+    FlacBlock,
+    /// A FLAC picture block encoded in base64 in a `METADATA_BLOCK_PICTURE` comment,
+    /// for the Ogg Vorbis, Opus and Speex files.
+    VorbisComment,
+    /// An MP4 `covr` atom.
+    Mp4Atom,
+    /// An APE binary item, e.g. `Cover Art (Front)`.
+    ApeItem,
+}
+
+impl PictureStorage {
+    /// How the pictures of a `tag_type` tag are stored in a `file_type` file.
+    pub(crate) fn of(tag_type: TagType, file_type: lofty::FileType) -> Option<Self> {
+        match tag_type {
+            TagType::Id3v2 => Some(Self::Id3v2Frame),
+            TagType::VorbisComments if file_type == lofty::FileType::Flac => Some(Self::FlacBlock),
+            TagType::VorbisComments => Some(Self::VorbisComment),
+            TagType::Mp4Ilst => Some(Self::Mp4Atom),
+            TagType::Ape => Some(Self::ApeItem),
+            _ => None,
+        }
+    }
+}
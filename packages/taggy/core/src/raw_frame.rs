@@ -0,0 +1,17 @@
+/// A frame of an ID3v2 tag as stored in the file, for the frames [Tag](crate::tag::Tag) doesn't
+/// model, e.g. `GEOB`, `PRIV` or proprietary ones, see [read_raw_frames](crate::api::read_raw_frames)
+/// and [write_raw_frame](crate::api::write_raw_frame).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RawFrame {
+    /// The frame ID, e.g. `GEOB`, of 3 characters in the ID3v2.2 tags.
+    pub id: String,
+    /// The frame flags, in the ID3v2.4 layout whatever the tag version, e.g. `0x1000`
+    /// for a read-only frame.
+    pub flags: u16,
+    /// The frame content, without its header.
+    ///
+    /// The unsynchronization and the data length indicator are removed, but the compressed or
+    /// encrypted content is kept as is.
+    pub data: Vec<u8>,
+}
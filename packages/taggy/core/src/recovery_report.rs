@@ -0,0 +1,12 @@
+/// The outcome of recovering the interrupted transactions of a folder,
+/// see [recover](crate::api::recover).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct RecoveryReport {
+    /// The files put back as they were before a transaction which didn't fully commit.
+    pub rolled_back: Vec<String>,
+    /// The files of a transaction which committed, but was interrupted while cleaning up.
+    pub completed: Vec<String>,
+    /// The files whose leftover copies were removed, the files themselves weren't changed.
+    pub discarded: Vec<String>,
+}
@@ -0,0 +1,19 @@
+/// The loudness normalization gains of a file, see [read_replay_gain](crate::api::read_replay_gain).
+///
+/// The gains are in dB relative to the ReplayGain reference of -18 LUFS whatever the format,
+/// the R128 gains of the Opus files being converted from their -23 LUFS reference.
+#[derive(Debug, Clone, PartialEq, Default)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ReplayGain {
+    /// The gain to apply to play the track at the reference loudness, e.g. `-6.5`.
+    pub track_gain_db: Option<f64>,
+    /// The peak sample of the track, `1.0` being full scale. Not stored by the Opus files.
+    pub track_peak: Option<f64>,
+    /// The gain to apply to play the album at the reference loudness.
+    pub album_gain_db: Option<f64>,
+    /// The peak sample of the album, `1.0` being full scale. Not stored by the Opus files.
+    pub album_peak: Option<f64>,
+    /// The output gain of the Opus header, which every decoder applies before the other gains,
+    /// `None` for the other formats.
+    pub opus_output_gain_db: Option<f64>,
+}
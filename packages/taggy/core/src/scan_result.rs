@@ -0,0 +1,63 @@
+use crate::error::TaggyError;
+use crate::taggy_file::TaggyFile;
+
+/// The outcome of a scan, see [scan_directory_with_quarantine](crate::api::scan_directory_with_quarantine).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ScanResult {
+    /// The files read successfully, sorted by path.
+    pub files: Vec<ScannedFile>,
+    /// The files and folders which couldn't be read, sorted by path, to be reviewed afterwards.
+    pub quarantine: Vec<QuarantinedFile>,
+}
+
+/// A file read successfully by a scan.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ScannedFile {
+    pub path: String,
+    pub file: TaggyFile,
+}
+
+/// A file, or a folder, which a scan couldn't read.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct QuarantinedFile {
+    pub path: String,
+    /// The [TaggyError::code] of the failure, e.g. `corrupt_tag` or `empty_or_truncated`.
+    pub code: String,
+    /// Why the file couldn't be read.
+    pub reason: String,
+}
+
+impl QuarantinedFile {
+    pub(crate) fn new(path: String, error: &anyhow::Error) -> Self {
+        let error = TaggyError::from(error);
+        Self {
+            path,
+            code: error.code().to_string(),
+            reason: error.to_string(),
+        }
+    }
+}
+
+impl ScanResult {
+    /// Splits the read `results` between the files and the quarantine,
+    /// along with the `unreadable` entries of the walk.
+    pub(crate) fn new(
+        results: Vec<(String, anyhow::Result<TaggyFile>)>,
+        unreadable: Vec<QuarantinedFile>,
+    ) -> Self {
+        let mut files = vec![];
+        let mut quarantine = unreadable;
+        for (path, result) in results {
+            match result {
+                Ok(file) => files.push(ScannedFile { path, file }),
+                Err(e) => quarantine.push(QuarantinedFile::new(path, &e)),
+            }
+        }
+        files.sort_by(|a, b| a.path.cmp(&b.path));
+        quarantine.sort_by(|a, b| a.path.cmp(&b.path));
+        Self { files, quarantine }
+    }
+}
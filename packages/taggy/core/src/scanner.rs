@@ -0,0 +1,377 @@
+//! Walks folders for audio files, see [scan_directory], [scan_directory_stream],
+//! [scan_directory_with_quarantine] and [scan_directory_with_options].
+use crate::api;
+use crate::batch_result::BatchReadResult;
+use crate::error::TaggyError;
+use crate::scan_result::{QuarantinedFile, ScanResult};
+use crate::utils::pool::{for_each_parallel, map_parallel};
+use lofty::FileType;
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// How many files are read between two [ScanEvent::Progress] events.
+const PROGRESS_INTERVAL: u32 = 25;
+
+/// An event of a streamed scan, see [scan_directory_stream].
+#[derive(Debug, Clone)]
+pub enum ScanEvent {
+    /// A file was read, or failed to be read.
+    File(Box<BatchReadResult>),
+    /// `done` files out of the `total` found were read.
+    ///
+    /// Emitted once the files are found, then periodically, and after the last file.
+    Progress { done: u32, total: u32 },
+}
+
+/// How a scan handles the symbolic links, and the junctions on Windows.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum SymlinkPolicy {
+    /// The links are ignored.
+    #[default]
+    Skip,
+    /// The links are followed, and each file is read once, under the first path it's found at
+    /// in path order, so the files linked from several places aren't duplicated.
+    Follow,
+    /// The links are followed, and each path a file is found at is read as a distinct entry.
+    Distinct,
+}
+
+/// What a scan walks and reads, see [scan_directory_with_options].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct ScanOptions {
+    /// Walk the subfolders too.
+    pub recursive: bool,
+    /// Only read the files with one of these extensions (case insensitive, without the dot,
+    /// e.g. `mp3`), or the files of any format taggy supports when empty.
+    pub extensions: Vec<String>,
+    /// The number of files read at the same time, `0` for one per CPU core.
+    pub concurrency: u32,
+    /// How the symbolic links are handled.
+    pub symlinks: SymlinkPolicy,
+    /// Skip the hidden files and folders, i.e. the ones starting with a dot,
+    /// or having the hidden attribute on Windows.
+    pub skip_hidden: bool,
+    /// Skip the files and folders left behind by the operating systems, which aren't audio files
+    /// even with an audio extension: the `._` AppleDouble files, the `.AppleDouble` and `__MACOSX`
+    /// folders, and the recycle bins (`$RECYCLE.BIN`, `.Trashes`...).
+    pub skip_system_files: bool,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        Self {
+            recursive: true,
+            extensions: vec![],
+            concurrency: 0,
+            symlinks: SymlinkPolicy::default(),
+            skip_hidden: true,
+            skip_system_files: true,
+        }
+    }
+}
+
+impl ScanOptions {
+    fn new(recursive: bool, extensions: &[String], concurrency: u32) -> Self {
+        Self {
+            recursive,
+            extensions: extensions.to_vec(),
+            concurrency,
+            ..Default::default()
+        }
+    }
+}
+
+/// The names of the folders holding the artifacts of the operating systems, see [ScanOptions]
+/// `skip_system_files`, lower cased.
+///
+/// The `.Trash`, `.Trashes` and `.Trash-<uid>` folders are matched by their prefix.
+const SYSTEM_FOLDERS: [&str; 6] = [
+    ".appledouble",
+    "__macosx",
+    "$recycle.bin",
+    "recycler",
+    "recycled",
+    "system volume information",
+];
+
+/// Reads all audio tags from the audio files in the directory at `dir`.
+///
+/// Only the files with one of the given `extensions` (case insensitive, without the dot,
+/// e.g. `mp3`) are read, or the files of any format taggy supports when `extensions` is empty.
+/// The subfolders are walked too when `recursive` is set, without following symbolic links.
+/// The hidden and system files are skipped, see [ScanOptions].
+///
+/// The files are read in parallel on `concurrency` threads (`0` for one per CPU core),
+/// see [read_all_batch](api::read_all_batch). The results are sorted by path.
+pub fn scan_directory(
+    dir: &str,
+    recursive: bool,
+    extensions: &[String],
+    concurrency: u32,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    let options = ScanOptions::new(recursive, extensions, concurrency);
+    scan_directory_with_options(dir, &options)
+}
+
+/// Same as [scan_directory], with all the [ScanOptions].
+pub fn scan_directory_with_options(
+    dir: &str,
+    options: &ScanOptions,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    let paths = find_audio_files(dir, options)?;
+    Ok(api::read_all_batch(paths, options.concurrency))
+}
+
+/// Same as [scan_directory], but the symbolic links are handled according to `symlinks`.
+///
+/// When the links are followed, the links to one of their own parent folders are skipped,
+/// so cycles don't hang the scan. Use the [TaggyFile](crate::taggy_file::TaggyFile)s
+/// `canonical_path` to tell where a linked file actually is.
+pub fn scan_directory_with_symlinks(
+    dir: &str,
+    recursive: bool,
+    extensions: &[String],
+    concurrency: u32,
+    symlinks: SymlinkPolicy,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    let options = ScanOptions {
+        symlinks,
+        ..ScanOptions::new(recursive, extensions, concurrency)
+    };
+    scan_directory_with_options(dir, &options)
+}
+
+/// Same as [scan_directory], but calls `on_event` with each file as soon as it's read,
+/// along with periodic [ScanEvent::Progress] events, so large libraries can be shown incrementally.
+///
+/// The files are reported in the order they finish being read, not sorted.
+pub fn scan_directory_stream(
+    dir: &str,
+    recursive: bool,
+    extensions: &[String],
+    concurrency: u32,
+    mut on_event: impl FnMut(ScanEvent),
+) -> anyhow::Result<()> {
+    let options = ScanOptions::new(recursive, extensions, concurrency);
+    let paths = find_audio_files(dir, &options)?;
+    let total = paths.len() as u32;
+    on_event(ScanEvent::Progress { done: 0, total });
+
+    let mut done = 0;
+    for_each_parallel(
+        &paths,
+        concurrency,
+        |path| BatchReadResult::new(path.clone(), api::read_all(path.clone())),
+        |_, result| {
+            done += 1;
+            on_event(ScanEvent::File(Box::new(result)));
+            if done % PROGRESS_INTERVAL == 0 || done == total {
+                on_event(ScanEvent::Progress { done, total });
+            }
+        },
+    );
+    Ok(())
+}
+
+/// Same as [scan_directory], but splits the files which can't be read into a separate
+/// quarantine list with the reason of each failure, so they can be reviewed afterwards.
+///
+/// The subfolders which can't be listed, e.g. for lack of permissions, are quarantined too
+/// instead of failing the whole scan.
+pub fn scan_directory_with_quarantine(
+    dir: &str,
+    recursive: bool,
+    extensions: &[String],
+    concurrency: u32,
+) -> anyhow::Result<ScanResult> {
+    let options = ScanOptions::new(recursive, extensions, concurrency);
+    let (paths, unreadable) = walk_audio_files(dir, &options)?;
+    let results = map_parallel(&paths, concurrency, |path| {
+        (path.clone(), api::read_all(path.clone()))
+    });
+    let unreadable = unreadable
+        .into_iter()
+        .map(|(path, e)| QuarantinedFile::new(path, &e.into()))
+        .collect();
+    Ok(ScanResult::new(results, unreadable))
+}
+
+/// Lists the paths of the audio files to scan, sorted.
+///
+/// Fails on the first subfolder which can't be listed.
+fn find_audio_files(dir: &str, options: &ScanOptions) -> anyhow::Result<Vec<String>> {
+    let (files, unreadable) = walk_audio_files(dir, options)?;
+    if let Some((_, e)) = unreadable.into_iter().next() {
+        return Err(e.into());
+    }
+    Ok(files)
+}
+
+/// A subfolder which can't be listed, with the reason.
+type UnlistedDir = (String, io::Error);
+
+/// Lists the paths of the audio files to scan, sorted, along with the subfolders
+/// which can't be listed.
+fn walk_audio_files(
+    dir: &str,
+    options: &ScanOptions,
+) -> anyhow::Result<(Vec<String>, Vec<UnlistedDir>)> {
+    let dir = Path::new(dir);
+    if !dir.is_dir() {
+        return Err(TaggyError::DirectoryNotFound.into());
+    }
+    let extensions: Vec<String> = options
+        .extensions
+        .iter()
+        .map(|e| e.trim_start_matches('.').to_lowercase())
+        .collect();
+
+    let mut walker = Walker {
+        options,
+        extensions: &extensions,
+        files: vec![],
+        unreadable: vec![],
+        ancestors: vec![],
+        visited: HashSet::new(),
+    };
+    let entries = fs::read_dir(dir)?;
+    walker.walk_dir(dir, entries);
+
+    let mut files = walker.files;
+    files.sort();
+    let files = files
+        .into_iter()
+        .map(|f| f.to_string_lossy().to_string())
+        .collect();
+    Ok((files, walker.unreadable))
+}
+
+/// The state of a walk, see [walk_audio_files].
+struct Walker<'a> {
+    options: &'a ScanOptions,
+    /// The `options` extensions, normalized.
+    extensions: &'a [String],
+    files: Vec<PathBuf>,
+    unreadable: Vec<UnlistedDir>,
+    /// The canonical paths of the folders being walked, the current one last, to detect cycles.
+    ///
+    /// Only tracked when the links are followed, as there's no cycle otherwise.
+    ancestors: Vec<PathBuf>,
+    /// The canonical paths of the files and folders already found, see [SymlinkPolicy::Follow].
+    visited: HashSet<PathBuf>,
+}
+
+impl Walker<'_> {
+    /// Walks the folder at `path`, listed as `entries`, unless it's one of its own ancestors
+    /// or was already walked with [SymlinkPolicy::Follow].
+    fn walk_dir(&mut self, path: &Path, entries: fs::ReadDir) {
+        if self.options.symlinks == SymlinkPolicy::Skip {
+            self.collect_files(entries);
+            return;
+        }
+        let canonical = canonical_path(path);
+        if self.ancestors.contains(&canonical) || !self.first_visit(path) {
+            return;
+        }
+        self.ancestors.push(canonical);
+        self.collect_files(entries);
+        self.ancestors.pop();
+    }
+
+    fn collect_files(&mut self, entries: fs::ReadDir) {
+        let mut entries: Vec<fs::DirEntry> = entries.flatten().collect();
+        // so the paths kept by [SymlinkPolicy::Follow] don't depend on the listing order.
+        entries.sort_by_key(|e| e.path());
+
+        for entry in entries {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            let path = entry.path();
+            if self.skips(&entry) {
+                continue;
+            }
+            let (is_dir, is_file) = if !file_type.is_symlink() {
+                (file_type.is_dir(), file_type.is_file())
+            } else if self.options.symlinks == SymlinkPolicy::Skip {
+                continue;
+            } else {
+                // broken links are skipped.
+                let Ok(meta) = fs::metadata(&path) else {
+                    continue;
+                };
+                (meta.is_dir(), meta.is_file())
+            };
+
+            if is_dir && self.options.recursive {
+                match fs::read_dir(&path) {
+                    Ok(entries) => self.walk_dir(&path, entries),
+                    Err(e) => self
+                        .unreadable
+                        .push((path.to_string_lossy().to_string(), e)),
+                }
+            } else if is_file
+                && has_audio_extension(&path, self.extensions)
+                && self.first_visit(&path)
+            {
+                self.files.push(path);
+            }
+        }
+    }
+
+    /// Whether the `entry` is skipped according to the `skip_hidden` and `skip_system_files` options.
+    fn skips(&self, entry: &fs::DirEntry) -> bool {
+        let name = entry.file_name().to_string_lossy().to_lowercase();
+        if self.options.skip_system_files
+            && (name.starts_with("._")
+                || name.starts_with(".trash")
+                || SYSTEM_FOLDERS.contains(&name.as_str()))
+        {
+            return true;
+        }
+        self.options.skip_hidden && (name.starts_with('.') || has_hidden_attribute(entry))
+    }
+
+    /// Whether the file or folder at `path` is found for the first time,
+    /// always `true` unless the links are followed with [SymlinkPolicy::Follow].
+    fn first_visit(&mut self, path: &Path) -> bool {
+        self.options.symlinks != SymlinkPolicy::Follow || self.visited.insert(canonical_path(path))
+    }
+}
+
+/// Whether the `entry` has the hidden or system attribute, which only exist on Windows.
+#[cfg(windows)]
+fn has_hidden_attribute(entry: &fs::DirEntry) -> bool {
+    use std::os::windows::fs::MetadataExt;
+    const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+    const FILE_ATTRIBUTE_SYSTEM: u32 = 0x4;
+
+    entry.metadata().map_or(false, |meta| {
+        meta.file_attributes() & (FILE_ATTRIBUTE_HIDDEN | FILE_ATTRIBUTE_SYSTEM) != 0
+    })
+}
+
+#[cfg(not(windows))]
+fn has_hidden_attribute(_entry: &fs::DirEntry) -> bool {
+    false
+}
+
+/// The `path` with all its links resolved, or as is when it can't be resolved.
+fn canonical_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn has_audio_extension(path: &Path, extensions: &[String]) -> bool {
+    let Some(extension) = path.extension().and_then(|e| e.to_str()) else {
+        return false;
+    };
+    if extensions.is_empty() {
+        FileType::from_ext(extension).is_some()
+    } else {
+        extensions.contains(&extension.to_lowercase())
+    }
+}
@@ -0,0 +1,21 @@
+/// Lyrics with the time each line is sung at, e.g. for karaoke-style highlighting,
+/// see [read_synced_lyrics](crate::api::read_synced_lyrics) and [parse_lrc](crate::api::parse_lrc).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SyncedLyrics {
+    /// The ISO 639-2 language of the lyrics, e.g. `eng`.
+    pub language: Option<String>,
+    /// The description of the lyrics, e.g. `Live version`.
+    pub description: Option<String>,
+    /// The lines, sorted by time.
+    pub lines: Vec<SyncedLine>,
+}
+
+/// A line of [SyncedLyrics].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct SyncedLine {
+    /// The time the line starts at, in milliseconds from the start of the track.
+    pub timestamp_ms: u32,
+    pub text: String,
+}
@@ -0,0 +1,346 @@
+use crate::builders::tag_builder::TagBuilder;
+use crate::comment::Comment;
+use crate::custom_item::CustomItem;
+use crate::picture::{Picture, PictureOrder};
+use crate::tag_date::TagDate;
+use crate::utils::picture_order::order_key;
+use rand::prelude::SliceRandom;
+
+#[derive(Debug, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct Tag {
+    pub tag_type: TagType,
+    pub pictures: Vec<Picture>,
+    // Track General Info
+    pub track_title: Option<String>,
+    pub track_artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub producer: Option<String>,
+    pub track_number: Option<u32>,
+    /// Total track count of this track's disc
+    pub track_total: Option<u32>,
+    pub disc_number: Option<u32>,
+    pub disc_total: Option<u32>,
+    pub year: Option<u32>,
+    pub recording_date: Option<String>,
+    pub original_release_date: Option<String>,
+    /// The release date, as precise as the tag stores it, i.e. the ID3v2.4 `TDRC` frame, the ID3v2.3
+    /// `TYER` and `TDAT` frames, the MP4 `©day` atom and the `DATE` Vorbis comment.
+    ///
+    /// Read from the same item as `recording_date`, and written instead of it when they differ.
+    /// The ID3v2 tags are written as ID3v2.4, so the `TYER` and `TDAT` frames become a `TDRC` one.
+    pub release_date: Option<TagDate>,
+    /// The original release date, i.e. the ID3v2.4 `TDOR` frame, the ID3v2.3 `TORY` one and
+    /// the `ORIGINALDATE` Vorbis comment, see `release_date`.
+    ///
+    /// Read from the same item as `original_release_date`, and written instead of it when they
+    /// differ.
+    pub original_date: Option<TagDate>,
+    pub language: Option<String>,
+    pub lyrics: Option<String>,
+    pub genre: Option<String>,
+    /// The International Standard Recording Code, e.g. `USRC17607839`.
+    pub isrc: Option<String>,
+    /// The first composer, see `composers`.
+    pub composer: Option<String>,
+    pub conductor: Option<String>,
+    pub remixer: Option<String>,
+    /// The track artist used for sorting, e.g. `Beatles, The`.
+    pub artist_sort: Option<String>,
+    /// The album title used for sorting.
+    pub album_sort: Option<String>,
+    /// The track title used for sorting.
+    pub title_sort: Option<String>,
+    /// The album artist used for sorting.
+    pub album_artist_sort: Option<String>,
+    /// The tempo in beats per minute, rounded to the nearest integer like the ID3v2 `TBPM` frame.
+    pub bpm: Option<u32>,
+    /// The musical key the track starts in, e.g. `Am` or `8A` in the Camelot notation.
+    pub initial_key: Option<String>,
+    /// The mood of the track, e.g. `Energetic`.
+    pub mood: Option<String>,
+    /// The rating from 0 to 100, i.e. 20 per star, see [Tag::rating_stars].
+    ///
+    /// Stored in the ID3v2 `POPM` frame with the Windows Media Player scale, where 0 is
+    /// unrated, the `RATING` Vorbis comment or APE item, and the MP4 `rate` atom.
+    pub rating: Option<u32>,
+    /// The ISO 639-2 language of the `lyrics`, e.g. `eng`. Only stored by the ID3v2 `USLT` frame.
+    pub lyrics_language: Option<String>,
+    /// The description of the `lyrics`, e.g. `Live version`. Only stored by the ID3v2 `USLT` frame.
+    pub lyrics_description: Option<String>,
+    /// The MusicBrainz ID of the recording, i.e. the `MUSICBRAINZ_TRACKID` Vorbis comment
+    /// and the ID3v2 `UFID` frame Picard writes.
+    pub musicbrainz_recording_id: Option<String>,
+    /// The MusicBrainz ID of the release, i.e. the album.
+    pub musicbrainz_release_id: Option<String>,
+    /// The MusicBrainz ID of the release group, gathering all the releases of an album.
+    pub musicbrainz_release_group_id: Option<String>,
+    /// The MusicBrainz ID of the track artist.
+    pub musicbrainz_artist_id: Option<String>,
+    /// The MusicBrainz ID of the album artist.
+    pub musicbrainz_album_artist_id: Option<String>,
+    /// The MusicBrainz ID of the track on the release, unlike `musicbrainz_recording_id`.
+    pub musicbrainz_track_id: Option<String>,
+    /// The AcoustID of the recording, found from its `acoustid_fingerprint`.
+    pub acoustid_id: Option<String>,
+    /// The Chromaprint fingerprint of the audio, as computed by `fpcalc`.
+    pub acoustid_fingerprint: Option<String>,
+    /// The barcode of the release, e.g. its UPC or EAN.
+    pub barcode: Option<String>,
+    /// The catalog number the label gave to the release.
+    pub catalog_number: Option<String>,
+    /// The record label, stored in the ID3v2 `TPUB` publisher frame.
+    pub label: Option<String>,
+    /// The copyright message, e.g. `℗ 2023 Some Label`.
+    pub copyright: Option<String>,
+    /// The grouping of the track, e.g. `Piano Sonatas`, i.e. the ID3v2 `TIT1` frame (and `GRP1`
+    /// one of iTunes), the MP4 `©grp` atom and the `GROUPING` Vorbis comment.
+    pub grouping: Option<String>,
+    /// The subtitle of the track, e.g. `Live at Wembley`, i.e. the ID3v2 `TIT3` frame and
+    /// the `SUBTITLE` Vorbis comment.
+    pub subtitle: Option<String>,
+    /// The classical work the track is part of, e.g. `Symphony No. 9`, i.e. the `WORK` `TXXX`
+    /// frame of ID3v2, the MP4 `©wrk` atom and the `WORK` Vorbis comment.
+    pub work: Option<String>,
+    /// The name of the movement, e.g. `Allegro ma non troppo`, i.e. the ID3v2 `MVNM` frame,
+    /// the MP4 `©mvn` atom and the `MOVEMENTNAME` Vorbis comment.
+    pub movement: Option<String>,
+    /// The number of the movement in the work, i.e. the ID3v2 `MVIN` frame, the MP4 `©mvi`
+    /// atom and the `MOVEMENT` Vorbis comment.
+    pub movement_number: Option<u32>,
+    /// The number of movements of the work, i.e. the total of the ID3v2 `MVIN` frame,
+    /// the MP4 `©mvc` atom and the `MOVEMENTTOTAL` Vorbis comment.
+    pub movement_total: Option<u32>,
+    /// Whether the album is a compilation of various artists, i.e. the ID3v2 `TCMP` frame,
+    /// the MP4 `cpil` atom and the `COMPILATION` Vorbis comment, which iTunes groups the tracks
+    /// of an album by instead of their album artist.
+    pub is_compilation: Option<bool>,
+    /// Whether the file is a podcast episode, i.e. the ID3v2 `PCST` frame, the MP4 `pcst` atom
+    /// and the `PODCAST` Vorbis comment.
+    pub is_podcast: Option<bool>,
+    /// The feed of the podcast, i.e. the ID3v2 `WFED` frame, the MP4 `purl` atom and the
+    /// `PODCASTURL` Vorbis comment.
+    pub podcast_url: Option<String>,
+    /// The globally unique identifier of the podcast episode, i.e. the ID3v2 `TGID` frame,
+    /// the MP4 `egid` atom and the `PODCASTGUID` Vorbis comment.
+    pub episode_id: Option<String>,
+    /// The narrator of the audiobook, i.e. the MP4 `©nrt` atom and the `NARRATOR` `TXXX`
+    /// frame of ID3v2 and Vorbis comment.
+    pub narrator: Option<String>,
+    /// The series the audiobook is part of, e.g. `The Expanse`, i.e. the `SERIES` `TXXX`
+    /// frame of ID3v2, MP4 freeform atom and Vorbis comment.
+    pub series: Option<String>,
+    /// The part of the audiobook in its series, e.g. `1` or `1.5`, i.e. the `SERIES-PART`
+    /// `TXXX` frame of ID3v2, MP4 freeform atom and Vorbis comment.
+    pub series_part: Option<String>,
+    /// The application specific fields, e.g. `MY_APP_PLAYCOUNT`, see [CustomItem].
+    pub custom_items: Vec<CustomItem>,
+    /// All the track artists, e.g. of a collaboration, the first one being `track_artist`.
+    ///
+    /// ID3v2 stores them in a single null-separated frame, Vorbis comments, APE and MP4
+    /// as separate values of the same field and the other tags only keep the first one.
+    /// Setting `track_artist` to another value than the first artist replaces all of them.
+    pub artists: Vec<String>,
+    /// All the genres, the first one being `genre`, stored like [Tag::artists].
+    pub genres: Vec<String>,
+    /// All the composers, the first one being `composer`, stored like [Tag::artists].
+    pub composers: Vec<String>,
+    /// The comments, e.g. the user comment along with the `iTunNORM` one, see [Comment].
+    pub comments: Vec<Comment>,
+}
+
+impl Clone for Tag {
+    fn clone(&self) -> Self {
+        Self {
+            tag_type: self.tag_type,
+            pictures: self.pictures.to_vec(),
+            track_title: (&self.track_title).clone(),
+            track_artist: (&self.track_artist).clone(),
+            album: (&self.album).clone(),
+            album_artist: (&self.album_artist).clone(),
+            producer: (&self.producer).clone(),
+            track_number: (&self.track_number).clone(),
+            track_total: (&self.track_total).clone(),
+            disc_number: (&self.disc_number).clone(),
+            disc_total: (&self.disc_total).clone(),
+            year: (&self.year).clone(),
+            recording_date: (&self.recording_date).clone(),
+            original_release_date: (&self.original_release_date).clone(),
+            release_date: (&self.release_date).clone(),
+            original_date: (&self.original_date).clone(),
+            language: (&self.language).clone(),
+            lyrics: (&self.lyrics).clone(),
+            genre: (&self.genre).clone(),
+            isrc: (&self.isrc).clone(),
+            composer: (&self.composer).clone(),
+            conductor: (&self.conductor).clone(),
+            remixer: (&self.remixer).clone(),
+            artist_sort: (&self.artist_sort).clone(),
+            album_sort: (&self.album_sort).clone(),
+            title_sort: (&self.title_sort).clone(),
+            album_artist_sort: (&self.album_artist_sort).clone(),
+            bpm: (&self.bpm).clone(),
+            initial_key: (&self.initial_key).clone(),
+            mood: (&self.mood).clone(),
+            rating: (&self.rating).clone(),
+            lyrics_language: (&self.lyrics_language).clone(),
+            lyrics_description: (&self.lyrics_description).clone(),
+            musicbrainz_recording_id: (&self.musicbrainz_recording_id).clone(),
+            musicbrainz_release_id: (&self.musicbrainz_release_id).clone(),
+            musicbrainz_release_group_id: (&self.musicbrainz_release_group_id).clone(),
+            musicbrainz_artist_id: (&self.musicbrainz_artist_id).clone(),
+            musicbrainz_album_artist_id: (&self.musicbrainz_album_artist_id).clone(),
+            musicbrainz_track_id: (&self.musicbrainz_track_id).clone(),
+            acoustid_id: (&self.acoustid_id).clone(),
+            acoustid_fingerprint: (&self.acoustid_fingerprint).clone(),
+            barcode: (&self.barcode).clone(),
+            catalog_number: (&self.catalog_number).clone(),
+            label: (&self.label).clone(),
+            copyright: (&self.copyright).clone(),
+            grouping: (&self.grouping).clone(),
+            subtitle: (&self.subtitle).clone(),
+            work: (&self.work).clone(),
+            movement: (&self.movement).clone(),
+            movement_number: (&self.movement_number).clone(),
+            movement_total: (&self.movement_total).clone(),
+            is_compilation: (&self.is_compilation).clone(),
+            is_podcast: (&self.is_podcast).clone(),
+            podcast_url: (&self.podcast_url).clone(),
+            episode_id: (&self.episode_id).clone(),
+            narrator: (&self.narrator).clone(),
+            series: (&self.series).clone(),
+            series_part: (&self.series_part).clone(),
+            custom_items: self.custom_items.to_vec(),
+            artists: self.artists.to_vec(),
+            genres: self.genres.to_vec(),
+            composers: self.composers.to_vec(),
+            comments: self.comments.to_vec(),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum TagType {
+    /// This covers both APEv1 and APEv2 as it doesn't matter much
+    Ape,
+    /// Represents an ID3v1 tag
+    Id3v1,
+    /// This covers all ID3v2 versions since they all get upgraded to ID3v2.4
+    Id3v2,
+    /// Represents an MP4 ilst atom
+    Mp4Ilst,
+    /// Represents vorbis comments
+    VorbisComments,
+    /// Represents a RIFF INFO LIST
+    RiffInfo,
+    /// Represents AIFF text chunks
+    AiffText,
+    /// This will be converted to the audio file primary tag type.
+    ///
+    /// **Note**: this is intended for the tag passed to `write_primary()` if you don't
+    /// know the file primary tag type.
+    FilePrimaryType,
+    /// Other type
+    Other,
+}
+impl Tag {
+    pub fn builder() -> TagBuilder {
+        TagBuilder::new()
+    }
+    pub fn new(tag_type: TagType) -> Self {
+        Self {
+            tag_type,
+            pictures: vec![],
+            track_title: None,
+            track_artist: None,
+            album: None,
+            album_artist: None,
+            producer: None,
+            track_number: None,
+            track_total: None,
+            disc_number: None,
+            disc_total: None,
+            year: None,
+            recording_date: None,
+            original_release_date: None,
+            release_date: None,
+            original_date: None,
+            language: None,
+            lyrics: None,
+            genre: None,
+            isrc: None,
+            composer: None,
+            conductor: None,
+            remixer: None,
+            artist_sort: None,
+            album_sort: None,
+            title_sort: None,
+            album_artist_sort: None,
+            bpm: None,
+            initial_key: None,
+            mood: None,
+            rating: None,
+            lyrics_language: None,
+            lyrics_description: None,
+            musicbrainz_recording_id: None,
+            musicbrainz_release_id: None,
+            musicbrainz_release_group_id: None,
+            musicbrainz_artist_id: None,
+            musicbrainz_album_artist_id: None,
+            musicbrainz_track_id: None,
+            acoustid_id: None,
+            acoustid_fingerprint: None,
+            barcode: None,
+            catalog_number: None,
+            label: None,
+            copyright: None,
+            grouping: None,
+            subtitle: None,
+            work: None,
+            movement: None,
+            movement_number: None,
+            movement_total: None,
+            is_compilation: None,
+            is_podcast: None,
+            podcast_url: None,
+            episode_id: None,
+            narrator: None,
+            series: None,
+            series_part: None,
+            custom_items: vec![],
+            artists: vec![],
+            genres: vec![],
+            composers: vec![],
+            comments: vec![],
+        }
+    }
+    /// The rating from 0 to 5 stars, in half stars.
+    pub fn rating_stars(&self) -> Option<f32> {
+        self.rating
+            .map(|rating| (rating.min(100) as f32 / 10.0).round() / 2.0)
+    }
+    /// The first of this tag pictures in the given `order`, e.g. the one to show as its cover
+    /// with [PictureOrder::FrontCoverFirst].
+    pub fn primary_picture(&self, order: PictureOrder) -> Option<&Picture> {
+        self.pictures
+            .iter()
+            .min_by_key(|picture| order_key(picture, order))
+    }
+}
+impl TagType {
+    pub fn random() -> TagType {
+        let enum_vals = [
+            TagType::Ape,
+            TagType::Other,
+            TagType::Id3v2,
+            TagType::Id3v1,
+            TagType::Mp4Ilst,
+            TagType::RiffInfo,
+            TagType::AiffText,
+            TagType::VorbisComments,
+        ];
+        enum_vals.choose(&mut rand::thread_rng()).unwrap().clone()
+    }
+}
@@ -0,0 +1,38 @@
+use std::fmt;
+
+/// A date of a tag, as precise as the tag stores it, e.g. only the year of the ID3v2.3 `TYER`
+/// frame, see [Tag::release_date](crate::tag::Tag::release_date).
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct TagDate {
+    pub year: u32,
+    /// From 1 to 12, `None` when only the year is known.
+    pub month: Option<u32>,
+    /// From 1 to 31, `None` when only the year and the month are known.
+    pub day: Option<u32>,
+}
+
+impl TagDate {
+    /// The date of a year only, e.g. the ID3v1 year.
+    pub fn year(year: u32) -> Self {
+        Self {
+            year,
+            month: None,
+            day: None,
+        }
+    }
+}
+
+/// Formats the date in ISO 8601, as precise as the date is, e.g. `2001`, `2001-03` or `2001-03-04`.
+impl fmt::Display for TagDate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:04}", self.year)?;
+        if let Some(month) = self.month {
+            write!(f, "-{month:02}")?;
+            if let Some(day) = self.day {
+                write!(f, "-{day:02}")?;
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,88 @@
+use crate::audio_info::AudioInfo;
+use crate::chapter::Chapter;
+use crate::picture::{Picture, PictureOrder};
+use crate::tag::{Tag, TagType};
+use crate::utils::picture_order::order_key;
+
+/// A generic representation of an audio file
+///
+/// Holds information about a file and its audio tags.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct TaggyFile {
+    /// The Type of this file
+    pub file_type: Option<FileType>,
+    /// The Size of this file
+    pub size: Option<u64>,
+    /// The properties of this file audio track.
+    pub audio: AudioInfo,
+    /// The tags included with this file.
+    pub tags: Vec<Tag>,
+    pub primary_tag_type: TagType,
+    /// The absolute path of this file with all the symbolic links resolved,
+    /// `None` when read from an in-memory buffer.
+    pub canonical_path: Option<String>,
+    /// When this file was created, in milliseconds since the Unix epoch,
+    /// `None` when the file system doesn't record it or when read from an in-memory buffer.
+    pub created_at_ms: Option<i64>,
+    /// When this file was last modified, in milliseconds since the Unix epoch,
+    /// `None` when read from an in-memory buffer.
+    pub modified_at_ms: Option<i64>,
+    /// When this file was added to the library, in milliseconds since the Unix epoch, as written
+    /// by some players to a `DATE_ADDED` or `ADDED` custom item of its tags.
+    pub date_added_ms: Option<i64>,
+    /// The chapters of this file, sorted by start time, see [Chapter].
+    pub chapters: Vec<Chapter>,
+    /// The issues found while reading this file, e.g. the fields its tags hold several times,
+    /// see [DuplicatePolicy](crate::duplicate_policy::DuplicatePolicy).
+    pub warnings: Vec<String>,
+}
+impl TaggyFile {
+    /// When this file was added to the library: its embedded `date_added_ms`, or else when it was
+    /// created, or else last modified. Meant for the "recently added" sort orders.
+    pub fn added_at_ms(&self) -> Option<i64> {
+        self.date_added_ms
+            .or(self.created_at_ms)
+            .or(self.modified_at_ms)
+    }
+
+    /// Returns the tag which has a [`TagType`] equals to this file `primary_tag_type`.
+    pub fn primary_tag(self) -> Option<Tag> {
+        self.tags
+            .iter()
+            .find(|&t| t.tag_type == self.primary_tag_type)
+            .cloned()
+    }
+    /// Returns the first tag of this file tags.
+    pub fn first_tag(self) -> Option<Tag> {
+        self.tags.first().cloned()
+    }
+    /// Returns the first picture of this file tags in the given `order`, searching the primary
+    /// tag first, see [Tag::primary_picture].
+    pub fn primary_picture(&self, order: PictureOrder) -> Option<&Picture> {
+        let mut tags: Vec<&Tag> = self.tags.iter().collect();
+        tags.sort_by_key(|t| t.tag_type != self.primary_tag_type);
+        tags.into_iter()
+            .flat_map(|t| &t.pictures)
+            .min_by_key(|picture| order_key(picture, order))
+    }
+}
+
+/// The type of a file
+#[derive(Debug, Copy, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum FileType {
+    Aac,
+    Aiff,
+    Ape,
+    Flac,
+    Mpeg,
+    Mp4,
+    Mpc,
+    Opus,
+    Vorbis,
+    Speex,
+    Wav,
+    WavPack,
+    Other,
+}
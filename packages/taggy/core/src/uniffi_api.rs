@@ -0,0 +1,721 @@
+//! UniFFI exports of the public API, so native Android/iOS apps can use taggy without Flutter.
+//!
+//! Each function here forwards to its counterpart in [crate::api],
+//! see `scripts/generate-uniffi-bindings.sh` for generating the Kotlin/Swift bindings.
+use crate::album_gaps::AlbumGaps;
+use crate::api;
+use crate::audio_info::AudioInfo;
+use crate::batch_result::BatchReadResult;
+use crate::chapter::Chapter;
+use crate::date_context::DateContext;
+use crate::disc_set::DiscSet;
+use crate::dj_markers::DjMarkers;
+use crate::effective_tag::EffectiveTag;
+use crate::error::TaggyError;
+use crate::featuring::{FeaturingChange, FeaturingStyle};
+use crate::field_class::FieldClass;
+use crate::field_value_count::FieldValueCount;
+use crate::item_key::ItemKey;
+use crate::lyrics_match::LyricsMatch;
+use crate::merge_suggestion::MergeSuggestion;
+use crate::parse_config::ParseConfig;
+use crate::picture::{Picture, PictureChanges, PictureOrder, PictureType};
+use crate::raw_frame::RawFrame;
+use crate::recovery_report::RecoveryReport;
+use crate::replay_gain::ReplayGain;
+use crate::scan_result::ScanResult;
+use crate::scanner::{ScanOptions, SymlinkPolicy};
+use crate::synced_lyrics::SyncedLyrics;
+use crate::tag::{Tag, TagType};
+use crate::taggy_file::TaggyFile;
+use crate::work_link::WorkLink;
+use crate::write_options::{LintProfile, WriteOptions, WriteProfile};
+use crate::write_report::WriteReport;
+use crate::write_request::WriteRequest;
+use crate::year_suggestion::YearSuggestion;
+use std::fmt::{Display, Formatter};
+
+/// The error thrown by the exported functions, one variant for each [TaggyError] kind
+/// holding the message of the underlying error.
+#[derive(Debug, uniffi::Error)]
+#[uniffi(flat_error)]
+pub enum TaggyException {
+    FileNotFound(String),
+    DirectoryNotFound(String),
+    PermissionDenied(String),
+    UnsupportedFormat(String),
+    EmptyOrTruncated(String),
+    UnsupportedTagType(String),
+    CorruptTag(String),
+    InvalidValue(String),
+    LimitExceeded(String),
+    Io(String),
+    SaveFailed(String),
+    Failed(String),
+}
+
+impl Display for TaggyException {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TaggyException::FileNotFound(message)
+            | TaggyException::DirectoryNotFound(message)
+            | TaggyException::PermissionDenied(message)
+            | TaggyException::UnsupportedFormat(message)
+            | TaggyException::EmptyOrTruncated(message)
+            | TaggyException::UnsupportedTagType(message)
+            | TaggyException::CorruptTag(message)
+            | TaggyException::InvalidValue(message)
+            | TaggyException::LimitExceeded(message)
+            | TaggyException::Io(message)
+            | TaggyException::SaveFailed(message)
+            | TaggyException::Failed(message) => write!(f, "{message}"),
+        }
+    }
+}
+
+impl From<anyhow::Error> for TaggyException {
+    fn from(value: anyhow::Error) -> Self {
+        let error = TaggyError::from(&value);
+        let message = error.to_string();
+        match error {
+            TaggyError::FileNotFound => TaggyException::FileNotFound(message),
+            TaggyError::DirectoryNotFound => TaggyException::DirectoryNotFound(message),
+            TaggyError::PermissionDenied => TaggyException::PermissionDenied(message),
+            TaggyError::UnsupportedFormat => TaggyException::UnsupportedFormat(message),
+            TaggyError::EmptyOrTruncated { .. } => TaggyException::EmptyOrTruncated(message),
+            TaggyError::UnsupportedTagType { .. } => TaggyException::UnsupportedTagType(message),
+            TaggyError::CorruptTag { .. } => TaggyException::CorruptTag(message),
+            TaggyError::InvalidValue { .. } => TaggyException::InvalidValue(message),
+            TaggyError::LimitExceeded { .. } => TaggyException::LimitExceeded(message),
+            TaggyError::Io { .. } => TaggyException::Io(message),
+            TaggyError::SaveFailed { .. } => TaggyException::SaveFailed(message),
+            TaggyError::Other { .. } => TaggyException::Failed(message),
+        }
+    }
+}
+
+/// See [api::read_all].
+#[uniffi::export]
+pub fn read_all(path: String) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_all(path)?)
+}
+
+/// See [api::read_primary].
+#[uniffi::export]
+pub fn read_primary(path: String) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_primary(path)?)
+}
+
+/// See [api::read_any].
+#[uniffi::export]
+pub fn read_any(path: String) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_any(path)?)
+}
+
+/// See [api::read_all_with_config].
+#[uniffi::export]
+pub fn read_all_with_config(
+    path: String,
+    config: ParseConfig,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_all_with_config(path, config)?)
+}
+
+/// See [api::read_primary_with_config].
+#[uniffi::export]
+pub fn read_primary_with_config(
+    path: String,
+    config: ParseConfig,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_primary_with_config(path, config)?)
+}
+
+/// See [api::read_any_with_config].
+#[uniffi::export]
+pub fn read_any_with_config(
+    path: String,
+    config: ParseConfig,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_any_with_config(path, config)?)
+}
+
+/// See [api::read_effective].
+#[uniffi::export]
+pub fn read_effective(path: String) -> Result<EffectiveTag, TaggyException> {
+    Ok(api::read_effective(path)?)
+}
+
+/// See [api::read_all_batch].
+#[uniffi::export]
+pub fn read_all_batch(paths: Vec<String>, concurrency: u32) -> Vec<BatchReadResult> {
+    api::read_all_batch(paths, concurrency)
+}
+
+/// See [api::scan_directory].
+#[uniffi::export]
+pub fn scan_directory(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+) -> Result<Vec<BatchReadResult>, TaggyException> {
+    Ok(api::scan_directory(
+        dir,
+        recursive,
+        extensions,
+        concurrency,
+    )?)
+}
+
+/// See [api::scan_directory_with_options].
+#[uniffi::export]
+pub fn scan_directory_with_options(
+    dir: String,
+    options: ScanOptions,
+) -> Result<Vec<BatchReadResult>, TaggyException> {
+    Ok(api::scan_directory_with_options(dir, options)?)
+}
+
+/// See [api::scan_directory_with_symlinks].
+#[uniffi::export]
+pub fn scan_directory_with_symlinks(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+    symlinks: SymlinkPolicy,
+) -> Result<Vec<BatchReadResult>, TaggyException> {
+    Ok(api::scan_directory_with_symlinks(
+        dir,
+        recursive,
+        extensions,
+        concurrency,
+        symlinks,
+    )?)
+}
+
+/// See [api::scan_directory_with_quarantine].
+#[uniffi::export]
+pub fn scan_directory_with_quarantine(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+) -> Result<ScanResult, TaggyException> {
+    Ok(api::scan_directory_with_quarantine(
+        dir,
+        recursive,
+        extensions,
+        concurrency,
+    )?)
+}
+
+/// See [api::field_values].
+#[uniffi::export]
+pub fn field_values(dir: String, field: String) -> Result<Vec<FieldValueCount>, TaggyException> {
+    Ok(api::field_values(dir, field)?)
+}
+
+/// See [api::suggest_merges].
+#[uniffi::export]
+pub fn suggest_merges(values: Vec<FieldValueCount>) -> Vec<MergeSuggestion> {
+    api::suggest_merges(values)
+}
+
+/// See [api::search_lyrics].
+#[uniffi::export]
+pub fn search_lyrics(dir: String, query: String) -> Result<Vec<LyricsMatch>, TaggyException> {
+    Ok(api::search_lyrics(dir, query)?)
+}
+
+/// See [api::find_gaps].
+#[uniffi::export]
+pub fn find_gaps(dir: String) -> Result<Vec<AlbumGaps>, TaggyException> {
+    Ok(api::find_gaps(dir)?)
+}
+
+/// See [api::read_all_with_sidecar].
+#[uniffi::export]
+pub fn read_all_with_sidecar(path: String) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_all_with_sidecar(path)?)
+}
+
+/// See [api::read_pictures].
+#[uniffi::export]
+pub fn read_pictures(path: String) -> Result<Vec<Picture>, TaggyException> {
+    Ok(api::read_pictures(path)?)
+}
+
+/// See [api::read_front_cover].
+#[uniffi::export]
+pub fn read_front_cover(path: String) -> Result<Option<Picture>, TaggyException> {
+    Ok(api::read_front_cover(path)?)
+}
+
+/// See [api::read_pictures_with_order].
+#[uniffi::export]
+pub fn read_pictures_with_order(
+    path: String,
+    order: PictureOrder,
+) -> Result<Vec<Picture>, TaggyException> {
+    Ok(api::read_pictures_with_order(path, order)?)
+}
+
+/// See [api::read_primary_picture].
+#[uniffi::export]
+pub fn read_primary_picture(
+    path: String,
+    order: PictureOrder,
+) -> Result<Option<Picture>, TaggyException> {
+    Ok(api::read_primary_picture(path, order)?)
+}
+
+/// See [api::export_picture].
+#[uniffi::export]
+pub fn export_picture(
+    path: String,
+    out_path: String,
+    picture_type: PictureType,
+) -> Result<bool, TaggyException> {
+    Ok(api::export_picture(path, out_path, picture_type)?)
+}
+
+/// See [api::read_field].
+#[uniffi::export]
+pub fn read_field(path: String, key: ItemKey) -> Result<Option<String>, TaggyException> {
+    Ok(api::read_field(path, key)?)
+}
+
+/// See [api::read_primary_tag_type].
+#[uniffi::export]
+pub fn read_primary_tag_type(path: String) -> Result<TagType, TaggyException> {
+    Ok(api::read_primary_tag_type(path)?)
+}
+
+/// See [api::read_duration].
+#[uniffi::export]
+pub fn read_duration(path: String) -> Result<u64, TaggyException> {
+    Ok(api::read_duration(path)?)
+}
+
+/// See [api::read_properties].
+#[uniffi::export]
+pub fn read_properties(path: String) -> Result<AudioInfo, TaggyException> {
+    Ok(api::read_properties(path)?)
+}
+
+/// See [api::read_all_from_buffer].
+#[uniffi::export]
+pub fn read_all_from_buffer(bytes: Vec<u8>) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_all_from_buffer(bytes)?)
+}
+
+/// See [api::read_primary_from_buffer].
+#[uniffi::export]
+pub fn read_primary_from_buffer(bytes: Vec<u8>) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_primary_from_buffer(bytes)?)
+}
+
+/// See [api::read_any_from_buffer].
+#[uniffi::export]
+pub fn read_any_from_buffer(bytes: Vec<u8>) -> Result<TaggyFile, TaggyException> {
+    Ok(api::read_any_from_buffer(bytes)?)
+}
+
+/// See [api::write_all_to_buffer].
+#[uniffi::export]
+pub fn write_all_to_buffer(
+    bytes: Vec<u8>,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> Result<Vec<u8>, TaggyException> {
+    Ok(api::write_all_to_buffer(bytes, tags, override_existent)?)
+}
+
+/// See [api::write_all].
+#[uniffi::export]
+pub fn write_all(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::write_all(path, tags, override_existent)?)
+}
+
+/// See [api::write_primary].
+#[uniffi::export]
+pub fn write_primary(
+    path: String,
+    tag: Tag,
+    keep_others: bool,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::write_primary(path, tag, keep_others)?)
+}
+
+/// See [api::write_all_with_options].
+#[uniffi::export]
+pub fn write_all_with_options(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+    options: WriteOptions,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::write_all_with_options(
+        path,
+        tags,
+        override_existent,
+        options,
+    )?)
+}
+
+/// See [api::write_all_with_report].
+#[uniffi::export]
+pub fn write_all_with_report(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+    options: WriteOptions,
+) -> Result<WriteReport, TaggyException> {
+    Ok(api::write_all_with_report(
+        path,
+        tags,
+        override_existent,
+        options,
+    )?)
+}
+
+/// See [api::write_primary_with_options].
+#[uniffi::export]
+pub fn write_primary_with_options(
+    path: String,
+    tag: Tag,
+    keep_others: bool,
+    options: WriteOptions,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::write_primary_with_options(
+        path,
+        tag,
+        keep_others,
+        options,
+    )?)
+}
+
+/// See [api::write_picture].
+#[uniffi::export]
+pub fn write_picture(
+    path: String,
+    picture: Picture,
+    picture_type: PictureType,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::write_picture(path, picture, picture_type)?)
+}
+
+/// See [api::picture_from_path].
+#[uniffi::export]
+pub fn picture_from_path(
+    image_path: String,
+    picture_type: PictureType,
+) -> Result<Picture, TaggyException> {
+    Ok(api::picture_from_path(image_path, picture_type)?)
+}
+
+/// See [api::write_picture_from_path].
+#[uniffi::export]
+pub fn write_picture_from_path(
+    path: String,
+    image_path: String,
+    picture_type: PictureType,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::write_picture_from_path(
+        path,
+        image_path,
+        picture_type,
+    )?)
+}
+
+/// See [api::remove_pictures].
+#[uniffi::export]
+pub fn remove_pictures(
+    path: String,
+    picture_type: Option<PictureType>,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::remove_pictures(path, picture_type)?)
+}
+
+/// See [api::update_picture_meta].
+#[uniffi::export]
+pub fn update_picture_meta(
+    path: String,
+    index: u32,
+    changes: PictureChanges,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::update_picture_meta(path, index, changes)?)
+}
+
+/// See [api::write_field].
+#[uniffi::export]
+pub fn write_field(
+    path: String,
+    key: ItemKey,
+    value: Option<String>,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::write_field(path, key, value)?)
+}
+
+/// See [api::read_replay_gain].
+#[uniffi::export]
+pub fn read_replay_gain(path: String) -> Result<ReplayGain, TaggyException> {
+    Ok(api::read_replay_gain(path)?)
+}
+
+/// See [api::write_replay_gain].
+#[uniffi::export]
+pub fn write_replay_gain(path: String, gain: ReplayGain) -> Result<(), TaggyException> {
+    Ok(api::write_replay_gain(path, gain)?)
+}
+
+/// See [api::write_transaction].
+#[uniffi::export]
+pub fn write_transaction(requests: Vec<WriteRequest>) -> Result<Vec<TaggyFile>, TaggyException> {
+    Ok(api::write_transaction(requests)?)
+}
+
+/// See [api::write_transaction_with_temp_dir].
+#[uniffi::export]
+pub fn write_transaction_with_temp_dir(
+    requests: Vec<WriteRequest>,
+    temp_dir: String,
+) -> Result<Vec<TaggyFile>, TaggyException> {
+    Ok(api::write_transaction_with_temp_dir(requests, temp_dir)?)
+}
+
+/// See [api::recover].
+#[uniffi::export]
+pub fn recover(dir: String) -> Result<RecoveryReport, TaggyException> {
+    Ok(api::recover(dir)?)
+}
+
+/// See [api::date_context_for_locale].
+#[uniffi::export]
+pub fn date_context_for_locale(locale: String, utc_offset_minutes: i32) -> DateContext {
+    api::date_context_for_locale(locale, utc_offset_minutes)
+}
+
+/// See [api::normalize_isrc].
+#[uniffi::export]
+pub fn normalize_isrc(isrc: String) -> Result<String, TaggyException> {
+    Ok(api::normalize_isrc(isrc)?)
+}
+
+/// See [api::generate_file_id].
+#[uniffi::export]
+pub fn generate_file_id() -> String {
+    api::generate_file_id()
+}
+
+/// See [api::read_file_id].
+#[uniffi::export]
+pub fn read_file_id(path: String, scope: String) -> Result<Option<String>, TaggyException> {
+    Ok(api::read_file_id(path, scope)?)
+}
+
+/// See [api::write_file_id].
+#[uniffi::export]
+pub fn write_file_id(path: String, scope: String, id: String) -> Result<(), TaggyException> {
+    Ok(api::write_file_id(path, scope, id)?)
+}
+
+/// See [api::ensure_file_id].
+#[uniffi::export]
+pub fn ensure_file_id(path: String, scope: String) -> Result<String, TaggyException> {
+    Ok(api::ensure_file_id(path, scope)?)
+}
+
+/// See [api::read_synced_lyrics].
+#[uniffi::export]
+pub fn read_synced_lyrics(path: String) -> Result<Option<SyncedLyrics>, TaggyException> {
+    Ok(api::read_synced_lyrics(path)?)
+}
+
+/// See [api::write_synced_lyrics].
+#[uniffi::export]
+pub fn write_synced_lyrics(path: String, lyrics: SyncedLyrics) -> Result<(), TaggyException> {
+    Ok(api::write_synced_lyrics(path, lyrics)?)
+}
+
+/// See [api::parse_lrc].
+#[uniffi::export]
+pub fn parse_lrc(lrc: String) -> Result<SyncedLyrics, TaggyException> {
+    Ok(api::parse_lrc(lrc)?)
+}
+
+/// See [api::to_lrc].
+#[uniffi::export]
+pub fn to_lrc(lyrics: SyncedLyrics) -> String {
+    api::to_lrc(lyrics)
+}
+
+/// See [api::read_chapters].
+#[uniffi::export]
+pub fn read_chapters(path: String) -> Result<Vec<Chapter>, TaggyException> {
+    Ok(api::read_chapters(path)?)
+}
+
+/// See [api::write_chapters].
+#[uniffi::export]
+pub fn write_chapters(path: String, chapters: Vec<Chapter>) -> Result<(), TaggyException> {
+    Ok(api::write_chapters(path, chapters)?)
+}
+
+/// See [api::read_raw_frames].
+#[uniffi::export]
+pub fn read_raw_frames(path: String) -> Result<Vec<RawFrame>, TaggyException> {
+    Ok(api::read_raw_frames(path)?)
+}
+
+/// See [api::write_raw_frame].
+#[uniffi::export]
+pub fn write_raw_frame(path: String, frame: RawFrame) -> Result<(), TaggyException> {
+    Ok(api::write_raw_frame(path, frame)?)
+}
+
+/// See [api::dump_raw_tag].
+#[uniffi::export]
+pub fn dump_raw_tag(path: String, tag_type: TagType) -> Result<Vec<u8>, TaggyException> {
+    Ok(api::dump_raw_tag(path, tag_type)?)
+}
+
+/// See [api::inject_raw_tag].
+#[uniffi::export]
+pub fn inject_raw_tag(path: String, bytes: Vec<u8>) -> Result<(), TaggyException> {
+    Ok(api::inject_raw_tag(path, bytes)?)
+}
+
+/// See [api::read_dj_markers].
+#[uniffi::export]
+pub fn read_dj_markers(path: String) -> Result<Option<DjMarkers>, TaggyException> {
+    Ok(api::read_dj_markers(path)?)
+}
+
+/// See [api::detect_disc_set].
+#[uniffi::export]
+pub fn detect_disc_set(dir: String) -> Result<DiscSet, TaggyException> {
+    Ok(api::detect_disc_set(dir)?)
+}
+
+/// See [api::fix_disc_numbers].
+#[uniffi::export]
+pub fn fix_disc_numbers(
+    dir: String,
+    fold_into_track_number: bool,
+) -> Result<DiscSet, TaggyException> {
+    Ok(api::fix_disc_numbers(dir, fold_into_track_number)?)
+}
+
+/// See [api::unify_album_art].
+#[uniffi::export]
+pub fn unify_album_art(
+    dir: String,
+    source: Option<Picture>,
+) -> Result<Vec<String>, TaggyException> {
+    Ok(api::unify_album_art(dir, source)?)
+}
+
+/// See [api::suggest_years].
+#[uniffi::export]
+pub fn suggest_years(dir: String) -> Result<Vec<YearSuggestion>, TaggyException> {
+    Ok(api::suggest_years(dir)?)
+}
+
+/// See [api::apply_years].
+#[uniffi::export]
+pub fn apply_years(suggestions: Vec<YearSuggestion>) -> Result<Vec<TaggyFile>, TaggyException> {
+    Ok(api::apply_years(suggestions)?)
+}
+
+/// See [api::suggest_featuring].
+#[uniffi::export]
+pub fn suggest_featuring(
+    dir: String,
+    style: FeaturingStyle,
+) -> Result<Vec<FeaturingChange>, TaggyException> {
+    Ok(api::suggest_featuring(dir, style)?)
+}
+
+/// See [api::apply_featuring].
+#[uniffi::export]
+pub fn apply_featuring(changes: Vec<FeaturingChange>) -> Result<Vec<TaggyFile>, TaggyException> {
+    Ok(api::apply_featuring(changes)?)
+}
+
+/// See [api::snapshot].
+#[uniffi::export]
+pub fn snapshot(path: String) -> Result<String, TaggyException> {
+    Ok(api::snapshot(path)?)
+}
+
+/// See [api::restore].
+#[uniffi::export]
+pub fn restore(path: String, snapshot: String) -> Result<TaggyFile, TaggyException> {
+    Ok(api::restore(path, snapshot)?)
+}
+
+/// See [api::remove_all].
+#[uniffi::export]
+pub fn remove_all(path: String) -> Result<(), TaggyException> {
+    Ok(api::remove_all(path)?)
+}
+
+/// See [api::remove_tag].
+#[uniffi::export]
+pub fn remove_tag(path: String, tag_type: TagType) -> Result<(), TaggyException> {
+    Ok(api::remove_tag(path, tag_type)?)
+}
+
+/// See [api::convert_tag].
+#[uniffi::export]
+pub fn convert_tag(
+    path: String,
+    from: TagType,
+    to: TagType,
+    strip_source: bool,
+) -> Result<TaggyFile, TaggyException> {
+    Ok(api::convert_tag(path, from, to, strip_source)?)
+}
+
+/// See [api::strip_fields].
+#[uniffi::export]
+pub fn strip_fields(
+    paths: Vec<String>,
+    classes: Vec<FieldClass>,
+) -> Result<Vec<TaggyFile>, TaggyException> {
+    Ok(api::strip_fields(paths, classes)?)
+}
+
+/// See [api::link_continuous_work].
+#[uniffi::export]
+pub fn link_continuous_work(
+    paths: Vec<String>,
+    work: String,
+    show_movement: bool,
+) -> Result<(), TaggyException> {
+    Ok(api::link_continuous_work(paths, work, show_movement)?)
+}
+
+/// See [api::read_work_link].
+#[uniffi::export]
+pub fn read_work_link(path: String) -> Result<Option<WorkLink>, TaggyException> {
+    Ok(api::read_work_link(path)?)
+}
+
+/// See [api::lint_tag].
+#[uniffi::export]
+pub fn lint_tag(tag: Tag, profile: LintProfile) -> Vec<String> {
+    api::lint_tag(tag, profile)
+}
+
+/// See [api::write_options_for_profile].
+#[uniffi::export]
+pub fn write_options_for_profile(profile: WriteProfile) -> WriteOptions {
+    api::write_options_for_profile(profile)
+}
@@ -0,0 +1,45 @@
+use crate::picture::{Picture, PictureType};
+use crate::tag::Tag;
+use crate::utils::artwork_rules::picture_size;
+
+/// The largest front cover of the given `tags`, by pixel count then by data size.
+///
+/// Falls back to the largest picture of any type when none of the tags has a front cover.
+pub(crate) fn largest_cover<'a>(tags: impl IntoIterator<Item = &'a Tag>) -> Option<Picture> {
+    let pictures: Vec<&Picture> = tags.into_iter().flat_map(|tag| &tag.pictures).collect();
+    let covers: Vec<&Picture> = pictures
+        .iter()
+        .copied()
+        .filter(|p| p.pic_type == PictureType::CoverFront)
+        .collect();
+    let candidates = if covers.is_empty() { pictures } else { covers };
+    candidates
+        .into_iter()
+        .max_by_key(|p| {
+            let pixels = picture_size(p).map_or(0, |(width, height)| width as u64 * height as u64);
+            (pixels, p.pic_data.len())
+        })
+        .map(|p| Picture {
+            pic_type: PictureType::CoverFront,
+            ..p.clone()
+        })
+}
+
+/// Replaces the front covers of `tag` with the given `cover`, keeping its other pictures.
+///
+/// Returns whether the tag changed, i.e. it didn't have exactly this cover already.
+pub(crate) fn set_cover(tag: &mut Tag, cover: &Picture) -> bool {
+    let mut covers = tag
+        .pictures
+        .iter()
+        .filter(|p| p.pic_type == PictureType::CoverFront);
+    let up_to_date =
+        covers.next().is_some_and(|p| p.pic_data == cover.pic_data) && covers.next().is_none();
+    if up_to_date {
+        return false;
+    }
+    tag.pictures
+        .retain(|p| p.pic_type != PictureType::CoverFront);
+    tag.pictures.insert(0, cover.clone());
+    true
+}
@@ -0,0 +1,85 @@
+use crate::artwork_optimization::{ArtworkOptions, ArtworkSavings};
+use crate::error::TaggyError;
+use crate::picture::{MimeType, Picture};
+use image::codecs::jpeg::JpegEncoder;
+use image::imageops::FilterType;
+use image::GenericImageView;
+
+/// Re-encodes the pictures of all the tags of the file at `path` with the `options`, and writes
+/// them back unless it's a dry run.
+///
+/// A picture is only replaced when its re-encoded data is smaller, so the file is only written
+/// when at least one picture got smaller. The pictures which can't be decoded are kept as they
+/// are and reported in the `error` of the savings.
+pub(crate) fn optimize_file(
+    path: &str,
+    options: &ArtworkOptions,
+) -> anyhow::Result<ArtworkSavings> {
+    let mut file = crate::api::read_all(path.to_string())?;
+    let mut savings = ArtworkSavings {
+        path: path.to_string(),
+        pictures_optimized: 0,
+        bytes_before: 0,
+        bytes_after: 0,
+        error: None,
+    };
+    let mut undecodable = 0;
+    for picture in file.tags.iter_mut().flat_map(|t| t.pictures.iter_mut()) {
+        savings.bytes_before += picture.pic_data.len() as u64;
+        match optimize_picture(picture, options) {
+            Ok(Some(optimized)) => {
+                *picture = optimized;
+                savings.pictures_optimized += 1;
+            }
+            Ok(None) => {}
+            Err(_) => undecodable += 1,
+        }
+        savings.bytes_after += picture.pic_data.len() as u64;
+    }
+    if undecodable > 0 {
+        savings.error = Some(format!(
+            "{undecodable} picture(s) can't be decoded and were left as they are"
+        ));
+    }
+    if savings.pictures_optimized > 0 && !options.dry_run {
+        crate::api::write_all(path.to_string(), file.tags, false)?;
+    }
+    Ok(savings)
+}
+
+/// The `picture` re-encoded as a JPEG of the quality of the `options`, downscaled to their
+/// `max_size`, `None` when it isn't smaller.
+///
+/// The pictures using transparency are kept as they are, JPEG can't store it.
+fn optimize_picture(
+    picture: &Picture,
+    options: &ArtworkOptions,
+) -> anyhow::Result<Option<Picture>> {
+    let mut image = image::load_from_memory(&picture.pic_data)
+        .map_err(|e| TaggyError::invalid_value(format!("The picture can't be decoded: {e}")))?;
+    let is_transparent = image.color().has_alpha() && image.pixels().any(|(_, _, p)| p[3] < 255);
+    if is_transparent {
+        return Ok(None);
+    }
+    if let Some(max_size) = options.max_size {
+        if image.width().max(image.height()) > max_size {
+            image = image.resize(max_size, max_size, FilterType::Lanczos3);
+        }
+    }
+    let rgb = image.to_rgb8();
+    let mut data = vec![];
+    JpegEncoder::new_with_quality(&mut data, options.quality).encode_image(&rgb)?;
+    if data.len() >= picture.pic_data.len() {
+        return Ok(None);
+    }
+    Ok(Some(Picture {
+        mime_type: Some(MimeType::Jpeg),
+        width: Some(rgb.width()),
+        height: Some(rgb.height()),
+        color_depth: Some(24),
+        num_colors: None,
+        data_size: Some(data.len() as u32),
+        pic_data: data,
+        ..picture.clone()
+    }))
+}
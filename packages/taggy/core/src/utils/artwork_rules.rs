@@ -0,0 +1,45 @@
+use crate::picture::{MimeType, Picture};
+use crate::tag::Tag;
+use crate::utils::lofty_intos::get_pic_from_data;
+use crate::write_options::WriteOptions;
+
+/// Drops the pictures of `tag` breaking the artwork rules of the `options`
+/// (`jpeg_pictures_only`, `max_picture_size`).
+///
+/// Returns the tag to write and a warning for each dropped picture.
+pub(crate) fn apply_artwork_rules(mut tag: Tag, options: &WriteOptions) -> (Tag, Vec<String>) {
+    let mut warnings = vec![];
+    tag.pictures.retain(|picture| {
+        if options.jpeg_pictures_only && picture.mime_type != Some(MimeType::Jpeg) {
+            warnings.push(format!(
+                "The {:?} picture of the {:?} tag was dropped, only JPEG pictures are allowed",
+                picture.pic_type, tag.tag_type
+            ));
+            return false;
+        }
+        let Some(max_picture_size) = options.max_picture_size else {
+            return true;
+        };
+        match picture_size(picture) {
+            Some((width, height)) if width.max(height) > max_picture_size => {
+                warnings.push(format!(
+                    "The {:?} picture of the {:?} tag was dropped, it's {width}x{height} pixels \
+                    while the limit is {max_picture_size}",
+                    picture.pic_type, tag.tag_type
+                ));
+                false
+            }
+            _ => true,
+        }
+    });
+    (tag, warnings)
+}
+
+/// The width and height of the `picture`, read from its data when they aren't set.
+pub(crate) fn picture_size(picture: &Picture) -> Option<(u32, u32)> {
+    if let (Some(width), Some(height)) = (picture.width, picture.height) {
+        return Some((width, height));
+    }
+    let info = lofty::PictureInformation::from_picture(&get_pic_from_data(picture)).ok()?;
+    Some((info.width, info.height))
+}
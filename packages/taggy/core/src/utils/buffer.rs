@@ -0,0 +1,50 @@
+use crate::utils::file_id::generate_uuid;
+use anyhow::anyhow;
+use lofty::{ParseOptions, Probe, TaggedFile};
+use std::env;
+use std::fs;
+use std::io::Cursor;
+
+/// A helper function to get a [`TaggedFile`] from the given in-memory `bytes`.
+///
+/// Unlike the path based helpers, this never touches the file system so it's
+/// the only reading path available for `wasm` builds.
+pub(crate) fn get_tagged_file_from_bytes(bytes: &[u8]) -> anyhow::Result<TaggedFile> {
+    let probe = Probe::new(Cursor::new(bytes))
+        .options(ParseOptions::new())
+        .guess_file_type()
+        .map_err(|e| anyhow!(e))?;
+    if probe.file_type().is_none() {
+        return Err(anyhow!(
+            "Unable to detect the file type from the given bytes"
+        ));
+    }
+    probe.read().map_err(|e| anyhow!(e))
+}
+
+/// Runs the write `op` on a temporary copy of the given in-memory `bytes`,
+/// and returns the bytes of the copy after it.
+///
+/// lofty only saves tags to a [`std::fs::File`], so unlike reading, writing to a buffer
+/// can't skip the file system. The copy lives in the system temp directory and is removed
+/// right after `op`, whether it succeeded or not.
+pub(crate) fn write_through_temp_file(
+    bytes: &[u8],
+    op: impl FnOnce(String) -> anyhow::Result<()>,
+) -> anyhow::Result<Vec<u8>> {
+    let probe = Probe::new(Cursor::new(bytes))
+        .guess_file_type()
+        .map_err(|e| anyhow!(e))?;
+    if probe.file_type().is_none() {
+        return Err(anyhow!(
+            "Unable to detect the file type from the given bytes"
+        ));
+    }
+
+    let temp_path = env::temp_dir().join(format!(".taggy-buffer-{}", generate_uuid()));
+    fs::write(&temp_path, bytes)?;
+    let result = op(temp_path.to_string_lossy().to_string());
+    let tagged_bytes = result.and_then(|_| fs::read(&temp_path).map_err(|e| anyhow!(e)));
+    let _ = fs::remove_file(&temp_path);
+    tagged_bytes
+}
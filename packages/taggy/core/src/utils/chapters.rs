@@ -0,0 +1,270 @@
+use crate::chapter::Chapter;
+use crate::error::TaggyError;
+use crate::picture::Picture;
+use crate::utils::file_id::{probe_file_type, read_id3v2};
+use crate::utils::lofty_intos::get_pic_from_data;
+use crate::utils::mp4_chapters::{read_mp4_chapters_from, write_mp4_chapters};
+use crate::utils::raw_id3v2::decode_text;
+use crate::utils::read_only::open_read_only;
+use anyhow::anyhow;
+use lofty::id3::v2::{AttachedPictureFrame, Frame, FrameFlags, FrameValue, Id3v2Tag, Id3v2Version};
+use lofty::{FileType, TagExt, TextEncoding};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom};
+
+/// The ID3v2 frame of a chapter.
+pub(crate) const CHAP: &str = "CHAP";
+/// The ID3v2 frame listing the chapters in their order.
+pub(crate) const CTOC: &str = "CTOC";
+/// The element ID of the table of contents written by [write_chapters].
+const TOC_ID: &str = "toc";
+/// The `CTOC` flags of a top-level table of contents whose entries are ordered.
+const TOC_FLAGS: u8 = 0b11;
+/// The `CHAP` byte offsets telling the chapter is located by its times only.
+const NO_OFFSET: u32 = u32::MAX;
+
+/// Reads the chapters of the file at `path`, sorted by start time.
+///
+/// The files which can't store chapters have none.
+pub(crate) fn read_chapters(path: &str) -> anyhow::Result<Vec<Chapter>> {
+    let file_type = probe_file_type(path)?;
+    read_chapters_from(&mut open_read_only(path)?, file_type)
+}
+
+/// Same as [read_chapters], for the `file_type` audio file of the `reader`.
+pub(crate) fn read_chapters_from<R: Read + Seek>(
+    reader: &mut R,
+    file_type: FileType,
+) -> anyhow::Result<Vec<Chapter>> {
+    if file_type == FileType::Mp4 {
+        return read_mp4_chapters_from(reader);
+    }
+    let Some(tag) = read_id3v2(reader, file_type)? else {
+        return Ok(vec![]);
+    };
+    Ok(id3v2_chapters(&tag))
+}
+
+/// Replaces the chapters of the file at `path` with the `chapters`,
+/// or removes them when there's none.
+///
+/// Fails when the file can't store chapters, i.e. isn't an MPEG, AIFF, WAV or MP4 one,
+/// when a chapter ends before it starts, or when an ID3v2 tag would hold more than 255 of them.
+pub(crate) fn write_chapters(path: &str, chapters: &[Chapter]) -> anyhow::Result<()> {
+    if let Some(chapter) = chapters.iter().find(|c| c.end_ms < c.start_ms) {
+        return Err(TaggyError::invalid_value(format!(
+            "The chapter starting at {} ms ends before it starts!",
+            chapter.start_ms
+        ))
+        .into());
+    }
+    let mut chapters = chapters.to_vec();
+    chapters.sort_by_key(|c| c.start_ms);
+
+    let file_type = probe_file_type(path)?;
+    match file_type {
+        FileType::Mp4 => write_mp4_chapters(path, &chapters),
+        FileType::Mpeg | FileType::Aiff | FileType::Wav => {
+            if chapters.len() > u8::MAX as usize {
+                return Err(TaggyError::invalid_value(format!(
+                    "ID3v2 tags hold up to 255 chapters, not {}!",
+                    chapters.len()
+                ))
+                .into());
+            }
+            let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+            let mut tag = read_id3v2(&mut file, file_type)?.unwrap_or_default();
+            tag.remove(CHAP);
+            tag.remove(CTOC);
+            for frame in chapter_frames(&chapters)? {
+                insert_chapter_frame(&mut tag, frame)?;
+            }
+            file.seek(SeekFrom::Start(0))?;
+            tag.save_to(&mut file).map_err(|e| anyhow!(e))
+        }
+        _ => Err(TaggyError::UnsupportedTagType {
+            message: format!("The chapters can't be stored in '{file_type:?}' files!"),
+        }
+        .into()),
+    }
+}
+
+/// The content of the `CHAP` and `CTOC` frames of the `tag`, which lofty keeps as binary,
+/// with their ID.
+pub(crate) fn chapter_frames_of(tag: &Id3v2Tag) -> Vec<(String, Vec<u8>)> {
+    tag.into_iter()
+        .filter(|frame| frame.id_str() == CHAP || frame.id_str() == CTOC)
+        .filter_map(|frame| match frame.content() {
+            FrameValue::Binary(data) => Some((frame.id_str().to_string(), data.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) fn insert_chapter_frame(
+    tag: &mut Id3v2Tag,
+    (id, data): (String, Vec<u8>),
+) -> anyhow::Result<()> {
+    tag.insert(Frame::new(
+        id,
+        FrameValue::Binary(data),
+        FrameFlags::default(),
+    )?);
+    Ok(())
+}
+
+fn id3v2_chapters(tag: &Id3v2Tag) -> Vec<Chapter> {
+    let mut chapters: Vec<Chapter> = chapter_frames_of(tag)
+        .into_iter()
+        .filter(|(id, _)| id == CHAP)
+        .filter_map(|(_, data)| parse_chap(&data))
+        .collect();
+    chapters.sort_by_key(|c| c.start_ms);
+    chapters
+}
+
+/// Parses the content of a `CHAP` frame: its null terminated element ID, its start and end times
+/// and byte offsets, then its sub-frames, e.g. the `TIT2` title.
+fn parse_chap(data: &[u8]) -> Option<Chapter> {
+    let id_end = data.iter().position(|b| *b == 0)?;
+    let times = data.get(id_end + 1..id_end + 17)?;
+    let mut chapter = Chapter {
+        start_ms: u32::from_be_bytes(times[0..4].try_into().ok()?),
+        end_ms: u32::from_be_bytes(times[4..8].try_into().ok()?),
+        title: None,
+        url: None,
+        picture: None,
+    };
+    let sub_frames = &data[id_end + 17..];
+    // the sizes are synchsafe in ID3v2.4 and plain in ID3v2.3, lofty keeps the frame content as
+    // it was read and the tag doesn't tell which version it was written with.
+    let frames = parse_sub_frames(sub_frames, true)
+        .or_else(|| parse_sub_frames(sub_frames, false))
+        .unwrap_or_default();
+    for (id, content) in frames {
+        match id.as_str() {
+            "TIT2" => chapter.title = decode_text_frame(content),
+            "WXXX" => chapter.url = decode_user_url_frame(content),
+            "APIC" => {
+                chapter.picture = AttachedPictureFrame::parse(&mut &content[..], Id3v2Version::V4)
+                    .ok()
+                    .map(|frame| Picture::from(&frame.picture))
+            }
+            _ => {}
+        }
+    }
+    Some(chapter)
+}
+
+/// Splits the embedded frames of a `CHAP` frame, `None` when their sizes don't add up.
+fn parse_sub_frames(mut data: &[u8], synchsafe: bool) -> Option<Vec<(String, &[u8])>> {
+    let mut frames = vec![];
+    while data.len() >= 10 && data[0] != 0 {
+        let id = std::str::from_utf8(&data[0..4]).ok()?;
+        if !id
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        {
+            return None;
+        }
+        let size_bytes: [u8; 4] = data[4..8].try_into().ok()?;
+        let size = if synchsafe {
+            if size_bytes.iter().any(|b| b & 0x80 != 0) {
+                return None;
+            }
+            size_bytes
+                .iter()
+                .fold(0usize, |size, b| (size << 7) | *b as usize)
+        } else {
+            u32::from_be_bytes(size_bytes) as usize
+        };
+        let content = data.get(10..10 + size)?;
+        frames.push((id.to_string(), content));
+        data = &data[10 + size..];
+    }
+    Some(frames)
+}
+
+/// Decodes a text frame, e.g. `TIT2`: its encoding byte, then the text.
+fn decode_text_frame(content: &[u8]) -> Option<String> {
+    let (encoding, text) = content.split_first()?;
+    Some(decode_text(*encoding, text)).filter(|t| !t.is_empty())
+}
+
+/// Decodes a `WXXX` frame: its encoding byte, the terminated description, then the latin1 URL.
+fn decode_user_url_frame(content: &[u8]) -> Option<String> {
+    let (encoding, rest) = content.split_first()?;
+    let url_start = match encoding {
+        // UTF-16 descriptions end with two null bytes, on a character boundary.
+        1 | 2 => {
+            (0..rest.len().saturating_sub(1))
+                .step_by(2)
+                .find(|i| rest[*i] == 0 && rest[i + 1] == 0)?
+                + 2
+        }
+        _ => rest.iter().position(|b| *b == 0)? + 1,
+    };
+    Some(decode_text(0, &rest[url_start..])).filter(|u| !u.is_empty())
+}
+
+/// The `CHAP` frames of the `chapters`, then the `CTOC` frame listing them.
+fn chapter_frames(chapters: &[Chapter]) -> anyhow::Result<Vec<(String, Vec<u8>)>> {
+    if chapters.is_empty() {
+        return Ok(vec![]);
+    }
+    let mut frames = vec![];
+    let mut toc = TOC_ID.as_bytes().to_vec();
+    toc.extend([0, TOC_FLAGS, chapters.len() as u8]);
+    for (index, chapter) in chapters.iter().enumerate() {
+        let element_id = format!("chp{index}");
+        toc.extend(element_id.as_bytes());
+        toc.push(0);
+        frames.push((CHAP.to_string(), chap_bytes(&element_id, chapter)?));
+    }
+    frames.push((CTOC.to_string(), toc));
+    Ok(frames)
+}
+
+fn chap_bytes(element_id: &str, chapter: &Chapter) -> anyhow::Result<Vec<u8>> {
+    let mut data = element_id.as_bytes().to_vec();
+    data.push(0);
+    data.extend(chapter.start_ms.to_be_bytes());
+    data.extend(chapter.end_ms.to_be_bytes());
+    data.extend(NO_OFFSET.to_be_bytes());
+    data.extend(NO_OFFSET.to_be_bytes());
+    if let Some(title) = &chapter.title {
+        let mut content = vec![TextEncoding::UTF8 as u8];
+        content.extend(title.as_bytes());
+        push_sub_frame(&mut data, "TIT2", &content);
+    }
+    if let Some(url) = &chapter.url {
+        // an empty UTF-8 description, then the URL.
+        let mut content = vec![TextEncoding::UTF8 as u8, 0];
+        // the URL is latin1, the characters out of it can't be stored.
+        content.extend(url.chars().map(|c| u8::try_from(c).unwrap_or(b'?')));
+        push_sub_frame(&mut data, "WXXX", &content);
+    }
+    if let Some(picture) = &chapter.picture {
+        let frame = AttachedPictureFrame {
+            encoding: TextEncoding::UTF8,
+            picture: get_pic_from_data(picture),
+        };
+        let content = frame.as_bytes(Id3v2Version::V4).map_err(|e| anyhow!(e))?;
+        push_sub_frame(&mut data, "APIC", &content);
+    }
+    Ok(data)
+}
+
+/// Appends an ID3v2.4 frame to `data`, with its synchsafe size and no flags.
+fn push_sub_frame(data: &mut Vec<u8>, id: &str, content: &[u8]) {
+    let size = content.len() as u32;
+    data.extend(id.as_bytes());
+    data.extend([
+        (size >> 21 & 0x7F) as u8,
+        (size >> 14 & 0x7F) as u8,
+        (size >> 7 & 0x7F) as u8,
+        (size & 0x7F) as u8,
+    ]);
+    data.extend([0, 0]);
+    data.extend(content);
+}
@@ -0,0 +1,101 @@
+use crate::comment::Comment;
+use crate::tag::{Tag, TagType};
+use crate::utils::lyrics::{language_of, parse_language, UNKNOWN_LANGUAGE};
+use lofty::id3::v2::{CommentFrame, Frame, FrameFlags, FrameValue, Id3v2Tag};
+use lofty::{ItemKey, TextEncoding};
+
+/// Reads the comments of the `tag`.
+///
+/// lofty only keeps the ID3v2 `COMM` frame without a description in its generic tags,
+/// see [add_id3v2_comments] for the others.
+pub(crate) fn comments_from(tag: &lofty::Tag) -> Vec<Comment> {
+    tag.get_strings(&ItemKey::Comment)
+        .filter(|text| !text.is_empty())
+        .map(Comment::new)
+        .collect()
+}
+
+/// The texts of the `comments` lofty writes to a tag of the `tag_type`.
+///
+/// The ID3v2 comments are written as `COMM` frames by [insert_id3v2_comments] as soon as one
+/// has a language or a description, the other tags can't store the comments having one.
+pub(crate) fn generic_comments(tag_type: lofty::TagType, comments: &[Comment]) -> Vec<String> {
+    if tag_type == lofty::TagType::Id3v2 && needs_comm_frames(comments) {
+        return vec![];
+    }
+    comments
+        .iter()
+        .filter(|c| c.description.is_none())
+        .map(|c| c.text.clone())
+        .collect()
+}
+
+/// Replaces the comments of the `tag` with the `COMM` frames of the `id3v2` tag, if any.
+pub(crate) fn add_id3v2_comments(tag: &mut Tag, id3v2: &Id3v2Tag) {
+    let comments: Vec<Comment> = id3v2
+        .into_iter()
+        .filter_map(|frame| match frame.content() {
+            FrameValue::Comment(comment) => Some(Comment {
+                language: language_of(comment.language),
+                description: (!comment.description.is_empty()).then(|| comment.description.clone()),
+                text: comment.content.clone(),
+            }),
+            _ => None,
+        })
+        .collect();
+    if !comments.is_empty() {
+        tag.comments = comments;
+    }
+}
+
+/// The `COMM` frames of the ID3v2 tag among `tags`, when lofty can't write its comments,
+/// see [generic_comments].
+///
+/// Fails when a language isn't a 3 letters ISO 639-2 code, so before anything is written.
+pub(crate) fn id3v2_comments(tags: &[Tag]) -> anyhow::Result<Vec<CommentFrame>> {
+    let Some(tag) = tags.iter().find(|t| t.tag_type == TagType::Id3v2) else {
+        return Ok(vec![]);
+    };
+    if !needs_comm_frames(&tag.comments) {
+        return Ok(vec![]);
+    }
+    tag.comments
+        .iter()
+        .map(|comment| {
+            Ok(CommentFrame {
+                encoding: TextEncoding::UTF8,
+                language: match &comment.language {
+                    Some(language) => parse_language(language)?,
+                    None => UNKNOWN_LANGUAGE,
+                },
+                description: comment.description.clone().unwrap_or_default(),
+                content: comment.text.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Replaces the `COMM` frames of the `id3v2` tag with the `comments`.
+pub(crate) fn insert_id3v2_comments(
+    id3v2: &mut Id3v2Tag,
+    comments: &[CommentFrame],
+) -> anyhow::Result<()> {
+    id3v2.remove("COMM");
+    for comment in comments {
+        id3v2.insert(Frame::new(
+            "COMM",
+            FrameValue::Comment(comment.clone()),
+            FrameFlags::default(),
+        )?);
+    }
+    Ok(())
+}
+
+/// Whether the `comments` don't fit the single `COMM` frame without language nor
+/// description lofty writes.
+fn needs_comm_frames(comments: &[Comment]) -> bool {
+    comments.len() > 1
+        || comments
+            .iter()
+            .any(|c| c.language.is_some() || c.description.is_some())
+}
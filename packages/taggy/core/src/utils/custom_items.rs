@@ -0,0 +1,87 @@
+use crate::custom_item::CustomItem;
+use crate::tag::{Tag, TagType};
+use crate::utils::freeform_fields::{is_freeform_description, is_freeform_key};
+use crate::utils::lyrics::UNSYNCED_LYRICS_KEY;
+use crate::utils::rating::RATING_KEY;
+use crate::utils::work::is_work_description;
+use lofty::id3::v2::{FrameValue, Id3v2Tag};
+use lofty::{ItemKey, ItemValue, TagItem};
+
+pub(crate) const MP4_FREEFORM_PREFIX: &str = "----:com.apple.iTunes:";
+
+/// Reads the text items of the `tag` which lofty doesn't map to one of its keys.
+///
+/// The MP4 freeform atoms are returned without their `----:com.apple.iTunes:` prefix.
+/// lofty doesn't keep the ID3v2 `TXXX` frames in its generic tags,
+/// see [add_id3v2_custom_items] for them.
+pub(crate) fn custom_items_from(tag: &lofty::Tag) -> Vec<CustomItem> {
+    tag.items()
+        .filter_map(|item| match (item.key(), item.value()) {
+            (ItemKey::Unknown(key), ItemValue::Text(value)) => {
+                custom_key(tag.tag_type(), key).map(|key| CustomItem::new(key, value))
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn custom_key(tag_type: lofty::TagType, key: &str) -> Option<&str> {
+    match tag_type {
+        // read as their own fields, see [freeform_value](crate::utils::freeform_fields::freeform_value).
+        _ if is_freeform_key(tag_type, key) => None,
+        lofty::TagType::Mp4Ilst => key.strip_prefix(MP4_FREEFORM_PREFIX),
+        // these are frames lofty doesn't know, e.g. `TDTG`, not `TXXX` ones.
+        lofty::TagType::Id3v2 => None,
+        // read as the rating, see [rating_from](crate::utils::rating::rating_from).
+        _ if key.eq_ignore_ascii_case(RATING_KEY) => None,
+        // read as the lyrics, see [unsynced_lyrics_item](crate::utils::lyrics::unsynced_lyrics_item).
+        _ if key.eq_ignore_ascii_case(UNSYNCED_LYRICS_KEY) => None,
+        _ => Some(key),
+    }
+}
+
+/// Appends the custom `items` to the `tag`, see [custom_items_from].
+///
+/// The ID3v2 ones are written separately by
+/// [completed_id3v2](crate::utils::id3v2_frames::completed_id3v2), lofty would
+/// turn their keys into frame IDs.
+pub(crate) fn push_custom_items(tag: &mut lofty::Tag, items: &[CustomItem]) {
+    for item in items {
+        let key = match tag.tag_type() {
+            lofty::TagType::Id3v2 => return,
+            lofty::TagType::Mp4Ilst => format!("{MP4_FREEFORM_PREFIX}{}", item.key),
+            _ => item.key.clone(),
+        };
+        tag.push_unchecked(TagItem::new(
+            ItemKey::Unknown(key),
+            ItemValue::Text(item.value.clone()),
+        ));
+    }
+}
+
+/// Adds the `TXXX` frames of the `id3v2` tag to the custom items of the `tag`, but the ones
+/// of the [FreeformField](crate::utils::freeform_fields::FreeformField)s and the work,
+/// see [read_id3v2_frames](crate::utils::id3v2_frames::read_id3v2_frames).
+pub(crate) fn add_id3v2_custom_items(tag: &mut Tag, id3v2: &Id3v2Tag) {
+    for frame in id3v2 {
+        if let FrameValue::UserText(text) = frame.content() {
+            if is_freeform_description(&text.description) || is_work_description(&text.description)
+            {
+                continue;
+            }
+            if !tag.custom_items.iter().any(|i| i.key == text.description) {
+                tag.custom_items
+                    .push(CustomItem::new(&text.description, &text.content));
+            }
+        }
+    }
+}
+
+/// The custom items of the ID3v2 tags among `tags`, to write with
+/// [completed_id3v2](crate::utils::id3v2_frames::completed_id3v2).
+pub(crate) fn id3v2_custom_items(tags: &[Tag]) -> Vec<CustomItem> {
+    tags.iter()
+        .filter(|t| t.tag_type == TagType::Id3v2)
+        .flat_map(|t| t.custom_items.iter().cloned())
+        .collect()
+}
@@ -0,0 +1,37 @@
+use crate::tag::Tag;
+use crate::utils::dates::unix_ms_of;
+
+/// The custom items some players and taggers write the date a file was added to the library to,
+/// compared ignoring the case, spaces and underscores, e.g. `DATE_ADDED` or `Date Added`.
+const DATE_ADDED_KEYS: [&str; 2] = ["DATEADDED", "ADDED"];
+
+/// A Unix timestamp above this one is read in milliseconds rather than seconds, i.e. after 5138.
+const MAX_UNIX_SECONDS: i64 = 100_000_000_000;
+
+/// The date the file was added to the library embedded in the custom items of the `tags`,
+/// in milliseconds since the Unix epoch.
+///
+/// The value is either an ISO 8601 date, in UTC when it has no time zone, e.g.
+/// `2021-06-15 20:30:00` as written by foobar2000, or a Unix timestamp in seconds or milliseconds.
+pub(crate) fn date_added_from(tags: &[Tag]) -> Option<i64> {
+    tags.iter()
+        .flat_map(|tag| tag.custom_items.iter())
+        .filter(|item| {
+            let key = item.key.replace([' ', '_'], "").to_uppercase();
+            DATE_ADDED_KEYS.contains(&key.as_str())
+        })
+        .find_map(|item| parse_date_added(&item.value))
+}
+
+fn parse_date_added(value: &str) -> Option<i64> {
+    let value = value.trim();
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        let timestamp: i64 = value.parse().ok()?;
+        return Some(if timestamp < MAX_UNIX_SECONDS {
+            timestamp * 1000
+        } else {
+            timestamp
+        });
+    }
+    unix_ms_of(value)
+}
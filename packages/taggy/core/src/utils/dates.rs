@@ -0,0 +1,321 @@
+use crate::date_context::{DateContext, DateOrder};
+use crate::tag::Tag;
+use crate::tag_date::TagDate;
+use lofty::id3::v2::{FrameValue, Id3v2Tag};
+
+/// The ID3v2.3 frame of the day and month of the `TYER` year, as `DDMM`.
+const TDAT: &str = "TDAT";
+
+/// Normalizes the dates of the `tag` to ISO 8601 according to the `context`, see [DateContext].
+///
+/// The structured dates missing from the `tag` are filled from the normalized ones.
+pub(crate) fn normalize_dates(tag: &mut Tag, context: &DateContext) {
+    for value in [&mut tag.recording_date, &mut tag.original_release_date]
+        .into_iter()
+        .flatten()
+    {
+        if let Some(normalized) = normalize_date(value, context) {
+            *value = normalized;
+        }
+    }
+    if tag.release_date.is_none() {
+        tag.release_date = release_date_of(tag.recording_date.as_deref(), tag.year);
+    }
+    if tag.original_date.is_none() {
+        tag.original_date = tag
+            .original_release_date
+            .as_deref()
+            .and_then(parse_tag_date);
+    }
+}
+
+/// Parses the ISO 8601 `value`, e.g. `2001-03-04` or `2001-03-04T21:30:00`, keeping its date.
+pub(crate) fn parse_tag_date(value: &str) -> Option<TagDate> {
+    let (date, _) = parse_iso(value.trim())?;
+    Some(TagDate {
+        year: u32::try_from(date.year).ok()?,
+        month: date.month,
+        day: date.day,
+    })
+}
+
+/// The release date of a tag with the `recording_date` and the `year`,
+/// see [Tag::release_date](crate::tag::Tag::release_date).
+pub(crate) fn release_date_of(recording_date: Option<&str>, year: Option<u32>) -> Option<TagDate> {
+    recording_date
+        .and_then(parse_tag_date)
+        .or(year.map(TagDate::year))
+}
+
+/// The text of the date to write, the structured `date` unless the `text` is read back as it,
+/// so a time of the day in the `text` isn't lost.
+pub(crate) fn date_to_write(
+    text: &Option<String>,
+    date: Option<TagDate>,
+    read_back: Option<TagDate>,
+) -> Option<String> {
+    match date {
+        Some(date) if read_back != Some(date) => Some(date.to_string()),
+        _ => text.clone(),
+    }
+}
+
+/// Adds the day and month of the ID3v2.3 `TDAT` frame to the release date of the `tag`,
+/// which lofty only reads the `TYER` year of.
+pub(crate) fn add_id3v2_dates(tag: &mut Tag, id3v2: &Id3v2Tag) {
+    let Some(date) = tag.release_date.filter(|d| d.month.is_none()) else {
+        return;
+    };
+    let Some((day, month)) = id3v2.get(TDAT).and_then(|frame| match frame.content() {
+        FrameValue::Text(text) => parse_tdat(&text.value),
+        _ => None,
+    }) else {
+        return;
+    };
+    let date = TagDate {
+        month: Some(month),
+        day: Some(day),
+        ..date
+    };
+    tag.recording_date = Some(date.to_string());
+    tag.release_date = Some(date);
+}
+
+/// Parses the `DDMM` day and month of a `TDAT` frame.
+fn parse_tdat(value: &str) -> Option<(u32, u32)> {
+    let value = value.trim();
+    if value.len() != 4 {
+        return None;
+    }
+    let day = parse_digits(&value[..2], 2)?;
+    let month = parse_digits(&value[2..], 2)?;
+    is_valid_date(Some(month), Some(day)).then_some((day, month))
+}
+
+/// A date and, optionally, a time of the day.
+#[derive(Debug)]
+struct DateTime {
+    year: i64,
+    month: Option<u32>,
+    day: Option<u32>,
+    /// The hours, minutes and seconds when there's a time.
+    time: Option<(u32, u32, Option<u32>)>,
+}
+
+/// Parses the `value` as a date, returns it in ISO 8601 or `None` when it isn't recognized.
+fn normalize_date(value: &str, context: &DateContext) -> Option<String> {
+    let value = value.trim();
+    let date = match parse_iso(value) {
+        Some((date, Some(offset_minutes))) => {
+            shift_minutes(date, context.utc_offset_minutes as i64 - offset_minutes)
+        }
+        Some((date, None)) => date,
+        None => parse_numeric(value, context.date_order)?,
+    };
+    Some(format_iso(&date))
+}
+
+/// Parses `YYYY[-MM[-DD]][(T| )HH:MM[:SS]][Z|±HH[:]MM]`, along with the time zone offset in minutes.
+fn parse_iso(value: &str) -> Option<(DateTime, Option<i64>)> {
+    let (date, time) = match value.split_once(['T', ' ']) {
+        Some((date, time)) => (date, Some(time)),
+        None => (value, None),
+    };
+    let mut parts = date.split('-');
+    let year = parse_digits(parts.next()?, 4)? as i64;
+    let month = parts
+        .next()
+        .map_or(Some(None), |m| parse_digits(m, 2).map(Some))?;
+    let day = parts
+        .next()
+        .map_or(Some(None), |d| parse_digits(d, 2).map(Some))?;
+    if parts.next().is_some() || !is_valid_date(month, day) {
+        return None;
+    }
+
+    let (time, offset) = match time {
+        Some(time) => {
+            let (time, offset) = split_offset(time)?;
+            (Some(parse_time(time)?), offset)
+        }
+        None => (None, None),
+    };
+    if time.is_some() && day.is_none() {
+        return None;
+    }
+    Some((
+        DateTime {
+            year,
+            month,
+            day,
+            time,
+        },
+        offset,
+    ))
+}
+
+/// Parses the numeric dates like `03/04/2001`, `03.04.2001` or `2001/03/04`.
+///
+/// The day and the month are told apart by their value when one is over 12, otherwise they're
+/// read in the `date_order`.
+fn parse_numeric(value: &str, date_order: DateOrder) -> Option<DateTime> {
+    let parts: Vec<&str> = value.split(['/', '.', '-']).collect();
+    let [first, second, third] = parts[..] else {
+        return None;
+    };
+    let (year, month, day) = if first.len() == 4 {
+        (
+            parse_digits(first, 4)?,
+            parse_number(second)?,
+            parse_number(third)?,
+        )
+    } else {
+        let (a, b, year) = (
+            parse_number(first)?,
+            parse_number(second)?,
+            parse_digits(third, 4)?,
+        );
+        match date_order {
+            _ if a > 12 => (year, b, a),
+            _ if b > 12 => (year, a, b),
+            DateOrder::DayMonth => (year, b, a),
+            DateOrder::MonthDay => (year, a, b),
+        }
+    };
+    if !is_valid_date(Some(month), Some(day)) {
+        return None;
+    }
+    Some(DateTime {
+        year: year as i64,
+        month: Some(month),
+        day: Some(day),
+        time: None,
+    })
+}
+
+/// Splits the trailing time zone of the `time`, as an offset in minutes.
+fn split_offset(time: &str) -> Option<(&str, Option<i64>)> {
+    if let Some(time) = time.strip_suffix('Z') {
+        return Some((time, Some(0)));
+    }
+    let Some(index) = time.rfind(['+', '-']) else {
+        return Some((time, None));
+    };
+    let (time, offset) = time.split_at(index);
+    let sign = if offset.starts_with('-') { -1 } else { 1 };
+    let digits = offset[1..].replace(':', "");
+    if digits.len() != 4 {
+        return None;
+    }
+    let hours = parse_digits(&digits[..2], 2)? as i64;
+    let minutes = parse_digits(&digits[2..], 2)? as i64;
+    Some((time, Some(sign * (hours * 60 + minutes))))
+}
+
+fn parse_time(time: &str) -> Option<(u32, u32, Option<u32>)> {
+    let mut parts = time.split(':');
+    let hours = parse_digits(parts.next()?, 2)?;
+    let minutes = parse_digits(parts.next()?, 2)?;
+    let seconds = parts
+        .next()
+        .map_or(Some(None), |s| parse_digits(s, 2).map(Some))?;
+    if parts.next().is_some() || hours > 23 || minutes > 59 || seconds.is_some_and(|s| s > 59) {
+        return None;
+    }
+    Some((hours, minutes, seconds))
+}
+
+/// Parses the `value` when it's exactly `len` digits.
+fn parse_digits(value: &str, len: usize) -> Option<u32> {
+    if value.len() == len && value.bytes().all(|b| b.is_ascii_digit()) {
+        value.parse().ok()
+    } else {
+        None
+    }
+}
+
+/// Parses the `value` when it's 1 or 2 digits.
+fn parse_number(value: &str) -> Option<u32> {
+    parse_digits(value, 1).or_else(|| parse_digits(value, 2))
+}
+
+fn is_valid_date(month: Option<u32>, day: Option<u32>) -> bool {
+    month.map_or(day.is_none(), |month| (1..=12).contains(&month))
+        && day.is_none_or(|day| (1..=31).contains(&day))
+}
+
+/// Moves the `date` by `minutes`, only the dates with a time are moved.
+fn shift_minutes(date: DateTime, minutes: i64) -> DateTime {
+    let (Some(month), Some(day), Some((hours, mins, seconds))) = (date.month, date.day, date.time)
+    else {
+        return date;
+    };
+    let total =
+        days_from_civil(date.year, month, day) * 24 * 60 + (hours * 60 + mins) as i64 + minutes;
+    let (year, month, day) = civil_from_days(total.div_euclid(24 * 60));
+    let minutes_of_day = total.rem_euclid(24 * 60) as u32;
+    DateTime {
+        year,
+        month: Some(month),
+        day: Some(day),
+        time: Some((minutes_of_day / 60, minutes_of_day % 60, seconds)),
+    }
+}
+
+/// Parses the ISO 8601 `value` to milliseconds since the Unix epoch, the dates without a time
+/// zone being in UTC and the ones without a month or a day at the first of them.
+pub(crate) fn unix_ms_of(value: &str) -> Option<i64> {
+    let (date, offset_minutes) = parse_iso(value.trim())?;
+    let days = days_from_civil(date.year, date.month.unwrap_or(1), date.day.unwrap_or(1));
+    let (hours, minutes, seconds) = date.time.unwrap_or((0, 0, None));
+    let minutes = days * 24 * 60 + (hours * 60 + minutes) as i64 - offset_minutes.unwrap_or(0);
+    Some((minutes * 60 + seconds.unwrap_or(0) as i64) * 1000)
+}
+
+fn format_iso(date: &DateTime) -> String {
+    let mut iso = format!("{:04}", date.year);
+    if let Some(month) = date.month {
+        iso += &format!("-{month:02}");
+    }
+    if let Some(day) = date.day {
+        iso += &format!("-{day:02}");
+    }
+    if let Some((hours, minutes, seconds)) = date.time {
+        iso += &format!("T{hours:02}:{minutes:02}");
+        if let Some(seconds) = seconds {
+            iso += &format!(":{seconds:02}");
+        }
+    }
+    iso
+}
+
+/// The number of days since 1970-01-01, see <http://howardhinnant.github.io/date_algorithms.html>.
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year.rem_euclid(400);
+    let month = month as i64;
+    let day_of_year =
+        (153 * (if month > 2 { month - 3 } else { month + 9 }) + 2) / 5 + day as i64 - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146097 + day_of_era - 719468
+}
+
+/// The inverse of [days_from_civil].
+fn civil_from_days(days: i64) -> (i64, u32, u32) {
+    let days = days + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = (day_of_year - (153 * month_index + 2) / 5 + 1) as u32;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    } as u32;
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    (year, month, day)
+}
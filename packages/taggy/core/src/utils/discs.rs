@@ -0,0 +1,81 @@
+use crate::api;
+use crate::disc_set::{Disc, DiscSet};
+use crate::error::TaggyError;
+use crate::utils::read_only::probe_read_only;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Finds the audio files in `dir` and its direct subfolders, grouped by disc.
+///
+/// The disc of each file is read from its `disc_number` tag, falling back to
+/// the number of its folder when named like `Disc 2`, `CD2` or `Disk 02`, then to `1`.
+pub(crate) fn detect_disc_set(dir: &str) -> anyhow::Result<DiscSet> {
+    let dir = Path::new(dir);
+    if !dir.is_dir() {
+        return Err(TaggyError::DirectoryNotFound.into());
+    }
+    let mut discs: BTreeMap<u32, Vec<String>> = BTreeMap::new();
+    for file in audio_files(dir)? {
+        let path = file.to_string_lossy().to_string();
+        let number = tagged_disc_number(&path)
+            .filter(|n| *n > 0)
+            .or_else(|| file.parent().and_then(disc_number_from_folder))
+            .unwrap_or(1);
+        discs.entry(number).or_default().push(path);
+    }
+    Ok(DiscSet {
+        discs: discs
+            .into_iter()
+            .map(|(number, mut files)| {
+                files.sort();
+                Disc { number, files }
+            })
+            .collect(),
+    })
+}
+
+fn tagged_disc_number(path: &str) -> Option<u32> {
+    let file = api::read_all(path.to_string()).ok()?;
+    file.tags.iter().find_map(|t| t.disc_number)
+}
+
+/// Lists the audio files of `dir` and of its direct subfolders.
+fn audio_files(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            for sub_entry in fs::read_dir(&path)?.flatten() {
+                if is_audio_file(&sub_entry.path()) {
+                    files.push(sub_entry.path());
+                }
+            }
+        } else if is_audio_file(&path) {
+            files.push(path);
+        }
+    }
+    Ok(files)
+}
+
+fn is_audio_file(path: &Path) -> bool {
+    path.is_file()
+        && probe_read_only(path)
+            .and_then(|p| Ok(p.guess_file_type()?))
+            .map(|p| p.file_type().is_some())
+            .unwrap_or(false)
+}
+
+/// Parses the disc number of folders named like `Disc 1`, `disk-2` or `CD03`.
+fn disc_number_from_folder(folder: &Path) -> Option<u32> {
+    let name = folder.file_name()?.to_str()?.to_lowercase();
+    let rest = ["disc", "disk", "cd"]
+        .iter()
+        .find_map(|prefix| name.strip_prefix(prefix))?;
+    let digits: String = rest
+        .trim_start_matches([' ', '-', '_', '.'])
+        .chars()
+        .take_while(char::is_ascii_digit)
+        .collect();
+    digits.parse().ok().filter(|n| *n > 0)
+}
@@ -0,0 +1,180 @@
+use crate::dj_markers::{CuePoint, DjLoop, DjMarkers};
+use crate::error::TaggyError;
+use crate::utils::file_id::{probe_file_type, read_id3v2};
+use crate::utils::raw_id3v2::decode_text;
+use crate::utils::read_only::open_read_only;
+use base64::alphabet::STANDARD;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use lofty::id3::v2::{Frame, FrameFlags, FrameValue, Id3v2Tag};
+
+/// The ID3v2 frame Serato DJ stores its analysis and markers in, one per description.
+const GEOB: &str = "GEOB";
+/// The ID3v2 frame Traktor stores its analysis and markers in, owned by `TRAKTOR4`.
+const PRIV: &str = "PRIV";
+/// The description of the `GEOB` frame holding the cues, loops and color of Serato DJ.
+const SERATO_MARKERS2: &str = "Serato Markers2";
+
+/// The base64 of Serato DJ, which is split in lines and often misses its padding.
+const SERATO_BASE64: GeneralPurpose = GeneralPurpose::new(
+    &STANDARD,
+    GeneralPurposeConfig::new()
+        .with_decode_padding_mode(DecodePaddingMode::Indifferent)
+        .with_decode_allow_trailing_bits(true),
+);
+
+/// Reads the cues, loops and color Serato DJ stored in the `Serato Markers2` frame of the ID3v2
+/// tag of the file at `path`, `None` when it has none.
+pub(crate) fn read_dj_markers(path: &str) -> anyhow::Result<Option<DjMarkers>> {
+    let file_type = probe_file_type(path)?;
+    let mut file = open_read_only(path)?;
+    let Some(tag) = read_id3v2(&mut file, file_type)? else {
+        return Ok(None);
+    };
+    let markers = dj_frames_of(&tag)
+        .into_iter()
+        .filter(|(id, _)| id == GEOB)
+        .find_map(|(_, data)| {
+            let (description, object) = split_geob(&data)?;
+            (description == SERATO_MARKERS2).then(|| object.to_vec())
+        });
+    let Some(markers) = markers else {
+        return Ok(None);
+    };
+    parse_markers2(&markers).map(Some).ok_or_else(|| {
+        TaggyError::CorruptTag {
+            message: format!("The '{SERATO_MARKERS2}' frame can't be decoded!"),
+        }
+        .into()
+    })
+}
+
+/// The content of the frames the DJ softwares store their prep work in, with their ID, which
+/// lofty keeps as binary: the `GEOB` frames of Serato DJ and the `PRIV` ones of Traktor.
+pub(crate) fn dj_frames_of(tag: &Id3v2Tag) -> Vec<(String, Vec<u8>)> {
+    tag.into_iter()
+        .filter(|frame| match frame.id_str() {
+            GEOB => true,
+            PRIV => {
+                matches!(frame.content(), FrameValue::Binary(data) if data.starts_with(b"TRAKTOR"))
+            }
+            _ => false,
+        })
+        .filter_map(|frame| match frame.content() {
+            FrameValue::Binary(data) => Some((frame.id_str().to_string(), data.clone())),
+            _ => None,
+        })
+        .collect()
+}
+
+pub(crate) fn insert_dj_frame(
+    tag: &mut Id3v2Tag,
+    (id, data): (String, Vec<u8>),
+) -> anyhow::Result<()> {
+    tag.insert(Frame::new(
+        id,
+        FrameValue::Binary(data),
+        FrameFlags::default(),
+    )?);
+    Ok(())
+}
+
+/// Splits the content of a `GEOB` frame into its description and its object, skipping its text
+/// encoding, MIME type and file name.
+fn split_geob(data: &[u8]) -> Option<(String, &[u8])> {
+    let (encoding, rest) = data.split_first()?;
+    let mime_end = rest.iter().position(|b| *b == 0)?;
+    let rest = &rest[mime_end + 1..];
+    let (_, rest) = split_terminated(rest, *encoding)?;
+    let (description, object) = split_terminated(rest, *encoding)?;
+    Some((decode_text(*encoding, description), object))
+}
+
+/// Splits the `data` after the null terminated text of the `encoding` it starts with,
+/// which ends with 2 null bytes in UTF-16.
+fn split_terminated(data: &[u8], encoding: u8) -> Option<(&[u8], &[u8])> {
+    let end = match encoding {
+        1 | 2 => data
+            .chunks_exact(2)
+            .position(|c| c == [0, 0])
+            .map(|i| (i * 2, i * 2 + 2)),
+        _ => data.iter().position(|b| *b == 0).map(|i| (i, i + 1)),
+    };
+    let (text_end, rest_start) = end?;
+    Some((&data[..text_end], &data[rest_start..]))
+}
+
+/// Parses the object of the `Serato Markers2` frame: a version, then base64 text up to a null
+/// byte, which decodes to a version and the entries, each made of a null terminated name,
+/// e.g. `CUE`, a size and its data.
+fn parse_markers2(object: &[u8]) -> Option<DjMarkers> {
+    let text = object.get(2..)?;
+    let text_end = text.iter().position(|b| *b == 0).unwrap_or(text.len());
+    let mut base64: Vec<u8> = text[..text_end]
+        .iter()
+        .copied()
+        .filter(|b| !b.is_ascii_whitespace() && *b != b'=')
+        .collect();
+    // a stray character is sometimes appended to the text.
+    if base64.len() % 4 == 1 {
+        base64.pop();
+    }
+    let payload = SERATO_BASE64.decode(base64).ok()?;
+
+    let mut markers = DjMarkers::default();
+    let mut entries = payload.get(2..)?;
+    while let Some(name_end) = entries.iter().position(|b| *b == 0) {
+        if name_end == 0 {
+            break;
+        }
+        let name = &entries[..name_end];
+        let size_bytes = entries.get(name_end + 1..name_end + 5)?;
+        let size = u32::from_be_bytes(size_bytes.try_into().ok()?) as usize;
+        let data = entries.get(name_end + 5..name_end + 5 + size)?;
+        match name {
+            b"CUE" => markers.cues.push(parse_cue(data)?),
+            b"LOOP" => markers.loops.push(parse_loop(data)?),
+            b"COLOR" => markers.track_color = Some(rgb(data.get(1..4)?)),
+            b"BPMLOCK" => markers.is_bpm_locked = data.first() == Some(&1),
+            // e.g. the `FLIP` entries of the recorded cue sequences.
+            _ => {}
+        }
+        entries = &entries[name_end + 5 + size..];
+    }
+    markers.cues.sort_by_key(|c| c.index);
+    markers.loops.sort_by_key(|l| l.index);
+    Some(markers)
+}
+
+/// Parses a `CUE` entry: its index, its position, its color and its name.
+fn parse_cue(data: &[u8]) -> Option<CuePoint> {
+    Some(CuePoint {
+        index: *data.get(1)?,
+        position_ms: u32::from_be_bytes(data.get(2..6)?.try_into().ok()?),
+        color: rgb(data.get(7..10)?),
+        name: name_of(data.get(12..)?),
+    })
+}
+
+/// Parses a `LOOP` entry: its index, its start and end positions, whether it's locked and its
+/// name.
+fn parse_loop(data: &[u8]) -> Option<DjLoop> {
+    Some(DjLoop {
+        index: *data.get(1)?,
+        start_ms: u32::from_be_bytes(data.get(2..6)?.try_into().ok()?),
+        end_ms: u32::from_be_bytes(data.get(6..10)?.try_into().ok()?),
+        is_locked: *data.get(19)? != 0,
+        name: name_of(data.get(20..)?),
+    })
+}
+
+fn rgb(bytes: &[u8]) -> u32 {
+    bytes.iter().fold(0, |rgb, b| (rgb << 8) | *b as u32)
+}
+
+/// The null terminated UTF-8 name of an entry, `None` when it's empty.
+fn name_of(data: &[u8]) -> Option<String> {
+    let end = data.iter().position(|b| *b == 0).unwrap_or(data.len());
+    let name = String::from_utf8_lossy(&data[..end]).to_string();
+    (!name.is_empty()).then_some(name)
+}
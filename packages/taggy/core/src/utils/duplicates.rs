@@ -0,0 +1,156 @@
+use crate::duplicate_policy::DuplicatePolicy;
+use crate::tag::{Tag, TagType};
+use crate::taggy_file::TaggyFile;
+use crate::utils::raw_id3v2::read_raw_frames_from;
+use lofty::{FileType, ItemKey, ItemValue, TaggedFile, TaggedFileExt};
+use std::io::{Read, Seek};
+
+/// The values of a field a tag holds several times, with its key in the tag format.
+struct Duplicate {
+    key: ItemKey,
+    native_key: String,
+    values: Vec<String>,
+}
+
+/// Reads the value of the `policy` for each field the tags of the `file` hold several times,
+/// and adds a warning about it to the `file`.
+///
+/// lofty only keeps the last of the ID3v2 frames having the same ID, so they're read again from
+/// the `reader` of the file, the other tags hold all their items.
+pub(crate) fn resolve_duplicates<R: Read + Seek>(
+    file: &mut TaggyFile,
+    tagged: &TaggedFile,
+    reader: &mut R,
+    policy: DuplicatePolicy,
+) {
+    for tag in file.tags.iter_mut() {
+        let duplicates = match tag.tag_type {
+            TagType::Id3v2 => id3v2_duplicates(reader, tagged.file_type()),
+            tag_type => tagged
+                .tags()
+                .iter()
+                .find(|t| TagType::from(t.tag_type()) == tag_type)
+                .map(item_duplicates)
+                .unwrap_or_default(),
+        };
+        for duplicate in duplicates {
+            if let Some(warning) = resolve(tag, &duplicate, policy) {
+                file.warnings.push(warning);
+            }
+        }
+    }
+}
+
+/// Sets the field of the `duplicate` in the `tag` to the value of the `policy`,
+/// returns the warning about it, `None` when the `tag` has no such field, e.g. for the
+/// artists, which are several by design.
+fn resolve(tag: &mut Tag, duplicate: &Duplicate, policy: DuplicatePolicy) -> Option<String> {
+    let tag_type = tag.tag_type;
+    let (field, value) = field_mut(tag, &duplicate.key)?;
+    let values = &duplicate.values;
+    let (read, resolution) = match policy {
+        DuplicatePolicy::First => (values[0].clone(), "the first one is read".to_string()),
+        DuplicatePolicy::Last => (
+            values[values.len() - 1].clone(),
+            "the last one is read".to_string(),
+        ),
+        DuplicatePolicy::Concatenate => (values.join("; "), "they're read joined".to_string()),
+        DuplicatePolicy::ReportAll => (
+            values[0].clone(),
+            format!(
+                "the first one is read: {}",
+                values
+                    .iter()
+                    .map(|v| format!("'{v}'"))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ),
+        ),
+    };
+    *value = Some(read);
+    Some(format!(
+        "The {tag_type:?} tag holds {} '{}' values for the {field}, {resolution}",
+        values.len(),
+        duplicate.native_key,
+    ))
+}
+
+/// The text frames the ID3v2 tag of the file at `path` holds several times, none when it
+/// can't be read.
+fn id3v2_duplicates<R: Read + Seek>(reader: &mut R, file_type: FileType) -> Vec<Duplicate> {
+    let Ok(Some(frames)) = read_raw_frames_from(reader, file_type) else {
+        return vec![];
+    };
+    let values = frames.iter().filter_map(|frame| {
+        let text = frame.text()?;
+        let key = ItemKey::from_key(lofty::TagType::Id3v2, &frame.id);
+        Some((key, frame.id.clone(), text))
+    });
+    duplicates_of(values)
+}
+
+/// The text items the `tag` holds several times.
+fn item_duplicates(tag: &lofty::Tag) -> Vec<Duplicate> {
+    let values = tag.items().filter_map(|item| match item.value() {
+        ItemValue::Text(text) => {
+            let native_key = item.key().map_key(tag.tag_type(), true)?.to_string();
+            Some((item.key().clone(), native_key, text.clone()))
+        }
+        _ => None,
+    });
+    duplicates_of(values)
+}
+
+/// Groups the `values` by key, in their order, and keeps the keys having several
+/// non-empty values.
+fn duplicates_of(values: impl Iterator<Item = (ItemKey, String, String)>) -> Vec<Duplicate> {
+    let mut groups: Vec<Duplicate> = vec![];
+    for (key, native_key, value) in values {
+        if value.is_empty() {
+            continue;
+        }
+        match groups.iter_mut().find(|d| d.key == key) {
+            Some(duplicate) => duplicate.values.push(value),
+            None => groups.push(Duplicate {
+                key,
+                native_key,
+                values: vec![value],
+            }),
+        }
+    }
+    groups.retain(|d| d.values.len() > 1);
+    groups
+}
+
+/// The name of the single value text field of the `tag` read from the `key`, and the field.
+///
+/// The fields read from several keys, e.g. the grouping, or derived from others,
+/// e.g. the release date, aren't concerned.
+fn field_mut<'a>(
+    tag: &'a mut Tag,
+    key: &ItemKey,
+) -> Option<(&'static str, &'a mut Option<String>)> {
+    Some(match key {
+        ItemKey::TrackTitle => ("track_title", &mut tag.track_title),
+        ItemKey::AlbumTitle => ("album", &mut tag.album),
+        ItemKey::AlbumArtist => ("album_artist", &mut tag.album_artist),
+        ItemKey::Producer => ("producer", &mut tag.producer),
+        ItemKey::Language => ("language", &mut tag.language),
+        ItemKey::Isrc => ("isrc", &mut tag.isrc),
+        ItemKey::Conductor => ("conductor", &mut tag.conductor),
+        ItemKey::Remixer => ("remixer", &mut tag.remixer),
+        ItemKey::TrackArtistSortOrder => ("artist_sort", &mut tag.artist_sort),
+        ItemKey::AlbumTitleSortOrder => ("album_sort", &mut tag.album_sort),
+        ItemKey::TrackTitleSortOrder => ("title_sort", &mut tag.title_sort),
+        ItemKey::AlbumArtistSortOrder => ("album_artist_sort", &mut tag.album_artist_sort),
+        ItemKey::InitialKey => ("initial_key", &mut tag.initial_key),
+        ItemKey::Mood => ("mood", &mut tag.mood),
+        ItemKey::Label => ("label", &mut tag.label),
+        ItemKey::CopyrightMessage => ("copyright", &mut tag.copyright),
+        ItemKey::TrackSubtitle => ("subtitle", &mut tag.subtitle),
+        ItemKey::Work => ("work", &mut tag.work),
+        ItemKey::Movement => ("movement", &mut tag.movement),
+        ItemKey::PodcastGlobalUniqueID => ("episode_id", &mut tag.episode_id),
+        _ => return None,
+    })
+}
@@ -0,0 +1,366 @@
+use crate::utils::file_id::probe_file_type;
+use crate::utils::read_only::open_read_only;
+use anyhow::anyhow;
+use lofty::{AudioFile, FileType, ParseOptions, Probe};
+use std::io::{BufReader, Read, Seek, SeekFrom};
+
+/// How far to look for the first MPEG frame after the ID3v2 tag (if any).
+const MAX_FRAME_SYNC_SCAN: u64 = 64 * 1024;
+
+const MPEG1_BITRATES: [[u32; 15]; 3] = [
+    // Layer I
+    [
+        0, 32, 64, 96, 128, 160, 192, 224, 256, 288, 320, 352, 384, 416, 448,
+    ],
+    // Layer II
+    [
+        0, 32, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320, 384,
+    ],
+    // Layer III
+    [
+        0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+    ],
+];
+const MPEG2_BITRATES: [[u32; 15]; 2] = [
+    // Layer I
+    [
+        0, 32, 48, 56, 64, 80, 96, 112, 128, 144, 160, 176, 192, 224, 256,
+    ],
+    // Layer II & III
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160],
+];
+
+/// Returns the duration in microseconds of the file at given `path`.
+///
+/// For MPEG (Xing/VBRI/CBR), FLAC (STREAMINFO) and MP4 (mvhd) only the headers are read,
+/// other formats fall back to lofty's properties reading, as do the files whose headers
+/// can't be read.
+pub(crate) fn read_duration_micros(path: &str) -> anyhow::Result<u64> {
+    let file_type = probe_file_type(path)?;
+    let file = open_read_only(path)?;
+    let file_len = file.metadata()?.len();
+    let mut reader = BufReader::new(file);
+
+    if let Some(duration) = duration_from_headers(&mut reader, file_type, file_len)? {
+        return Ok(duration);
+    }
+    // not a format we know how to shortcut, let lofty figure it out.
+    reader.seek(SeekFrom::Start(0))?;
+    let mut probe = Probe::new(reader).options(ParseOptions::new());
+    probe.set_file_type(file_type);
+    let tagged = probe.read().map_err(|e| anyhow!(e))?;
+    Ok(tagged.properties().duration().as_micros() as u64)
+}
+
+/// Reads the duration from the headers of the `file_type`, the MPEG frames are only looked
+/// for in the MPEG files since any binary data may hold a frame sync.
+fn duration_from_headers<R: Read + Seek>(
+    reader: &mut R,
+    file_type: FileType,
+    file_len: u64,
+) -> anyhow::Result<Option<u64>> {
+    let mut ident = [0u8; 8];
+    if reader.read_exact(&mut ident).is_err() {
+        return Ok(None);
+    }
+    match file_type {
+        FileType::Mp4 => {
+            reader.seek(SeekFrom::Start(0))?;
+            mp4_duration(reader, file_len)
+        }
+        FileType::Flac => {
+            let audio_start = skip_id3v2(reader, &ident)?;
+            reader.seek(SeekFrom::Start(audio_start))?;
+            let mut magic = [0u8; 4];
+            if reader.read_exact(&mut magic).is_err() || &magic != b"fLaC" {
+                return Ok(None);
+            }
+            flac_duration(reader)
+        }
+        FileType::Mpeg => {
+            let audio_start = skip_id3v2(reader, &ident)?;
+            let audio_end = file_len.saturating_sub(trailing_tags_len(reader, file_len)?);
+            reader.seek(SeekFrom::Start(audio_start))?;
+            mpeg_duration(reader, audio_start, audio_end)
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the offset right after the ID3v2 tag, or `0` if there's none.
+pub(crate) fn skip_id3v2<R: Read + Seek>(reader: &mut R, ident: &[u8; 8]) -> anyhow::Result<u64> {
+    if &ident[0..3] != b"ID3" {
+        return Ok(0);
+    }
+    reader.seek(SeekFrom::Start(5))?;
+    let mut header = [0u8; 5];
+    reader.read_exact(&mut header)?;
+    let flags = header[0];
+    // the size is stored as a synchsafe integer.
+    let size = header[1..5]
+        .iter()
+        .fold(0u64, |acc, b| (acc << 7) | (*b as u64 & 0x7F));
+    let footer = if flags & 0x10 != 0 { 10 } else { 0 };
+    Ok(10 + size + footer)
+}
+
+/// Returns the size of the ID3v1 and APE tags at the end of the file, which aren't audio.
+fn trailing_tags_len<R: Read + Seek>(reader: &mut R, file_len: u64) -> anyhow::Result<u64> {
+    let mut len = 0;
+    if file_len >= 128 {
+        reader.seek(SeekFrom::Start(file_len - 128))?;
+        let mut ident = [0u8; 3];
+        reader.read_exact(&mut ident)?;
+        if &ident == b"TAG" {
+            len = 128;
+        }
+    }
+    if file_len >= len + 32 {
+        reader.seek(SeekFrom::Start(file_len - len - 32))?;
+        let mut footer = [0u8; 32];
+        reader.read_exact(&mut footer)?;
+        if &footer[0..8] == b"APETAGEX" {
+            // the size includes the footer but not the optional header.
+            let size = u32::from_le_bytes(footer[12..16].try_into()?) as u64;
+            let flags = u32::from_le_bytes(footer[20..24].try_into()?);
+            let header = if flags & 0x8000_0000 != 0 { 32 } else { 0 };
+            len += size + header;
+        }
+    }
+    Ok(len.min(file_len))
+}
+
+/// Reads the total samples count from the STREAMINFO block, which is always the first one.
+fn flac_duration<R: Read>(reader: &mut R) -> anyhow::Result<Option<u64>> {
+    let mut block_header = [0u8; 4];
+    reader.read_exact(&mut block_header)?;
+    if block_header[0] & 0x7F != 0 {
+        return Ok(None);
+    }
+    let mut info = [0u8; 18];
+    reader.read_exact(&mut info)?;
+    let sample_rate =
+        ((info[10] as u64) << 12) | ((info[11] as u64) << 4) | ((info[12] as u64) >> 4);
+    let total_samples = ((info[13] as u64 & 0x0F) << 32)
+        | ((info[14] as u64) << 24)
+        | ((info[15] as u64) << 16)
+        | ((info[16] as u64) << 8)
+        | info[17] as u64;
+    if sample_rate == 0 || total_samples == 0 {
+        return Ok(None);
+    }
+    Ok(Some(total_samples * 1_000_000 / sample_rate))
+}
+
+/// Walks the top level atoms until `moov.mvhd` is found.
+fn mp4_duration<R: Read + Seek>(reader: &mut R, file_len: u64) -> anyhow::Result<Option<u64>> {
+    let Some((moov_start, moov_len)) = find_atom(reader, 0, file_len, b"moov")? else {
+        return Ok(None);
+    };
+    let Some((mvhd_start, _)) = find_atom(reader, moov_start, moov_start + moov_len, b"mvhd")?
+    else {
+        return Ok(None);
+    };
+    reader.seek(SeekFrom::Start(mvhd_start))?;
+    let mut version = [0u8; 4];
+    reader.read_exact(&mut version)?;
+    let (timescale, duration) = if version[0] == 1 {
+        let mut buf = [0u8; 28];
+        reader.read_exact(&mut buf)?;
+        (
+            u32::from_be_bytes(buf[16..20].try_into()?) as u64,
+            u64::from_be_bytes(buf[20..28].try_into()?),
+        )
+    } else {
+        let mut buf = [0u8; 16];
+        reader.read_exact(&mut buf)?;
+        (
+            u32::from_be_bytes(buf[8..12].try_into()?) as u64,
+            u32::from_be_bytes(buf[12..16].try_into()?) as u64,
+        )
+    };
+    if timescale == 0 {
+        return Ok(None);
+    }
+    Ok(Some(duration * 1_000_000 / timescale))
+}
+
+/// Looks for an atom named `name` between `start` and `end`.
+///
+/// Returns the position of the atom's content and its length.
+fn find_atom<R: Read + Seek>(
+    reader: &mut R,
+    start: u64,
+    end: u64,
+    name: &[u8; 4],
+) -> anyhow::Result<Option<(u64, u64)>> {
+    let mut pos = start;
+    while pos + 8 <= end {
+        reader.seek(SeekFrom::Start(pos))?;
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header)?;
+        let mut size = u32::from_be_bytes(header[0..4].try_into()?) as u64;
+        let mut header_len = 8;
+        if size == 1 {
+            let mut extended = [0u8; 8];
+            reader.read_exact(&mut extended)?;
+            size = u64::from_be_bytes(extended);
+            header_len = 16;
+        } else if size == 0 {
+            // the atom extends to the end of the file
+            size = end - pos;
+        }
+        if size < header_len {
+            return Ok(None);
+        }
+        if &header[4..8] == name {
+            return Ok(Some((pos + header_len, size - header_len)));
+        }
+        pos += size;
+    }
+    Ok(None)
+}
+
+/// Finds the first MPEG frame and reads the frames count from its Xing/Info or VBRI header,
+/// or estimates the duration from the bitrate of the audio between `audio_start` and
+/// `audio_end` for CBR files.
+fn mpeg_duration<R: Read + Seek>(
+    reader: &mut R,
+    audio_start: u64,
+    audio_end: u64,
+) -> anyhow::Result<Option<u64>> {
+    let mut scan = Vec::new();
+    reader
+        .by_ref()
+        .take(MAX_FRAME_SYNC_SCAN)
+        .read_to_end(&mut scan)?;
+
+    // a frame sync alone may be part of the junk before the audio, so the header is only
+    // trusted when the next frame follows it.
+    let Some((offset, header)) = (0..scan.len().saturating_sub(4)).find_map(|i| {
+        let header = MpegHeader::parse(&scan[i..i + 4])?;
+        let next = i + header.frame_len(&scan[i..i + 4]);
+        if next + 4 <= scan.len() {
+            let next_header = MpegHeader::parse(&scan[next..next + 4])?;
+            if !header.is_followed_by(&next_header) {
+                return None;
+            }
+        }
+        Some((i, header))
+    }) else {
+        return Ok(None);
+    };
+
+    let side_info_len = match (header.is_mpeg1(), header.is_mono) {
+        (true, true) => 17,
+        (true, false) => 32,
+        (false, true) => 9,
+        (false, false) => 17,
+    };
+    let xing_pos = offset + 4 + side_info_len;
+    let vbri_pos = offset + 4 + 32;
+
+    let frames = if scan.len() >= xing_pos + 12
+        && (&scan[xing_pos..xing_pos + 4] == b"Xing" || &scan[xing_pos..xing_pos + 4] == b"Info")
+    {
+        let flags = u32::from_be_bytes(scan[xing_pos + 4..xing_pos + 8].try_into()?);
+        (flags & 0x1 != 0)
+            .then(|| u32::from_be_bytes(scan[xing_pos + 8..xing_pos + 12].try_into().unwrap()))
+    } else if scan.len() >= vbri_pos + 18 && &scan[vbri_pos..vbri_pos + 4] == b"VBRI" {
+        Some(u32::from_be_bytes(
+            scan[vbri_pos + 14..vbri_pos + 18].try_into()?,
+        ))
+    } else {
+        None
+    };
+
+    let duration = match frames {
+        Some(frames) => {
+            frames as u64 * header.samples_per_frame() as u64 * 1_000_000
+                / header.sample_rate as u64
+        }
+        None => {
+            let audio_len = audio_end.saturating_sub(audio_start + offset as u64);
+            audio_len * 8 * 1_000 / header.bitrate_kbps as u64
+        }
+    };
+    Ok(Some(duration))
+}
+
+struct MpegHeader {
+    /// `3` for MPEG-1, `2` for MPEG-2 and `0` for MPEG-2.5
+    version: u8,
+    /// `1`, `2` or `3`
+    layer: u8,
+    bitrate_kbps: u32,
+    sample_rate: u32,
+    is_mono: bool,
+}
+
+impl MpegHeader {
+    fn parse(bytes: &[u8]) -> Option<Self> {
+        if bytes[0] != 0xFF || bytes[1] & 0xE0 != 0xE0 {
+            return None;
+        }
+        let version = (bytes[1] >> 3) & 0x3;
+        let layer = match (bytes[1] >> 1) & 0x3 {
+            0b01 => 3,
+            0b10 => 2,
+            0b11 => 1,
+            _ => return None,
+        };
+        let bitrate_index = (bytes[2] >> 4) as usize;
+        let sample_rate_index = ((bytes[2] >> 2) & 0x3) as usize;
+        if version == 1 || bitrate_index == 0 || bitrate_index == 15 || sample_rate_index == 3 {
+            return None;
+        }
+        let bitrate_kbps = match version {
+            3 => MPEG1_BITRATES[layer as usize - 1][bitrate_index],
+            _ if layer == 1 => MPEG2_BITRATES[0][bitrate_index],
+            _ => MPEG2_BITRATES[1][bitrate_index],
+        };
+        let sample_rate = match version {
+            3 => [44100, 48000, 32000][sample_rate_index],
+            2 => [22050, 24000, 16000][sample_rate_index],
+            _ => [11025, 12000, 8000][sample_rate_index],
+        };
+        Some(Self {
+            version,
+            layer,
+            bitrate_kbps,
+            sample_rate,
+            is_mono: bytes[3] >> 6 == 0b11,
+        })
+    }
+
+    fn is_mpeg1(&self) -> bool {
+        self.version == 3
+    }
+
+    /// Returns the length in bytes of the frame starting with the header `bytes`.
+    fn frame_len(&self, bytes: &[u8]) -> usize {
+        let padding = ((bytes[2] >> 1) & 0x1) as u32;
+        let len = if self.layer == 1 {
+            (12 * self.bitrate_kbps * 1000 / self.sample_rate + padding) * 4
+        } else {
+            self.samples_per_frame() / 8 * self.bitrate_kbps * 1000 / self.sample_rate + padding
+        };
+        len as usize
+    }
+
+    /// Whether `next` may be the header of the frame after this one.
+    fn is_followed_by(&self, next: &MpegHeader) -> bool {
+        self.version == next.version
+            && self.layer == next.layer
+            && self.sample_rate == next.sample_rate
+    }
+
+    fn samples_per_frame(&self) -> u32 {
+        match (self.layer, self.is_mpeg1()) {
+            (1, _) => 384,
+            (2, _) => 1152,
+            (_, true) => 1152,
+            (_, false) => 576,
+        }
+    }
+}
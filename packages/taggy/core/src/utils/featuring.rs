@@ -0,0 +1,81 @@
+use crate::featuring::FeaturingStyle;
+
+/// The words introducing the featured artists, longest first so `feat.` isn't read as `feat`.
+const MARKERS: [&str; 5] = ["featuring", "feat.", "feat", "ft.", "ft"];
+
+/// Splits `value` around its featured artists, e.g. `Song (feat. X) - Remix`
+/// into `Song - Remix` and `X`, or `A ft. X` into `A` and `X`.
+///
+/// The marker must be a whole word after something else, so `Feat. X` alone isn't split.
+pub(crate) fn split_featuring(value: &str) -> Option<(String, String)> {
+    for (index, _) in value.char_indices().filter(|(i, _)| *i > 0) {
+        let before = &value[..index];
+        let closing = match before.chars().last() {
+            Some('(') => Some(')'),
+            Some('[') => Some(']'),
+            Some(' ') => None,
+            _ => continue,
+        };
+        let Some(marker) = MARKERS.iter().find(|marker| {
+            value
+                .get(index..index + marker.len())
+                .is_some_and(|word| word.eq_ignore_ascii_case(marker))
+        }) else {
+            continue;
+        };
+        let rest = &value[index + marker.len()..];
+        if !rest.starts_with(' ') {
+            continue;
+        }
+        let (featured, after) = match closing.and_then(|c| rest.find(c)) {
+            Some(end) => (&rest[..end], &rest[end + 1..]),
+            None => (rest, ""),
+        };
+        let before = match closing {
+            Some(_) => &before[..before.len() - 1],
+            None => before,
+        };
+        let base = [before.trim(), after.trim()]
+            .into_iter()
+            .filter(|part| !part.is_empty())
+            .collect::<Vec<_>>()
+            .join(" ");
+        let featured = featured.trim();
+        if !base.is_empty() && !featured.is_empty() {
+            return Some((base, featured.to_string()));
+        }
+    }
+    None
+}
+
+/// Moves the featured artists between the `title` and the `artist` according to the `style`,
+/// e.g. `Song` by `A feat. X` becomes `Song (feat. X)` by `A` in the title.
+///
+/// Returns the new title and artist, or `None` when there's nothing to move. When both
+/// already credit the featured artists, only the credit in the wrong place is removed.
+pub(crate) fn relocate_featuring(
+    title: &str,
+    artist: &str,
+    style: FeaturingStyle,
+) -> Option<(String, String)> {
+    match style {
+        FeaturingStyle::InTitle => {
+            let (artist, featured) = split_featuring(artist)?;
+            let title = if split_featuring(title).is_some() {
+                title.to_string()
+            } else {
+                format!("{title} (feat. {featured})")
+            };
+            Some((title, artist))
+        }
+        FeaturingStyle::InArtist => {
+            let (title, featured) = split_featuring(title)?;
+            let artist = if split_featuring(artist).is_some() {
+                artist.to_string()
+            } else {
+                format!("{artist} feat. {featured}")
+            };
+            Some((title, artist))
+        }
+    }
+}
@@ -0,0 +1,106 @@
+use crate::error::TaggyError;
+use crate::utils::read_only::{open_read_only, probe_read_only};
+use anyhow::anyhow;
+use lofty::id3::v2::Id3v2Tag;
+use lofty::iff::aiff::AiffFile;
+use lofty::iff::wav::WavFile;
+use lofty::mpeg::MpegFile;
+use lofty::{
+    AudioFile, BoundTaggedFile, FileType, ItemKey, ParseOptions, TagExt, TagType, TaggedFileExt,
+};
+use rand::RngCore;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom};
+
+/// Generates a random (version 4) UUID, e.g. `0b9e9e50-5bd2-4b4f-9a53-1f5c9f7a1c3e`.
+///
+/// The 122 random bits come from a cryptographically secure generator,
+/// so collisions are practically impossible even across large libraries.
+pub(crate) fn generate_uuid() -> String {
+    let mut bytes = [0u8; 16];
+    rand::thread_rng().fill_bytes(&mut bytes);
+    // set the version (4) and the variant (RFC 4122) bits.
+    bytes[6] = (bytes[6] & 0x0f) | 0x40;
+    bytes[8] = (bytes[8] & 0x3f) | 0x80;
+    let hex: String = bytes.iter().map(|b| format!("{b:02x}")).collect();
+    format!(
+        "{}-{}-{}-{}-{}",
+        &hex[0..8],
+        &hex[8..12],
+        &hex[12..16],
+        &hex[16..20],
+        &hex[20..32]
+    )
+}
+
+/// Reads the identifier stored by the application `scope` in the file at `path`.
+///
+/// ID3v2 tags store it in a `TXXX` frame described by `scope`, MP4 files in a
+/// `----:com.apple.iTunes:<scope>` freeform atom and the others in a `<scope>` item.
+pub(crate) fn read_file_id(path: &str, scope: &str) -> anyhow::Result<Option<String>> {
+    let file_type = probe_file_type(path)?;
+    if file_type.primary_tag_type() == TagType::Id3v2 {
+        let mut file = open_read_only(path)?;
+        let tag = read_id3v2(&mut file, file_type)?;
+        return Ok(tag.and_then(|t| t.get_user_text(scope).map(str::to_string)));
+    }
+    let tagged = probe_read_only(path)?.read()?;
+    let key = item_key(file_type.primary_tag_type(), scope);
+    Ok(tagged
+        .primary_tag()
+        .and_then(|t| t.get_string(&key))
+        .map(str::to_string))
+}
+
+/// Stores the `id` of the application `scope` in the file at `path`, see [read_file_id].
+pub(crate) fn write_file_id(path: &str, scope: &str, id: &str) -> anyhow::Result<()> {
+    let file_type = probe_file_type(path)?;
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    if file_type.primary_tag_type() == TagType::Id3v2 {
+        let mut tag = read_id3v2(&mut file, file_type)?.unwrap_or_default();
+        tag.insert_user_text(scope.to_string(), id.to_string());
+        file.seek(SeekFrom::Start(0))?;
+        return tag.save_to(&mut file).map_err(|e| anyhow!(e));
+    }
+    let mut tagged = BoundTaggedFile::read_from(file, ParseOptions::new())?;
+    let tag_type = tagged.primary_tag_type();
+    if tagged.primary_tag().is_none() {
+        tagged.insert_tag(lofty::Tag::new(tag_type));
+    }
+    if let Some(tag) = tagged.primary_tag_mut() {
+        tag.insert_text(item_key(tag_type, scope), id.to_string());
+    }
+    tagged.save().map_err(|e| anyhow!(e))
+}
+
+pub(crate) fn probe_file_type(path: &str) -> anyhow::Result<FileType> {
+    let probe = probe_read_only(path)
+        .map_err(|_| TaggyError::FileNotFound)?
+        .guess_file_type()?;
+    probe
+        .file_type()
+        .ok_or_else(|| TaggyError::UnsupportedFormat.into())
+}
+
+/// Reads the ID3v2 tag of the formats having it as their primary tag, from the start of the
+/// `reader`.
+pub(crate) fn read_id3v2<R: Read + Seek>(
+    reader: &mut R,
+    file_type: FileType,
+) -> anyhow::Result<Option<Id3v2Tag>> {
+    reader.rewind()?;
+    let options = ParseOptions::new().read_properties(false);
+    Ok(match file_type {
+        FileType::Mpeg => MpegFile::read_from(reader, options)?.id3v2().cloned(),
+        FileType::Aiff => AiffFile::read_from(reader, options)?.id3v2().cloned(),
+        FileType::Wav => WavFile::read_from(reader, options)?.id3v2().cloned(),
+        _ => None,
+    })
+}
+
+fn item_key(tag_type: TagType, scope: &str) -> ItemKey {
+    match tag_type {
+        TagType::Mp4Ilst => ItemKey::Unknown(format!("----:com.apple.iTunes:{scope}")),
+        _ => ItemKey::Unknown(scope.to_string()),
+    }
+}
@@ -0,0 +1,72 @@
+use crate::utils::file_id::generate_uuid;
+use std::fs::{self, File};
+use std::io::{self, Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub fn get_file_size(path: &String) -> Option<u64> {
+    match fs::metadata(Path::new(&path)) {
+        Ok(meta) => Some(meta.len()),
+        Err(_) => None,
+    }
+}
+
+/// The absolute path of the file at `path` with all the symbolic links resolved.
+pub fn get_canonical_path(path: &String) -> Option<String> {
+    fs::canonicalize(Path::new(&path))
+        .ok()
+        .map(|p| p.to_string_lossy().to_string())
+}
+
+/// The creation and last modification times of the file at `path`, in milliseconds since
+/// the Unix epoch, each being `None` when the file system doesn't record it.
+pub fn get_file_times(path: &String) -> (Option<i64>, Option<i64>) {
+    match fs::metadata(Path::new(&path)) {
+        Ok(meta) => (unix_ms(meta.created()), unix_ms(meta.modified())),
+        Err(_) => (None, None),
+    }
+}
+
+fn unix_ms(time: io::Result<SystemTime>) -> Option<i64> {
+    match time.ok()?.duration_since(UNIX_EPOCH) {
+        Ok(since) => Some(since.as_millis() as i64),
+        Err(before) => Some(-(before.duration().as_millis() as i64)),
+    }
+}
+
+/// Replaces the bytes from `start` to `end` of the file at `path` with the `bytes`, through a
+/// copy next to it which replaces it once `finish` succeeded on it, so a failure leaves the file
+/// as it was.
+///
+/// `finish` gets the path of the copy, e.g. to patch or check it.
+pub(crate) fn splice_file(
+    path: &str,
+    start: u64,
+    end: u64,
+    bytes: &[u8],
+    finish: impl FnOnce(&str) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let path = Path::new(path);
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let copy_path = path.with_file_name(format!(".{file_name}.taggy-{}", generate_uuid()));
+    let result = (|| -> anyhow::Result<()> {
+        let mut file = File::open(path)?;
+        let mut copy = File::create(&copy_path)?;
+        io::copy(&mut (&mut file).take(start), &mut copy)?;
+        copy.write_all(bytes)?;
+        file.seek(SeekFrom::Start(end))?;
+        io::copy(&mut file, &mut copy)?;
+        copy.sync_all()?;
+        let permissions = file.metadata()?.permissions();
+        // some platforms can't replace the files which are open.
+        drop((file, copy));
+        finish(&copy_path.to_string_lossy())?;
+        fs::set_permissions(&copy_path, permissions)?;
+        fs::rename(&copy_path, path)?;
+        Ok(())
+    })();
+    if result.is_err() {
+        let _ = fs::remove_file(&copy_path);
+    }
+    result
+}
@@ -0,0 +1,305 @@
+use crate::custom_item::CustomItem;
+use crate::tag::{Tag, TagType};
+use crate::utils::custom_items::MP4_FREEFORM_PREFIX;
+use lofty::id3::v2::{Frame, FrameFlags, FrameValue, Id3v2Tag, UniqueFileIdentifierFrame};
+use lofty::{ItemKey, ItemValue, TagItem};
+
+/// The keys of a [Tag] field which lofty doesn't map for every tag type,
+/// named the way MusicBrainz Picard writes them.
+pub(crate) struct FreeformField {
+    /// The Vorbis comment and APE item, e.g. `MUSICBRAINZ_ALBUMID`.
+    vorbis: &'static str,
+    /// The description of the ID3v2 `TXXX` frame, `None` when stored in another frame.
+    id3v2: Option<&'static str>,
+    /// The MP4 freeform atom, without its `----:com.apple.iTunes:` prefix,
+    /// or the iTunes atom when it starts with `©`, e.g. `©nrt`.
+    mp4: &'static str,
+}
+
+/// Stored in the ID3v2 `UFID` frame of the [MUSICBRAINZ_UFID_OWNER].
+pub(crate) const MUSICBRAINZ_RECORDING_ID: FreeformField = FreeformField {
+    vorbis: "MUSICBRAINZ_TRACKID",
+    id3v2: None,
+    mp4: "MusicBrainz Track Id",
+};
+
+pub(crate) const MUSICBRAINZ_RELEASE_ID: FreeformField = FreeformField {
+    vorbis: "MUSICBRAINZ_ALBUMID",
+    id3v2: Some("MusicBrainz Album Id"),
+    mp4: "MusicBrainz Album Id",
+};
+
+pub(crate) const MUSICBRAINZ_RELEASE_GROUP_ID: FreeformField = FreeformField {
+    vorbis: "MUSICBRAINZ_RELEASEGROUPID",
+    id3v2: Some("MusicBrainz Release Group Id"),
+    mp4: "MusicBrainz Release Group Id",
+};
+
+pub(crate) const MUSICBRAINZ_ARTIST_ID: FreeformField = FreeformField {
+    vorbis: "MUSICBRAINZ_ARTISTID",
+    id3v2: Some("MusicBrainz Artist Id"),
+    mp4: "MusicBrainz Artist Id",
+};
+
+pub(crate) const MUSICBRAINZ_ALBUM_ARTIST_ID: FreeformField = FreeformField {
+    vorbis: "MUSICBRAINZ_ALBUMARTISTID",
+    id3v2: Some("MusicBrainz Album Artist Id"),
+    mp4: "MusicBrainz Album Artist Id",
+};
+
+/// The release track, unlike the `MUSICBRAINZ_TRACKID` Vorbis comment holding the recording.
+pub(crate) const MUSICBRAINZ_TRACK_ID: FreeformField = FreeformField {
+    vorbis: "MUSICBRAINZ_RELEASETRACKID",
+    id3v2: Some("MusicBrainz Release Track Id"),
+    mp4: "MusicBrainz Release Track Id",
+};
+
+pub(crate) const ACOUSTID_ID: FreeformField = FreeformField {
+    vorbis: "ACOUSTID_ID",
+    id3v2: Some("Acoustid Id"),
+    mp4: "Acoustid Id",
+};
+
+pub(crate) const ACOUSTID_FINGERPRINT: FreeformField = FreeformField {
+    vorbis: "ACOUSTID_FINGERPRINT",
+    id3v2: Some("Acoustid Fingerprint"),
+    mp4: "Acoustid Fingerprint",
+};
+
+pub(crate) const BARCODE: FreeformField = FreeformField {
+    vorbis: "BARCODE",
+    id3v2: Some("BARCODE"),
+    mp4: "BARCODE",
+};
+
+pub(crate) const CATALOG_NUMBER: FreeformField = FreeformField {
+    vorbis: "CATALOGNUMBER",
+    id3v2: Some("CATALOGNUMBER"),
+    mp4: "CATALOGNUMBER",
+};
+
+/// Stored in the iTunes `©nrt` atom of MP4 files.
+pub(crate) const NARRATOR: FreeformField = FreeformField {
+    vorbis: "NARRATOR",
+    id3v2: Some("NARRATOR"),
+    mp4: "©nrt",
+};
+
+pub(crate) const SERIES: FreeformField = FreeformField {
+    vorbis: "SERIES",
+    id3v2: Some("SERIES"),
+    mp4: "SERIES",
+};
+
+pub(crate) const SERIES_PART: FreeformField = FreeformField {
+    vorbis: "SERIES-PART",
+    id3v2: Some("SERIES-PART"),
+    mp4: "SERIES-PART",
+};
+
+const FREEFORM_FIELDS: [&FreeformField; 13] = [
+    &MUSICBRAINZ_RECORDING_ID,
+    &MUSICBRAINZ_RELEASE_ID,
+    &MUSICBRAINZ_RELEASE_GROUP_ID,
+    &MUSICBRAINZ_ARTIST_ID,
+    &MUSICBRAINZ_ALBUM_ARTIST_ID,
+    &MUSICBRAINZ_TRACK_ID,
+    &ACOUSTID_ID,
+    &ACOUSTID_FINGERPRINT,
+    &BARCODE,
+    &CATALOG_NUMBER,
+    &NARRATOR,
+    &SERIES,
+    &SERIES_PART,
+];
+
+/// The owner of the ID3v2 `UFID` frame holding the MusicBrainz recording ID.
+const MUSICBRAINZ_UFID_OWNER: &str = "http://musicbrainz.org";
+
+/// The fields of the `tag` stored as [FreeformField]s, with their keys.
+fn freeform_fields(tag: &Tag) -> [(&FreeformField, &Option<String>); 13] {
+    [
+        (&MUSICBRAINZ_RECORDING_ID, &tag.musicbrainz_recording_id),
+        (&MUSICBRAINZ_RELEASE_ID, &tag.musicbrainz_release_id),
+        (
+            &MUSICBRAINZ_RELEASE_GROUP_ID,
+            &tag.musicbrainz_release_group_id,
+        ),
+        (&MUSICBRAINZ_ARTIST_ID, &tag.musicbrainz_artist_id),
+        (
+            &MUSICBRAINZ_ALBUM_ARTIST_ID,
+            &tag.musicbrainz_album_artist_id,
+        ),
+        (&MUSICBRAINZ_TRACK_ID, &tag.musicbrainz_track_id),
+        (&ACOUSTID_ID, &tag.acoustid_id),
+        (&ACOUSTID_FINGERPRINT, &tag.acoustid_fingerprint),
+        (&BARCODE, &tag.barcode),
+        (&CATALOG_NUMBER, &tag.catalog_number),
+        (&NARRATOR, &tag.narrator),
+        (&SERIES, &tag.series),
+        (&SERIES_PART, &tag.series_part),
+    ]
+}
+
+fn freeform_fields_mut(tag: &mut Tag) -> [(&FreeformField, &mut Option<String>); 13] {
+    [
+        (&MUSICBRAINZ_RECORDING_ID, &mut tag.musicbrainz_recording_id),
+        (&MUSICBRAINZ_RELEASE_ID, &mut tag.musicbrainz_release_id),
+        (
+            &MUSICBRAINZ_RELEASE_GROUP_ID,
+            &mut tag.musicbrainz_release_group_id,
+        ),
+        (&MUSICBRAINZ_ARTIST_ID, &mut tag.musicbrainz_artist_id),
+        (
+            &MUSICBRAINZ_ALBUM_ARTIST_ID,
+            &mut tag.musicbrainz_album_artist_id,
+        ),
+        (&MUSICBRAINZ_TRACK_ID, &mut tag.musicbrainz_track_id),
+        (&ACOUSTID_ID, &mut tag.acoustid_id),
+        (&ACOUSTID_FINGERPRINT, &mut tag.acoustid_fingerprint),
+        (&BARCODE, &mut tag.barcode),
+        (&CATALOG_NUMBER, &mut tag.catalog_number),
+        (&NARRATOR, &mut tag.narrator),
+        (&SERIES, &mut tag.series),
+        (&SERIES_PART, &mut tag.series_part),
+    ]
+}
+
+impl FreeformField {
+    /// The key of the field in a tag of `tag_type`, `None` for the ID3v2 tags which store
+    /// it in a frame lofty doesn't keep in its generic tags, see [add_id3v2_freeform_fields].
+    fn key(&self, tag_type: lofty::TagType) -> Option<String> {
+        match tag_type {
+            lofty::TagType::VorbisComments | lofty::TagType::Ape => Some(self.vorbis.to_string()),
+            lofty::TagType::Mp4Ilst if self.mp4.starts_with('©') => Some(self.mp4.to_string()),
+            lofty::TagType::Mp4Ilst => Some(format!("{MP4_FREEFORM_PREFIX}{}", self.mp4)),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the `field` of the `tag`.
+///
+/// lofty maps some of the keys to its own, e.g. `MUSICBRAINZ_ALBUMID` to
+/// [ItemKey::MusicBrainzReleaseId], and keeps the others as unknown ones.
+pub(crate) fn freeform_value(tag: &lofty::Tag, field: &FreeformField) -> Option<String> {
+    let key = field.key(tag.tag_type())?;
+    if let Some(value) = tag.get_string(&ItemKey::from_key(tag.tag_type(), &key)) {
+        return Some(value.to_string());
+    }
+    tag.items()
+        .find_map(|item| match (item.key(), item.value()) {
+            (ItemKey::Unknown(k), ItemValue::Text(value)) if k.eq_ignore_ascii_case(&key) => {
+                Some(value.to_string())
+            }
+            _ => None,
+        })
+}
+
+/// Appends the [FreeformField]s of `fields` to the `tag`, see [freeform_value].
+///
+/// The ID3v2 ones are written separately by
+/// [completed_id3v2](crate::utils::id3v2_frames::completed_id3v2).
+pub(crate) fn push_freeform_fields(tag: &mut lofty::Tag, fields: &Tag) {
+    for (field, value) in freeform_fields(fields) {
+        let (Some(key), Some(value)) = (field.key(tag.tag_type()), value) else {
+            continue;
+        };
+        tag.push_unchecked(TagItem::new(
+            ItemKey::from_key(tag.tag_type(), &key),
+            ItemValue::Text(value.clone()),
+        ));
+    }
+}
+
+/// Whether the unknown `key` of a tag of `tag_type` holds a [FreeformField],
+/// so it isn't read as a custom item too.
+pub(crate) fn is_freeform_key(tag_type: lofty::TagType, key: &str) -> bool {
+    FREEFORM_FIELDS.iter().any(|field| {
+        field
+            .key(tag_type)
+            .is_some_and(|k| k.eq_ignore_ascii_case(key))
+    })
+}
+
+/// Whether the ID3v2 `TXXX` frame of the `description` holds a [FreeformField].
+pub(crate) fn is_freeform_description(description: &str) -> bool {
+    FREEFORM_FIELDS
+        .iter()
+        .filter_map(|field| field.id3v2)
+        .any(|d| d.eq_ignore_ascii_case(description))
+}
+
+/// Sets the [FreeformField]s of the `tag` from the `TXXX` and `UFID` frames of the `id3v2`
+/// tag, see [read_id3v2_frames](crate::utils::id3v2_frames::read_id3v2_frames).
+pub(crate) fn add_id3v2_freeform_fields(tag: &mut Tag, id3v2: &Id3v2Tag) {
+    let recording_id = musicbrainz_ufid(id3v2);
+    for (field, value) in freeform_fields_mut(tag) {
+        if value.is_some() {
+            continue;
+        }
+        *value = match field.id3v2 {
+            Some(description) => id3v2.into_iter().find_map(|frame| match frame.content() {
+                FrameValue::UserText(text)
+                    if text.description.eq_ignore_ascii_case(description) =>
+                {
+                    Some(text.content.clone())
+                }
+                _ => None,
+            }),
+            None => recording_id.clone(),
+        };
+    }
+}
+
+/// The MusicBrainz recording ID of the `UFID` frame of the `id3v2` tag.
+fn musicbrainz_ufid(id3v2: &Id3v2Tag) -> Option<String> {
+    id3v2.into_iter().find_map(|frame| match frame.content() {
+        FrameValue::UniqueFileIdentifier(ufid) if ufid.owner == MUSICBRAINZ_UFID_OWNER => {
+            Some(String::from_utf8_lossy(&ufid.identifier).to_string())
+        }
+        _ => None,
+    })
+}
+
+/// The [FreeformField]s of the ID3v2 tags among `tags` stored as `TXXX` frames,
+/// to write along with their custom items.
+pub(crate) fn id3v2_freeform_items(tags: &[Tag]) -> Vec<CustomItem> {
+    tags.iter()
+        .filter(|t| t.tag_type == TagType::Id3v2)
+        .flat_map(|t| {
+            freeform_fields(t)
+                .into_iter()
+                .filter_map(|(field, value)| Some(CustomItem::new(field.id3v2?, value.as_ref()?)))
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+/// The MusicBrainz recording ID of the ID3v2 tags among `tags`, to write with
+/// [insert_musicbrainz_ufid].
+pub(crate) fn id3v2_recording_id(tags: &[Tag]) -> Option<String> {
+    tags.iter()
+        .filter(|t| t.tag_type == TagType::Id3v2)
+        .find_map(|t| t.musicbrainz_recording_id.clone())
+}
+
+/// Replaces the `UFID` frame of the `id3v2` tag with the MusicBrainz `recording_id`.
+pub(crate) fn insert_musicbrainz_ufid(
+    id3v2: &mut Id3v2Tag,
+    recording_id: &str,
+) -> anyhow::Result<()> {
+    id3v2.retain(|frame| {
+        !matches!(frame.content(),
+            FrameValue::UniqueFileIdentifier(ufid) if ufid.owner == MUSICBRAINZ_UFID_OWNER)
+    });
+    id3v2.insert(Frame::new(
+        "UFID",
+        UniqueFileIdentifierFrame {
+            owner: MUSICBRAINZ_UFID_OWNER.to_string(),
+            identifier: recording_id.as_bytes().to_vec(),
+        },
+        FrameFlags::default(),
+    )?);
+    Ok(())
+}
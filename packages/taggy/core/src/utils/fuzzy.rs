@@ -0,0 +1,75 @@
+use crate::field_value_count::FieldValueCount;
+use crate::merge_suggestion::MergeSuggestion;
+
+/// Groups the near-duplicates of the given `values`, e.g. `Hip Hop`, `Hip-Hop` and `hiphop`.
+///
+/// Two values are near-duplicates when their [comparison keys](comparison_key) are the same,
+/// or close enough by edit distance: one edit for every 5 characters, so short values
+/// like `Pop` and `Rap` are never grouped. Each value is compared with the canonical value of
+/// the groups only, the most common values first, so groups don't chain into unrelated values.
+///
+/// Only the groups with more than one value are returned, the most common first.
+pub(crate) fn group_near_duplicates(values: &[FieldValueCount]) -> Vec<MergeSuggestion> {
+    let mut values: Vec<&FieldValueCount> = values.iter().collect();
+    values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+
+    let mut groups: Vec<(Vec<char>, MergeSuggestion)> = vec![];
+    for value in values {
+        let key = comparison_key(&value.value);
+        let group = groups.iter_mut().find(|(canonical_key, _)| {
+            let max_distance = canonical_key.len().max(key.len()) / 5;
+            edit_distance(canonical_key, &key) <= max_distance
+        });
+        match group {
+            Some((_, suggestion)) => {
+                suggestion.variants.push(value.value.clone());
+                suggestion.count += value.count;
+            }
+            None => groups.push((
+                key,
+                MergeSuggestion {
+                    canonical: value.value.clone(),
+                    variants: vec![],
+                    count: value.count,
+                },
+            )),
+        }
+    }
+
+    let mut suggestions: Vec<MergeSuggestion> = groups
+        .into_iter()
+        .map(|(_, suggestion)| suggestion)
+        .filter(|suggestion| !suggestion.variants.is_empty())
+        .collect();
+    suggestions.sort_by(|a, b| {
+        b.count
+            .cmp(&a.count)
+            .then_with(|| a.canonical.cmp(&b.canonical))
+    });
+    suggestions
+}
+
+/// The `value` lower cased without the characters other than letters and digits,
+/// so spacing, punctuation and case differences don't count as edits.
+fn comparison_key(value: &str) -> Vec<char> {
+    value
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+/// The Levenshtein distance between `a` and `b`.
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    let mut current = vec![0; b.len() + 1];
+    for (i, a_char) in a.iter().enumerate() {
+        current[0] = i + 1;
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != b_char);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        std::mem::swap(&mut previous, &mut current);
+    }
+    previous[b.len()]
+}
@@ -0,0 +1,44 @@
+use crate::error::TaggyError;
+use crate::field_value_count::FieldValueCount;
+use crate::tag::Tag;
+use crate::utils::tag_fields::for_each_tag_field;
+use std::collections::HashMap;
+
+/// Counts the distinct values of the `field` (e.g. `genre`) of the given `tags`.
+///
+/// The values are sorted by their count, the most common first, then alphabetically.
+pub(crate) fn count_field_values<'a>(
+    tags: impl IntoIterator<Item = &'a Tag>,
+    field: &str,
+) -> anyhow::Result<Vec<FieldValueCount>> {
+    let mut counts: HashMap<String, u32> = HashMap::new();
+    for tag in tags {
+        let Some(value) = field_value(tag, field) else {
+            return Err(
+                TaggyError::invalid_value(format!("The field '{field}' doesn't exist!")).into(),
+            );
+        };
+        if let Some(value) = value {
+            *counts.entry(value).or_default() += 1;
+        }
+    }
+    let mut values: Vec<FieldValueCount> = counts
+        .into_iter()
+        .map(|(value, count)| FieldValueCount { value, count })
+        .collect();
+    values.sort_by(|a, b| b.count.cmp(&a.count).then_with(|| a.value.cmp(&b.value)));
+    Ok(values)
+}
+
+/// The value of the `field` of `tag` as text, `None` when there's no such field.
+fn field_value(tag: &Tag, field: &str) -> Option<Option<String>> {
+    macro_rules! value_of {
+        ($field:ident) => {
+            if field == stringify!($field) {
+                return Some(tag.$field.as_ref().map(|v| v.to_string()));
+            }
+        };
+    }
+    for_each_tag_field!(value_of);
+    None
+}
@@ -0,0 +1,223 @@
+use crate::custom_item::CustomItem;
+use crate::error::TaggyError;
+use crate::tag::{Tag, TagType};
+use crate::utils::chapters::{chapter_frames_of, insert_chapter_frame};
+use crate::utils::comments::{add_id3v2_comments, id3v2_comments, insert_id3v2_comments};
+use crate::utils::custom_items::{add_id3v2_custom_items, id3v2_custom_items};
+use crate::utils::dates::add_id3v2_dates;
+use crate::utils::dj::{dj_frames_of, insert_dj_frame};
+use crate::utils::file_id::{probe_file_type, read_id3v2};
+use crate::utils::freeform_fields::{
+    add_id3v2_freeform_fields, id3v2_freeform_items, id3v2_recording_id, insert_musicbrainz_ufid,
+};
+use crate::utils::lyrics::{add_id3v2_lyrics, id3v2_lyrics, insert_id3v2_lyrics, UnsyncedLyrics};
+use crate::utils::raw_tag::dump_raw_tag;
+use crate::utils::read_only::open_read_only;
+use crate::utils::sylt::{insert_sylt_frame, sylt_frames};
+use crate::utils::work::{add_id3v2_work, id3v2_work_items};
+use lofty::id3::v2::{CommentFrame, Id3v2Tag};
+use lofty::ogg::VorbisComments;
+use lofty::{BoundTaggedFile, FileType, TagExt, TaggedFileExt};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek};
+
+/// Adds the frames of the file at `path` which lofty doesn't keep in its generic tags,
+/// i.e. the `TXXX` custom items, the lyrics language and description, the `COMM` frames,
+/// the freeform fields, e.g. the MusicBrainz identifiers, the work, and the day and month of the
+/// ID3v2.3 `TDAT` frame, to its ID3v2 tag in `tags`, if any.
+///
+/// The file is read again, failing to do so leaves the `tags` as they are.
+pub(crate) fn read_id3v2_frames(tags: &mut [Tag], path: &str) {
+    let Ok(file_type) = probe_file_type(path) else {
+        return;
+    };
+    if let Ok(mut file) = open_read_only(path) {
+        read_id3v2_frames_from(tags, &mut file, file_type);
+    }
+}
+
+/// Same as [read_id3v2_frames], for the `file_type` audio file of the `reader`.
+pub(crate) fn read_id3v2_frames_from<R: Read + Seek>(
+    tags: &mut [Tag],
+    reader: &mut R,
+    file_type: FileType,
+) {
+    let Some(tag) = tags.iter_mut().find(|t| t.tag_type == TagType::Id3v2) else {
+        return;
+    };
+    let Ok(Some(id3v2)) = read_id3v2(reader, file_type) else {
+        return;
+    };
+    add_id3v2_custom_items(tag, &id3v2);
+    add_id3v2_lyrics(tag, &id3v2);
+    add_id3v2_comments(tag, &id3v2);
+    add_id3v2_freeform_fields(tag, &id3v2);
+    add_id3v2_dates(tag, &id3v2);
+    add_id3v2_work(tag, &id3v2);
+}
+
+/// The ID3v2 frames lofty doesn't write from its generic tags, to write along with them with
+/// [completed_id3v2] and [save_tags_with].
+pub(crate) struct Id3v2Frames {
+    custom_items: Vec<CustomItem>,
+    lyrics: Option<UnsyncedLyrics>,
+    comments: Vec<CommentFrame>,
+    /// The MusicBrainz recording ID, stored in the `UFID` frame.
+    recording_id: Option<String>,
+    /// The content of the `SYLT` frames already in the file, which aren't part of the tags.
+    synced_lyrics: Vec<Vec<u8>>,
+    /// The ID and content of the `CHAP` and `CTOC` frames already in the file.
+    chapters: Vec<(String, Vec<u8>)>,
+    /// The ID and content of the frames of the DJ softwares already in the file, e.g. the
+    /// Serato DJ cues.
+    dj_frames: Vec<(String, Vec<u8>)>,
+}
+
+impl Id3v2Frames {
+    /// The frames of the ID3v2 tags among `tags`, and the synchronized lyrics, chapters and DJ
+    /// markers of the file at `path`, so they're kept when the tags are saved.
+    ///
+    /// The ID3v2 tag of the file is parsed once for all of them, failing to read it keeps none.
+    /// Fails before anything is written when the lyrics or a comment language is invalid.
+    pub(crate) fn to_write(path: &str, tags: &[Tag]) -> anyhow::Result<Self> {
+        let existing = read_primary_id3v2(path);
+        Ok(Self {
+            // the freeform fields and the work are `TXXX` frames too.
+            custom_items: [
+                id3v2_custom_items(tags),
+                id3v2_freeform_items(tags),
+                id3v2_work_items(tags),
+            ]
+            .concat(),
+            lyrics: id3v2_lyrics(tags)?,
+            comments: id3v2_comments(tags)?,
+            recording_id: id3v2_recording_id(tags),
+            synced_lyrics: existing
+                .as_ref()
+                .map(|tag| sylt_frames(tag).cloned().collect())
+                .unwrap_or_default(),
+            chapters: existing.as_ref().map(chapter_frames_of).unwrap_or_default(),
+            dj_frames: existing.as_ref().map(dj_frames_of).unwrap_or_default(),
+        })
+    }
+
+    fn is_empty(&self) -> bool {
+        self.custom_items.is_empty()
+            && self.lyrics.is_none()
+            && self.comments.is_empty()
+            && self.recording_id.is_none()
+            && self.synced_lyrics.is_empty()
+            && self.chapters.is_empty()
+            && self.dj_frames.is_empty()
+    }
+
+    /// Inserts the custom items as `TXXX` frames, the lyrics as a `USLT` frame, the comments as
+    /// `COMM` frames, the MusicBrainz recording ID as a `UFID` frame and puts back the `SYLT`,
+    /// `CHAP`, `CTOC` and DJ software frames into the ID3v2 `tag`.
+    fn insert_into(&self, tag: &mut Id3v2Tag) -> anyhow::Result<()> {
+        for item in &self.custom_items {
+            tag.insert_user_text(item.key.clone(), item.value.clone());
+        }
+        if let Some(lyrics) = &self.lyrics {
+            insert_id3v2_lyrics(tag, lyrics)?;
+        }
+        if !self.comments.is_empty() {
+            insert_id3v2_comments(tag, &self.comments)?;
+        }
+        if let Some(recording_id) = &self.recording_id {
+            insert_musicbrainz_ufid(tag, recording_id)?;
+        }
+        if sylt_frames(tag).next().is_none() {
+            for data in &self.synced_lyrics {
+                insert_sylt_frame(tag, data.clone())?;
+            }
+        }
+        if chapter_frames_of(tag).is_empty() {
+            for frame in &self.chapters {
+                insert_chapter_frame(tag, frame.clone())?;
+            }
+        }
+        if dj_frames_of(tag).is_empty() {
+            for frame in &self.dj_frames {
+                insert_dj_frame(tag, frame.clone())?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The ID3v2 tag of the `file` completed with the `frames`, as it's saved by
+/// [save_tags_with], `None` when the file has no ID3v2 tag or there are no frames to add.
+pub(crate) fn completed_id3v2(
+    file: &BoundTaggedFile,
+    frames: &Id3v2Frames,
+) -> anyhow::Result<Option<Id3v2Tag>> {
+    if frames.is_empty() {
+        return Ok(None);
+    }
+    let Some(tag) = file.tag(lofty::TagType::Id3v2) else {
+        return Ok(None);
+    };
+    let mut id3v2 = Id3v2Tag::from(tag.clone());
+    frames.insert_into(&mut id3v2)?;
+    Ok(Some(id3v2))
+}
+
+/// Whether the `id3v2` tag would be written exactly as the one of the file at `path`.
+pub(crate) fn is_id3v2_unchanged(path: &str, id3v2: &Id3v2Tag) -> bool {
+    let mut bytes = vec![];
+    if id3v2.dump_to(&mut bytes).is_err() {
+        return false;
+    }
+    dump_raw_tag(path, TagType::Id3v2).is_ok_and(|existing| existing == bytes)
+}
+
+/// Saves the tags of the `file` at `path`, with the `id3v2` tag of [completed_id3v2] and the
+/// `vorbis_comments` of
+/// [vorbis_comments_with_information](crate::utils::picture_blocks::vorbis_comments_with_information)
+/// in place of its generic ones, if any.
+///
+/// The tags are written one after the other through a single handle as lofty does, so the
+/// frames and picture blocks lofty doesn't write are part of the same save.
+pub(crate) fn save_tags_with(
+    file: &mut BoundTaggedFile,
+    path: &str,
+    id3v2: Option<Id3v2Tag>,
+    vorbis_comments: Option<VorbisComments>,
+) -> anyhow::Result<()> {
+    if id3v2.is_none() && vorbis_comments.is_none() {
+        file.save().map_err(TaggyError::save_failed)?;
+        return Ok(());
+    }
+    let mut handle = OpenOptions::new().read(true).write(true).open(path)?;
+    for tag in file.tags() {
+        handle.rewind()?;
+        let saved = match (tag.tag_type(), &id3v2, &vorbis_comments) {
+            (lofty::TagType::Id3v2, Some(id3v2), _) => id3v2.save_to(&mut handle),
+            (lofty::TagType::VorbisComments, _, Some(comments)) => comments.save_to(&mut handle),
+            _ => tag.save_to(&mut handle),
+        };
+        saved.map_err(TaggyError::save_failed)?;
+    }
+    // as lofty, the empty tags were removed from the file.
+    let empty: Vec<lofty::TagType> = file
+        .tags()
+        .iter()
+        .filter(|tag| tag.is_empty())
+        .map(|tag| tag.tag_type())
+        .collect();
+    for tag_type in empty {
+        file.remove(tag_type);
+    }
+    Ok(())
+}
+
+/// The ID3v2 tag of the file at `path` when it's its primary tag, `None` when it can't be read.
+fn read_primary_id3v2(path: &str) -> Option<Id3v2Tag> {
+    let file_type = probe_file_type(path).ok()?;
+    if file_type.primary_tag_type() != lofty::TagType::Id3v2 {
+        return None;
+    }
+    let mut file = open_read_only(path).ok()?;
+    read_id3v2(&mut file, file_type).ok()?
+}
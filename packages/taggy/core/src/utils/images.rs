@@ -0,0 +1,10 @@
+use crate::error::TaggyError;
+use image::RgbImage;
+
+/// Decodes the picture `data` and downscales it to fit in `size` x `size` pixels,
+/// keeping its aspect ratio.
+pub(crate) fn decode_thumbnail(data: &[u8], size: u32) -> anyhow::Result<RgbImage> {
+    let image = image::load_from_memory(data)
+        .map_err(|e| TaggyError::invalid_value(format!("The picture can't be decoded: {e}")))?;
+    Ok(image.thumbnail(size, size).to_rgb8())
+}
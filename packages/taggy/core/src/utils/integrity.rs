@@ -0,0 +1,116 @@
+use crate::error::TaggyError;
+use crate::utils::read_only::open_read_only;
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+
+/// Fails with [TaggyError::EmptyOrTruncated] when the file at `path` is empty,
+/// or shorter than the length declared by its headers.
+///
+/// Only the headers are read: the ID3v2 tag size, the RIFF/AIFF chunk size,
+/// the FLAC metadata blocks and the MP4 top level atoms.
+pub(crate) fn check_not_truncated(path: &str) -> anyhow::Result<()> {
+    let file = open_read_only(path)?;
+    let actual_len = file.metadata()?.len();
+    if actual_len == 0 {
+        return Err(TaggyError::EmptyOrTruncated {
+            expected_len: None,
+            actual_len,
+        }
+        .into());
+    }
+    let expected_len = match declared_len(&mut BufReader::new(file), actual_len) {
+        Ok(Some(len)) if len > actual_len => Some(len),
+        Ok(_) => return Ok(()),
+        // a header is cut in the middle.
+        Err(e) if e.kind() == io::ErrorKind::UnexpectedEof => None,
+        Err(e) => return Err(e.into()),
+    };
+    Err(TaggyError::EmptyOrTruncated {
+        expected_len,
+        actual_len,
+    }
+    .into())
+}
+
+/// The minimum length of the file according to its headers, `None` when its format has none.
+fn declared_len<R: Read + Seek>(reader: &mut R, actual_len: u64) -> io::Result<Option<u64>> {
+    if actual_len < 12 {
+        // too short to tell, the parsers will reject it anyway.
+        return Ok(None);
+    }
+    let mut ident = [0u8; 12];
+    reader.read_exact(&mut ident)?;
+    match &ident[0..4] {
+        b"RIFF" => {
+            let size = u32::from_le_bytes([ident[4], ident[5], ident[6], ident[7]]);
+            return Ok(Some(8 + size as u64));
+        }
+        b"FORM" => {
+            let size = u32::from_be_bytes([ident[4], ident[5], ident[6], ident[7]]);
+            return Ok(Some(8 + size as u64));
+        }
+        _ => {}
+    }
+    if &ident[4..8] == b"ftyp" {
+        return mp4_len(reader, actual_len);
+    }
+
+    let audio_start = if &ident[0..3] == b"ID3" {
+        // the size is stored as a synchsafe integer.
+        let size = ident[6..10]
+            .iter()
+            .fold(0u64, |acc, b| (acc << 7) | (*b as u64 & 0x7F));
+        let footer = if ident[5] & 0x10 != 0 { 10 } else { 0 };
+        10 + size + footer
+    } else {
+        0
+    };
+    if audio_start < actual_len {
+        reader.seek(SeekFrom::Start(audio_start))?;
+        let mut magic = [0u8; 4];
+        reader.read_exact(&mut magic)?;
+        if &magic == b"fLaC" {
+            return flac_len(reader, audio_start + 4, actual_len).map(Some);
+        }
+    }
+    Ok(Some(audio_start))
+}
+
+/// The end of the last FLAC metadata block, the blocks starting at `start`.
+fn flac_len<R: Read + Seek>(reader: &mut R, start: u64, actual_len: u64) -> io::Result<u64> {
+    let mut pos = start;
+    loop {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header)?;
+        let len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as u64;
+        pos += 4 + len;
+        if header[0] & 0x80 != 0 || pos > actual_len {
+            return Ok(pos);
+        }
+        reader.seek(SeekFrom::Start(pos))?;
+    }
+}
+
+/// The end of the MP4 top level atoms.
+fn mp4_len<R: Read + Seek>(reader: &mut R, actual_len: u64) -> io::Result<Option<u64>> {
+    let mut pos = 0;
+    while pos + 8 <= actual_len {
+        reader.seek(SeekFrom::Start(pos))?;
+        let mut header = [0u8; 8];
+        reader.read_exact(&mut header)?;
+        let size = match u32::from_be_bytes([header[0], header[1], header[2], header[3]]) {
+            // the atom extends to the end of the file.
+            0 => return Ok(None),
+            1 => {
+                let mut extended = [0u8; 8];
+                reader.read_exact(&mut extended)?;
+                u64::from_be_bytes(extended)
+            }
+            size => size as u64,
+        };
+        if size < 8 {
+            return Ok(None);
+        }
+        pos += size;
+    }
+    Ok(Some(pos))
+}
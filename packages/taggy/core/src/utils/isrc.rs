@@ -0,0 +1,54 @@
+use crate::error::TaggyError;
+use crate::tag::Tag;
+use crate::taggy_file::TaggyFile;
+
+/// Normalizes the given `isrc` to its 12 characters form, e.g. `US-RC1-76-07839` to `USRC17607839`.
+///
+/// Separators (`-`, spaces, `_`, `.`) are dropped and letters are upper cased, then the code
+/// is validated against the `CC-XXX-YY-NNNNN` structure:
+/// - `CC`: the country code, 2 letters
+/// - `XXX`: the registrant code, 3 letters or digits
+/// - `YY`: the year of reference, 2 digits
+/// - `NNNNN`: the designation code, 5 digits
+pub(crate) fn normalize_isrc(isrc: &str) -> anyhow::Result<String> {
+    let code: String = isrc
+        .chars()
+        .filter(|c| !matches!(c, '-' | ' ' | '_' | '.'))
+        .map(|c| c.to_ascii_uppercase())
+        .collect();
+    let chars: Vec<char> = code.chars().collect();
+    let valid = chars.len() == 12
+        && chars[0..2].iter().all(char::is_ascii_uppercase)
+        && chars[2..5].iter().all(char::is_ascii_alphanumeric)
+        && chars[5..12].iter().all(char::is_ascii_digit);
+    if valid {
+        Ok(code)
+    } else {
+        Err(TaggyError::invalid_value(format!("'{isrc}' is not a valid ISRC!")).into())
+    }
+}
+
+/// The warning about the ISRC of the `tag` when it isn't valid, it's written as it is then.
+pub(crate) fn isrc_write_warning(tag: &Tag) -> Option<String> {
+    let isrc = tag.isrc.as_ref()?;
+    normalize_isrc(isrc).err()?;
+    Some(format!(
+        "The ISRC '{isrc}' of the {:?} tag isn't valid, it was written as it is",
+        tag.tag_type
+    ))
+}
+
+/// Adds a warning to the `file` for each of its tags holding an ISRC which isn't valid.
+pub(crate) fn warn_invalid_isrcs(file: &mut TaggyFile) {
+    for tag in &file.tags {
+        let Some(isrc) = &tag.isrc else {
+            continue;
+        };
+        if normalize_isrc(isrc).is_err() {
+            file.warnings.push(format!(
+                "The ISRC '{isrc}' of the {:?} tag isn't valid",
+                tag.tag_type
+            ));
+        }
+    }
+}
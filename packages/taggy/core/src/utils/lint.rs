@@ -0,0 +1,73 @@
+use crate::tag::Tag;
+use crate::utils::tag_fields::for_each_text_field;
+use crate::write_options::LintProfile;
+
+/// The length most players display in full, the lyrics aside.
+const STANDARD_MAX_LENGTH: usize = 255;
+/// The length of the title, artist and album beyond which car head units cut the text.
+const CAR_PLAY_MAX_LENGTH: usize = 64;
+
+/// Checks the text fields of `tag` against the rules of the `profile`.
+///
+/// Returns a warning for each issue, the tag itself is left as is.
+pub(crate) fn lint_tag(tag: &Tag, profile: LintProfile) -> Vec<String> {
+    let mut warnings = vec![];
+    if profile == LintProfile::Off {
+        return warnings;
+    }
+    macro_rules! lint {
+        ($field:ident) => {
+            if let Some(value) = &tag.$field {
+                lint_value(stringify!($field), value, profile, &mut warnings);
+            }
+        };
+    }
+    for_each_text_field!(lint);
+    warnings
+}
+
+fn lint_value(field: &str, value: &str, profile: LintProfile, warnings: &mut Vec<String>) {
+    let length = value.chars().count();
+    let multiline = field == "lyrics";
+    // the fingerprint isn't meant to be displayed.
+    let is_displayed = field != "acoustid_fingerprint";
+    if !multiline && is_displayed && length > STANDARD_MAX_LENGTH {
+        warnings.push(format!(
+            "The {field} is {length} characters long, most players only show the first {STANDARD_MAX_LENGTH}"
+        ));
+    }
+    let is_allowed_control = |c: char| multiline && matches!(c, '\n' | '\r' | '\t');
+    if value
+        .chars()
+        .any(|c| c.is_control() && !is_allowed_control(c))
+    {
+        warnings.push(format!("The {field} contains control characters"));
+    }
+    if profile != LintProfile::CarPlaySafe {
+        return;
+    }
+    let is_headline = matches!(
+        field,
+        "track_title" | "track_artist" | "album" | "album_artist"
+    );
+    if is_headline && length > CAR_PLAY_MAX_LENGTH {
+        warnings.push(format!(
+            "The {field} is {length} characters long, car head units only show the first {CAR_PLAY_MAX_LENGTH}"
+        ));
+    }
+    if value.chars().any(|c| c as u32 > 0xFFFF) {
+        warnings.push(format!(
+            "The {field} contains characters outside of the Basic Multilingual Plane (e.g. emojis), which car head units can't display"
+        ));
+    }
+    if value.chars().any(is_invisible_or_private) {
+        warnings.push(format!(
+            "The {field} contains invisible or private use characters, which car head units show as boxes"
+        ));
+    }
+}
+
+/// The zero-width characters, byte order marks and private use characters.
+fn is_invisible_or_private(c: char) -> bool {
+    matches!(c, '\u{200B}'..='\u{200F}' | '\u{2060}' | '\u{FEFF}' | '\u{E000}'..='\u{F8FF}')
+}
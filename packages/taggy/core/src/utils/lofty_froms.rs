@@ -0,0 +1,465 @@
+use lofty::{
+    Accessor, AudioFile, BoundTaggedFile, FileProperties, ItemKey, ItemValue, TaggedFile,
+    TaggedFileExt,
+};
+//
+use crate::audio_info::{AudioDuration, AudioInfo};
+use crate::picture::{MimeType, Picture, PictureStorage, PictureType};
+use crate::tag::{Tag, TagType};
+use crate::taggy_file::{FileType, TaggyFile};
+use crate::utils::chapters::read_chapters;
+use crate::utils::comments::comments_from;
+use crate::utils::custom_items::custom_items_from;
+use crate::utils::date_added::date_added_from;
+use crate::utils::dates::{parse_tag_date, release_date_of};
+use crate::utils::file_utils::{get_canonical_path, get_file_size, get_file_times};
+use crate::utils::freeform_fields::{
+    freeform_value, ACOUSTID_FINGERPRINT, ACOUSTID_ID, BARCODE, CATALOG_NUMBER,
+    MUSICBRAINZ_ALBUM_ARTIST_ID, MUSICBRAINZ_ARTIST_ID, MUSICBRAINZ_RECORDING_ID,
+    MUSICBRAINZ_RELEASE_GROUP_ID, MUSICBRAINZ_RELEASE_ID, MUSICBRAINZ_TRACK_ID, NARRATOR, SERIES,
+    SERIES_PART,
+};
+use crate::utils::id3v2_frames::read_id3v2_frames;
+use crate::utils::lyrics::unsynced_lyrics_item;
+use crate::utils::rating::rating_from;
+
+impl From<&lofty::Picture> for Picture {
+    fn from(value: &lofty::Picture) -> Picture {
+        let [width, height, color_depth, num_colors] = extract_image_info(&value);
+        Self {
+            pic_type: PictureType::from(value.pic_type()),
+            pic_data: value.data().to_vec(),
+            mime_type: Some(MimeType::from(value.mime_type())),
+            width,
+            height,
+            color_depth,
+            num_colors,
+            data_size: Some(value.data().len() as u32),
+            description: value.description().map(str::to_string),
+            storage: None,
+        }
+    }
+}
+
+fn extract_image_info(pic: &lofty::Picture) -> [Option<u32>; 4] {
+    match lofty::PictureInformation::from_picture(pic) {
+        Ok(info) => [
+            Some(info.width),
+            Some(info.height),
+            Some(info.color_depth),
+            Some(info.num_colors),
+        ],
+        Err(_) => [None; 4],
+    }
+}
+
+impl From<&lofty::Tag> for Tag {
+    fn from(value: &lofty::Tag) -> Self {
+        let artists = extract_lofty_tag_string_items(value, &ItemKey::TrackArtist);
+        let genres = extract_lofty_tag_string_items(value, &ItemKey::Genre);
+        let composers = extract_lofty_tag_string_items(value, &ItemKey::Composer);
+        let (track_number, track_total) = extract_lofty_tag_number_pair(
+            value,
+            &ItemKey::TrackNumber,
+            &ItemKey::TrackTotal,
+            "TOTALTRACKS",
+        );
+        let (disc_number, disc_total) = extract_lofty_tag_number_pair(
+            value,
+            &ItemKey::DiscNumber,
+            &ItemKey::DiscTotal,
+            "TOTALDISCS",
+        );
+        let (movement_number, movement_total) = extract_lofty_tag_number_pair(
+            value,
+            &ItemKey::MovementNumber,
+            &ItemKey::MovementTotal,
+            "MOVEMENTTOTAL",
+        );
+        let year = value.year();
+        let recording_date = extract_lofty_tag_string_item(&value, &ItemKey::RecordingDate);
+        let original_release_date =
+            extract_lofty_tag_string_item(&value, &ItemKey::OriginalReleaseDate);
+        Self {
+            tag_type: TagType::from(value.tag_type()),
+            pictures: value.pictures().iter().map(Picture::from).collect(),
+            track_title: extract_lofty_tag_string_item(&value, &ItemKey::TrackTitle),
+            track_artist: artists.first().cloned(),
+            album: extract_lofty_tag_string_item(&value, &ItemKey::AlbumTitle),
+            album_artist: extract_lofty_tag_string_item(&value, &ItemKey::AlbumArtist),
+            producer: extract_lofty_tag_string_item(&value, &ItemKey::Producer),
+            track_number,
+            track_total,
+            disc_number,
+            disc_total,
+            year,
+            release_date: release_date_of(recording_date.as_deref(), year),
+            original_date: original_release_date.as_deref().and_then(parse_tag_date),
+            recording_date,
+            original_release_date,
+            language: extract_lofty_tag_string_item(&value, &ItemKey::Language),
+            lyrics: extract_lofty_tag_string_item(&value, &ItemKey::Lyrics)
+                .or_else(|| unsynced_lyrics_item(value)),
+            lyrics_language: None,
+            lyrics_description: None,
+            genre: genres.first().cloned(),
+            isrc: extract_lofty_tag_string_item(&value, &ItemKey::Isrc),
+            composer: composers.first().cloned(),
+            conductor: extract_lofty_tag_string_item(&value, &ItemKey::Conductor),
+            remixer: extract_lofty_tag_string_item(&value, &ItemKey::Remixer),
+            artist_sort: extract_lofty_tag_string_item(&value, &ItemKey::TrackArtistSortOrder),
+            album_sort: extract_lofty_tag_string_item(&value, &ItemKey::AlbumTitleSortOrder),
+            title_sort: extract_lofty_tag_string_item(&value, &ItemKey::TrackTitleSortOrder),
+            album_artist_sort: extract_lofty_tag_string_item(
+                &value,
+                &ItemKey::AlbumArtistSortOrder,
+            ),
+            bpm: value.get_string(&ItemKey::Bpm).and_then(parse_bpm),
+            initial_key: extract_lofty_tag_string_item(&value, &ItemKey::InitialKey),
+            mood: extract_lofty_tag_string_item(&value, &ItemKey::Mood),
+            rating: rating_from(value),
+            musicbrainz_recording_id: freeform_value(value, &MUSICBRAINZ_RECORDING_ID),
+            musicbrainz_release_id: freeform_value(value, &MUSICBRAINZ_RELEASE_ID),
+            musicbrainz_release_group_id: freeform_value(value, &MUSICBRAINZ_RELEASE_GROUP_ID),
+            musicbrainz_artist_id: freeform_value(value, &MUSICBRAINZ_ARTIST_ID),
+            musicbrainz_album_artist_id: freeform_value(value, &MUSICBRAINZ_ALBUM_ARTIST_ID),
+            musicbrainz_track_id: freeform_value(value, &MUSICBRAINZ_TRACK_ID),
+            acoustid_id: freeform_value(value, &ACOUSTID_ID),
+            acoustid_fingerprint: freeform_value(value, &ACOUSTID_FINGERPRINT),
+            barcode: freeform_value(value, &BARCODE),
+            catalog_number: freeform_value(value, &CATALOG_NUMBER),
+            // some taggers write the label as the publisher, e.g. to the Vorbis comments.
+            label: extract_lofty_tag_string_item(&value, &ItemKey::Label)
+                .or_else(|| extract_lofty_tag_string_item(&value, &ItemKey::Publisher)),
+            copyright: extract_lofty_tag_string_item(&value, &ItemKey::CopyrightMessage),
+            grouping: extract_lofty_tag_string_item(&value, &ItemKey::AppleId3v2ContentGroup)
+                .or_else(|| extract_lofty_tag_string_item(&value, &ItemKey::ContentGroup)),
+            subtitle: extract_lofty_tag_string_item(&value, &ItemKey::TrackSubtitle),
+            // lofty doesn't map the ID3v2 `WORK` `TXXX` frame, see [add_id3v2_work](crate::utils::work::add_id3v2_work).
+            work: extract_lofty_tag_string_item(&value, &ItemKey::Work),
+            movement: extract_lofty_tag_string_item(&value, &ItemKey::Movement),
+            movement_number,
+            movement_total,
+            is_compilation: value
+                .get_string(&ItemKey::FlagCompilation)
+                .and_then(parse_flag),
+            is_podcast: value.get_string(&ItemKey::FlagPodcast).and_then(parse_flag),
+            podcast_url: extract_lofty_tag_url_item(&value, &ItemKey::PodcastURL),
+            episode_id: extract_lofty_tag_string_item(&value, &ItemKey::PodcastGlobalUniqueID),
+            narrator: freeform_value(value, &NARRATOR),
+            series: freeform_value(value, &SERIES),
+            series_part: freeform_value(value, &SERIES_PART),
+            custom_items: custom_items_from(value),
+            artists,
+            genres,
+            composers,
+            comments: comments_from(value),
+        }
+    }
+}
+
+fn extract_lofty_tag_string_item(tag: &lofty::Tag, key: &ItemKey) -> Option<String> {
+    tag.get_string(&key).map(|e| e.to_string())
+}
+
+/// The value of the `key`, which lofty keeps as a locator for the ID3v2 URL frames,
+/// e.g. `WFED`, and as text for the other tags.
+fn extract_lofty_tag_url_item(tag: &lofty::Tag, key: &ItemKey) -> Option<String> {
+    tag.get_items(key).find_map(|item| match item.value() {
+        ItemValue::Text(url) | ItemValue::Locator(url) => Some(url.to_string()),
+        ItemValue::Binary(_) => None,
+    })
+}
+
+/// All the values of the `key`, including the ones of the ID3v2.4 frames
+/// holding several null-separated values.
+fn extract_lofty_tag_string_items(tag: &lofty::Tag, key: &ItemKey) -> Vec<String> {
+    tag.get_strings(key)
+        .flat_map(|value| value.split('\0'))
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// The number and total of the `number_key`, e.g. the disc number and disc total.
+///
+/// lofty splits the ID3v2 `TPOS=1/2` frames and MP4 `disk` atoms itself, but not the
+/// `n/m` values other taggers write to the Vorbis comments and APE items, e.g. `DISCNUMBER=1/2`.
+/// The total is also read from the `alias_total_key` some taggers write instead of the
+/// standard one, e.g. `TOTALDISCS`.
+fn extract_lofty_tag_number_pair(
+    tag: &lofty::Tag,
+    number_key: &ItemKey,
+    total_key: &ItemKey,
+    alias_total_key: &str,
+) -> (Option<u32>, Option<u32>) {
+    let (number, pair_total) = match tag.get_string(number_key) {
+        Some(value) => match value.split_once('/') {
+            Some((number, total)) => (parse_number(number), parse_number(total)),
+            None => (parse_number(value), None),
+        },
+        None => (None, None),
+    };
+    let total = tag
+        .get_string(total_key)
+        .or_else(|| tag.get_string(&ItemKey::Unknown(alias_total_key.to_string())))
+        .and_then(parse_number)
+        .or(pair_total);
+    (number, total)
+}
+
+fn parse_number(value: &str) -> Option<u32> {
+    value.trim().parse().ok()
+}
+
+/// Parses the BPM, rounding the decimal ones some DJ software writes, e.g. `127.98`.
+fn parse_bpm(value: &str) -> Option<u32> {
+    let bpm: f64 = value.trim().parse().ok()?;
+    (bpm >= 0.0).then(|| bpm.round() as u32)
+}
+
+/// Parses a flag like the compilation one, written as `1` by most taggers.
+fn parse_flag(value: &str) -> Option<bool> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "1" | "true" | "yes" => Some(true),
+        "0" | "false" | "no" => Some(false),
+        _ => None,
+    }
+}
+
+impl From<lofty::PictureType> for PictureType {
+    fn from(value: lofty::PictureType) -> Self {
+        match value {
+            lofty::PictureType::Other => PictureType::Other,
+            lofty::PictureType::Icon => PictureType::Icon,
+            lofty::PictureType::OtherIcon => PictureType::OtherIcon,
+            lofty::PictureType::CoverFront => PictureType::CoverFront,
+            lofty::PictureType::CoverBack => PictureType::CoverBack,
+            lofty::PictureType::Leaflet => PictureType::Leaflet,
+            lofty::PictureType::Media => PictureType::Media,
+            lofty::PictureType::LeadArtist => PictureType::LeadArtist,
+            lofty::PictureType::Artist => PictureType::Artist,
+            lofty::PictureType::Conductor => PictureType::Conductor,
+            lofty::PictureType::Band => PictureType::Band,
+            lofty::PictureType::Composer => PictureType::Composer,
+            lofty::PictureType::Lyricist => PictureType::Lyricist,
+            lofty::PictureType::RecordingLocation => PictureType::RecordingLocation,
+            lofty::PictureType::DuringRecording => PictureType::DuringRecording,
+            lofty::PictureType::DuringPerformance => PictureType::DuringPerformance,
+            lofty::PictureType::ScreenCapture => PictureType::ScreenCapture,
+            lofty::PictureType::BrightFish => PictureType::BrightFish,
+            lofty::PictureType::Illustration => PictureType::Illustration,
+            lofty::PictureType::BandLogo => PictureType::BandLogo,
+            lofty::PictureType::PublisherLogo => PictureType::PublisherLogo,
+            lofty::PictureType::Undefined(_) => PictureType::Undefined,
+            _ => PictureType::Undefined,
+        }
+    }
+}
+
+impl<'a> From<&'a lofty::MimeType> for MimeType {
+    fn from(other: &lofty::MimeType) -> Self {
+        match other {
+            lofty::MimeType::Png => MimeType::Png,
+            lofty::MimeType::Jpeg => MimeType::Jpeg,
+            lofty::MimeType::Tiff => MimeType::Tiff,
+            lofty::MimeType::Bmp => MimeType::Bmp,
+            lofty::MimeType::Gif => MimeType::Gif,
+            lofty::MimeType::Unknown(_) => MimeType::Unknown,
+            lofty::MimeType::None => MimeType::None,
+            _ => MimeType::Unknown,
+        }
+    }
+}
+
+impl From<lofty::FileType> for FileType {
+    fn from(value: lofty::FileType) -> Self {
+        match value {
+            lofty::FileType::Aac => FileType::Aac,
+            lofty::FileType::Aiff => FileType::Aiff,
+            lofty::FileType::Ape => FileType::Ape,
+            lofty::FileType::Flac => FileType::Flac,
+            lofty::FileType::Mpeg => FileType::Mpeg,
+            lofty::FileType::Mp4 => FileType::Mp4,
+            lofty::FileType::Mpc => FileType::Mpc,
+            lofty::FileType::Opus => FileType::Opus,
+            lofty::FileType::Vorbis => FileType::Vorbis,
+            lofty::FileType::Speex => FileType::Speex,
+            lofty::FileType::Wav => FileType::Wav,
+            lofty::FileType::WavPack => FileType::WavPack,
+            lofty::FileType::Custom(_) => FileType::Other,
+            _ => FileType::Other,
+        }
+    }
+}
+
+impl<'a> From<&'a FileProperties> for AudioInfo {
+    fn from(value: &FileProperties) -> Self {
+        Self {
+            duration_sec: Some(value.duration().as_secs()),
+            duration_ms: Some(value.duration().as_millis() as u64),
+            duration: Some(AudioDuration::from(value.duration())),
+            overall_bitrate: value.overall_bitrate(),
+            audio_bitrate: value.audio_bitrate(),
+            sample_rate: value.sample_rate(),
+            bit_depth: value.bit_depth(),
+            channels: value.channels(),
+            channel_mask: match value.channel_mask() {
+                None => None,
+                Some(cm) => Some(cm.bits()),
+            },
+            frame_length: None,
+            seek_granularity_us: None,
+        }
+    }
+}
+
+/// The [AudioInfo] of a file of the given `file_type`, with its frame length when the format has a fixed one.
+pub(crate) fn audio_info_from(
+    properties: &FileProperties,
+    file_type: lofty::FileType,
+) -> AudioInfo {
+    let info = AudioInfo::from(properties);
+    let frame_length = match file_type {
+        lofty::FileType::Aac => Some(1024),
+        // Layer III, as in `.mp3` files: MPEG-1 sample rates start at 32kHz.
+        lofty::FileType::Mpeg => info
+            .sample_rate
+            .map(|rate| if rate >= 32000 { 1152 } else { 576 }),
+        lofty::FileType::Mpc => Some(1152),
+        lofty::FileType::Wav | lofty::FileType::Aiff => Some(1),
+        _ => None,
+    };
+    let seek_granularity_us = frame_length
+        .zip(info.sample_rate)
+        .filter(|(_, rate)| *rate > 0)
+        .map(|(length, rate)| (length as u64 * 1_000_000 / rate as u64) as u32);
+    AudioInfo {
+        frame_length,
+        seek_granularity_us,
+        ..info
+    }
+}
+
+impl From<lofty::TagType> for TagType {
+    fn from(value: lofty::TagType) -> Self {
+        match value {
+            lofty::TagType::Ape => TagType::Ape,
+            lofty::TagType::Id3v1 => TagType::Id3v1,
+            lofty::TagType::Id3v2 => TagType::Id3v2,
+            lofty::TagType::Mp4Ilst => TagType::Mp4Ilst,
+            lofty::TagType::VorbisComments => TagType::VorbisComments,
+            lofty::TagType::RiffInfo => TagType::RiffInfo,
+            lofty::TagType::AiffText => TagType::AiffText,
+            _ => TagType::Other,
+        }
+    }
+}
+pub fn taggy_from_tagged(file: &TaggedFile, path: &String) -> TaggyFile {
+    let (created_at_ms, modified_at_ms) = get_file_times(path);
+    TaggyFile {
+        canonical_path: get_canonical_path(path),
+        created_at_ms,
+        modified_at_ms,
+        ..taggy_from_tagged_with_size(file, get_file_size(path))
+    }
+}
+
+/// Same as [taggy_from_tagged] but for files which doesn't exist on disk (e.g. in-memory buffers)
+/// so the `size` has to be provided.
+pub(crate) fn taggy_from_tagged_with_size(file: &TaggedFile, size: Option<u64>) -> TaggyFile {
+    // convert the [`TaggedFile::tags`] to a `Vec` of our taggy's [`Tag`]
+    let tags = tags_from(file.tags(), file.file_type());
+    TaggyFile {
+        file_type: Some(file.file_type().into()),
+        size,
+        audio: audio_info_from(file.properties(), file.file_type()),
+        date_added_ms: date_added_from(&tags),
+        tags,
+        primary_tag_type: TagType::from(file.primary_tag_type()),
+        canonical_path: None,
+        created_at_ms: None,
+        modified_at_ms: None,
+        chapters: vec![],
+        warnings: vec![],
+    }
+}
+
+pub(crate) fn taggy_from_bound_tagged(file: &BoundTaggedFile, path: &String) -> TaggyFile {
+    // convert the [`TaggedFile::tags`] to a `Vec` of our taggy's [`Tag`]
+    let mut tags = tags_from(file.tags(), file.file_type());
+    read_id3v2_frames(&mut tags, path);
+    let (created_at_ms, modified_at_ms) = get_file_times(path);
+    TaggyFile {
+        file_type: Some(file.file_type().into()),
+        size: get_file_size(path),
+        audio: audio_info_from(file.properties(), file.file_type()),
+        date_added_ms: date_added_from(&tags),
+        tags,
+        primary_tag_type: TagType::from(file.primary_tag_type()),
+        canonical_path: get_canonical_path(path),
+        created_at_ms,
+        modified_at_ms,
+        chapters: read_chapters(path).unwrap_or_default(),
+        warnings: vec![],
+    }
+}
+/// Returns a list of [Tag] with only the primary tag if exists
+pub(crate) fn get_primary_tag_from_tagged_file(file: &TaggedFile) -> Vec<Tag> {
+    tags_from(file.primary_tag(), file.file_type())
+}
+
+pub(crate) fn get_any_tag_from_tagged_file(file: &TaggedFile) -> Vec<Tag> {
+    tags_from(file.first_tag(), file.file_type())
+}
+
+/// Converts the `lofty_tags` of a `file_type` file, with the [PictureStorage] of their pictures.
+fn tags_from<'a>(
+    lofty_tags: impl IntoIterator<Item = &'a lofty::Tag>,
+    file_type: lofty::FileType,
+) -> Vec<Tag> {
+    lofty_tags
+        .into_iter()
+        .map(|lofty_tag| {
+            let mut tag = Tag::from(lofty_tag);
+            let storage = PictureStorage::of(tag.tag_type, file_type);
+            for picture in &mut tag.pictures {
+                picture.storage = storage;
+            }
+            tag
+        })
+        .collect()
+}
+
+/// The pictures of all the tags of the `file`, the primary tag ones first.
+///
+/// The same picture stored in several tags (e.g. ID3v2 and APE) is only returned once.
+pub(crate) fn get_pictures_from_tagged_file(file: &TaggedFile) -> Vec<Picture> {
+    let mut pictures: Vec<(&lofty::Picture, Option<PictureStorage>)> = vec![];
+    for tag in tags_primary_first(file) {
+        let storage = PictureStorage::of(TagType::from(tag.tag_type()), file.file_type());
+        for picture in tag.pictures() {
+            let is_duplicate = pictures
+                .iter()
+                .any(|(p, _)| p.pic_type() == picture.pic_type() && p.data() == picture.data());
+            if !is_duplicate {
+                pictures.push((picture, storage));
+            }
+        }
+    }
+    pictures
+        .into_iter()
+        .map(|(picture, storage)| Picture {
+            storage,
+            ..Picture::from(picture)
+        })
+        .collect()
+}
+
+/// The tags of the `file`, the primary one first.
+pub(crate) fn tags_primary_first(file: &TaggedFile) -> Vec<&lofty::Tag> {
+    let primary_tag_type = file.primary_tag_type();
+    let mut tags: Vec<&lofty::Tag> = file.tags().iter().collect();
+    tags.sort_by_key(|t| t.tag_type() != primary_tag_type);
+    tags
+}
@@ -0,0 +1,332 @@
+use crate::parse_config::{ParseConfig, ParsingMode};
+use crate::picture::{MimeType, Picture, PictureType};
+use crate::tag::{Tag, TagType};
+use crate::utils::comments::generic_comments;
+use crate::utils::custom_items::push_custom_items;
+use crate::utils::dates::{date_to_write, parse_tag_date, release_date_of};
+use crate::utils::freeform_fields::push_freeform_fields;
+use crate::utils::isrc::normalize_isrc;
+use crate::utils::mime::detect_mime_type;
+use crate::utils::rating::push_rating;
+use lofty::{ItemKey, ItemValue, TagItem};
+
+impl Tag {
+    pub fn to_lofty(&self) -> lofty::Tag {
+        let tag_type = self.tag_type.clone().into();
+        let mut lofty_tag = lofty::Tag::new(tag_type);
+
+        if let Some(title) = &self.track_title {
+            lofty_tag.insert_text(ItemKey::TrackTitle, title.to_string());
+        };
+
+        // Artist
+        insert_values(
+            &mut lofty_tag,
+            ItemKey::TrackArtist,
+            values_to_write(&self.track_artist, &self.artists),
+        );
+
+        // Album Title
+        if let Some(album) = &self.album {
+            lofty_tag.insert_text(ItemKey::AlbumTitle, album.to_string());
+        };
+        if let Some(album_artist) = &self.album_artist {
+            lofty_tag.insert_text(ItemKey::AlbumArtist, album_artist.to_string());
+        };
+        if let Some(lyrics) = &self.lyrics {
+            lofty_tag.insert_text(ItemKey::Lyrics, lyrics.to_string());
+        };
+        if let Some(language) = &self.language {
+            lofty_tag.insert_text(ItemKey::Language, language.to_string());
+        };
+        if let Some(producer) = &self.producer {
+            // Writing a producer for this tag type results in an error due to writing
+            // some invalid frame for the tag.
+            // TODO:: remove when 'lofty::ParsingMode::Relaxed' is implemented.
+            if tag_type != lofty::TagType::Id3v2 {
+                lofty_tag.insert_text(ItemKey::Producer, producer.to_string());
+            }
+        };
+        let original_release_date = date_to_write(
+            &self.original_release_date,
+            self.original_date,
+            self.original_release_date
+                .as_deref()
+                .and_then(parse_tag_date),
+        );
+        if let Some(release) = original_release_date {
+            lofty_tag.insert_text(ItemKey::OriginalReleaseDate, release);
+        };
+        let recording_date = date_to_write(
+            &self.recording_date,
+            self.release_date,
+            release_date_of(self.recording_date.as_deref(), self.year),
+        );
+        if let Some(recording_date) = &recording_date {
+            lofty_tag.insert_text(ItemKey::RecordingDate, recording_date.to_string());
+        };
+        if let Some(track_total) = &self.track_total {
+            lofty_tag.insert_text(ItemKey::TrackTotal, track_total.to_string());
+        };
+        if let Some(track_num) = &self.track_number {
+            lofty_tag.insert_text(ItemKey::TrackNumber, track_num.to_string());
+        };
+        if let Some(disc) = &self.disc_number {
+            lofty_tag.insert_text(ItemKey::DiscNumber, disc.to_string());
+        };
+        if let Some(disc_total) = &self.disc_total {
+            lofty_tag.insert_text(ItemKey::DiscTotal, disc_total.to_string());
+        };
+
+        // Year
+        let written_date = recording_date.as_deref().and_then(parse_tag_date);
+        // the year of a structured release date written instead of the `recording_date`.
+        let year = match written_date {
+            Some(date) if recording_date != self.recording_date => Some(date.year),
+            _ => self.year,
+        };
+        if let Some(year) = year {
+            // the year may be stored in the same item as the date, writing it again
+            // would then drop the month and day of the date.
+            if written_date.map(|date| date.year) != Some(year) {
+                lofty_tag.insert_text(ItemKey::Year, year.to_string());
+            }
+        };
+
+        // Genre
+        insert_values(
+            &mut lofty_tag,
+            ItemKey::Genre,
+            values_to_write(&self.genre, &self.genres),
+        );
+        insert_values(
+            &mut lofty_tag,
+            ItemKey::Composer,
+            values_to_write(&self.composer, &self.composers),
+        );
+        if let Some(conductor) = &self.conductor {
+            lofty_tag.insert_text(ItemKey::Conductor, conductor.to_string());
+        };
+        if let Some(remixer) = &self.remixer {
+            lofty_tag.insert_text(ItemKey::Remixer, remixer.to_string());
+        };
+        if let Some(artist_sort) = &self.artist_sort {
+            lofty_tag.insert_text(ItemKey::TrackArtistSortOrder, artist_sort.to_string());
+        };
+        if let Some(album_sort) = &self.album_sort {
+            lofty_tag.insert_text(ItemKey::AlbumTitleSortOrder, album_sort.to_string());
+        };
+        if let Some(title_sort) = &self.title_sort {
+            lofty_tag.insert_text(ItemKey::TrackTitleSortOrder, title_sort.to_string());
+        };
+        if let Some(album_artist_sort) = &self.album_artist_sort {
+            lofty_tag.insert_text(ItemKey::AlbumArtistSortOrder, album_artist_sort.to_string());
+        };
+        if let Some(bpm) = &self.bpm {
+            lofty_tag.insert_text(ItemKey::Bpm, bpm.to_string());
+        };
+        if let Some(initial_key) = &self.initial_key {
+            lofty_tag.insert_text(ItemKey::InitialKey, initial_key.to_string());
+        };
+        if let Some(mood) = &self.mood {
+            lofty_tag.insert_text(ItemKey::Mood, mood.to_string());
+        };
+        if let Some(label) = &self.label {
+            // ID3v2 has no label frame, Picard writes it to the `TPUB` publisher one.
+            let key = match tag_type {
+                lofty::TagType::Id3v2 => ItemKey::Publisher,
+                _ => ItemKey::Label,
+            };
+            lofty_tag.insert_text(key, label.to_string());
+        };
+        if let Some(copyright) = &self.copyright {
+            lofty_tag.insert_text(ItemKey::CopyrightMessage, copyright.to_string());
+        };
+        if let Some(grouping) = &self.grouping {
+            // iTunes reads the grouping from the `GRP1` frame, most other players from `TIT1`.
+            if tag_type == lofty::TagType::Id3v2 {
+                lofty_tag.insert_text(ItemKey::AppleId3v2ContentGroup, grouping.to_string());
+            }
+            lofty_tag.insert_text(ItemKey::ContentGroup, grouping.to_string());
+        };
+        if let Some(subtitle) = &self.subtitle {
+            lofty_tag.insert_text(ItemKey::TrackSubtitle, subtitle.to_string());
+        };
+        if let Some(work) = &self.work {
+            // written as a `TXXX` frame to the ID3v2 tags,
+            // see [id3v2_work_items](crate::utils::work::id3v2_work_items).
+            if tag_type != lofty::TagType::Id3v2 {
+                lofty_tag.insert_text(ItemKey::Work, work.to_string());
+            }
+        };
+        if let Some(movement) = &self.movement {
+            lofty_tag.insert_text(ItemKey::Movement, movement.to_string());
+        };
+        if let Some(movement_number) = self.movement_number {
+            lofty_tag.insert_text(ItemKey::MovementNumber, movement_number.to_string());
+        };
+        if let Some(movement_total) = self.movement_total {
+            lofty_tag.insert_text(ItemKey::MovementTotal, movement_total.to_string());
+        };
+        if let Some(is_compilation) = self.is_compilation {
+            let flag = if is_compilation { "1" } else { "0" };
+            lofty_tag.insert_text(ItemKey::FlagCompilation, flag.to_string());
+        };
+        if let Some(is_podcast) = self.is_podcast {
+            let flag = if is_podcast { "1" } else { "0" };
+            lofty_tag.insert_text(ItemKey::FlagPodcast, flag.to_string());
+        };
+        if let Some(podcast_url) = &self.podcast_url {
+            // the ID3v2 `WFED` frame is a URL one.
+            let value = match tag_type {
+                lofty::TagType::Id3v2 => ItemValue::Locator(podcast_url.to_string()),
+                _ => ItemValue::Text(podcast_url.to_string()),
+            };
+            lofty_tag.insert(TagItem::new(ItemKey::PodcastURL, value));
+        };
+        if let Some(episode_id) = &self.episode_id {
+            lofty_tag.insert_text(ItemKey::PodcastGlobalUniqueID, episode_id.to_string());
+        };
+        if let Some(rating) = self.rating {
+            push_rating(&mut lofty_tag, rating);
+        };
+        if let Some(isrc) = &self.isrc {
+            // write the normalized code when possible, distributors reject malformed ones.
+            // The malformed ones are reported in the write warnings.
+            let isrc = normalize_isrc(isrc).unwrap_or_else(|_| isrc.to_string());
+            lofty_tag.insert_text(ItemKey::Isrc, isrc);
+        };
+        insert_values(
+            &mut lofty_tag,
+            ItemKey::Comment,
+            generic_comments(tag_type, &self.comments),
+        );
+        push_freeform_fields(&mut lofty_tag, self);
+        push_custom_items(&mut lofty_tag, &self.custom_items);
+        // Pictures
+        for (i, picture) in self.pictures.to_vec().into_iter().enumerate() {
+            lofty_tag.set_picture(i, get_pic_from_data(&picture));
+        }
+        lofty_tag
+    }
+}
+
+/// The values of a field having both a single value and multiple values in [Tag],
+/// e.g. `track_artist` and `artists`.
+///
+/// The `single` value replaces the `values` when it was set to another value than their first.
+fn values_to_write(single: &Option<String>, values: &[String]) -> Vec<String> {
+    match single {
+        Some(single) if values.first() != Some(single) => vec![single.to_string()],
+        _ => values.to_vec(),
+    }
+}
+
+/// Inserts the `values` of the `key` into the `tag`, in a single null-separated frame
+/// for ID3v2 and as separate items for the tags supporting several values of a key.
+fn insert_values(tag: &mut lofty::Tag, key: ItemKey, values: Vec<String>) {
+    if values.is_empty() {
+        return;
+    }
+    match tag.tag_type() {
+        lofty::TagType::Id3v2 => {
+            tag.insert_text(key, values.join("\0"));
+        }
+        lofty::TagType::VorbisComments | lofty::TagType::Ape | lofty::TagType::Mp4Ilst => {
+            for value in values {
+                tag.push_unchecked(TagItem::new(key.clone(), ItemValue::Text(value)));
+            }
+        }
+        _ => {
+            tag.insert_text(key, values[0].to_string());
+        }
+    }
+}
+
+/// Converts the `pic` to a [lofty::Picture].
+///
+/// When the `pic` has no known mime type, it's detected from the data, since several players
+/// don't show the pictures without one.
+pub(crate) fn get_pic_from_data(pic: &Picture) -> lofty::Picture {
+    let mime_type = match pic.mime_type {
+        None | Some(MimeType::None) | Some(MimeType::Unknown) => {
+            detect_mime_type(&pic.pic_data).or_else(|| pic.mime_type.map(|m| m.into()))
+        }
+        Some(mime_type) => Some(mime_type.into()),
+    };
+    lofty::Picture::new_unchecked(
+        pic.pic_type.into(),
+        mime_type.unwrap_or(lofty::MimeType::None),
+        pic.description.clone(),
+        pic.pic_data.clone(),
+    )
+}
+
+impl Into<lofty::TagType> for TagType {
+    fn into(self) -> lofty::TagType {
+        match self {
+            TagType::Ape => lofty::TagType::Ape,
+            TagType::Id3v1 => lofty::TagType::Id3v1,
+            TagType::Id3v2 => lofty::TagType::Id3v2,
+            TagType::Mp4Ilst => lofty::TagType::Mp4Ilst,
+            TagType::VorbisComments => lofty::TagType::VorbisComments,
+            TagType::RiffInfo => lofty::TagType::RiffInfo,
+            TagType::AiffText => lofty::TagType::AiffText,
+            TagType::Other => lofty::TagType::Id3v2,
+            TagType::FilePrimaryType => lofty::TagType::Id3v2,
+        }
+    }
+}
+impl Into<lofty::MimeType> for MimeType {
+    fn into(self) -> lofty::MimeType {
+        match self {
+            MimeType::Png => lofty::MimeType::Png,
+            MimeType::Jpeg => lofty::MimeType::Jpeg,
+            MimeType::Tiff => lofty::MimeType::Tiff,
+            MimeType::Bmp => lofty::MimeType::Bmp,
+            MimeType::Gif => lofty::MimeType::Gif,
+            MimeType::Unknown => lofty::MimeType::Unknown("unknown".to_string()),
+            MimeType::None => lofty::MimeType::None,
+        }
+    }
+}
+impl Into<lofty::PictureType> for PictureType {
+    fn into(self) -> lofty::PictureType {
+        match self {
+            PictureType::Other => lofty::PictureType::Other,
+            PictureType::Icon => lofty::PictureType::Icon,
+            PictureType::OtherIcon => lofty::PictureType::OtherIcon,
+            PictureType::CoverFront => lofty::PictureType::CoverFront,
+            PictureType::CoverBack => lofty::PictureType::CoverBack,
+            PictureType::Leaflet => lofty::PictureType::Leaflet,
+            PictureType::Media => lofty::PictureType::Media,
+            PictureType::LeadArtist => lofty::PictureType::LeadArtist,
+            PictureType::Artist => lofty::PictureType::Artist,
+            PictureType::Conductor => lofty::PictureType::Conductor,
+            PictureType::Band => lofty::PictureType::Band,
+            PictureType::Composer => lofty::PictureType::Composer,
+            PictureType::Lyricist => lofty::PictureType::Lyricist,
+            PictureType::RecordingLocation => lofty::PictureType::RecordingLocation,
+            PictureType::DuringRecording => lofty::PictureType::DuringRecording,
+            PictureType::DuringPerformance => lofty::PictureType::DuringPerformance,
+            PictureType::ScreenCapture => lofty::PictureType::ScreenCapture,
+            PictureType::BrightFish => lofty::PictureType::BrightFish,
+            PictureType::Illustration => lofty::PictureType::Illustration,
+            PictureType::BandLogo => lofty::PictureType::BandLogo,
+            PictureType::PublisherLogo => lofty::PictureType::PublisherLogo,
+            PictureType::Undefined => lofty::PictureType::Undefined(0),
+        }
+    }
+}
+
+impl From<ParseConfig> for lofty::ParseOptions {
+    fn from(config: ParseConfig) -> Self {
+        lofty::ParseOptions::new()
+            .max_junk_bytes(config.max_junk_bytes as usize)
+            .read_properties(config.read_properties)
+            .parsing_mode(match config.parsing_mode {
+                ParsingMode::Strict => lofty::ParsingMode::Strict,
+                ParsingMode::BestAttempt => lofty::ParsingMode::BestAttempt,
+            })
+    }
+}
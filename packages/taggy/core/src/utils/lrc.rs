@@ -0,0 +1,98 @@
+use crate::error::TaggyError;
+use crate::synced_lyrics::{SyncedLine, SyncedLyrics};
+
+/// Parses the LRC `text`, made of `[mm:ss.xx]` prefixed lines, e.g. `[00:12.34]First line`.
+///
+/// A line may have several timestamps, e.g. `[00:12.00][01:30.00]Chorus`, and the
+/// `[offset:+500]` tag shifts all the lines this many milliseconds earlier.
+/// The other tags, e.g. `[ar:Artist]`, are ignored.
+pub(crate) fn parse_lrc(text: &str) -> anyhow::Result<SyncedLyrics> {
+    let mut offset_ms: i64 = 0;
+    let mut lines: Vec<(i64, String)> = vec![];
+    for line in text.lines() {
+        let mut rest = line.trim();
+        let mut timestamps = vec![];
+        while let Some((tag, after)) = leading_tag(rest) {
+            let Some(timestamp) = parse_timestamp(tag) else {
+                break;
+            };
+            timestamps.push(timestamp);
+            rest = after;
+        }
+        if timestamps.is_empty() {
+            if let Some(offset) = leading_tag(rest).and_then(|(tag, _)| parse_offset(tag)) {
+                offset_ms = offset;
+            }
+            continue;
+        }
+        // the lines without text are kept, they clear the previous line.
+        let text = rest.trim();
+        lines.extend(timestamps.into_iter().map(|t| (t, text.to_string())));
+    }
+    if lines.is_empty() {
+        return Err(TaggyError::invalid_value("The text has no timed LRC line!").into());
+    }
+
+    lines.sort_by_key(|(timestamp, _)| *timestamp);
+    Ok(SyncedLyrics {
+        lines: lines
+            .into_iter()
+            .map(|(timestamp, text)| SyncedLine {
+                timestamp_ms: (timestamp - offset_ms).clamp(0, u32::MAX as i64) as u32,
+                text,
+            })
+            .collect(),
+        ..SyncedLyrics::default()
+    })
+}
+
+/// Formats the `lyrics` as LRC text, one `[mm:ss.xx]` prefixed line per line.
+pub(crate) fn format_lrc(lyrics: &SyncedLyrics) -> String {
+    lyrics
+        .lines
+        .iter()
+        .map(|line| {
+            let ms = line.timestamp_ms;
+            let (minutes, seconds, hundredths) = (ms / 60_000, ms / 1000 % 60, ms % 1000 / 10);
+            format!("[{minutes:02}:{seconds:02}.{hundredths:02}]{}\n", line.text)
+        })
+        .collect()
+}
+
+/// The content of the `[...]` tag the `line` starts with, and the text after it.
+fn leading_tag(line: &str) -> Option<(&str, &str)> {
+    let rest = line.strip_prefix('[')?;
+    let end = rest.find(']')?;
+    Some((&rest[..end], &rest[end + 1..]))
+}
+
+/// Parses a `mm:ss`, `mm:ss.xx` or `mm:ss.xxx` timestamp to milliseconds.
+fn parse_timestamp(tag: &str) -> Option<i64> {
+    let (minutes, seconds) = tag.trim().split_once(':')?;
+    // some editors separate the fraction with a colon, e.g. `00:12:34`.
+    let (seconds, fraction) = seconds.split_once(['.', ':']).unwrap_or((seconds, ""));
+    let minutes = parse_digits(minutes)?;
+    let seconds = parse_digits(seconds).filter(|s| *s < 60)?;
+    let fraction_ms = match fraction.len() {
+        0 => 0,
+        len @ 1..=3 => parse_digits(fraction)? * 10_i64.pow(3 - len as u32),
+        _ => return None,
+    };
+    Some(minutes * 60_000 + seconds * 1000 + fraction_ms)
+}
+
+/// Parses an `offset:+500` tag to milliseconds.
+fn parse_offset(tag: &str) -> Option<i64> {
+    let (key, value) = tag.split_once(':')?;
+    if !key.trim().eq_ignore_ascii_case("offset") {
+        return None;
+    }
+    value.trim().trim_start_matches('+').parse().ok()
+}
+
+fn parse_digits(value: &str) -> Option<i64> {
+    if value.is_empty() || !value.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    value.parse().ok()
+}
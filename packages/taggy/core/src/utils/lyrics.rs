@@ -0,0 +1,119 @@
+use crate::error::TaggyError;
+use crate::tag::{Tag, TagType};
+use lofty::id3::v2::{Frame, FrameFlags, Id3v2Tag, UnsynchronizedTextFrame};
+use lofty::{ItemKey, ItemValue, TextEncoding};
+
+/// The Vorbis comment and APE item some players write the lyrics to instead of `LYRICS`.
+pub(crate) const UNSYNCED_LYRICS_KEY: &str = "UNSYNCEDLYRICS";
+
+/// The language of the ID3v2 `USLT` frames whose language isn't known.
+pub(crate) const UNKNOWN_LANGUAGE: [u8; 3] = *b"XXX";
+
+/// The lyrics of an ID3v2 tag along with their language and description,
+/// which lofty doesn't keep in its generic tags.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UnsyncedLyrics {
+    content: String,
+    language: [u8; 3],
+    description: String,
+}
+
+/// The `UNSYNCEDLYRICS` item of the Vorbis comments and APE tags, for the `tag`
+/// without a `LYRICS` one.
+pub(crate) fn unsynced_lyrics_item(tag: &lofty::Tag) -> Option<String> {
+    if !matches!(
+        tag.tag_type(),
+        lofty::TagType::VorbisComments | lofty::TagType::Ape
+    ) {
+        return None;
+    }
+    tag.items()
+        .find_map(|item| match (item.key(), item.value()) {
+            (ItemKey::Unknown(key), ItemValue::Text(value))
+                if key.eq_ignore_ascii_case(UNSYNCED_LYRICS_KEY) =>
+            {
+                Some(value.to_string())
+            }
+            _ => None,
+        })
+}
+
+/// Sets the language and description of the `tag` lyrics from the matching `USLT` frame
+/// of the `id3v2` tag, or the first one.
+///
+/// lofty only keeps the lyrics without a description in its generic tags,
+/// so the lyrics are also taken from the frame when the `tag` has none.
+pub(crate) fn add_id3v2_lyrics(tag: &mut Tag, id3v2: &Id3v2Tag) {
+    let frames: Vec<&UnsynchronizedTextFrame> = id3v2.unsync_text().collect();
+    let frame = frames
+        .iter()
+        .find(|frame| tag.lyrics.as_ref() == Some(&frame.content))
+        .or_else(|| frames.first());
+    let Some(frame) = frame else {
+        return;
+    };
+    if tag.lyrics.is_none() {
+        tag.lyrics = Some(frame.content.clone());
+    }
+    tag.lyrics_language = language_of(frame.language);
+    tag.lyrics_description = (!frame.description.is_empty()).then(|| frame.description.clone());
+}
+
+/// The lyrics of the ID3v2 tags among `tags` having a language or a description,
+/// to write with [insert_id3v2_lyrics]. The others are written by lofty.
+///
+/// Fails when the language isn't a 3 letters ISO 639-2 code, e.g. `eng`,
+/// so before anything is written.
+pub(crate) fn id3v2_lyrics(tags: &[Tag]) -> anyhow::Result<Option<UnsyncedLyrics>> {
+    let tag = tags.iter().find(|t| {
+        t.tag_type == TagType::Id3v2
+            && t.lyrics.is_some()
+            && (t.lyrics_language.is_some() || t.lyrics_description.is_some())
+    });
+    let Some(tag) = tag else {
+        return Ok(None);
+    };
+    let language = match &tag.lyrics_language {
+        Some(language) => parse_language(language)?,
+        None => UNKNOWN_LANGUAGE,
+    };
+    Ok(Some(UnsyncedLyrics {
+        content: tag.lyrics.clone().unwrap_or_default(),
+        language,
+        description: tag.lyrics_description.clone().unwrap_or_default(),
+    }))
+}
+
+/// Replaces the `USLT` frames of the `id3v2` tag with the given `lyrics`.
+pub(crate) fn insert_id3v2_lyrics(
+    id3v2: &mut Id3v2Tag,
+    lyrics: &UnsyncedLyrics,
+) -> anyhow::Result<()> {
+    id3v2.remove("USLT");
+    let frame = UnsynchronizedTextFrame {
+        encoding: TextEncoding::UTF8,
+        language: lyrics.language,
+        description: lyrics.description.clone(),
+        content: lyrics.content.clone(),
+    };
+    id3v2.insert(Frame::new("USLT", frame, FrameFlags::default())?);
+    Ok(())
+}
+
+/// The ISO 639-2 code of the `language` of an ID3v2 frame, `None` when it's unknown.
+pub(crate) fn language_of(language: [u8; 3]) -> Option<String> {
+    (language != UNKNOWN_LANGUAGE && language != [0; 3])
+        .then(|| String::from_utf8_lossy(&language).to_lowercase())
+}
+
+/// Parses the 3 letters ISO 639-2 `language` code of the ID3v2 frames, e.g. `eng`.
+pub(crate) fn parse_language(language: &str) -> anyhow::Result<[u8; 3]> {
+    let code = language.trim().to_lowercase();
+    match code.as_bytes() {
+        [a, b, c] if code.chars().all(|c| c.is_ascii_lowercase()) => Ok([*a, *b, *c]),
+        _ => Err(TaggyError::invalid_value(format!(
+            "'{language}' is not a valid ISO 639-2 language code!"
+        ))
+        .into()),
+    }
+}
@@ -0,0 +1,54 @@
+use crate::lyrics_match::LyricsMatch;
+
+/// Finds the occurrences of the `query` in the `lyrics` of the file at `path`, ignoring the
+/// case and treating every whitespace as a space, e.g. a line break.
+///
+/// Returns `None` when the `query` isn't found.
+pub(crate) fn match_lyrics(path: &str, lyrics: &str, query: &str) -> Option<LyricsMatch> {
+    let occurrences = find_occurrences(lyrics, query);
+    let (start, end) = *occurrences.first()?;
+    let snippet_start = lyrics[..start].rfind('\n').map_or(0, |i| i + 1);
+    let snippet_end = lyrics[end..].find('\n').map_or(lyrics.len(), |i| end + i);
+    let snippet = lyrics[snippet_start..snippet_end].trim_end_matches('\r');
+    Some(LyricsMatch {
+        path: path.to_string(),
+        snippet: snippet.to_string(),
+        snippet_offset: utf16_len(&lyrics[snippet_start..start]),
+        snippet_length: utf16_len(&lyrics[start..end]),
+        offsets: occurrences
+            .iter()
+            .map(|(start, _)| utf16_len(&lyrics[..*start]))
+            .collect(),
+    })
+}
+
+/// The byte ranges of the non overlapping occurrences of the `query` in the `text`.
+fn find_occurrences(text: &str, query: &str) -> Vec<(usize, usize)> {
+    let query: Vec<char> = query.chars().flat_map(folded).collect();
+    // each folded char along with the byte range of the char it comes from.
+    let folded_text: Vec<(usize, usize, char)> = text
+        .char_indices()
+        .flat_map(|(i, c)| folded(c).map(move |f| (i, i + c.len_utf8(), f)))
+        .collect();
+    let mut occurrences = vec![];
+    let mut i = 0;
+    while !query.is_empty() && i + query.len() <= folded_text.len() {
+        let window = &folded_text[i..i + query.len()];
+        if window.iter().map(|(_, _, c)| *c).eq(query.iter().copied()) {
+            occurrences.push((window[0].0, window[query.len() - 1].1));
+            i += query.len();
+        } else {
+            i += 1;
+        }
+    }
+    occurrences
+}
+
+fn folded(c: char) -> impl Iterator<Item = char> {
+    let c = if c.is_whitespace() { ' ' } else { c };
+    c.to_lowercase()
+}
+
+fn utf16_len(text: &str) -> u32 {
+    text.encode_utf16().count() as u32
+}
@@ -0,0 +1,55 @@
+use crate::effective_tag::{EffectiveTag, FieldSource};
+use crate::tag::{Tag, TagType};
+use crate::utils::tag_fields::{for_each_multi_value_field, for_each_tag_field};
+
+/// Merges the given `tags` into a single tag, recording where each value came from.
+///
+/// Each field takes the value of the first tag having it, with the `primary_tag_type` tag first
+/// and the others in their order in the file. The custom items are merged by key the same way.
+pub(crate) fn merge_tags(tags: &[Tag], primary_tag_type: TagType) -> EffectiveTag {
+    let mut ordered: Vec<&Tag> = tags.iter().collect();
+    // a stable sort, so the other tags keep their order.
+    ordered.sort_by_key(|t| t.tag_type != primary_tag_type);
+
+    let mut tag = Tag::new(primary_tag_type);
+    let mut sources = vec![];
+    for source in ordered {
+        macro_rules! take {
+            ($field:ident) => {
+                if tag.$field.is_none() && source.$field.is_some() {
+                    tag.$field = source.$field.clone();
+                    sources.push(FieldSource {
+                        field: stringify!($field).to_string(),
+                        tag_type: source.tag_type,
+                    });
+                }
+            };
+        }
+        for_each_tag_field!(take);
+        macro_rules! take_values {
+            ($field:ident) => {
+                if tag.$field.is_empty() && !source.$field.is_empty() {
+                    tag.$field = source.$field.clone();
+                    sources.push(FieldSource {
+                        field: stringify!($field).to_string(),
+                        tag_type: source.tag_type,
+                    });
+                }
+            };
+        }
+        for_each_multi_value_field!(take_values);
+        for item in &source.custom_items {
+            if !tag.custom_items.iter().any(|i| i.key == item.key) {
+                tag.custom_items.push(item.clone());
+            }
+        }
+        if tag.pictures.is_empty() && !source.pictures.is_empty() {
+            tag.pictures = source.pictures.clone();
+            sources.push(FieldSource {
+                field: "pictures".to_string(),
+                tag_type: source.tag_type,
+            });
+        }
+    }
+    EffectiveTag { tag, sources }
+}
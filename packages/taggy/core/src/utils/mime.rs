@@ -0,0 +1,19 @@
+/// Detects the mime type of the image `data` from its magic bytes,
+/// for the JPEG, PNG, GIF, BMP, WebP and TIFF formats.
+///
+/// WebP has no [lofty::MimeType] variant, so it's returned as `image/webp` in
+/// [lofty::MimeType::Unknown], which is written as is.
+pub(crate) fn detect_mime_type(data: &[u8]) -> Option<lofty::MimeType> {
+    let mime_type = match data {
+        [0xFF, 0xD8, 0xFF, ..] => lofty::MimeType::Jpeg,
+        [0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, ..] => lofty::MimeType::Png,
+        [b'G', b'I', b'F', b'8', b'7' | b'9', b'a', ..] => lofty::MimeType::Gif,
+        [b'B', b'M', ..] => lofty::MimeType::Bmp,
+        [b'R', b'I', b'F', b'F', _, _, _, _, b'W', b'E', b'B', b'P', ..] => {
+            lofty::MimeType::Unknown("image/webp".to_string())
+        }
+        [b'I', b'I', 0x2A, 0x00, ..] | [b'M', b'M', 0x00, 0x2A, ..] => lofty::MimeType::Tiff,
+        _ => return None,
+    };
+    Some(mime_type)
+}
@@ -0,0 +1,66 @@
+pub(crate) mod album_art;
+#[cfg(all(feature = "artwork", not(target_arch = "wasm32")))]
+pub(crate) mod artwork_optimizer;
+pub(crate) mod artwork_rules;
+pub(crate) mod buffer;
+pub(crate) mod chapters;
+pub(crate) mod comments;
+pub(crate) mod custom_items;
+pub(crate) mod date_added;
+pub(crate) mod dates;
+pub(crate) mod discs;
+pub(crate) mod dj;
+pub(crate) mod duplicates;
+pub(crate) mod duration;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod featuring;
+pub(crate) mod file_id;
+pub(crate) mod file_utils;
+pub(crate) mod freeform_fields;
+pub(crate) mod fuzzy;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod histogram;
+pub(crate) mod id3v2_frames;
+#[cfg(feature = "artwork")]
+pub(crate) mod images;
+pub(crate) mod integrity;
+pub(crate) mod isrc;
+pub(crate) mod lint;
+pub(crate) mod lofty_froms;
+pub(crate) mod lofty_intos;
+pub(crate) mod lrc;
+pub(crate) mod lyrics;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod lyrics_search;
+pub(crate) mod merge;
+pub(crate) mod mime;
+pub(crate) mod mp4_chapters;
+pub(crate) mod opus_head;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod path_locks;
+pub(crate) mod picture_blocks;
+pub(crate) mod picture_data;
+pub(crate) mod picture_meta;
+pub(crate) mod picture_order;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod pool;
+pub(crate) mod properties;
+pub(crate) mod protection;
+pub(crate) mod rating;
+pub(crate) mod raw_id3v2;
+pub(crate) mod raw_tag;
+pub(crate) mod read_only;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod release_year;
+pub(crate) mod replay_gain;
+pub(crate) mod sidecar;
+pub(crate) mod snapshot;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod staging;
+pub(crate) mod strip_fields;
+pub(crate) mod sylt;
+pub(crate) mod tag_fields;
+#[cfg(not(target_arch = "wasm32"))]
+pub(crate) mod track_gaps;
+pub(crate) mod truncation;
+pub(crate) mod work;
@@ -0,0 +1,539 @@
+use crate::chapter::Chapter;
+use crate::error::TaggyError;
+use crate::utils::file_utils::splice_file;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// The boxes holding other boxes, among the ones walked to find the chapters.
+const CONTAINERS: [&[u8; 4]; 9] = [
+    b"moov", b"trak", b"mdia", b"minf", b"stbl", b"udta", b"tref", b"edts", b"dinf",
+];
+/// The Nero chapters atom, in the `moov.udta` box.
+const CHPL: &[u8; 4] = b"chpl";
+/// The `tref` entry pointing to the chapter track of an audio track.
+const CHAP: &[u8; 4] = b"chap";
+/// The unit of the Nero chapters start times, in a second.
+const CHPL_TIMESCALE: u64 = 10_000_000;
+
+/// A box of an MP4 file, with its children when it holds other boxes, see [CONTAINERS].
+#[derive(Debug, Clone)]
+enum Mp4Box {
+    Leaf {
+        kind: [u8; 4],
+        data: Vec<u8>,
+    },
+    Container {
+        kind: [u8; 4],
+        children: Vec<Mp4Box>,
+    },
+}
+
+impl Mp4Box {
+    fn kind(&self) -> &[u8; 4] {
+        match self {
+            Mp4Box::Leaf { kind, .. } | Mp4Box::Container { kind, .. } => kind,
+        }
+    }
+
+    fn children(&self) -> &[Mp4Box] {
+        match self {
+            Mp4Box::Container { children, .. } => children,
+            Mp4Box::Leaf { .. } => &[],
+        }
+    }
+
+    fn child(&self, kind: &[u8; 4]) -> Option<&Mp4Box> {
+        self.children().iter().find(|b| b.kind() == kind)
+    }
+
+    /// The content of the leaf box at the `path` of kinds under this one.
+    fn leaf(&self, path: &[&[u8; 4]]) -> Option<&[u8]> {
+        let (last, containers) = path.split_last()?;
+        let mut current = self;
+        for kind in containers {
+            current = current.child(kind)?;
+        }
+        match current.child(last)? {
+            Mp4Box::Leaf { data, .. } => Some(data),
+            Mp4Box::Container { .. } => None,
+        }
+    }
+
+    fn size(&self) -> u64 {
+        8 + match self {
+            Mp4Box::Leaf { data, .. } => data.len() as u64,
+            Mp4Box::Container { children, .. } => children.iter().map(Mp4Box::size).sum(),
+        }
+    }
+
+    fn write_to(&self, out: &mut Vec<u8>) -> anyhow::Result<()> {
+        let size = u32::try_from(self.size())
+            .map_err(|_| TaggyError::invalid_value("The MP4 metadata is too large!"))?;
+        out.extend(size.to_be_bytes());
+        out.extend(self.kind());
+        match self {
+            Mp4Box::Leaf { data, .. } => out.extend(data),
+            Mp4Box::Container { children, .. } => {
+                for child in children {
+                    child.write_to(out)?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A top-level box of an MP4 file, located by its header.
+#[derive(Debug, Clone, Copy)]
+struct TopLevelBox {
+    kind: [u8; 4],
+    offset: u64,
+    header_len: u64,
+    size: u64,
+}
+
+/// Reads the chapters of the MP4 file of the `reader`, from its chapter track when it has one,
+/// as written by iTunes, or else from its Nero `chpl` atom.
+pub(crate) fn read_mp4_chapters_from<R: Read + Seek>(
+    reader: &mut R,
+) -> anyhow::Result<Vec<Chapter>> {
+    let Some(moov) = read_moov(reader)? else {
+        return Ok(vec![]);
+    };
+    let chapters = match chapter_track(&moov) {
+        Some(track) => read_track_chapters(reader, track)?,
+        None => read_chpl(&moov),
+    };
+    Ok(chapters)
+}
+
+/// Replaces the chapters of the MP4 file at `path` with the Nero `chpl` atom of the `chapters`,
+/// sorted by start time, or removes it when there's none.
+///
+/// The chapter track of the file, if any, is unlinked from its audio track so players don't show
+/// its outdated chapters. When the `moov` box comes before the audio data, the chunk offsets are
+/// updated and the file is rewritten through a copy, otherwise only its end is rewritten.
+pub(crate) fn write_mp4_chapters(path: &str, chapters: &[Chapter]) -> anyhow::Result<()> {
+    if chapters.len() > u8::MAX as usize {
+        return Err(TaggyError::invalid_value(format!(
+            "MP4 files hold up to 255 chapters, not {}!",
+            chapters.len()
+        ))
+        .into());
+    }
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let boxes = top_level_boxes(&mut file)?;
+    let Some(moov_box) = boxes.iter().find(|b| &b.kind == b"moov").copied() else {
+        return Err(TaggyError::UnsupportedFormat.into());
+    };
+    let mut moov = read_box(&mut file, moov_box)?;
+
+    let Mp4Box::Container { children, .. } = &mut moov else {
+        return Err(TaggyError::UnsupportedFormat.into());
+    };
+    for trak in children.iter_mut().filter(|b| b.kind() == b"trak") {
+        unlink_chapter_track(trak);
+    }
+    if !children.iter().any(|b| b.kind() == b"udta") {
+        children.push(Mp4Box::Container {
+            kind: *b"udta",
+            children: vec![],
+        });
+    }
+    if let Some(Mp4Box::Container { children: udta, .. }) =
+        children.iter_mut().find(|b| b.kind() == b"udta")
+    {
+        udta.retain(|b| b.kind() != CHPL);
+        if !chapters.is_empty() {
+            udta.push(Mp4Box::Leaf {
+                kind: *CHPL,
+                data: chpl_bytes(chapters),
+            });
+        }
+    }
+
+    let delta = moov.size() as i64 - moov_box.size as i64;
+    let moov_is_last = boxes.last().is_some_and(|b| b.offset == moov_box.offset);
+    if !moov_is_last && delta != 0 {
+        // the audio data after the `moov` box moves along with it.
+        shift_chunk_offsets(&mut moov, moov_box.offset, delta)?;
+    }
+    let mut bytes = Vec::with_capacity(moov.size() as usize);
+    moov.write_to(&mut bytes)?;
+
+    if moov_is_last {
+        file.seek(SeekFrom::Start(moov_box.offset))?;
+        file.write_all(&bytes)?;
+        file.set_len(moov_box.offset + bytes.len() as u64)?;
+        return Ok(());
+    }
+    drop(file);
+    // through a copy, so a failure leaves the file as it was.
+    splice_file(
+        path,
+        moov_box.offset,
+        moov_box.offset + moov_box.size,
+        &bytes,
+        |_| Ok(()),
+    )
+}
+
+fn top_level_boxes<R: Read + Seek>(file: &mut R) -> anyhow::Result<Vec<TopLevelBox>> {
+    let file_len = file.seek(SeekFrom::End(0))?;
+    let mut boxes = vec![];
+    let mut offset = 0;
+    while offset + 8 <= file_len {
+        file.seek(SeekFrom::Start(offset))?;
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header)?;
+        let kind: [u8; 4] = header[4..8].try_into()?;
+        let (size, header_len) = match u32::from_be_bytes(header[0..4].try_into()?) {
+            // the box extends to the end of the file.
+            0 => (file_len - offset, 8),
+            1 => {
+                let mut large_size = [0u8; 8];
+                file.read_exact(&mut large_size)?;
+                (u64::from_be_bytes(large_size), 16)
+            }
+            size => (size as u64, 8),
+        };
+        if size < header_len || offset + size > file_len {
+            break;
+        }
+        boxes.push(TopLevelBox {
+            kind,
+            offset,
+            header_len,
+            size,
+        });
+        offset += size;
+    }
+    Ok(boxes)
+}
+
+fn read_moov<R: Read + Seek>(file: &mut R) -> anyhow::Result<Option<Mp4Box>> {
+    let boxes = top_level_boxes(file)?;
+    match boxes.into_iter().find(|b| &b.kind == b"moov") {
+        Some(moov) => Ok(Some(read_box(file, moov)?)),
+        None => Ok(None),
+    }
+}
+
+fn read_box<R: Read + Seek>(file: &mut R, top_level: TopLevelBox) -> anyhow::Result<Mp4Box> {
+    file.seek(SeekFrom::Start(top_level.offset + top_level.header_len))?;
+    let mut data = vec![0u8; (top_level.size - top_level.header_len) as usize];
+    file.read_exact(&mut data)?;
+    Ok(parse_box(top_level.kind, data))
+}
+
+fn parse_box(kind: [u8; 4], data: Vec<u8>) -> Mp4Box {
+    if !CONTAINERS.contains(&&kind) {
+        return Mp4Box::Leaf { kind, data };
+    }
+    let mut children = vec![];
+    let mut rest = data.as_slice();
+    while rest.len() >= 8 {
+        let size = u32::from_be_bytes([rest[0], rest[1], rest[2], rest[3]]) as usize;
+        let child_kind = [rest[4], rest[5], rest[6], rest[7]];
+        let (header_len, size) = match size {
+            0 => (8, rest.len()),
+            1 if rest.len() >= 16 => {
+                let large_size: [u8; 8] = rest[8..16].try_into().unwrap_or_default();
+                (16, u64::from_be_bytes(large_size) as usize)
+            }
+            size => (8, size),
+        };
+        if size < header_len || size > rest.len() {
+            // keep what can't be parsed as is.
+            return Mp4Box::Leaf { kind, data };
+        }
+        children.push(parse_box(child_kind, rest[header_len..size].to_vec()));
+        rest = &rest[size..];
+    }
+    Mp4Box::Container { kind, children }
+}
+
+/// The track the audio track points to as its chapter track, with its `tref.chap` entry.
+fn chapter_track(moov: &Mp4Box) -> Option<&Mp4Box> {
+    let tracks: Vec<&Mp4Box> = moov
+        .children()
+        .iter()
+        .filter(|b| b.kind() == b"trak")
+        .collect();
+    let chapter_ids: Vec<u32> = tracks
+        .iter()
+        .filter_map(|t| t.leaf(&[b"tref", CHAP]))
+        .flat_map(|ids| ids.chunks_exact(4).map(be_u32))
+        .collect();
+    tracks
+        .into_iter()
+        .find(|t| track_id(t).is_some_and(|id| chapter_ids.contains(&id)))
+}
+
+fn track_id(trak: &Mp4Box) -> Option<u32> {
+    let tkhd = trak.leaf(&[b"tkhd"])?;
+    let at = if tkhd.first()? == &1 { 20 } else { 12 };
+    tkhd.get(at..at + 4).map(be_u32)
+}
+
+/// Reads the chapters of the text `track`, each sample holding the title of a chapter
+/// and lasting as long as it.
+fn read_track_chapters<R: Read + Seek>(
+    file: &mut R,
+    track: &Mp4Box,
+) -> anyhow::Result<Vec<Chapter>> {
+    let Some(samples) = track_samples(track) else {
+        return Ok(vec![]);
+    };
+    let mut chapters = Vec::with_capacity(samples.len());
+    for sample in samples {
+        file.seek(SeekFrom::Start(sample.offset))?;
+        let mut data = vec![0u8; sample.size.min(u16::MAX as u32 + 2) as usize];
+        file.read_exact(&mut data)?;
+        chapters.push(Chapter {
+            start_ms: sample.start_ms,
+            end_ms: sample.end_ms,
+            title: decode_text_sample(&data),
+            url: None,
+            picture: None,
+        });
+    }
+    Ok(chapters)
+}
+
+/// A sample of a track, located in the file and timed in milliseconds.
+struct Sample {
+    offset: u64,
+    size: u32,
+    start_ms: u32,
+    end_ms: u32,
+}
+
+/// The samples of the `track`, from its sample tables, `None` when they're missing or malformed.
+fn track_samples(track: &Mp4Box) -> Option<Vec<Sample>> {
+    let mdhd = track.leaf(&[b"mdia", b"mdhd"])?;
+    let timescale = be_u32(mdhd.get(if mdhd.first()? == &1 { 20..24 } else { 12..16 })?);
+    if timescale == 0 {
+        return None;
+    }
+    let stbl = |kind: &[u8; 4]| track.leaf(&[b"mdia", b"minf", b"stbl", kind]);
+
+    let mut durations = vec![];
+    for entry in table(stbl(b"stts")?, 8)? {
+        durations.extend(std::iter::repeat_n(
+            be_u32(&entry[4..8]),
+            be_u32(&entry[0..4]) as usize,
+        ));
+    }
+    let stsz = stbl(b"stsz")?;
+    let fixed_size = be_u32(stsz.get(4..8)?);
+    let sizes: Vec<u32> = match fixed_size {
+        0 => stsz.get(12..)?.chunks_exact(4).map(be_u32).collect(),
+        size => vec![size; be_u32(stsz.get(8..12)?) as usize],
+    };
+    let chunk_offsets: Vec<u64> = match stbl(b"stco") {
+        Some(stco) => table(stco, 4)?.map(|o| be_u32(o) as u64).collect(),
+        None => table(stbl(b"co64")?, 8)?
+            .map(|o| u64::from_be_bytes(o.try_into().unwrap_or_default()))
+            .collect(),
+    };
+    let sample_to_chunk: Vec<(u32, u32)> = table(stbl(b"stsc")?, 12)?
+        .map(|e| (be_u32(&e[0..4]), be_u32(&e[4..8])))
+        .collect();
+
+    let mut samples = vec![];
+    let mut time = 0u64;
+    for (index, chunk_offset) in chunk_offsets.iter().enumerate() {
+        let chunk = index as u32 + 1;
+        let per_chunk = sample_to_chunk
+            .iter()
+            .rev()
+            .find(|(first_chunk, _)| *first_chunk <= chunk)
+            .map(|(_, per_chunk)| *per_chunk)?;
+        let mut offset = *chunk_offset;
+        for _ in 0..per_chunk {
+            let index = samples.len();
+            let (Some(size), Some(duration)) = (sizes.get(index), durations.get(index)) else {
+                return Some(samples);
+            };
+            let end = time + *duration as u64;
+            samples.push(Sample {
+                offset,
+                size: *size,
+                start_ms: to_ms(time, timescale as u64),
+                end_ms: to_ms(end, timescale as u64),
+            });
+            offset += *size as u64;
+            time = end;
+        }
+    }
+    Some(samples)
+}
+
+/// The entries of a sample table, after its version, flags and entry count.
+fn table(data: &[u8], entry_len: usize) -> Option<impl Iterator<Item = &[u8]>> {
+    let count = be_u32(data.get(4..8)?) as usize;
+    let entries = data.get(8..8 + count.checked_mul(entry_len)?)?;
+    Some(entries.chunks_exact(entry_len))
+}
+
+/// Decodes a text sample: its length, then its UTF-8 or UTF-16 text.
+fn decode_text_sample(data: &[u8]) -> Option<String> {
+    let len = u16::from_be_bytes([*data.first()?, *data.get(1)?]) as usize;
+    let text = data.get(2..2 + len)?;
+    let title = match text {
+        [0xFE, 0xFF, rest @ ..] => {
+            let units: Vec<u16> = rest
+                .chunks_exact(2)
+                .map(|c| u16::from_be_bytes([c[0], c[1]]))
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        _ => String::from_utf8_lossy(text).to_string(),
+    };
+    Some(title).filter(|t| !t.is_empty())
+}
+
+/// Reads the Nero chapters of the `moov` box, each one ending when the next one starts
+/// and the last one with the track.
+fn read_chpl(moov: &Mp4Box) -> Vec<Chapter> {
+    let Some(chpl) = moov.leaf(&[b"udta", CHPL]) else {
+        return vec![];
+    };
+    let mut starts: Vec<(u32, Option<String>)> = vec![];
+    // its version, flags and, from the version 1, 4 reserved bytes.
+    let mut at = if chpl.first() == Some(&1) { 8 } else { 4 };
+    let count = chpl.get(at).copied().unwrap_or_default();
+    at += 1;
+    for _ in 0..count {
+        let (Some(start), Some(len)) = (chpl.get(at..at + 8), chpl.get(at + 8)) else {
+            break;
+        };
+        let start = u64::from_be_bytes(start.try_into().unwrap_or_default());
+        let Some(title) = chpl.get(at + 9..at + 9 + *len as usize) else {
+            break;
+        };
+        starts.push((
+            to_ms(start, CHPL_TIMESCALE),
+            Some(String::from_utf8_lossy(title).to_string()).filter(|t| !t.is_empty()),
+        ));
+        at += 9 + *len as usize;
+    }
+
+    let duration_ms = movie_duration_ms(moov).unwrap_or(u32::MAX);
+    let ends: Vec<u32> = starts
+        .iter()
+        .skip(1)
+        .map(|(start, _)| *start)
+        .chain([duration_ms])
+        .collect();
+    starts
+        .into_iter()
+        .zip(ends)
+        .map(|((start_ms, title), end_ms)| Chapter {
+            start_ms,
+            end_ms: end_ms.max(start_ms),
+            title,
+            url: None,
+            picture: None,
+        })
+        .collect()
+}
+
+fn movie_duration_ms(moov: &Mp4Box) -> Option<u32> {
+    let mvhd = moov.leaf(&[b"mvhd"])?;
+    let (timescale, duration) = match mvhd.first()? {
+        1 => (
+            be_u32(mvhd.get(20..24)?),
+            u64::from_be_bytes(mvhd.get(24..32)?.try_into().ok()?),
+        ),
+        _ => (be_u32(mvhd.get(12..16)?), be_u32(mvhd.get(16..20)?) as u64),
+    };
+    (timescale != 0).then(|| to_ms(duration, timescale as u64))
+}
+
+/// The Nero `chpl` atom content of the `chapters`: version 1, 4 reserved bytes,
+/// then the start time and title of each chapter.
+fn chpl_bytes(chapters: &[Chapter]) -> Vec<u8> {
+    let mut data = vec![1, 0, 0, 0, 0, 0, 0, 0, chapters.len() as u8];
+    for chapter in chapters {
+        let start = chapter.start_ms as u64 * CHPL_TIMESCALE / 1000;
+        data.extend(start.to_be_bytes());
+        let title = truncate_utf8(chapter.title.as_deref().unwrap_or_default(), 255);
+        data.push(title.len() as u8);
+        data.extend(title.as_bytes());
+    }
+    data
+}
+
+/// Removes the `tref.chap` entry of the `trak`, and its `tref` box once empty.
+fn unlink_chapter_track(trak: &mut Mp4Box) {
+    let Mp4Box::Container { children, .. } = trak else {
+        return;
+    };
+    for tref in children.iter_mut() {
+        if let Mp4Box::Container {
+            kind,
+            children: refs,
+        } = tref
+        {
+            if kind == b"tref" {
+                refs.retain(|b| b.kind() != CHAP);
+            }
+        }
+    }
+    children.retain(|b| b.kind() != b"tref" || !b.children().is_empty());
+}
+
+/// Adds `delta` to the chunk offsets of all the tracks of the `moov` box pointing after
+/// its `moov_offset`, since the data there moves when the `moov` box changes size.
+fn shift_chunk_offsets(moov: &mut Mp4Box, moov_offset: u64, delta: i64) -> anyhow::Result<()> {
+    let shift = |offset: u64| -> anyhow::Result<u64> {
+        if offset < moov_offset {
+            return Ok(offset);
+        }
+        offset
+            .checked_add_signed(delta)
+            .ok_or_else(|| TaggyError::invalid_value("The MP4 chunk offsets overflow!").into())
+    };
+    match moov {
+        Mp4Box::Container { children, .. } => {
+            for child in children {
+                shift_chunk_offsets(child, moov_offset, delta)?;
+            }
+        }
+        Mp4Box::Leaf { kind, data } if kind == b"stco" => {
+            for entry in data.get_mut(8..).unwrap_or_default().chunks_exact_mut(4) {
+                let offset = u32::try_from(shift(be_u32(entry) as u64)?).map_err(|_| {
+                    TaggyError::invalid_value("The MP4 chunk offsets don't fit 32 bits!")
+                })?;
+                entry.copy_from_slice(&offset.to_be_bytes());
+            }
+        }
+        Mp4Box::Leaf { kind, data } if kind == b"co64" => {
+            for entry in data.get_mut(8..).unwrap_or_default().chunks_exact_mut(8) {
+                let offset = shift(u64::from_be_bytes(entry.try_into()?))?;
+                entry.copy_from_slice(&offset.to_be_bytes());
+            }
+        }
+        Mp4Box::Leaf { .. } => {}
+    }
+    Ok(())
+}
+
+fn be_u32(bytes: &[u8]) -> u32 {
+    u32::from_be_bytes(bytes[..4].try_into().unwrap_or_default())
+}
+
+fn to_ms(time: u64, timescale: u64) -> u32 {
+    (time * 1000 / timescale).min(u32::MAX as u64) as u32
+}
+
+/// The longest start of `text` holding in `max_len` bytes, without splitting a character.
+fn truncate_utf8(text: &str, max_len: usize) -> &str {
+    let mut end = text.len().min(max_len);
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    &text[..end]
+}
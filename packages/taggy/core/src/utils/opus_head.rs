@@ -0,0 +1,85 @@
+use crate::error::TaggyError;
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom, Write};
+
+/// The size of an Ogg page header without its segment table.
+const OGG_PAGE_HEADER_LEN: usize = 27;
+
+/// Where the CRC is in an Ogg page header.
+const OGG_CRC_OFFSET: usize = 22;
+
+/// Where the output gain is in the `OpusHead` packet.
+const OUTPUT_GAIN_OFFSET: usize = 16;
+
+/// Reads the output gain of the `OpusHead` packet of the Opus file at `path`,
+/// a Q7.8 fixed point number in dB.
+pub(crate) fn read_output_gain(path: &str) -> anyhow::Result<i16> {
+    let mut file = OpenOptions::new().read(true).open(path)?;
+    let page = read_first_page(&mut file)?;
+    let gain = output_gain_offset(&page)?;
+    Ok(i16::from_le_bytes([page[gain], page[gain + 1]]))
+}
+
+/// Writes the output `gain` to the `OpusHead` packet of the Opus file at `path`,
+/// updating the CRC of its page.
+///
+/// The page keeps its size, so nothing else of the file is moved.
+pub(crate) fn write_output_gain(path: &str, gain: i16) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let mut page = read_first_page(&mut file)?;
+    let offset = output_gain_offset(&page)?;
+    page[offset..offset + 2].copy_from_slice(&gain.to_le_bytes());
+    page[OGG_CRC_OFFSET..OGG_CRC_OFFSET + 4].fill(0);
+    let crc = ogg_crc(&page);
+    page[OGG_CRC_OFFSET..OGG_CRC_OFFSET + 4].copy_from_slice(&crc.to_le_bytes());
+    file.seek(SeekFrom::Start(0))?;
+    file.write_all(&page)?;
+    Ok(())
+}
+
+/// Reads the first Ogg page of the `file`, which holds only the `OpusHead` packet.
+fn read_first_page(file: &mut (impl Read + Seek)) -> anyhow::Result<Vec<u8>> {
+    file.seek(SeekFrom::Start(0))?;
+    let mut page = vec![0; OGG_PAGE_HEADER_LEN];
+    file.read_exact(&mut page).map_err(|_| corrupt_head())?;
+    if &page[..4] != b"OggS" {
+        return Err(corrupt_head().into());
+    }
+    let mut segments = vec![0; page[26] as usize];
+    file.read_exact(&mut segments).map_err(|_| corrupt_head())?;
+    let mut data = vec![0; segments.iter().map(|s| *s as usize).sum()];
+    file.read_exact(&mut data).map_err(|_| corrupt_head())?;
+    page.extend(segments);
+    page.extend(data);
+    Ok(page)
+}
+
+/// Where the output gain is in the first `page`, checking it holds an `OpusHead` packet.
+fn output_gain_offset(page: &[u8]) -> anyhow::Result<usize> {
+    let packet = OGG_PAGE_HEADER_LEN + page[26] as usize;
+    let offset = packet + OUTPUT_GAIN_OFFSET;
+    if !page[packet..].starts_with(b"OpusHead") || page.len() < offset + 2 {
+        return Err(corrupt_head().into());
+    }
+    Ok(offset)
+}
+
+/// The CRC-32 of an Ogg page, see RFC 3533: polynomial `0x04c11db7`, no reflection,
+/// no initial nor final XOR.
+pub(crate) fn ogg_crc(data: &[u8]) -> u32 {
+    data.iter().fold(0u32, |crc, byte| {
+        (0..8).fold(crc ^ ((*byte as u32) << 24), |crc, _| {
+            if crc & 0x8000_0000 != 0 {
+                (crc << 1) ^ 0x04c1_1db7
+            } else {
+                crc << 1
+            }
+        })
+    })
+}
+
+fn corrupt_head() -> TaggyError {
+    TaggyError::CorruptTag {
+        message: "The file doesn't start with a valid OpusHead page!".to_string(),
+    }
+}
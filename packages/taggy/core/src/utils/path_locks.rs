@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, PoisonError, RwLock};
+
+type PathLocks = HashMap<PathBuf, Arc<RwLock<()>>>;
+
+/// The locks of the files currently accessed through a [TaggyClient](crate::client::TaggyClient),
+/// shared by all the clients of the process so two isolates writing the same file don't
+/// interleave their writes.
+static PATH_LOCKS: OnceLock<Mutex<PathLocks>> = OnceLock::new();
+
+/// Runs the read `op` on the file at `path` once no other thread is writing it.
+pub(crate) fn with_read_lock<T>(path: &str, op: impl FnOnce() -> T) -> T {
+    let key = lock_key(path);
+    let lock = path_lock(&key);
+    let result = {
+        let _guard = lock.read().unwrap_or_else(PoisonError::into_inner);
+        op()
+    };
+    release(&key, lock);
+    result
+}
+
+/// Runs the write `op` on the file at `path` once no other thread is reading or writing it.
+pub(crate) fn with_write_lock<T>(path: &str, op: impl FnOnce() -> T) -> T {
+    let key = lock_key(path);
+    let lock = path_lock(&key);
+    let result = {
+        let _guard = lock.write().unwrap_or_else(PoisonError::into_inner);
+        op()
+    };
+    release(&key, lock);
+    result
+}
+
+/// The same file may be given with different paths, e.g. a relative and an absolute one.
+fn lock_key(path: &str) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| PathBuf::from(path))
+}
+
+fn locks() -> MutexGuard<'static, PathLocks> {
+    PATH_LOCKS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+}
+
+fn path_lock(key: &Path) -> Arc<RwLock<()>> {
+    locks().entry(key.to_path_buf()).or_default().clone()
+}
+
+/// Drops the lock of the file at `key` once no other thread holds it,
+/// so the registry doesn't grow with every file ever accessed.
+fn release(key: &Path, lock: Arc<RwLock<()>>) {
+    let mut locks = locks();
+    drop(lock);
+    if locks.get(key).is_some_and(|l| Arc::strong_count(l) == 1) {
+        locks.remove(key);
+    }
+}
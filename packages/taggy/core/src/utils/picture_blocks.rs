@@ -0,0 +1,130 @@
+use crate::picture::Picture;
+use crate::utils::lofty_intos::get_pic_from_data;
+use crate::utils::read_only::open_read_only;
+use lofty::flac::FlacFile;
+use lofty::ogg::{OggPictureStorage, OpusFile, SpeexFile, VorbisComments, VorbisFile};
+use lofty::{
+    AudioFile, BoundTaggedFile, FileType, ParseOptions, PictureInformation, TagType, TaggedFileExt,
+};
+
+type PictureBlock = (lofty::Picture, PictureInformation);
+
+/// The `pictures` to pass to [vorbis_comments_with_information] for a file of `file_type`,
+/// i.e. the ones with any image information when the file stores FLAC picture blocks.
+pub(crate) fn pictures_with_information<'a>(
+    file_type: FileType,
+    pictures: impl IntoIterator<Item = &'a Picture>,
+) -> Vec<Picture> {
+    if !has_picture_blocks(file_type) {
+        return vec![];
+    }
+    pictures
+        .into_iter()
+        .filter(|picture| has_information(picture))
+        .cloned()
+        .collect()
+}
+
+/// The Vorbis comments of the `file` at `path` with the `width`, `height`, `color_depth` and
+/// `num_colors` of the given `pictures` in their picture blocks, which store them along with the
+/// picture data, to save in place of its generic Vorbis comments with
+/// [save_tags_with](crate::utils::id3v2_frames::save_tags_with).
+///
+/// The picture blocks are the FLAC `PICTURE` metadata blocks, and the `METADATA_BLOCK_PICTURE`
+/// comments of the Ogg Vorbis, Opus and Speex files, which hold the same blocks in base64.
+///
+/// lofty fills these blocks from the image header when saving a generic tag, so this is only
+/// `Some` when one of the `pictures` sets a value different from the one in the file, e.g. for
+/// an image format lofty can't parse. The blocks are matched to the `pictures` by type and data,
+/// the others keep the information they have in the file.
+pub(crate) fn vorbis_comments_with_information(
+    path: &str,
+    file: &BoundTaggedFile,
+    pictures: &[Picture],
+) -> anyhow::Result<Option<VorbisComments>> {
+    if pictures.is_empty() {
+        return Ok(None);
+    }
+    let Some(tag) = file.tag(TagType::VorbisComments) else {
+        return Ok(None);
+    };
+    let existing = existing_blocks(path, file.file_type())?;
+    let mut changed = false;
+    let blocks: Vec<PictureBlock> = tag
+        .pictures()
+        .iter()
+        .cloned()
+        .map(|block| {
+            let current = existing
+                .iter()
+                .find(|(existing, _)| *existing == block)
+                .map(|(_, information)| *information);
+            let information = current
+                .unwrap_or_else(|| PictureInformation::from_picture(&block).unwrap_or_default());
+            let picture = pictures.iter().find(|p| {
+                get_pic_from_data(p).pic_type() == block.pic_type() && p.pic_data == block.data()
+            });
+            let Some(picture) = picture else {
+                return (block, information);
+            };
+            let updated = PictureInformation {
+                width: picture.width.unwrap_or(information.width),
+                height: picture.height.unwrap_or(information.height),
+                color_depth: picture.color_depth.unwrap_or(information.color_depth),
+                num_colors: picture.num_colors.unwrap_or(information.num_colors),
+            };
+            changed |= Some(updated) != current;
+            (block, updated)
+        })
+        .collect();
+    if !changed {
+        return Ok(None);
+    }
+
+    let mut generic = tag.clone();
+    for (block, _) in &blocks {
+        generic.remove_picture_type(block.pic_type());
+    }
+    let mut comments = VorbisComments::from(generic);
+    for (block, information) in blocks {
+        comments.insert_picture(block, Some(information))?;
+    }
+    Ok(Some(comments))
+}
+
+/// The picture blocks of the file at `path`, with their information as it's stored.
+fn existing_blocks(path: &str, file_type: FileType) -> anyhow::Result<Vec<PictureBlock>> {
+    let mut file = open_read_only(path)?;
+    let options = ParseOptions::new().read_properties(false);
+    let blocks = match file_type {
+        FileType::Flac => FlacFile::read_from(&mut file, options)?.pictures().to_vec(),
+        FileType::Vorbis => VorbisFile::read_from(&mut file, options)?
+            .vorbis_comments()
+            .pictures()
+            .to_vec(),
+        FileType::Opus => OpusFile::read_from(&mut file, options)?
+            .vorbis_comments()
+            .pictures()
+            .to_vec(),
+        FileType::Speex => SpeexFile::read_from(&mut file, options)?
+            .vorbis_comments()
+            .pictures()
+            .to_vec(),
+        _ => vec![],
+    };
+    Ok(blocks)
+}
+
+fn has_picture_blocks(file_type: FileType) -> bool {
+    matches!(
+        file_type,
+        FileType::Flac | FileType::Vorbis | FileType::Opus | FileType::Speex
+    )
+}
+
+fn has_information(picture: &Picture) -> bool {
+    picture.width.is_some()
+        || picture.height.is_some()
+        || picture.color_depth.is_some()
+        || picture.num_colors.is_some()
+}
@@ -0,0 +1,53 @@
+use crate::picture::Picture;
+use crate::tag::Tag;
+
+/// Empties the `pic_data` of the pictures of the given `tags`, keeping their metadata.
+pub(crate) fn strip_picture_data(tags: &mut [Tag]) {
+    for picture in tags.iter_mut().flat_map(|tag| tag.pictures.iter_mut()) {
+        picture.pic_data = vec![];
+    }
+}
+
+/// Gives back their data to the pictures of `tag` read without it,
+/// see [strip_picture_data], from the file tags before the write.
+///
+/// A stripped picture is matched with a picture of the same type and data size in
+/// the file tag of the same type, then in any other tag. The stripped pictures which
+/// can't be matched anymore are dropped, rather than written without data.
+pub(crate) fn restore_picture_data(mut tag: Tag, tags_before: &[lofty::Tag]) -> Tag {
+    if !tag.pictures.iter().any(is_stripped) {
+        return tag;
+    }
+    let tag_type: lofty::TagType = tag.tag_type.into();
+    let mut candidates: Vec<&lofty::Tag> = tags_before.iter().collect();
+    // the tag of the same type first.
+    candidates.sort_by_key(|t| t.tag_type() != tag_type);
+
+    tag.pictures = tag
+        .pictures
+        .into_iter()
+        .filter_map(|picture| {
+            if !is_stripped(&picture) {
+                return Some(picture);
+            }
+            let pic_type: lofty::PictureType = picture.pic_type.into();
+            let data = candidates
+                .iter()
+                .flat_map(|t| t.pictures())
+                .find(|p| {
+                    p.pic_type() == pic_type && Some(p.data().len() as u32) == picture.data_size
+                })?
+                .data()
+                .to_vec();
+            Some(Picture {
+                pic_data: data,
+                ..picture
+            })
+        })
+        .collect();
+    tag
+}
+
+fn is_stripped(picture: &Picture) -> bool {
+    picture.pic_data.is_empty() && picture.data_size.is_some_and(|size| size > 0)
+}
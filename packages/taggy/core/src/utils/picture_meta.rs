@@ -0,0 +1,68 @@
+use crate::error::TaggyError;
+use crate::picture::PictureChanges;
+use lofty::{BoundTaggedFile, TaggedFileExt};
+
+/// Applies the `changes` to the picture at `index` among the pictures of the `file`, in the order
+/// of [read_pictures](crate::api::read_pictures), i.e. the primary tag ones first and each
+/// picture stored in several tags once.
+///
+/// The picture is changed in all the tags storing it, its data is kept as is.
+pub(crate) fn update_picture_meta(
+    file: &mut BoundTaggedFile,
+    index: usize,
+    changes: &PictureChanges,
+) -> anyhow::Result<()> {
+    let primary_tag_type = file.primary_tag_type();
+    let mut tags: Vec<&lofty::Tag> = file.tags().iter().collect();
+    tags.sort_by_key(|t| t.tag_type() != primary_tag_type);
+    let mut pictures: Vec<&lofty::Picture> = vec![];
+    for picture in tags.iter().flat_map(|t| t.pictures()) {
+        if !pictures.iter().any(|p| is_same_picture(p, picture)) {
+            pictures.push(picture);
+        }
+    }
+    let Some(target) = pictures.get(index).map(|p| (*p).clone()) else {
+        return Err(TaggyError::invalid_value(format!(
+            "The file has {} pictures, there's none at index {index}!",
+            pictures.len()
+        ))
+        .into());
+    };
+
+    let tag_types: Vec<lofty::TagType> = file.tags().iter().map(|t| t.tag_type()).collect();
+    for tag_type in tag_types {
+        let Some(tag) = file.tag_mut(tag_type) else {
+            continue;
+        };
+        let positions: Vec<usize> = (0..tag.pictures().len())
+            .filter(|i| is_same_picture(&tag.pictures()[*i], &target))
+            .collect();
+        for position in positions {
+            let changed = changed_picture(&tag.pictures()[position], changes);
+            tag.set_picture(position, changed);
+        }
+    }
+    Ok(())
+}
+
+/// Whether the pictures are the same one, the way
+/// [read_pictures](crate::api::read_pictures) tells them apart.
+fn is_same_picture(a: &lofty::Picture, b: &lofty::Picture) -> bool {
+    a.pic_type() == b.pic_type() && a.data() == b.data()
+}
+
+fn changed_picture(picture: &lofty::Picture, changes: &PictureChanges) -> lofty::Picture {
+    let description = match &changes.description {
+        Some(description) if description.is_empty() => None,
+        Some(description) => Some(description.clone()),
+        None => picture.description().map(str::to_string),
+    };
+    lofty::Picture::new_unchecked(
+        changes.pic_type.map_or(picture.pic_type(), Into::into),
+        changes
+            .mime_type
+            .map_or_else(|| picture.mime_type().clone(), Into::into),
+        description,
+        picture.data().to_vec(),
+    )
+}
@@ -0,0 +1,45 @@
+use crate::picture::{Picture, PictureOrder, PictureType};
+use crate::utils::artwork_rules::picture_size;
+use std::cmp::Reverse;
+
+/// The sort key of the `picture` in the given `order`, the smallest comes first.
+///
+/// The pictures with the same key keep their stored order.
+pub(crate) fn order_key(
+    picture: &Picture,
+    order: PictureOrder,
+) -> (u16, Reverse<u64>, Reverse<usize>) {
+    match order {
+        PictureOrder::AsStored => (0, Reverse(0), Reverse(0)),
+        PictureOrder::FrontCoverFirst => (
+            u16::from(picture.pic_type != PictureType::CoverFront),
+            Reverse(0),
+            Reverse(0),
+        ),
+        PictureOrder::ByType => (type_rank(picture.pic_type), Reverse(0), Reverse(0)),
+        PictureOrder::BySize => {
+            let pixels =
+                picture_size(picture).map_or(0, |(width, height)| width as u64 * height as u64);
+            (0, Reverse(pixels), Reverse(picture.pic_data.len()))
+        }
+    }
+}
+
+/// Sorts the `pictures` in the given `order`.
+pub(crate) fn sort_pictures(pictures: &mut [Picture], order: PictureOrder) {
+    if order != PictureOrder::AsStored {
+        pictures.sort_by_cached_key(|picture| order_key(picture, order));
+    }
+}
+
+/// The front cover first, then the back cover, then the other types in their ID3v2 order.
+fn type_rank(pic_type: PictureType) -> u16 {
+    match pic_type {
+        PictureType::CoverFront => 0,
+        PictureType::CoverBack => 1,
+        _ => {
+            let lofty_type: lofty::PictureType = pic_type.into();
+            2 + lofty_type.as_u8() as u16
+        }
+    }
+}
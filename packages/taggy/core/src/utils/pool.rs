@@ -0,0 +1,71 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// The number of worker threads to use for the requested `concurrency`,
+/// `0` meaning one per available CPU core.
+pub(crate) fn worker_count(concurrency: u32, items: usize) -> usize {
+    let workers = match concurrency {
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n as usize,
+    };
+    workers.min(items).max(1)
+}
+
+/// Applies `op` to each of the `items` on `concurrency` worker threads, see [worker_count],
+/// and passes each `(index, output)` to `on_output` on the calling thread as soon as it's done.
+///
+/// The workers take the next item as soon as they're free, so slow items (e.g. a large file on a
+/// network share) don't hold back the others.
+pub(crate) fn for_each_parallel<T, R>(
+    items: &[T],
+    concurrency: u32,
+    op: impl Fn(&T) -> R + Sync,
+    mut on_output: impl FnMut(usize, R),
+) where
+    T: Sync,
+    R: Send,
+{
+    if items.is_empty() {
+        return;
+    }
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+    thread::scope(|scope| {
+        for _ in 0..worker_count(concurrency, items.len()) {
+            let sender = sender.clone();
+            let (next, op) = (&next, &op);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let Some(item) = items.get(index) else {
+                    return;
+                };
+                if sender.send((index, op(item))).is_err() {
+                    return;
+                }
+            });
+        }
+        // the receiver ends once all the workers are done.
+        drop(sender);
+        for (index, output) in receiver {
+            on_output(index, output);
+        }
+    });
+}
+
+/// Same as [for_each_parallel], but collects the outputs in the order of the `items`.
+pub(crate) fn map_parallel<T, R>(
+    items: &[T],
+    concurrency: u32,
+    op: impl Fn(&T) -> R + Sync,
+) -> Vec<R>
+where
+    T: Sync,
+    R: Send,
+{
+    let mut outputs: Vec<Option<R>> = (0..items.len()).map(|_| None).collect();
+    for_each_parallel(items, concurrency, op, |index, output| {
+        outputs[index] = Some(output);
+    });
+    outputs.into_iter().flatten().collect()
+}
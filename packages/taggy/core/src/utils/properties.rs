@@ -0,0 +1,132 @@
+use crate::audio_info::AudioInfo;
+use crate::error::TaggyError;
+use crate::utils::duration::skip_id3v2;
+use crate::utils::integrity::check_not_truncated;
+use crate::utils::lofty_froms::audio_info_from;
+use crate::utils::read_only::open_read_only;
+use anyhow::anyhow;
+use lofty::{AudioFile, ParseOptions, Probe, TaggedFileExt};
+use std::io::{self, BufReader, Read, Seek, SeekFrom};
+
+/// Returns the audio properties of the file at given `path`, without parsing its tags.
+///
+/// lofty always parses the tags along with the properties, so for MPEG and FLAC files it's
+/// given a view of the file without them instead: the leading ID3v2 tag is skipped, and only
+/// the STREAMINFO block is kept of the FLAC metadata, which drops its pictures and comments.
+/// Other formats, and files the view doesn't work for, fall back to a regular read.
+pub(crate) fn read_audio_info(path: &str) -> anyhow::Result<AudioInfo> {
+    check_not_truncated(path)?;
+    let mut file = open_read_only(path)?;
+    let file_len = file.metadata()?.len();
+    if let Ok((prefix, audio_start)) = audio_only_layout(&mut file) {
+        let reader = BufReader::new(SplicedReader {
+            prefix,
+            inner: file,
+            offset: audio_start.min(file_len),
+            inner_len: file_len,
+            pos: 0,
+        });
+        if let Ok(info) = read_properties_from(reader) {
+            return Ok(info);
+        }
+    }
+    let file = open_read_only(path)?;
+    read_properties_from(BufReader::new(file))
+}
+
+fn read_properties_from<R: Read + Seek>(reader: R) -> anyhow::Result<AudioInfo> {
+    let probe = Probe::new(reader)
+        .options(ParseOptions::new())
+        .guess_file_type()?;
+    if probe.file_type().is_none() {
+        return Err(TaggyError::UnsupportedFormat.into());
+    }
+    let tagged = probe.read().map_err(|e| anyhow!(e))?;
+    Ok(audio_info_from(tagged.properties(), tagged.file_type()))
+}
+
+/// Returns the bytes to put in front of the audio, and where the audio starts in the file.
+fn audio_only_layout<R: Read + Seek>(reader: &mut R) -> anyhow::Result<(Vec<u8>, u64)> {
+    let mut ident = [0u8; 8];
+    reader.read_exact(&mut ident)?;
+    let audio_start = skip_id3v2(reader, &ident)?;
+    reader.seek(SeekFrom::Start(audio_start))?;
+    let mut magic = [0u8; 4];
+    reader.read_exact(&mut magic)?;
+    if &magic != b"fLaC" {
+        return Ok((vec![], audio_start));
+    }
+
+    // STREAMINFO is always the first block, keep it alone by flagging it as the last one.
+    let mut stream_info = [0u8; 4 + 34];
+    reader.read_exact(&mut stream_info)?;
+    if stream_info[0] & 0x7F != 0 {
+        return Err(TaggyError::CorruptTag {
+            message: "The FLAC STREAMINFO block is missing".to_string(),
+        }
+        .into());
+    }
+    let mut is_last = stream_info[0] & 0x80 != 0;
+    stream_info[0] |= 0x80;
+    let mut pos = audio_start + 4 + stream_info.len() as u64;
+    while !is_last {
+        let mut header = [0u8; 4];
+        reader.read_exact(&mut header)?;
+        is_last = header[0] & 0x80 != 0;
+        let len = u32::from_be_bytes([0, header[1], header[2], header[3]]) as u64;
+        pos += 4 + len;
+        reader.seek(SeekFrom::Start(pos))?;
+    }
+
+    let mut prefix = magic.to_vec();
+    prefix.extend_from_slice(&stream_info);
+    Ok((prefix, pos))
+}
+
+/// A reader over `prefix` followed by the bytes of `inner` from `offset`,
+/// so the start of a file can be skipped or replaced without copying the file.
+struct SplicedReader<R> {
+    prefix: Vec<u8>,
+    inner: R,
+    offset: u64,
+    /// The length of `inner`, from its start.
+    inner_len: u64,
+    pos: u64,
+}
+
+impl<R: Read + Seek> Read for SplicedReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let prefix_len = self.prefix.len() as u64;
+        let read = if self.pos < prefix_len {
+            let start = self.pos as usize;
+            let len = buf.len().min(self.prefix.len() - start);
+            buf[..len].copy_from_slice(&self.prefix[start..start + len]);
+            len
+        } else {
+            self.inner
+                .seek(SeekFrom::Start(self.offset + self.pos - prefix_len))?;
+            self.inner.read(buf)?
+        };
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl<R: Read + Seek> Seek for SplicedReader<R> {
+    fn seek(&mut self, pos: SeekFrom) -> io::Result<u64> {
+        let len = self.prefix.len() as u64 + self.inner_len - self.offset;
+        let new_pos = match pos {
+            SeekFrom::Start(pos) => Some(pos),
+            SeekFrom::End(delta) => len.checked_add_signed(delta),
+            SeekFrom::Current(delta) => self.pos.checked_add_signed(delta),
+        };
+        let Some(new_pos) = new_pos else {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "invalid seek to a negative position",
+            ));
+        };
+        self.pos = new_pos;
+        Ok(new_pos)
+    }
+}
@@ -0,0 +1,85 @@
+use crate::tag::Tag;
+use crate::utils::tag_fields::{for_each_multi_value_field, for_each_tag_field};
+use crate::write_options::WriteOptions;
+use anyhow::anyhow;
+
+/// Checks `tag` against the `existing` tag of the same type before writing it.
+///
+/// Returns the tag to write, which keeps the existing values of the protected fields
+/// when `options.skip_protected` is set, or fails listing the fields `tag` would overwrite.
+pub(crate) fn protect_existing_fields(
+    existing: &[Tag],
+    tag: Tag,
+    options: &WriteOptions,
+) -> anyhow::Result<Tag> {
+    let Some(existing) = existing.iter().find(|t| t.tag_type == tag.tag_type) else {
+        return Ok(tag);
+    };
+    let overwritten = overwritten_fields(existing, &tag, options);
+    if overwritten.is_empty() {
+        return Ok(tag);
+    }
+    if !options.skip_protected {
+        return Err(anyhow!(
+            "The write would overwrite the existing fields: {}!",
+            overwritten.join(", ")
+        ));
+    }
+    Ok(keep_existing_fields(existing, tag, options))
+}
+
+/// The names of the fields which have a value in `existing` and a different one in `tag`.
+fn overwritten_fields(existing: &Tag, tag: &Tag, options: &WriteOptions) -> Vec<&'static str> {
+    let mut fields = vec![];
+    macro_rules! check {
+        ($field:ident) => {
+            if existing.$field.is_some() && tag.$field.is_some() && existing.$field != tag.$field {
+                fields.push(stringify!($field));
+            }
+        };
+    }
+    for_each_tag_field!(check);
+    macro_rules! check_values {
+        ($field:ident) => {
+            if !existing.$field.is_empty()
+                && !tag.$field.is_empty()
+                && existing.$field != tag.$field
+            {
+                fields.push(stringify!($field));
+            }
+        };
+    }
+    for_each_multi_value_field!(check_values);
+    // the existing pictures are meant to be dropped anyway.
+    if !options.clear_pictures
+        && !existing.pictures.is_empty()
+        && !tag.pictures.is_empty()
+        && existing.pictures != tag.pictures
+    {
+        fields.push("pictures");
+    }
+    fields
+}
+
+fn keep_existing_fields(existing: &Tag, mut tag: Tag, options: &WriteOptions) -> Tag {
+    macro_rules! keep {
+        ($field:ident) => {
+            if existing.$field.is_some() {
+                tag.$field = existing.$field.clone();
+            }
+        };
+    }
+    for_each_tag_field!(keep);
+    macro_rules! keep_values {
+        ($field:ident) => {
+            if !existing.$field.is_empty() {
+                tag.$field = existing.$field.clone();
+            }
+        };
+    }
+    for_each_multi_value_field!(keep_values);
+    if !options.clear_pictures && !existing.pictures.is_empty() {
+        tag.pictures = existing.pictures.clone();
+    }
+    tag
+}
@@ -0,0 +1,132 @@
+use lofty::{ItemKey, ItemValue, TagItem};
+
+/// The Vorbis comment and APE item holding the rating.
+pub(crate) const RATING_KEY: &str = "RATING";
+
+/// The MP4 atom holding the rating, from 0 to 100.
+pub(crate) const MP4_RATE_ATOM: &str = "rate";
+
+/// The POPM email of Windows Media Player, whose byte values most players read back as stars.
+const POPM_EMAIL: &str = "Windows Media Player 9 Series";
+
+/// The POPM bytes of Windows Media Player for 1 to 5 stars, and the ones MusicBee
+/// adds for the half stars, with their rating from 0 to 100.
+const POPM_RATINGS: [(u8, u32); 10] = [
+    (13, 10),
+    (1, 20),
+    (54, 30),
+    (64, 40),
+    (118, 50),
+    (128, 60),
+    (186, 70),
+    (196, 80),
+    (242, 90),
+    (255, 100),
+];
+
+/// Reads the rating of the `tag`, from 0 to 100.
+///
+/// - ID3v2: the first `POPM` frame with a rating, its byte being converted with the
+///   Windows Media Player scale also used by MusicBee and foobar2000, see [POPM_RATINGS].
+/// - Vorbis comments and APE: the `RATING` item, either from 0 to 100, from 0 to 5 stars,
+///   or from `0.0` to `1.0` as some players write it.
+/// - MP4: the `rate` atom, from 0 to 100.
+pub(crate) fn rating_from(tag: &lofty::Tag) -> Option<u32> {
+    match tag.tag_type() {
+        lofty::TagType::Id3v2 => tag
+            .get_items(&ItemKey::Popularimeter)
+            .filter_map(|item| match item.value() {
+                ItemValue::Binary(popm) => popm_rating(popm),
+                _ => None,
+            })
+            .next(),
+        lofty::TagType::VorbisComments | lofty::TagType::Ape => {
+            text_item(tag, RATING_KEY).and_then(parse_rating)
+        }
+        lofty::TagType::Mp4Ilst => text_item(tag, MP4_RATE_ATOM)
+            .and_then(|rate| rate.trim().parse::<u32>().ok())
+            .map(|rate| rate.min(100)),
+        _ => None,
+    }
+}
+
+/// Inserts the `rating`, from 0 to 100, in the `tag`, see [rating_from].
+pub(crate) fn push_rating(tag: &mut lofty::Tag, rating: u32) {
+    let rating = rating.min(100);
+    let item = match tag.tag_type() {
+        lofty::TagType::Id3v2 => TagItem::new(
+            ItemKey::Popularimeter,
+            ItemValue::Binary(popm_bytes(rating)),
+        ),
+        lofty::TagType::VorbisComments | lofty::TagType::Ape => TagItem::new(
+            ItemKey::Unknown(RATING_KEY.to_string()),
+            ItemValue::Text(rating.to_string()),
+        ),
+        lofty::TagType::Mp4Ilst => TagItem::new(
+            ItemKey::Unknown(MP4_RATE_ATOM.to_string()),
+            ItemValue::Text(rating.to_string()),
+        ),
+        _ => return,
+    };
+    tag.push_unchecked(item);
+}
+
+fn text_item<'a>(tag: &'a lofty::Tag, key: &str) -> Option<&'a str> {
+    tag.items()
+        .find_map(|item| match (item.key(), item.value()) {
+            (ItemKey::Unknown(k), ItemValue::Text(value)) if k.eq_ignore_ascii_case(key) => {
+                Some(value.as_str())
+            }
+            _ => None,
+        })
+}
+
+/// Parses a `RATING` value, see [rating_from].
+fn parse_rating(value: &str) -> Option<u32> {
+    let value = value.trim();
+    let rating: f64 = value.parse().ok()?;
+    if rating < 0.0 {
+        return None;
+    }
+    let rating = if value.contains('.') && rating <= 1.0 {
+        rating * 100.0
+    } else if rating <= 5.0 {
+        rating * 20.0
+    } else {
+        rating
+    };
+    Some((rating.round() as u32).min(100))
+}
+
+/// The rating of the `POPM` frame content `popm`, i.e. a null terminated email,
+/// the rating byte and an optional play counter, or `None` when it's unrated.
+fn popm_rating(popm: &[u8]) -> Option<u32> {
+    let email_end = popm.iter().position(|b| *b == 0)?;
+    let byte = *popm.get(email_end + 1)?;
+    if byte == 0 {
+        return None;
+    }
+    let rating = POPM_RATINGS
+        .iter()
+        .find(|(b, _)| *b == byte)
+        .map_or_else(|| (byte as u32 * 100 + 127) / 255, |(_, rating)| *rating);
+    Some(rating)
+}
+
+/// The `POPM` frame content for the `rating`, with the byte of the nearest half star.
+fn popm_bytes(rating: u32) -> Vec<u8> {
+    let byte = if rating == 0 {
+        0
+    } else {
+        POPM_RATINGS
+            .iter()
+            .min_by_key(|(_, r)| r.abs_diff(rating))
+            .map_or(0, |(byte, _)| *byte)
+    };
+    let mut popm = POPM_EMAIL.as_bytes().to_vec();
+    popm.push(0);
+    popm.push(byte);
+    // the play counter, which isn't tracked.
+    popm.extend_from_slice(&[0; 4]);
+    popm
+}
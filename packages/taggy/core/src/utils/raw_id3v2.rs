@@ -0,0 +1,312 @@
+use crate::error::TaggyError;
+use crate::raw_frame::RawFrame;
+use crate::utils::file_id::{probe_file_type, read_id3v2};
+use crate::utils::read_only::open_read_only;
+use anyhow::anyhow;
+use lofty::id3::v2::{Frame, FrameFlags, FrameValue};
+use lofty::{FileType, TagExt};
+use std::fs::OpenOptions;
+use std::io::{Read, Seek, SeekFrom};
+
+/// The ID3v2 tag header flag of the whole tag being unsynchronized, up to ID3v2.3.
+const TAG_UNSYNCHRONIZED: u8 = 0x80;
+/// The ID3v2 tag header flag of the extended header.
+const TAG_EXTENDED_HEADER: u8 = 0x40;
+// the ID3v2.4 frame flags, see [RawFrame::flags].
+const TAG_ALTER_PRESERVATION: u16 = 0x4000;
+const FILE_ALTER_PRESERVATION: u16 = 0x2000;
+const READ_ONLY: u16 = 0x1000;
+const GROUPING_IDENTITY: u16 = 0x0040;
+const COMPRESSION: u16 = 0x0008;
+const ENCRYPTION: u16 = 0x0004;
+const UNSYNCHRONIZATION: u16 = 0x0002;
+const DATA_LENGTH_INDICATOR: u16 = 0x0001;
+/// The flags telling how the frame content is stored, rather than what to do with it.
+const FORMAT_FLAGS: u16 =
+    GROUPING_IDENTITY | COMPRESSION | ENCRYPTION | UNSYNCHRONIZATION | DATA_LENGTH_INDICATOR;
+
+impl RawFrame {
+    /// The text of this text frame, e.g. `TIT2`, with the values of the ID3v2.4 frames holding
+    /// several ones null-separated, `None` when it isn't one or can't be decoded.
+    pub(crate) fn text(&self) -> Option<String> {
+        if !self.id.starts_with('T') || self.id == "TXXX" || self.id == "TXX" {
+            return None;
+        }
+        if self.flags & (COMPRESSION | ENCRYPTION) != 0 {
+            return None;
+        }
+        let (encoding, text) = self.data.split_first()?;
+        Some(decode_text(*encoding, text))
+    }
+}
+
+/// Reads the frames of the ID3v2 tag of the file at `path` as they're stored, `None` when it
+/// has none.
+///
+/// Unlike lofty, the frames having the same ID are all kept, in their order, e.g. two `TIT2`
+/// frames. The tag is looked for at the start of the MPEG files and in the `ID3 ` chunk of the
+/// WAV and AIFF files.
+pub(crate) fn read_raw_frames(path: &str) -> anyhow::Result<Option<Vec<RawFrame>>> {
+    let file_type = probe_file_type(path)?;
+    let mut file = open_read_only(path)?;
+    read_raw_frames_from(&mut file, file_type)
+}
+
+/// Same as [read_raw_frames], for the `file_type` audio file of the `reader`.
+pub(crate) fn read_raw_frames_from<R: Read + Seek>(
+    reader: &mut R,
+    file_type: FileType,
+) -> anyhow::Result<Option<Vec<RawFrame>>> {
+    let offset = match file_type {
+        FileType::Mpeg => Some(0),
+        FileType::Wav => find_id3_chunk(reader, false)?.map(|(offset, _)| offset),
+        FileType::Aiff => find_id3_chunk(reader, true)?.map(|(offset, _)| offset),
+        _ => None,
+    };
+    let Some(offset) = offset else {
+        return Ok(None);
+    };
+    reader.seek(SeekFrom::Start(offset))?;
+    let mut header = [0u8; 10];
+    if reader.read_exact(&mut header).is_err() || &header[0..3] != b"ID3" {
+        return Ok(None);
+    }
+    let (version, flags) = (header[3], header[5]);
+    let size = synchsafe(&header[6..10]) as usize;
+    let mut body = vec![0u8; size];
+    reader.read_exact(&mut body)?;
+    if flags & TAG_UNSYNCHRONIZED != 0 && version < 4 {
+        body = remove_unsynchronization(&body);
+    }
+    let mut frames = body.as_slice();
+    if flags & TAG_EXTENDED_HEADER != 0 && version >= 3 {
+        let size_bytes = frames.get(0..4).unwrap_or_default();
+        let extended_len = match version {
+            3 => 4 + u32::from_be_bytes(size_bytes.try_into()?) as usize,
+            _ => synchsafe(size_bytes) as usize,
+        };
+        frames = frames.get(extended_len..).unwrap_or_default();
+    }
+    Ok(Some(parse_frames(frames, version)))
+}
+
+/// Adds the `frame` to the ID3v2 tag of the file at `path`, which is created when there's none.
+///
+/// The `frame` isn't added again when the tag already holds a frame with the same ID and data.
+/// Only its status flags are written, i.e. the alter preservation and read-only ones.
+///
+/// Fails when the file can't store an ID3v2 tag, i.e. isn't an MPEG, AIFF or WAV one, when the
+/// ID isn't a 4 characters ID3v2.4 one, or when a flag tells the data isn't plain, e.g. compressed.
+pub(crate) fn write_raw_frame(path: &str, frame: &RawFrame) -> anyhow::Result<()> {
+    let is_valid_id = frame.id.len() == 4
+        && frame
+            .id
+            .bytes()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit());
+    if !is_valid_id {
+        return Err(TaggyError::invalid_value(format!(
+            "'{}' isn't an ID3v2.4 frame ID!",
+            frame.id
+        ))
+        .into());
+    }
+    if frame.flags & FORMAT_FLAGS != 0 {
+        return Err(TaggyError::invalid_value(
+            "Only plain frames can be written, not grouped, compressed, encrypted or unsynchronized ones!",
+        )
+        .into());
+    }
+    let file_type = probe_file_type(path)?;
+    if !matches!(file_type, FileType::Mpeg | FileType::Aiff | FileType::Wav) {
+        return Err(TaggyError::UnsupportedTagType {
+            message: format!("The ID3v2 frames can't be stored in '{file_type:?}' files!"),
+        }
+        .into());
+    }
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let mut tag = read_id3v2(&mut file, file_type)?.unwrap_or_default();
+    let exists = (&tag).into_iter().any(|f| {
+        f.id_str() == frame.id
+            && matches!(f.content(), FrameValue::Binary(data) if *data == frame.data)
+    });
+    if exists {
+        return Ok(());
+    }
+    let flags = FrameFlags {
+        tag_alter_preservation: frame.flags & TAG_ALTER_PRESERVATION != 0,
+        file_alter_preservation: frame.flags & FILE_ALTER_PRESERVATION != 0,
+        read_only: frame.flags & READ_ONLY != 0,
+        ..FrameFlags::default()
+    };
+    tag.insert(Frame::new(
+        frame.id.clone(),
+        FrameValue::Binary(frame.data.clone()),
+        flags,
+    )?);
+    file.seek(SeekFrom::Start(0))?;
+    tag.save_to(&mut file).map_err(|e| anyhow!(e))
+}
+
+/// Writes the ID3v2 tag of the file at `path` again with all its frames, which lofty saves as
+/// ID3v2.4 whatever the version it was read from, does nothing when it has none.
+pub(crate) fn rewrite_id3v2(path: &str) -> anyhow::Result<()> {
+    let file_type = probe_file_type(path)?;
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let Some(tag) = read_id3v2(&mut file, file_type)? else {
+        return Ok(());
+    };
+    file.seek(SeekFrom::Start(0))?;
+    tag.save_to(&mut file).map_err(|e| anyhow!(e))
+}
+
+/// The offset and size of the content of the `ID3 ` chunk of a WAV file, or an AIFF one when
+/// `big_endian`.
+pub(crate) fn find_id3_chunk<R: Read + Seek>(
+    file: &mut R,
+    big_endian: bool,
+) -> anyhow::Result<Option<(u64, u64)>> {
+    let len = file.seek(SeekFrom::End(0))?;
+    // after the `RIFF` or `FORM` header.
+    let mut offset = 12;
+    while offset + 8 <= len {
+        file.seek(SeekFrom::Start(offset))?;
+        let mut header = [0u8; 8];
+        file.read_exact(&mut header)?;
+        let size_bytes = [header[4], header[5], header[6], header[7]];
+        let size = match big_endian {
+            true => u32::from_be_bytes(size_bytes),
+            false => u32::from_le_bytes(size_bytes),
+        } as u64;
+        if header[0..4].eq_ignore_ascii_case(b"ID3 ") {
+            return Ok(Some((offset + 8, size)));
+        }
+        // the chunks are padded to an even size.
+        offset += 8 + size + (size & 1);
+    }
+    Ok(None)
+}
+
+fn parse_frames(mut data: &[u8], version: u8) -> Vec<RawFrame> {
+    let (id_len, header_len) = if version == 2 { (3, 6) } else { (4, 10) };
+    let mut frames = vec![];
+    while data.len() >= header_len {
+        let id = &data[0..id_len];
+        // the padding, or garbage.
+        if !id
+            .iter()
+            .all(|b| b.is_ascii_uppercase() || b.is_ascii_digit())
+        {
+            break;
+        }
+        let (size, flags) = match version {
+            2 => (
+                u32::from_be_bytes([0, data[3], data[4], data[5]]) as usize,
+                0,
+            ),
+            3 => (
+                u32::from_be_bytes([data[4], data[5], data[6], data[7]]) as usize,
+                v3_flags(u16::from_be_bytes([data[8], data[9]])),
+            ),
+            _ => (
+                synchsafe(&data[4..8]) as usize,
+                u16::from_be_bytes([data[8], data[9]]),
+            ),
+        };
+        let Some(content) = data.get(header_len..header_len + size) else {
+            break;
+        };
+        frames.push(decoded(
+            String::from_utf8_lossy(id).to_string(),
+            flags,
+            content,
+        ));
+        data = &data[header_len + size..];
+    }
+    frames
+}
+
+/// The ID3v2.4 layout of the ID3v2.3 frame `flags`.
+fn v3_flags(flags: u16) -> u16 {
+    [
+        (0x8000, TAG_ALTER_PRESERVATION),
+        (0x4000, FILE_ALTER_PRESERVATION),
+        (0x2000, READ_ONLY),
+        (0x0080, COMPRESSION | DATA_LENGTH_INDICATOR),
+        (0x0040, ENCRYPTION),
+        (0x0020, GROUPING_IDENTITY),
+    ]
+    .into_iter()
+    .filter(|(v3, _)| flags & v3 != 0)
+    .fold(0, |v4, (_, flag)| v4 | flag)
+}
+
+/// The frame of the `content` without its unsynchronization and data length indicator,
+/// unless it's compressed or encrypted.
+fn decoded(id: String, flags: u16, content: &[u8]) -> RawFrame {
+    let raw = RawFrame {
+        id: id.clone(),
+        flags,
+        data: content.to_vec(),
+    };
+    if flags & (COMPRESSION | ENCRYPTION) != 0 {
+        return raw;
+    }
+    let mut data = content;
+    if flags & DATA_LENGTH_INDICATOR != 0 {
+        let Some(rest) = data.get(4..) else {
+            return raw;
+        };
+        data = rest;
+    }
+    RawFrame {
+        id,
+        flags: flags & !(UNSYNCHRONIZATION | DATA_LENGTH_INDICATOR),
+        data: match flags & UNSYNCHRONIZATION != 0 {
+            true => remove_unsynchronization(data),
+            false => data.to_vec(),
+        },
+    }
+}
+
+pub(crate) fn synchsafe(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .fold(0u32, |size, b| (size << 7) | (*b as u32 & 0x7F))
+}
+
+/// Removes the `0x00` bytes inserted after the `0xFF` ones, so no false MPEG sync is stored.
+fn remove_unsynchronization(data: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(data.len());
+    let mut previous = 0;
+    for byte in data {
+        if !(previous == 0xFF && *byte == 0) {
+            out.push(*byte);
+        }
+        previous = *byte;
+    }
+    out
+}
+
+/// Decodes the ID3v2 `text` of the `encoding`: latin1, UTF-16 with a BOM, UTF-16BE or UTF-8.
+pub(crate) fn decode_text(encoding: u8, text: &[u8]) -> String {
+    let text = match encoding {
+        1 | 2 => {
+            let (big_endian, text) = match text {
+                [0xFF, 0xFE, rest @ ..] => (false, rest),
+                [0xFE, 0xFF, rest @ ..] => (true, rest),
+                _ => (encoding == 2, text),
+            };
+            let units: Vec<u16> = text
+                .chunks_exact(2)
+                .map(|c| match big_endian {
+                    true => u16::from_be_bytes([c[0], c[1]]),
+                    false => u16::from_le_bytes([c[0], c[1]]),
+                })
+                .collect();
+            String::from_utf16_lossy(&units)
+        }
+        3 => String::from_utf8_lossy(text).to_string(),
+        _ => text.iter().map(|b| *b as char).collect(),
+    };
+    text.trim_end_matches('\0').to_string()
+}
@@ -0,0 +1,247 @@
+use crate::error::TaggyError;
+use crate::tag::TagType;
+use crate::utils::file_id::probe_file_type;
+use crate::utils::file_utils::splice_file;
+use crate::utils::raw_id3v2::{find_id3_chunk, synchsafe};
+use crate::utils::read_only::open_read_only;
+use lofty::{FileType, ParseOptions, ParsingMode, Probe, TaggedFileExt};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, Read, Seek, SeekFrom, Write};
+
+/// The size of an ID3v1 tag, at the end of the file.
+const ID3V1_LEN: u64 = 128;
+/// The size of the header and of the footer of an APE tag.
+const APE_HEADER_LEN: u64 = 32;
+/// The APE tag flag of the tag having a header, in addition to its footer.
+const APE_HAS_HEADER: u32 = 1 << 31;
+/// The ID of the chunk written for the ID3v2 tags of the WAV and AIFF files.
+const ID3_CHUNK_ID: &[u8; 4] = b"ID3 ";
+
+/// The bytes of the `tag_type` tag of the file at `path` as they're stored, none when it has no
+/// such tag.
+///
+/// Only the ID3v2, ID3v1 and APE tags are supported, the other ones aren't stored as a single
+/// block of the file, e.g. the MP4 atoms.
+pub(crate) fn dump_raw_tag(path: &str, tag_type: TagType) -> anyhow::Result<Vec<u8>> {
+    let file_type = probe_file_type(path)?;
+    let tag_type = match tag_type {
+        TagType::FilePrimaryType => TagType::from(file_type.primary_tag_type()),
+        tag_type => tag_type,
+    };
+    check_supported(file_type, tag_type)?;
+    let mut file = open_read_only(path)?;
+    let Some((start, end)) = locate(&mut file, file_type, tag_type)? else {
+        return Ok(vec![]);
+    };
+    file.seek(SeekFrom::Start(start))?;
+    let mut bytes = vec![0u8; (end - start) as usize];
+    file.read_exact(&mut bytes)?;
+    Ok(bytes)
+}
+
+/// Replaces the tag of the type of the `bytes` of the file at `path` with them, or adds it when
+/// the file has no such tag, e.g. the bytes of [dump_raw_tag].
+///
+/// The `bytes` are checked to be a whole ID3v2, ID3v1 or APE tag, and the file is written through
+/// a copy which only replaces it once lofty strictly parses its new tag.
+pub(crate) fn inject_raw_tag(path: &str, bytes: &[u8]) -> anyhow::Result<()> {
+    let tag_type = tag_type_of(bytes)?;
+    let file_type = probe_file_type(path)?;
+    check_supported(file_type, tag_type)?;
+    let mut file = open_read_only(path)?;
+    let len = file.metadata()?.len();
+    let existing = locate(&mut file, file_type, tag_type)?;
+
+    let is_chunked = tag_type == TagType::Id3v2 && file_type != FileType::Mpeg;
+    let (start, end, replacement) = if is_chunked {
+        let big_endian = file_type == FileType::Aiff;
+        let (start, end, id) = match find_id3_chunk(&mut file, big_endian)? {
+            Some((offset, size)) => {
+                file.seek(SeekFrom::Start(offset - 8))?;
+                let mut id = [0u8; 4];
+                file.read_exact(&mut id)?;
+                // the chunks are padded to an even size.
+                ((offset - 8), (offset + size + (size & 1)).min(len), id)
+            }
+            None => (len, len, *ID3_CHUNK_ID),
+        };
+        let size = bytes.len() as u32;
+        let mut chunk = id.to_vec();
+        chunk.extend(match big_endian {
+            true => size.to_be_bytes(),
+            false => size.to_le_bytes(),
+        });
+        chunk.extend(bytes);
+        if size & 1 == 1 {
+            chunk.push(0);
+        }
+        (start, end, chunk)
+    } else {
+        let (start, end) = existing.unwrap_or_else(|| match tag_type {
+            TagType::Id3v2 => (0, 0),
+            // before the ID3v1 tag, if any.
+            TagType::Ape if has_id3v1(&mut file, len) => (len - ID3V1_LEN, len - ID3V1_LEN),
+            _ => (len, len),
+        });
+        (start, end, bytes.to_vec())
+    };
+    drop(file);
+
+    splice_file(path, start, end, &replacement, |copy| {
+        if is_chunked {
+            patch_container_size(copy, file_type == FileType::Aiff)?;
+        }
+        let mut probe = Probe::new(BufReader::new(open_read_only(copy)?)).options(
+            ParseOptions::new()
+                .read_properties(false)
+                .parsing_mode(ParsingMode::Strict),
+        );
+        probe.set_file_type(file_type);
+        let tagged = probe.read()?;
+        if tagged.tag(tag_type.into()).is_none() {
+            return Err(TaggyError::CorruptTag {
+                message: format!("The injected {tag_type:?} tag can't be read back!"),
+            }
+            .into());
+        }
+        Ok(())
+    })
+}
+
+/// The type of the tag of the `bytes`, which have to be a whole ID3v2, ID3v1 or APE tag.
+fn tag_type_of(bytes: &[u8]) -> anyhow::Result<TagType> {
+    let len = bytes.len() as u64;
+    if bytes.starts_with(b"ID3") {
+        let is_valid_header =
+            len >= 10 && (2..=4).contains(&bytes[3]) && bytes[6..10].iter().all(|b| b & 0x80 == 0);
+        if !is_valid_header {
+            return Err(TaggyError::invalid_value("The ID3v2 tag header is invalid!").into());
+        }
+        let footer_len = if bytes[3] == 4 && bytes[5] & 0x10 != 0 {
+            10
+        } else {
+            0
+        };
+        if len != 10 + synchsafe(&bytes[6..10]) as u64 + footer_len {
+            return Err(TaggyError::invalid_value(format!(
+                "The ID3v2 tag size doesn't match its header, it has {len} bytes!"
+            ))
+            .into());
+        }
+        return Ok(TagType::Id3v2);
+    }
+    if bytes.starts_with(b"TAG") && len == ID3V1_LEN {
+        return Ok(TagType::Id3v1);
+    }
+    if len >= APE_HEADER_LEN && &bytes[bytes.len() - 32..bytes.len() - 24] == b"APETAGEX" {
+        let footer = &bytes[bytes.len() - 32..];
+        let size = u32::from_le_bytes(footer[12..16].try_into()?) as u64;
+        let flags = u32::from_le_bytes(footer[20..24].try_into()?);
+        let has_header = flags & APE_HAS_HEADER != 0;
+        let header_len = if has_header { APE_HEADER_LEN } else { 0 };
+        if size + header_len != len || (has_header && !bytes.starts_with(b"APETAGEX")) {
+            return Err(
+                TaggyError::invalid_value("The APE tag size doesn't match its footer!").into(),
+            );
+        }
+        return Ok(TagType::Ape);
+    }
+    Err(TaggyError::invalid_value("The bytes aren't an ID3v2, ID3v1 or APE tag!").into())
+}
+
+fn check_supported(file_type: FileType, tag_type: TagType) -> anyhow::Result<()> {
+    let is_supported = match tag_type {
+        TagType::Id3v2 => matches!(file_type, FileType::Mpeg | FileType::Wav | FileType::Aiff),
+        TagType::Id3v1 | TagType::Ape => matches!(
+            file_type,
+            FileType::Mpeg | FileType::Ape | FileType::Mpc | FileType::WavPack
+        ),
+        _ => false,
+    };
+    if !is_supported {
+        return Err(TaggyError::UnsupportedTagType {
+            message: format!(
+                "The raw {tag_type:?} tags of '{file_type:?}' files aren't supported, only the \
+                 ID3v2, ID3v1 and APE ones are stored as a single block!"
+            ),
+        }
+        .into());
+    }
+    Ok(())
+}
+
+/// The start and end of the `tag_type` tag of the `file`, `None` when it has none.
+fn locate(
+    file: &mut File,
+    file_type: FileType,
+    tag_type: TagType,
+) -> anyhow::Result<Option<(u64, u64)>> {
+    let len = file.metadata()?.len();
+    match tag_type {
+        TagType::Id3v2 => {
+            let start = match file_type {
+                FileType::Mpeg => Some(0),
+                _ => find_id3_chunk(file, file_type == FileType::Aiff)?.map(|(offset, _)| offset),
+            };
+            let Some(start) = start else {
+                return Ok(None);
+            };
+            file.seek(SeekFrom::Start(start))?;
+            let mut header = [0u8; 10];
+            if file.read_exact(&mut header).is_err() || &header[0..3] != b"ID3" {
+                return Ok(None);
+            }
+            let footer_len = if header[5] & 0x10 != 0 { 10 } else { 0 };
+            let end = start + 10 + synchsafe(&header[6..10]) as u64 + footer_len;
+            Ok((end <= len).then_some((start, end)))
+        }
+        TagType::Id3v1 => Ok(has_id3v1(file, len).then_some((len - ID3V1_LEN, len))),
+        TagType::Ape => {
+            let end = if has_id3v1(file, len) {
+                len - ID3V1_LEN
+            } else {
+                len
+            };
+            if end < APE_HEADER_LEN {
+                return Ok(None);
+            }
+            file.seek(SeekFrom::Start(end - APE_HEADER_LEN))?;
+            let mut footer = [0u8; 32];
+            file.read_exact(&mut footer)?;
+            if &footer[0..8] != b"APETAGEX" {
+                return Ok(None);
+            }
+            let size = u32::from_le_bytes(footer[12..16].try_into()?) as u64;
+            let flags = u32::from_le_bytes(footer[20..24].try_into()?);
+            let header_len = if flags & APE_HAS_HEADER != 0 {
+                APE_HEADER_LEN
+            } else {
+                0
+            };
+            Ok(end.checked_sub(size + header_len).map(|start| (start, end)))
+        }
+        _ => Ok(None),
+    }
+}
+
+fn has_id3v1(file: &mut File, len: u64) -> bool {
+    let mut ident = [0u8; 3];
+    len >= ID3V1_LEN
+        && file.seek(SeekFrom::Start(len - ID3V1_LEN)).is_ok()
+        && file.read_exact(&mut ident).is_ok()
+        && &ident == b"TAG"
+}
+
+/// Updates the size of the `RIFF` container of the WAV file at `path`, or of the `FORM` one of
+/// the AIFF file when `big_endian`, after a chunk changed.
+fn patch_container_size(path: &str, big_endian: bool) -> anyhow::Result<()> {
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let size = u32::try_from(file.metadata()?.len() - 8)
+        .map_err(|_| TaggyError::invalid_value("The file is too large for its container!"))?;
+    file.seek(SeekFrom::Start(4))?;
+    file.write_all(&match big_endian {
+        true => size.to_be_bytes(),
+        false => size.to_le_bytes(),
+    })?;
+    Ok(())
+}
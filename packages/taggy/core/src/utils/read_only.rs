@@ -0,0 +1,38 @@
+use crate::error::TaggyError;
+use lofty::{FileType, Probe};
+use std::fs::{File, OpenOptions};
+use std::io::{BufReader, ErrorKind, Read, Seek};
+use std::path::Path;
+
+/// Opens the file at `path` for reading only.
+///
+/// All the reading paths open files through here, so reading never requests write access:
+/// it works on read-only media, and doesn't wake up the sync clients watching for writes.
+pub(crate) fn open_read_only(path: impl AsRef<Path>) -> anyhow::Result<File> {
+    OpenOptions::new()
+        .read(true)
+        .open(path)
+        .map_err(|e| match e.kind() {
+            ErrorKind::NotFound => TaggyError::FileNotFound.into(),
+            ErrorKind::PermissionDenied => TaggyError::PermissionDenied.into(),
+            _ => e.into(),
+        })
+}
+
+/// Same as [Probe::open] through [open_read_only], the file type is guessed from the extension.
+pub(crate) fn probe_read_only(path: impl AsRef<Path>) -> anyhow::Result<Probe<BufReader<File>>> {
+    let path = path.as_ref();
+    Ok(probe_with_extension(
+        BufReader::new(open_read_only(path)?),
+        path,
+    ))
+}
+
+/// A [Probe] of the `reader` of the file at `path`, the file type is guessed from the extension.
+pub(crate) fn probe_with_extension<R: Read + Seek>(reader: R, path: &Path) -> Probe<R> {
+    let mut probe = Probe::new(reader);
+    if let Some(file_type) = FileType::from_path(path) {
+        probe.set_file_type(file_type);
+    }
+    probe
+}
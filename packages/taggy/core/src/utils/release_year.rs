@@ -0,0 +1,55 @@
+use std::path::Path;
+
+/// The years accepted as release years, so other 4 digits numbers
+/// (catalog numbers, bitrates...) aren't mistaken for one.
+const YEARS: std::ops::RangeInclusive<u32> = 1900..=2099;
+
+/// Looks for a release year in the folder name of the file at `path`, then in its file name.
+///
+/// Returns the year and the name it was found in.
+pub(crate) fn year_from_path(path: &Path) -> Option<(u32, String)> {
+    let folder = path.parent().and_then(Path::file_name);
+    let file = path.file_stem();
+    [folder, file].into_iter().flatten().find_map(|name| {
+        let name = name.to_string_lossy();
+        year_from_name(&name).map(|year| (year, name.to_string()))
+    })
+}
+
+/// Finds the release year in names like `Artist - Album (1997)`, `[1997] Album` or `1997 - Album`.
+///
+/// A year between brackets is preferred, otherwise the name must have a single
+/// standalone year, so names like `1999 - 2001 Live` aren't guessed.
+fn year_from_name(name: &str) -> Option<u32> {
+    let chars: Vec<char> = name.chars().collect();
+    let mut years = vec![];
+    let mut start = 0;
+    while start < chars.len() {
+        if !chars[start].is_ascii_digit() {
+            start += 1;
+            continue;
+        }
+        let end = (start..chars.len())
+            .find(|i| !chars[*i].is_ascii_digit())
+            .unwrap_or(chars.len());
+        if end - start == 4 {
+            let year: u32 = chars[start..end].iter().collect::<String>().parse().ok()?;
+            let bracketed = start > 0
+                && matches!(chars[start - 1], '(' | '[')
+                && matches!(chars.get(end), Some(')' | ']'));
+            if YEARS.contains(&year) {
+                years.push((year, bracketed));
+            }
+        }
+        start = end;
+    }
+    years
+        .iter()
+        .find(|(_, bracketed)| *bracketed)
+        .or(if years.len() == 1 {
+            years.first()
+        } else {
+            None
+        })
+        .map(|(year, _)| *year)
+}
@@ -0,0 +1,110 @@
+use crate::replay_gain::ReplayGain;
+use lofty::{ItemKey, TagItem};
+
+/// The Opus comment holding the track gain, see [r128_gain_db].
+pub(crate) const R128_TRACK_GAIN_KEY: &str = "R128_TRACK_GAIN";
+
+/// The Opus comment holding the album gain, see [r128_gain_db].
+pub(crate) const R128_ALBUM_GAIN_KEY: &str = "R128_ALBUM_GAIN";
+
+/// The R128 gains are relative to -23 LUFS, 5 dB below the ReplayGain reference of -18 LUFS.
+const R128_REFERENCE_OFFSET_DB: f64 = 5.0;
+
+/// Reads the gains and peaks of the `tag`, from the `R128_*` comments for the Opus files
+/// and the `REPLAYGAIN_*` fields otherwise.
+///
+/// The `REPLAYGAIN_*` gains some taggers write to the Opus files are read when they have no
+/// `R128_*` ones.
+pub(crate) fn replay_gain_from(tag: &lofty::Tag, is_opus: bool) -> ReplayGain {
+    let r128 = |key: &str| {
+        is_opus
+            .then(|| tag.get_string(&ItemKey::Unknown(key.to_string())))
+            .flatten()
+            .and_then(r128_gain_db)
+    };
+    let number = |key: &ItemKey| tag.get_string(key).and_then(parse_number);
+    ReplayGain {
+        track_gain_db: r128(R128_TRACK_GAIN_KEY).or_else(|| number(&ItemKey::ReplayGainTrackGain)),
+        track_peak: number(&ItemKey::ReplayGainTrackPeak),
+        album_gain_db: r128(R128_ALBUM_GAIN_KEY).or_else(|| number(&ItemKey::ReplayGainAlbumGain)),
+        album_peak: number(&ItemKey::ReplayGainAlbumPeak),
+        opus_output_gain_db: None,
+    }
+}
+
+/// Replaces the gains and peaks of the `tag` with the ones of `gain`, the `None` ones being
+/// removed.
+///
+/// The Opus files get `R128_*` comments and no `REPLAYGAIN_*` field, as required by
+/// RFC 7845, so their peaks are dropped.
+pub(crate) fn insert_replay_gain(tag: &mut lofty::Tag, gain: &ReplayGain, is_opus: bool) {
+    let fields = if is_opus {
+        [
+            (
+                r128_key(R128_TRACK_GAIN_KEY),
+                gain.track_gain_db.map(r128_value),
+            ),
+            (
+                r128_key(R128_ALBUM_GAIN_KEY),
+                gain.album_gain_db.map(r128_value),
+            ),
+            (ItemKey::ReplayGainTrackGain, None),
+            (ItemKey::ReplayGainAlbumGain, None),
+            (ItemKey::ReplayGainTrackPeak, None),
+            (ItemKey::ReplayGainAlbumPeak, None),
+        ]
+    } else {
+        let db = |gain: f64| format!("{gain:.2} dB");
+        let peak = |peak: f64| format!("{peak:.6}");
+        [
+            (ItemKey::ReplayGainTrackGain, gain.track_gain_db.map(db)),
+            (ItemKey::ReplayGainAlbumGain, gain.album_gain_db.map(db)),
+            (ItemKey::ReplayGainTrackPeak, gain.track_peak.map(peak)),
+            (ItemKey::ReplayGainAlbumPeak, gain.album_peak.map(peak)),
+            (r128_key(R128_TRACK_GAIN_KEY), None),
+            (r128_key(R128_ALBUM_GAIN_KEY), None),
+        ]
+    };
+    for (key, value) in fields {
+        tag.remove_key(&key);
+        if let Some(value) = value {
+            tag.insert(TagItem::new(key, lofty::ItemValue::Text(value)));
+        }
+    }
+}
+
+/// Converts an `R128_*` value, a Q7.8 fixed point number relative to -23 LUFS, e.g. `-2560`
+/// for -10 dB, to a ReplayGain gain in dB.
+pub(crate) fn r128_gain_db(value: &str) -> Option<f64> {
+    let q78: i16 = value.trim().parse().ok()?;
+    Some(q78_to_db(q78) + R128_REFERENCE_OFFSET_DB)
+}
+
+/// The inverse of [r128_gain_db].
+pub(crate) fn r128_value(gain_db: f64) -> String {
+    db_to_q78(gain_db - R128_REFERENCE_OFFSET_DB).to_string()
+}
+
+/// Converts a Q7.8 fixed point number, as used by the Opus header and comments, to dB.
+pub(crate) fn q78_to_db(q78: i16) -> f64 {
+    q78 as f64 / 256.0
+}
+
+/// The inverse of [q78_to_db], clamped to the range of a Q7.8 number.
+pub(crate) fn db_to_q78(db: f64) -> i16 {
+    (db * 256.0).round().clamp(i16::MIN as f64, i16::MAX as f64) as i16
+}
+
+fn r128_key(key: &str) -> ItemKey {
+    ItemKey::Unknown(key.to_string())
+}
+
+/// Parses a gain or a peak, e.g. `-6.48 dB` or `0.988525`.
+fn parse_number(value: &str) -> Option<f64> {
+    let value = value.trim();
+    let value = value
+        .strip_suffix("dB")
+        .or_else(|| value.strip_suffix("db"))
+        .unwrap_or(value);
+    value.trim().parse().ok().filter(|n: &f64| n.is_finite())
+}
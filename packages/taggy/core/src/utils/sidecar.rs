@@ -0,0 +1,94 @@
+use crate::tag::Tag;
+use crate::taggy_file::TaggyFile;
+use crate::utils::date_added::date_added_from;
+use crate::utils::snapshot::Snapshot;
+use crate::write_report::{TagWriteAction, TagWriteResult};
+use std::fs::{self, OpenOptions};
+use std::io::ErrorKind;
+use std::path::PathBuf;
+
+/// The path of the sidecar of the file at `path`, e.g. `track.flac.taggy.json`.
+pub(crate) fn sidecar_path(path: &str) -> PathBuf {
+    PathBuf::from(format!("{path}.taggy.json"))
+}
+
+/// Whether the file at `path` can't be written, because of its permissions
+/// or because its volume is mounted read-only.
+pub(crate) fn is_read_only(path: &str) -> bool {
+    if fs::metadata(path).is_ok_and(|m| m.permissions().readonly()) {
+        return true;
+    }
+    match OpenOptions::new().write(true).open(path) {
+        Ok(_) => false,
+        Err(e) => e.kind() == ErrorKind::PermissionDenied || is_read_only_fs_error(&e),
+    }
+}
+
+#[cfg(unix)]
+fn is_read_only_fs_error(e: &std::io::Error) -> bool {
+    // EROFS
+    e.raw_os_error() == Some(30)
+}
+
+#[cfg(not(unix))]
+fn is_read_only_fs_error(_: &std::io::Error) -> bool {
+    false
+}
+
+/// Reads the tags of the sidecar of the file at `path`, if it has one.
+pub(crate) fn read_sidecar_tags(path: &str) -> anyhow::Result<Vec<Tag>> {
+    let sidecar = sidecar_path(path);
+    if !sidecar.is_file() {
+        return Ok(vec![]);
+    }
+    let json = fs::read_to_string(sidecar)?;
+    let tags = Snapshot::from_json(&json)?.to_tags()?;
+    Ok(tags.iter().map(Tag::from).collect())
+}
+
+/// Writes the `tags` to the sidecar of the file at `path`, replacing the sidecar tags
+/// of the same types, or all of them when `override_existent` is set.
+pub(crate) fn write_sidecar_tags(
+    path: &str,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> anyhow::Result<Vec<TagWriteResult>> {
+    let mut sidecar_tags = if override_existent {
+        vec![]
+    } else {
+        read_sidecar_tags(path)?
+    };
+    let mut results = vec![];
+    for tag in tags {
+        let action = match sidecar_tags.iter().position(|t| t.tag_type == tag.tag_type) {
+            Some(index) => {
+                sidecar_tags.remove(index);
+                TagWriteAction::Replaced
+            }
+            None => TagWriteAction::Inserted,
+        };
+        results.push(TagWriteResult::new(
+            tag.tag_type,
+            Some(tag.tag_type),
+            action,
+        ));
+        sidecar_tags.push(tag);
+    }
+    let lofty_tags: Vec<lofty::Tag> = sidecar_tags.iter().map(Tag::to_lofty).collect();
+    fs::write(
+        sidecar_path(path),
+        Snapshot::from_tags(&lofty_tags).to_json()?,
+    )?;
+    Ok(results)
+}
+
+/// Overlays the `sidecar_tags` onto the tags of the given `file`,
+/// a sidecar tag replaces the embedded tag of the same type.
+pub(crate) fn overlay(mut file: TaggyFile, sidecar_tags: Vec<Tag>) -> TaggyFile {
+    for tag in sidecar_tags {
+        file.tags.retain(|t| t.tag_type != tag.tag_type);
+        file.tags.push(tag);
+    }
+    file.date_added_ms = date_added_from(&file.tags);
+    file
+}
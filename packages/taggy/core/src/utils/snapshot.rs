@@ -0,0 +1,197 @@
+use crate::error::TaggyError;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use lofty::{ItemKey, ItemValue, MimeType, PictureType, TagItem, TagType};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// The version of the snapshot format, bumped on incompatible changes.
+const SNAPSHOT_VERSION: u32 = 1;
+
+/// The complete metadata of a file, independent of its audio data.
+///
+/// Each item is stored with the key used by its tag format (e.g. `TIT2` for ID3v2),
+/// which also covers the items taggy doesn't model. The pictures are stored once
+/// by the hash of their data, so the same cover in several tags isn't duplicated.
+#[derive(Debug, Serialize, Deserialize)]
+pub(crate) struct Snapshot {
+    version: u32,
+    tags: Vec<TagSnapshot>,
+    pictures: BTreeMap<String, String>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct TagSnapshot {
+    tag_type: String,
+    items: Vec<ItemSnapshot>,
+    pictures: Vec<PictureSnapshot>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct ItemSnapshot {
+    key: String,
+    #[serde(flatten)]
+    value: ValueSnapshot,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(tag = "kind", content = "value", rename_all = "snake_case")]
+enum ValueSnapshot {
+    Text(String),
+    Locator(String),
+    /// Base64 encoded.
+    Binary(String),
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct PictureSnapshot {
+    pic_type: u8,
+    mime_type: Option<String>,
+    description: Option<String>,
+    /// The key of the picture data in [Snapshot::pictures].
+    hash: String,
+}
+
+impl Snapshot {
+    pub(crate) fn from_tags(tags: &[lofty::Tag]) -> Self {
+        let mut pictures = BTreeMap::new();
+        let tags = tags
+            .iter()
+            .map(|tag| {
+                let tag_type = tag.tag_type();
+                TagSnapshot {
+                    tag_type: tag_type_name(tag_type).to_string(),
+                    items: tag
+                        .items()
+                        .filter_map(|item| {
+                            Some(ItemSnapshot {
+                                key: item.key().map_key(tag_type, true)?.to_string(),
+                                value: match item.value() {
+                                    ItemValue::Text(text) => ValueSnapshot::Text(text.clone()),
+                                    ItemValue::Locator(url) => ValueSnapshot::Locator(url.clone()),
+                                    ItemValue::Binary(data) => {
+                                        ValueSnapshot::Binary(BASE64.encode(data))
+                                    }
+                                },
+                            })
+                        })
+                        .collect(),
+                    pictures: tag
+                        .pictures()
+                        .iter()
+                        .map(|pic| {
+                            let hash = hash_of(pic.data());
+                            pictures
+                                .entry(hash.clone())
+                                .or_insert_with(|| BASE64.encode(pic.data()));
+                            PictureSnapshot {
+                                pic_type: pic.pic_type().as_u8(),
+                                mime_type: Some(pic.mime_type().as_str().to_string())
+                                    .filter(|m| !m.is_empty()),
+                                description: pic.description().map(str::to_string),
+                                hash,
+                            }
+                        })
+                        .collect(),
+                }
+            })
+            .collect();
+        Self {
+            version: SNAPSHOT_VERSION,
+            tags,
+            pictures,
+        }
+    }
+
+    pub(crate) fn to_tags(&self) -> anyhow::Result<Vec<lofty::Tag>> {
+        if self.version > SNAPSHOT_VERSION {
+            return Err(TaggyError::invalid_value(format!(
+                "The snapshot version {} is not supported!",
+                self.version
+            ))
+            .into());
+        }
+        self.tags
+            .iter()
+            .map(|snapshot| {
+                let tag_type = tag_type_from_name(&snapshot.tag_type)?;
+                let mut tag = lofty::Tag::new(tag_type);
+                for item in &snapshot.items {
+                    let value = match &item.value {
+                        ValueSnapshot::Text(text) => ItemValue::Text(text.clone()),
+                        ValueSnapshot::Locator(url) => ItemValue::Locator(url.clone()),
+                        ValueSnapshot::Binary(data) => ItemValue::Binary(BASE64.decode(data)?),
+                    };
+                    let key = ItemKey::from_key(tag_type, &item.key);
+                    tag.insert_unchecked(TagItem::new(key, value));
+                }
+                for pic in &snapshot.pictures {
+                    let data = self.pictures.get(&pic.hash).ok_or_else(|| {
+                        TaggyError::invalid_value(format!(
+                            "The snapshot is missing the picture {}!",
+                            pic.hash
+                        ))
+                    })?;
+                    tag.push_picture(lofty::Picture::new_unchecked(
+                        PictureType::from_u8(pic.pic_type),
+                        pic.mime_type
+                            .as_deref()
+                            .map_or(MimeType::None, MimeType::from_str),
+                        pic.description.clone(),
+                        BASE64.decode(data)?,
+                    ));
+                }
+                Ok(tag)
+            })
+            .collect()
+    }
+
+    pub(crate) fn to_json(&self) -> anyhow::Result<String> {
+        Ok(serde_json::to_string(self)?)
+    }
+
+    pub(crate) fn from_json(json: &str) -> anyhow::Result<Self> {
+        let snapshot = serde_json::from_str(json)
+            .map_err(|e| TaggyError::invalid_value(format!("The snapshot is malformed: {e}")))?;
+        Ok(snapshot)
+    }
+}
+
+/// A stable (FNV-1a) hash of the given `data`, as hex.
+fn hash_of(data: &[u8]) -> String {
+    let hash = data.iter().fold(0xcbf29ce484222325u64, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    });
+    format!("{hash:016x}-{}", data.len())
+}
+
+fn tag_type_name(tag_type: TagType) -> &'static str {
+    match tag_type {
+        TagType::Ape => "ape",
+        TagType::Id3v1 => "id3v1",
+        TagType::Id3v2 => "id3v2",
+        TagType::Mp4Ilst => "mp4_ilst",
+        TagType::VorbisComments => "vorbis_comments",
+        TagType::RiffInfo => "riff_info",
+        TagType::AiffText => "aiff_text",
+        _ => "unknown",
+    }
+}
+
+fn tag_type_from_name(name: &str) -> anyhow::Result<TagType> {
+    Ok(match name {
+        "ape" => TagType::Ape,
+        "id3v1" => TagType::Id3v1,
+        "id3v2" => TagType::Id3v2,
+        "mp4_ilst" => TagType::Mp4Ilst,
+        "vorbis_comments" => TagType::VorbisComments,
+        "riff_info" => TagType::RiffInfo,
+        "aiff_text" => TagType::AiffText,
+        _ => {
+            return Err(TaggyError::invalid_value(format!(
+                "The snapshot tag type '{name}' is not supported!"
+            ))
+            .into())
+        }
+    })
+}
@@ -0,0 +1,366 @@
+use crate::error::TaggyError;
+use crate::recovery_report::RecoveryReport;
+use crate::utils::file_id::generate_uuid;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STAGED_PREFIX: &str = ".taggy-staged-";
+const BACKUP_PREFIX: &str = ".taggy-backup-";
+const JOURNAL_PREFIX: &str = ".taggy-journal-";
+/// The folders holding the copies of the transactions staged in a temp folder.
+const TRANSACTION_PREFIX: &str = ".taggy-transaction-";
+const TRANSACTION_JOURNAL: &str = "journal.json";
+
+/// A copy of a file which receives the changes instead of the file itself,
+/// until it's committed over the original file.
+pub(crate) struct StagedFile {
+    pub(crate) original: PathBuf,
+    pub(crate) staged: PathBuf,
+    backup: PathBuf,
+    /// The journal next to the file, `None` for the files staged in a [TransactionDir],
+    /// whose journal is written before any copy.
+    journal: Option<PathBuf>,
+}
+
+/// The folder of a transaction staged away from its files, e.g. in the app cache folder
+/// for the scoped storage locations which don't allow creating files next to them.
+///
+/// The copies may be on another file system than the files, so they're committed by copying
+/// them over the files instead of renaming them.
+pub(crate) struct TransactionDir {
+    dir: PathBuf,
+}
+
+/// The files committed together, written next to each of them before the commit starts
+/// and removed once it's finished, so an interrupted commit can be told apart from
+/// a finished one by [recover].
+#[derive(Serialize, Deserialize)]
+struct Journal {
+    files: Vec<PathBuf>,
+}
+
+impl StagedFile {
+    fn new(original: PathBuf) -> Option<Self> {
+        let file_name = original.file_name()?.to_str()?.to_string();
+        Some(Self {
+            staged: original.with_file_name(format!("{STAGED_PREFIX}{file_name}")),
+            backup: original.with_file_name(format!("{BACKUP_PREFIX}{file_name}")),
+            journal: Some(original.with_file_name(format!("{JOURNAL_PREFIX}{file_name}"))),
+            original,
+        })
+    }
+
+    /// The file at position `index` in the journal of the transaction staged in `dir`.
+    fn in_transaction_dir(original: PathBuf, dir: &Path, index: usize) -> Self {
+        Self {
+            staged: dir.join(format!("{index}.staged")),
+            backup: dir.join(format!("{index}.backup")),
+            journal: None,
+            original,
+        }
+    }
+
+    fn is_in_place(&self) -> bool {
+        self.journal.is_some()
+    }
+
+    /// Copies the file at `path` next to it, so renaming it later stays on the same file system.
+    pub(crate) fn stage(path: &str) -> anyhow::Result<Self> {
+        let Some(file) = Self::new(PathBuf::from(path)) else {
+            return Err(TaggyError::FileNotFound.into());
+        };
+        if !file.original.is_file() {
+            return Err(TaggyError::FileNotFound.into());
+        }
+        fs::copy(&file.original, &file.staged)?;
+        Ok(file)
+    }
+
+    pub(crate) fn staged_path(&self) -> String {
+        self.staged.to_string_lossy().to_string()
+    }
+
+    fn original_path(&self) -> String {
+        self.original.to_string_lossy().to_string()
+    }
+
+    /// Moves the original file aside and the staged copy in its place.
+    fn commit(&self) -> anyhow::Result<()> {
+        if !self.is_in_place() {
+            return self.commit_by_copy();
+        }
+        fs::rename(&self.original, &self.backup)?;
+        if let Err(e) = fs::rename(&self.staged, &self.original) {
+            let _ = fs::rename(&self.backup, &self.original);
+            return Err(e.into());
+        }
+        Ok(())
+    }
+
+    /// Same as [StagedFile::commit] for the copies of a [TransactionDir]. The staged copy
+    /// is removed once copied, so [recover] still tells the committed files apart.
+    fn commit_by_copy(&self) -> anyhow::Result<()> {
+        // a backup cut short by a crash must not be mistaken for a complete one.
+        let partial_backup = self.backup.with_extension("part");
+        fs::copy(&self.original, &partial_backup)?;
+        fs::rename(&partial_backup, &self.backup)?;
+        if let Err(e) = fs::copy(&self.staged, &self.original) {
+            let _ = fs::copy(&self.backup, &self.original);
+            return Err(e.into());
+        }
+        fs::remove_file(&self.staged)?;
+        Ok(())
+    }
+
+    /// Puts the original file back after a [StagedFile::commit].
+    fn revert(&self) {
+        if self.is_in_place() {
+            let _ = fs::rename(&self.backup, &self.original);
+        } else if fs::copy(&self.backup, &self.original).is_ok() {
+            remove_if_exists(&self.backup);
+        }
+    }
+
+    fn discard(&self) {
+        remove_if_exists(&self.staged);
+        if let Some(journal) = &self.journal {
+            remove_if_exists(journal);
+        }
+    }
+
+    fn finish(&self) {
+        remove_if_exists(&self.backup);
+        if let Some(journal) = &self.journal {
+            remove_if_exists(journal);
+        }
+    }
+}
+
+impl TransactionDir {
+    /// Creates the folder of a transaction of the files at `paths` in `temp_dir`,
+    /// with its journal listing the files.
+    pub(crate) fn create(temp_dir: &Path, paths: &[String]) -> anyhow::Result<Self> {
+        if !temp_dir.is_dir() {
+            return Err(TaggyError::DirectoryNotFound.into());
+        }
+        let dir = temp_dir.join(format!("{TRANSACTION_PREFIX}{}", generate_uuid()));
+        fs::create_dir(&dir)?;
+        let journal = Journal {
+            files: paths.iter().map(|p| absolute_path(Path::new(p))).collect(),
+        };
+        if let Err(e) = fs::write(
+            dir.join(TRANSACTION_JOURNAL),
+            serde_json::to_string(&journal)?,
+        ) {
+            let _ = fs::remove_dir_all(&dir);
+            return Err(e.into());
+        }
+        Ok(Self { dir })
+    }
+
+    /// Copies the file at `path`, the `index`th file of the transaction, to the folder.
+    pub(crate) fn stage(&self, path: &str, index: usize) -> anyhow::Result<StagedFile> {
+        let original = PathBuf::from(path);
+        if !original.is_file() {
+            return Err(TaggyError::FileNotFound.into());
+        }
+        let file = StagedFile::in_transaction_dir(original, &self.dir, index);
+        fs::copy(&file.original, &file.staged)?;
+        Ok(file)
+    }
+
+    /// Removes the folder once the transaction is committed or discarded.
+    pub(crate) fn close(self) {
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+/// Commits all the `files`, or none of them.
+///
+/// If committing any file fails, the already committed ones are reverted
+/// and all staged copies are removed. A journal is written next to each file first,
+/// so a commit interrupted by a crash is rolled back or completed by [recover].
+pub(crate) fn commit_all(files: &[StagedFile]) -> anyhow::Result<()> {
+    if let Err(e) = write_journals(files) {
+        discard_all(files);
+        return Err(e);
+    }
+    for (index, file) in files.iter().enumerate() {
+        if let Err(e) = file.commit() {
+            files[..index].iter().for_each(StagedFile::revert);
+            discard_all(files);
+            return Err(e);
+        }
+    }
+    files.iter().for_each(StagedFile::finish);
+    Ok(())
+}
+
+/// Removes the staged copies, leaving the original files untouched.
+pub(crate) fn discard_all(files: &[StagedFile]) {
+    files.iter().for_each(StagedFile::discard);
+}
+
+fn write_journals(files: &[StagedFile]) -> anyhow::Result<()> {
+    let journal = Journal {
+        files: files.iter().map(|f| absolute_path(&f.original)).collect(),
+    };
+    let json = serde_json::to_string(&journal)?;
+    for journal in files.iter().filter_map(|f| f.journal.as_ref()) {
+        fs::write(journal, &json)?;
+    }
+    Ok(())
+}
+
+/// The absolute path of the file at `path`, so the journals can be recovered
+/// from any working directory.
+fn absolute_path(path: &Path) -> PathBuf {
+    fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf())
+}
+
+/// Rolls back or completes the commits of the files in the folder at `dir` which were
+/// interrupted, e.g. because the app was killed, and removes their leftover copies.
+///
+/// A commit is completed when all the files of its transaction were already replaced
+/// by their staged copies, and rolled back otherwise, so a transaction is never half-applied.
+/// The other files of a transaction are recovered along, even when they're in other folders,
+/// and so are the transactions whose [TransactionDir] is in `dir`.
+pub(crate) fn recover(dir: &Path) -> anyhow::Result<RecoveryReport> {
+    if !dir.is_dir() {
+        return Err(TaggyError::DirectoryNotFound.into());
+    }
+    let mut report = RecoveryReport::default();
+    for original in leftover_originals(dir, JOURNAL_PREFIX)? {
+        let Some(file) = StagedFile::new(original) else {
+            continue;
+        };
+        // already recovered along with another file of its transaction.
+        if file.journal.as_ref().is_some_and(|j| j.exists()) {
+            recover_transaction(&file, &mut report);
+        }
+    }
+    for dir in transaction_dirs(dir)? {
+        recover_transaction_dir(&dir, &mut report);
+    }
+
+    // the copies of the writes interrupted before their commit, the files weren't changed yet.
+    for original in leftover_originals(dir, STAGED_PREFIX)? {
+        if let Some(file) = StagedFile::new(original) {
+            remove_if_exists(&file.staged);
+            report.discarded.push(file.original_path());
+        }
+    }
+    // only left by the commits which predate the journals.
+    for original in leftover_originals(dir, BACKUP_PREFIX)? {
+        let Some(file) = StagedFile::new(original) else {
+            continue;
+        };
+        if file.original.exists() {
+            file.finish();
+            report.completed.push(file.original_path());
+        } else {
+            file.revert();
+            report.rolled_back.push(file.original_path());
+        }
+    }
+    Ok(report)
+}
+
+fn recover_transaction(file: &StagedFile, report: &mut RecoveryReport) {
+    // a journal cut short by the crash was being written, so no file was committed yet.
+    let originals = file
+        .journal
+        .as_ref()
+        .and_then(|journal| read_journal(journal))
+        .map_or_else(|| vec![file.original.clone()], |journal| journal.files);
+    let files: Vec<StagedFile> = originals.into_iter().filter_map(StagedFile::new).collect();
+    recover_files(&files, report);
+}
+
+/// Recovers the transaction staged in the [TransactionDir] at `dir`, then removes it.
+fn recover_transaction_dir(dir: &Path, report: &mut RecoveryReport) {
+    // the journal is written before any copy, so no file was committed without one.
+    if let Some(journal) = read_journal(&dir.join(TRANSACTION_JOURNAL)) {
+        let files: Vec<StagedFile> = journal
+            .files
+            .into_iter()
+            .enumerate()
+            .map(|(index, original)| StagedFile::in_transaction_dir(original, dir, index))
+            .collect();
+        if files.iter().any(|f| f.backup.exists()) {
+            recover_files(&files, report);
+        } else {
+            // interrupted before its commit, the files weren't changed yet.
+            let staged = files.iter().filter(|f| f.staged.exists());
+            report
+                .discarded
+                .extend(staged.map(StagedFile::original_path));
+        }
+    }
+    let _ = fs::remove_dir_all(dir);
+}
+
+fn read_journal(path: &Path) -> Option<Journal> {
+    let json = fs::read_to_string(path).ok()?;
+    serde_json::from_str(&json).ok()
+}
+
+/// Rolls back the `files` of a transaction when any of them wasn't committed yet,
+/// and completes their commit otherwise.
+fn recover_files(files: &[StagedFile], report: &mut RecoveryReport) {
+    if files.iter().any(|f| f.staged.exists()) {
+        for file in files {
+            if file.backup.exists() {
+                file.revert();
+                report.rolled_back.push(file.original_path());
+            }
+            file.discard();
+        }
+    } else {
+        for file in files {
+            if file.backup.exists() {
+                report.completed.push(file.original_path());
+            }
+            file.finish();
+        }
+    }
+}
+
+/// The paths of the files which have a leftover file starting with `prefix` in `dir`.
+fn leftover_originals(dir: &Path, prefix: &str) -> anyhow::Result<Vec<PathBuf>> {
+    let mut originals = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if let Some(file_name) = entry
+            .file_name()
+            .to_str()
+            .and_then(|n| n.strip_prefix(prefix))
+        {
+            originals.push(dir.join(file_name));
+        }
+    }
+    Ok(originals)
+}
+
+/// The folders of the transactions staged in `dir` used as temp folder, see [TransactionDir].
+fn transaction_dirs(dir: &Path) -> anyhow::Result<Vec<PathBuf>> {
+    let mut dirs = vec![];
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        let is_transaction = entry
+            .file_name()
+            .to_str()
+            .is_some_and(|n| n.starts_with(TRANSACTION_PREFIX));
+        if is_transaction && entry.path().is_dir() {
+            dirs.push(entry.path());
+        }
+    }
+    Ok(dirs)
+}
+
+fn remove_if_exists(path: &Path) {
+    if path.exists() {
+        let _ = fs::remove_file(path);
+    }
+}
@@ -0,0 +1,96 @@
+use crate::field_class::FieldClass;
+use crate::utils::file_id::{probe_file_type, read_id3v2};
+use crate::utils::lyrics::UNSYNCED_LYRICS_KEY;
+use crate::utils::rating::{MP4_RATE_ATOM, RATING_KEY};
+use crate::utils::read_only::probe_read_only;
+use anyhow::anyhow;
+use lofty::{ItemKey, ItemValue, TagExt, TagItem, TagType, TaggedFileExt};
+use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom};
+
+/// The URL fields lofty maps for the tags other than ID3v2.
+const URL_KEYS: [ItemKey; 8] = [
+    ItemKey::AudioFileUrl,
+    ItemKey::AudioSourceUrl,
+    ItemKey::CommercialInformationUrl,
+    ItemKey::CopyrightUrl,
+    ItemKey::TrackArtistUrl,
+    ItemKey::RadioStationUrl,
+    ItemKey::PaymentUrl,
+    ItemKey::PublisherUrl,
+];
+
+/// Removes the fields of the `classes` from all the tags of the file at `path`,
+/// returns whether the file was changed.
+///
+/// The ID3v2 tag of the MPEG, AIFF and WAV files is edited frame by frame, so the frames lofty
+/// doesn't keep in its generic tags (e.g. the `TXXX` ones) are kept. Only the changed tags are
+/// written back.
+pub(crate) fn strip_fields(path: &str, classes: &[FieldClass]) -> anyhow::Result<bool> {
+    let file_type = probe_file_type(path)?;
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let mut changed = false;
+
+    let id3v2 = read_id3v2(&mut file, file_type)?;
+    if let Some(mut tag) = id3v2.clone() {
+        let frames_count = tag.len();
+        tag.retain(|frame| {
+            !classes
+                .iter()
+                .any(|class| is_id3v2_frame_of(frame.id_str(), *class))
+        });
+        if tag.len() != frames_count {
+            file.seek(SeekFrom::Start(0))?;
+            tag.save_to(&mut file).map_err(|e| anyhow!(e))?;
+            changed = true;
+        }
+    }
+
+    let tagged = probe_read_only(path)?.read()?;
+    for tag in tagged.tags() {
+        // already stripped frame by frame.
+        if tag.tag_type() == TagType::Id3v2 && id3v2.is_some() {
+            continue;
+        }
+        let mut stripped = tag.clone();
+        stripped.retain(|item| !classes.iter().any(|class| is_item_of(tag, item, *class)));
+        if stripped.len() != tag.len() {
+            file.seek(SeekFrom::Start(0))?;
+            stripped.save_to(&mut file).map_err(|e| anyhow!(e))?;
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
+/// Whether the ID3v2 frame `id` holds a field of the `class`.
+fn is_id3v2_frame_of(id: &str, class: FieldClass) -> bool {
+    match class {
+        FieldClass::Comments => id == "COMM",
+        FieldClass::Lyrics => id == "USLT" || id == "SYLT",
+        FieldClass::Ratings => id == "POPM" || id == "PCNT",
+        FieldClass::PrivateData => id == "PRIV" || id == "GEOB",
+        FieldClass::Urls => id.starts_with('W'),
+    }
+}
+
+/// Whether the `item` of the `tag` holds a field of the `class`.
+fn is_item_of(tag: &lofty::Tag, item: &TagItem, class: FieldClass) -> bool {
+    let is_unknown =
+        |key: &str| matches!(item.key(), ItemKey::Unknown(k) if k.eq_ignore_ascii_case(key));
+    match class {
+        FieldClass::Comments => item.key() == &ItemKey::Comment,
+        FieldClass::Lyrics => item.key() == &ItemKey::Lyrics || is_unknown(UNSYNCED_LYRICS_KEY),
+        FieldClass::Ratings => match tag.tag_type() {
+            TagType::Mp4Ilst => is_unknown(MP4_RATE_ATOM),
+            _ => item.key() == &ItemKey::Popularimeter || is_unknown(RATING_KEY),
+        },
+        FieldClass::PrivateData => {
+            matches!(item.key(), ItemKey::Unknown(_))
+                && matches!(item.value(), ItemValue::Binary(_))
+        }
+        FieldClass::Urls => {
+            URL_KEYS.contains(item.key()) || matches!(item.value(), ItemValue::Locator(_))
+        }
+    }
+}
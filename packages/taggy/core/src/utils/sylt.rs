@@ -0,0 +1,107 @@
+use crate::error::TaggyError;
+use crate::synced_lyrics::{SyncedLine, SyncedLyrics};
+use crate::utils::file_id::{probe_file_type, read_id3v2};
+use crate::utils::lyrics::{language_of, parse_language, UNKNOWN_LANGUAGE};
+use crate::utils::read_only::open_read_only;
+use anyhow::anyhow;
+use lofty::id3::v2::{
+    Frame, FrameFlags, FrameValue, Id3v2Tag, SyncTextContentType, SynchronizedText, TimestampFormat,
+};
+use lofty::{TagExt, TagType, TextEncoding};
+use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom};
+
+/// The ID3v2 frame of the synchronized lyrics.
+const SYLT: &str = "SYLT";
+
+/// Reads the first `SYLT` frame of the file at `path` holding lyrics timed in milliseconds.
+///
+/// The frames timed in MPEG frames are skipped, their timestamps depend on the audio stream.
+pub(crate) fn read_synced_lyrics(path: &str) -> anyhow::Result<Option<SyncedLyrics>> {
+    let file_type = probe_file_type(path)?;
+    let mut file = open_read_only(path)?;
+    let Some(tag) = read_id3v2(&mut file, file_type)? else {
+        return Ok(None);
+    };
+    // collected before `tag` is dropped, which the frames borrow from.
+    let lyrics = sylt_frames(&tag)
+        .filter_map(|data| SynchronizedText::parse(data).ok())
+        .find(|sylt| {
+            matches!(sylt.timestamp_format, TimestampFormat::MS)
+                && matches!(sylt.content_type, SyncTextContentType::Lyrics)
+        })
+        .map(|sylt| SyncedLyrics {
+            language: language_of(sylt.language),
+            description: sylt.description.filter(|d| !d.is_empty()),
+            lines: sylt
+                .content
+                .into_iter()
+                .map(|(timestamp_ms, text)| SyncedLine { timestamp_ms, text })
+                .collect(),
+        });
+    Ok(lyrics)
+}
+
+/// Replaces the `SYLT` frames of the file at `path` with the `lyrics`,
+/// or removes them when the `lyrics` have no line.
+///
+/// Fails when the file primary tag isn't an ID3v2 one, or the language isn't a 3 letters
+/// ISO 639-2 code.
+pub(crate) fn write_synced_lyrics(path: &str, lyrics: &SyncedLyrics) -> anyhow::Result<()> {
+    let file_type = probe_file_type(path)?;
+    if file_type.primary_tag_type() != TagType::Id3v2 {
+        return Err(TaggyError::UnsupportedTagType {
+            message: format!(
+                "The synchronized lyrics are only stored in ID3v2 tags, \
+                 which the file type '{file_type:?}' doesn't use!"
+            ),
+        }
+        .into());
+    }
+    let language = match &lyrics.language {
+        Some(language) => parse_language(language)?,
+        None => UNKNOWN_LANGUAGE,
+    };
+    let mut lines: Vec<(u32, String)> = lyrics
+        .lines
+        .iter()
+        .map(|line| (line.timestamp_ms, line.text.clone()))
+        .collect();
+    lines.sort_by_key(|(timestamp, _)| *timestamp);
+
+    let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+    let mut tag = read_id3v2(&mut file, file_type)?.unwrap_or_default();
+    tag.remove(SYLT);
+    if !lines.is_empty() {
+        let sylt = SynchronizedText {
+            encoding: TextEncoding::UTF8,
+            language,
+            timestamp_format: TimestampFormat::MS,
+            content_type: SyncTextContentType::Lyrics,
+            description: lyrics.description.clone(),
+            content: lines,
+        };
+        insert_sylt_frame(&mut tag, sylt.as_bytes()?)?;
+    }
+    file.seek(SeekFrom::Start(0))?;
+    tag.save_to(&mut file).map_err(|e| anyhow!(e))
+}
+
+/// The content of the `SYLT` frames of the `tag`, which lofty keeps as binary.
+pub(crate) fn sylt_frames(tag: &Id3v2Tag) -> impl Iterator<Item = &Vec<u8>> {
+    tag.into_iter()
+        .filter(|frame| frame.id_str() == SYLT)
+        .filter_map(|frame| match frame.content() {
+            FrameValue::Binary(data) => Some(data),
+            _ => None,
+        })
+}
+
+pub(crate) fn insert_sylt_frame(tag: &mut Id3v2Tag, data: Vec<u8>) -> anyhow::Result<()> {
+    tag.insert(Frame::new(
+        SYLT,
+        FrameValue::Binary(data),
+        FrameFlags::default(),
+    )?);
+    Ok(())
+}
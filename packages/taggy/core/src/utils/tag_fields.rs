@@ -0,0 +1,126 @@
+/// Calls `$op!(field)` for each optional field of a [Tag](crate::tag::Tag).
+macro_rules! for_each_tag_field {
+    ($op:ident) => {
+        $op!(track_title);
+        $op!(track_artist);
+        $op!(album);
+        $op!(album_artist);
+        $op!(producer);
+        $op!(track_number);
+        $op!(track_total);
+        $op!(disc_number);
+        $op!(disc_total);
+        $op!(year);
+        $op!(recording_date);
+        $op!(original_release_date);
+        $op!(release_date);
+        $op!(original_date);
+        $op!(language);
+        $op!(lyrics);
+        $op!(genre);
+        $op!(isrc);
+        $op!(composer);
+        $op!(conductor);
+        $op!(remixer);
+        $op!(artist_sort);
+        $op!(album_sort);
+        $op!(title_sort);
+        $op!(album_artist_sort);
+        $op!(bpm);
+        $op!(initial_key);
+        $op!(mood);
+        $op!(rating);
+        $op!(lyrics_language);
+        $op!(lyrics_description);
+        $op!(musicbrainz_recording_id);
+        $op!(musicbrainz_release_id);
+        $op!(musicbrainz_release_group_id);
+        $op!(musicbrainz_artist_id);
+        $op!(musicbrainz_album_artist_id);
+        $op!(musicbrainz_track_id);
+        $op!(acoustid_id);
+        $op!(acoustid_fingerprint);
+        $op!(barcode);
+        $op!(catalog_number);
+        $op!(label);
+        $op!(copyright);
+        $op!(grouping);
+        $op!(subtitle);
+        $op!(work);
+        $op!(movement);
+        $op!(movement_number);
+        $op!(movement_total);
+        $op!(is_compilation);
+        $op!(is_podcast);
+        $op!(podcast_url);
+        $op!(episode_id);
+        $op!(narrator);
+        $op!(series);
+        $op!(series_part);
+    };
+}
+
+pub(crate) use for_each_tag_field;
+
+/// Calls `$op!(field)` for each optional text field of a [Tag](crate::tag::Tag).
+macro_rules! for_each_text_field {
+    ($op:ident) => {
+        $op!(track_title);
+        $op!(track_artist);
+        $op!(album);
+        $op!(album_artist);
+        $op!(producer);
+        $op!(recording_date);
+        $op!(original_release_date);
+        $op!(language);
+        $op!(lyrics);
+        $op!(genre);
+        $op!(isrc);
+        $op!(composer);
+        $op!(conductor);
+        $op!(remixer);
+        $op!(artist_sort);
+        $op!(album_sort);
+        $op!(title_sort);
+        $op!(album_artist_sort);
+        $op!(initial_key);
+        $op!(mood);
+        $op!(lyrics_language);
+        $op!(lyrics_description);
+        $op!(musicbrainz_recording_id);
+        $op!(musicbrainz_release_id);
+        $op!(musicbrainz_release_group_id);
+        $op!(musicbrainz_artist_id);
+        $op!(musicbrainz_album_artist_id);
+        $op!(musicbrainz_track_id);
+        $op!(acoustid_id);
+        $op!(acoustid_fingerprint);
+        $op!(barcode);
+        $op!(catalog_number);
+        $op!(label);
+        $op!(copyright);
+        $op!(grouping);
+        $op!(subtitle);
+        $op!(work);
+        $op!(movement);
+        $op!(podcast_url);
+        $op!(episode_id);
+        $op!(narrator);
+        $op!(series);
+        $op!(series_part);
+    };
+}
+
+pub(crate) use for_each_text_field;
+
+/// Calls `$op!(field)` for each multi-value field of a [Tag](crate::tag::Tag).
+macro_rules! for_each_multi_value_field {
+    ($op:ident) => {
+        $op!(artists);
+        $op!(genres);
+        $op!(composers);
+        $op!(comments);
+    };
+}
+
+pub(crate) use for_each_multi_value_field;
@@ -0,0 +1,66 @@
+use crate::album_gaps::AlbumGaps;
+use crate::tag::Tag;
+use std::collections::BTreeMap;
+
+/// The album artist, album and disc number the files are grouped by.
+type DiscKey = (Option<String>, String, u32);
+
+/// Groups the files by album artist, album and disc, then finds the track numbers missing
+/// from each disc and the ones shared by several files.
+///
+/// The files without an album or a track number are skipped, and only the discs with
+/// missing or duplicated tracks are returned, sorted by album artist, album and disc.
+pub(crate) fn find_track_gaps(files: Vec<(String, Tag)>) -> Vec<AlbumGaps> {
+    let mut albums: BTreeMap<DiscKey, Vec<(String, Tag)>> = BTreeMap::new();
+    for (path, tag) in files {
+        let Some(album) = tag.album.clone().filter(|a| !a.trim().is_empty()) else {
+            continue;
+        };
+        if tag.track_number.is_none() {
+            continue;
+        }
+        let disc_number = tag.disc_number.filter(|n| *n > 0).unwrap_or(1);
+        albums
+            .entry((tag.album_artist.clone(), album, disc_number))
+            .or_default()
+            .push((path, tag));
+    }
+
+    albums
+        .into_iter()
+        .filter_map(|((album_artist, album, disc_number), tracks)| {
+            let mut counts: BTreeMap<u32, u32> = BTreeMap::new();
+            for (_, tag) in &tracks {
+                *counts.entry(tag.track_number.unwrap_or(0)).or_default() += 1;
+            }
+            let highest = counts.keys().copied().max().unwrap_or(0);
+            let track_total = tracks
+                .iter()
+                .filter_map(|(_, tag)| tag.track_total)
+                .max()
+                .unwrap_or(highest);
+            let missing: Vec<u32> = (1..=track_total)
+                .filter(|n| !counts.contains_key(n))
+                .collect();
+            let duplicated: Vec<u32> = counts
+                .iter()
+                .filter(|(_, count)| **count > 1)
+                .map(|(n, _)| *n)
+                .collect();
+            if missing.is_empty() && duplicated.is_empty() {
+                return None;
+            }
+            let mut files: Vec<String> = tracks.into_iter().map(|(path, _)| path).collect();
+            files.sort();
+            Some(AlbumGaps {
+                album,
+                album_artist,
+                disc_number,
+                track_total,
+                missing,
+                duplicated,
+                files,
+            })
+        })
+        .collect()
+}
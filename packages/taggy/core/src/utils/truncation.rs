@@ -0,0 +1,82 @@
+use crate::tag::{Tag, TagType};
+use crate::utils::tag_fields::for_each_text_field;
+use crate::write_options::TruncationStrategy;
+
+/// The marker appended to the values cut with [TruncationStrategy::Ellipsis],
+/// ASCII so it also fits the Latin-1 only ID3v1 tags.
+const ELLIPSIS: &str = "...";
+
+/// Cuts the text fields of `tag` exceeding the limits of its tag type with the `strategy`.
+///
+/// Returns the tag to write and a warning for each cut field.
+pub(crate) fn truncate_fields(mut tag: Tag, strategy: TruncationStrategy) -> (Tag, Vec<String>) {
+    let mut warnings = vec![];
+    if strategy == TruncationStrategy::Off {
+        return (tag, warnings);
+    }
+    let tag_type = tag.tag_type;
+    macro_rules! truncate {
+        ($field:ident) => {
+            if let Some(max_length) = max_length(tag_type, stringify!($field)) {
+                if let Some(value) = &tag.$field {
+                    if value.chars().count() > max_length {
+                        let truncated = truncate(value, max_length, strategy);
+                        warnings.push(format!(
+                            "The {} is longer than the {max_length} characters supported by {tag_type:?}, it was truncated to '{truncated}'",
+                            stringify!($field)
+                        ));
+                        tag.$field = Some(truncated);
+                    }
+                }
+            }
+        };
+    }
+    for_each_text_field!(truncate);
+    (tag, warnings)
+}
+
+/// The maximum number of characters of the text `field` in a tag of `tag_type`, if limited.
+fn max_length(tag_type: TagType, field: &str) -> Option<usize> {
+    match tag_type {
+        // the fixed size fields of the ID3v1 layout.
+        TagType::Id3v1 => match field {
+            "track_title" | "track_artist" | "album" => Some(30),
+            _ => None,
+        },
+        // the atoms have no limit of their own, but iTunes and most hardware players
+        // cut the displayed text at 255 characters.
+        TagType::Mp4Ilst => match field {
+            // a cut fingerprint would be useless.
+            "lyrics" | "acoustid_fingerprint" => None,
+            _ => Some(255),
+        },
+        _ => None,
+    }
+}
+
+fn truncate(value: &str, max_length: usize, strategy: TruncationStrategy) -> String {
+    let cut = |len: usize| value.chars().take(len).collect::<String>();
+    match strategy {
+        TruncationStrategy::Off => value.to_string(),
+        TruncationStrategy::HardCut => cut(max_length),
+        TruncationStrategy::Ellipsis => {
+            let len = max_length.saturating_sub(ELLIPSIS.len());
+            format!("{}{ELLIPSIS}", cut(len).trim_end())
+        }
+        TruncationStrategy::WordBoundary => {
+            let hard_cut = cut(max_length);
+            // the cut already falls between two words.
+            if value
+                .chars()
+                .nth(max_length)
+                .is_some_and(char::is_whitespace)
+            {
+                return hard_cut.trim_end().to_string();
+            }
+            match hard_cut.trim_end().rfind(char::is_whitespace) {
+                Some(index) if index > 0 => hard_cut[..index].trim_end().to_string(),
+                _ => hard_cut,
+            }
+        }
+    }
+}
@@ -0,0 +1,186 @@
+use crate::custom_item::CustomItem;
+use crate::tag::Tag;
+use crate::utils::file_id::{probe_file_type, read_id3v2};
+use crate::utils::read_only::{open_read_only, probe_read_only};
+use crate::work_link::WorkLink;
+use anyhow::anyhow;
+use lofty::id3::v2::Id3v2Tag;
+use lofty::mp4::{Atom, AtomData, AtomIdent, Ilst, Mp4File};
+use lofty::{AudioFile, BoundTaggedFile, ItemKey, ParseOptions, TagExt, TagType, TaggedFileExt};
+use std::fs::OpenOptions;
+use std::io::{Seek, SeekFrom};
+
+/// The `TXXX` description (and item key of the other text tags) of the work name in ID3v2 tags.
+const WORK_KEY: &str = "WORK";
+/// The `TXXX` description (and item key of the other text tags) of the iTunes gapless flag.
+const GAPLESS_KEY: &str = "ITUNPGAP";
+/// The `TXXX` description (and item key of the other text tags) of the show movement flag.
+const SHOW_MOVEMENT_KEY: &str = "SHOWMOVEMENT";
+/// The iTunes gapless playback atom.
+const GAPLESS_ATOM: [u8; 4] = *b"pgap";
+/// The iTunes show work and movement atom.
+const SHOW_MOVEMENT_ATOM: [u8; 4] = *b"shwm";
+
+/// Reads the [WorkLink] of the file at `path`, `None` when it isn't part of a work.
+pub(crate) fn read_work_link(path: &str) -> anyhow::Result<Option<WorkLink>> {
+    let file_type = probe_file_type(path)?;
+    let tagged = probe_read_only(path)?.read()?;
+    let Some(tag) = tagged.primary_tag() else {
+        return Ok(None);
+    };
+    let number = |key: &ItemKey| tag.get_string(key).and_then(|n| n.trim().parse().ok());
+    let movement_number = number(&ItemKey::MovementNumber);
+    let movement_total = number(&ItemKey::MovementTotal);
+
+    let (work, gapless, show_movement) = match file_type.primary_tag_type() {
+        TagType::Id3v2 => {
+            let mut file = open_read_only(path)?;
+            let Some(id3v2) = read_id3v2(&mut file, file_type)? else {
+                return Ok(None);
+            };
+            (
+                id3v2.get_user_text(WORK_KEY).map(str::to_string),
+                is_set(id3v2.get_user_text(GAPLESS_KEY)),
+                is_set(id3v2.get_user_text(SHOW_MOVEMENT_KEY)),
+            )
+        }
+        TagType::Mp4Ilst => {
+            let mut file = open_read_only(path)?;
+            let mp4 = Mp4File::read_from(&mut file, ParseOptions::new().read_properties(false))?;
+            let ilst = mp4.ilst();
+            (
+                tag.get_string(&ItemKey::Work).map(str::to_string),
+                ilst.is_some_and(|ilst| is_flag_atom_set(ilst, GAPLESS_ATOM)),
+                ilst.is_some_and(|ilst| is_flag_atom_set(ilst, SHOW_MOVEMENT_ATOM)),
+            )
+        }
+        _ => (
+            tag.get_string(&ItemKey::Work).map(str::to_string),
+            is_set(tag.get_string(&ItemKey::Unknown(GAPLESS_KEY.to_string()))),
+            is_set(tag.get_string(&ItemKey::Unknown(SHOW_MOVEMENT_KEY.to_string()))),
+        ),
+    };
+
+    Ok(work.map(|work| WorkLink {
+        work,
+        movement_number,
+        movement_total,
+        gapless,
+        show_movement,
+    }))
+}
+
+/// Writes the `link` to the file at `path`, keeping its other tags.
+///
+/// The movement numbers are written to the items lofty maps for the format (e.g. `MVIN` for ID3v2
+/// and `©mvi` for MP4). The work and the flags follow the iTunes conventions: the `©wrk`, `pgap`
+/// and `shwm` atoms for MP4, and `TXXX` frames for ID3v2.
+pub(crate) fn write_work_link(path: &str, link: &WorkLink) -> anyhow::Result<()> {
+    let file_type = probe_file_type(path)?;
+    let tag_type = file_type.primary_tag_type();
+
+    let file = OpenOptions::new().read(true).write(true).open(path)?;
+    let mut tagged = BoundTaggedFile::read_from(file, ParseOptions::new())?;
+    if tagged.primary_tag().is_none() {
+        tagged.insert_tag(lofty::Tag::new(tag_type));
+    }
+    if let Some(tag) = tagged.primary_tag_mut() {
+        insert_number(tag, ItemKey::MovementNumber, link.movement_number);
+        insert_number(tag, ItemKey::MovementTotal, link.movement_total);
+        if tag_type != TagType::Id3v2 {
+            tag.insert_text(ItemKey::Work, link.work.clone());
+        }
+        if tag_type != TagType::Id3v2 && tag_type != TagType::Mp4Ilst {
+            tag.insert_text(
+                ItemKey::Unknown(GAPLESS_KEY.to_string()),
+                flag(link.gapless),
+            );
+            tag.insert_text(
+                ItemKey::Unknown(SHOW_MOVEMENT_KEY.to_string()),
+                flag(link.show_movement),
+            );
+        }
+    }
+    tagged.save().map_err(|e| anyhow!(e))?;
+
+    match tag_type {
+        TagType::Id3v2 => {
+            let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+            let mut id3v2 = read_id3v2(&mut file, file_type)?.unwrap_or_default();
+            id3v2.insert_user_text(WORK_KEY.to_string(), link.work.clone());
+            id3v2.insert_user_text(GAPLESS_KEY.to_string(), flag(link.gapless));
+            id3v2.insert_user_text(SHOW_MOVEMENT_KEY.to_string(), flag(link.show_movement));
+            file.seek(SeekFrom::Start(0))?;
+            id3v2.save_to(&mut file).map_err(|e| anyhow!(e))
+        }
+        TagType::Mp4Ilst => {
+            let mut file = OpenOptions::new().read(true).write(true).open(path)?;
+            let options = ParseOptions::new().read_properties(false);
+            let mut ilst = Mp4File::read_from(&mut file, options)?
+                .ilst()
+                .cloned()
+                .unwrap_or_default();
+            ilst.replace_atom(Atom::new(
+                AtomIdent::Fourcc(GAPLESS_ATOM),
+                AtomData::Bool(link.gapless),
+            ));
+            ilst.replace_atom(Atom::new(
+                AtomIdent::Fourcc(SHOW_MOVEMENT_ATOM),
+                AtomData::Bool(link.show_movement),
+            ));
+            file.seek(SeekFrom::Start(0))?;
+            ilst.save_to(&mut file).map_err(|e| anyhow!(e))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Sets the [Tag::work] of the `tag` from the `WORK` `TXXX` frame of the `id3v2` tag, which lofty
+/// doesn't map, see [read_id3v2_frames](crate::utils::id3v2_frames::read_id3v2_frames).
+pub(crate) fn add_id3v2_work(tag: &mut Tag, id3v2: &Id3v2Tag) {
+    if tag.work.is_none() {
+        tag.work = id3v2.get_user_text(WORK_KEY).map(str::to_string);
+    }
+}
+
+/// The works of the ID3v2 tags among `tags`, to write as `TXXX` frames along with their
+/// custom items.
+pub(crate) fn id3v2_work_items(tags: &[Tag]) -> Vec<CustomItem> {
+    tags.iter()
+        .filter(|t| t.tag_type == crate::tag::TagType::Id3v2)
+        .filter_map(|t| Some(CustomItem::new(WORK_KEY, t.work.as_ref()?)))
+        .collect()
+}
+
+/// Whether the ID3v2 `TXXX` frame of the `description` holds the [Tag::work].
+pub(crate) fn is_work_description(description: &str) -> bool {
+    description.eq_ignore_ascii_case(WORK_KEY)
+}
+
+fn insert_number(tag: &mut lofty::Tag, key: ItemKey, number: Option<u32>) {
+    match number {
+        Some(n) => {
+            tag.insert_text(key, n.to_string());
+        }
+        None => tag.remove_key(&key),
+    }
+}
+
+fn flag(value: bool) -> String {
+    String::from(if value { "1" } else { "0" })
+}
+
+fn is_set(value: Option<&str>) -> bool {
+    value.is_some_and(|v| v.trim() == "1")
+}
+
+fn is_flag_atom_set(ilst: &Ilst, fourcc: [u8; 4]) -> bool {
+    ilst.get(&AtomIdent::Fourcc(fourcc))
+        .and_then(|atom| atom.data().next())
+        .is_some_and(|data| match data {
+            AtomData::Bool(value) => *value,
+            AtomData::SignedInteger(value) => *value != 0,
+            AtomData::UnsignedInteger(value) => *value != 0,
+            _ => false,
+        })
+}
@@ -0,0 +1,132 @@
+use crate::utils::read_only::open_read_only;
+use anyhow::anyhow;
+use std::path::Path;
+use symphonia::core::audio::SampleBuffer;
+use symphonia::core::codecs::{DecoderOptions, CODEC_TYPE_NULL};
+use symphonia::core::errors::Error as DecodeError;
+use symphonia::core::formats::FormatOptions;
+use symphonia::core::io::MediaSourceStream;
+use symphonia::core::meta::MetadataOptions;
+use symphonia::core::probe::Hint;
+
+/// The number of frames folded into a single intermediate peak.
+///
+/// Peaks are first collected per block and then merged into the requested buckets,
+/// so we don't need to know the track length up front (it's missing for some streams).
+const FRAMES_PER_BLOCK: usize = 256;
+
+/// Decodes the audio track of the file at given `path` and returns `buckets` peak values
+/// normalized to the `0.0..=1.0` range.
+pub(crate) fn compute_peaks(path: &str, buckets: usize) -> anyhow::Result<Vec<f32>> {
+    if buckets == 0 {
+        return Ok(vec![]);
+    }
+    let block_peaks = decode_block_peaks(path)?;
+    Ok(normalize(fold_into_buckets(&block_peaks, buckets)))
+}
+
+/// Decodes the whole default track and returns the absolute peak of every [FRAMES_PER_BLOCK].
+fn decode_block_peaks(path: &str) -> anyhow::Result<Vec<f32>> {
+    let file = open_read_only(path)?;
+    let stream = MediaSourceStream::new(Box::new(file), Default::default());
+
+    let mut hint = Hint::new();
+    if let Some(ext) = Path::new(path).extension().and_then(|e| e.to_str()) {
+        hint.with_extension(ext);
+    }
+    let probed = symphonia::default::get_probe()
+        .format(
+            &hint,
+            stream,
+            &FormatOptions::default(),
+            &MetadataOptions::default(),
+        )
+        .map_err(|e| anyhow!(e))?;
+    let mut format = probed.format;
+
+    let track = format
+        .tracks()
+        .iter()
+        .find(|t| t.codec_params.codec != CODEC_TYPE_NULL)
+        .ok_or_else(|| anyhow!("The file has no decodable audio track"))?;
+    let track_id = track.id;
+    let mut decoder = symphonia::default::get_codecs()
+        .make(&track.codec_params, &DecoderOptions::default())
+        .map_err(|e| anyhow!(e))?;
+
+    let mut peaks: Vec<f32> = vec![];
+    let mut block_peak = 0f32;
+    let mut frames_in_block = 0usize;
+    let mut sample_buf: Option<SampleBuffer<f32>> = None;
+
+    loop {
+        let packet = match format.next_packet() {
+            Ok(packet) => packet,
+            // reaching the end of the stream is reported as an io error.
+            Err(DecodeError::IoError(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+            Err(DecodeError::ResetRequired) => break,
+            Err(e) => return Err(anyhow!(e)),
+        };
+        if packet.track_id() != track_id {
+            continue;
+        }
+        let decoded = match decoder.decode(&packet) {
+            Ok(decoded) => decoded,
+            // a corrupt packet shouldn't fail the whole waveform.
+            Err(DecodeError::DecodeError(_)) => continue,
+            Err(e) => return Err(anyhow!(e)),
+        };
+        let spec = *decoded.spec();
+        let channels = spec.channels.count().max(1);
+        // the decoder may hand us bigger packets later on, so re-allocate when needed.
+        if sample_buf
+            .as_ref()
+            .map_or(true, |b| b.capacity() < decoded.capacity() * channels)
+        {
+            sample_buf = Some(SampleBuffer::new(decoded.capacity() as u64, spec));
+        }
+        let buf = sample_buf.as_mut().unwrap();
+        buf.copy_interleaved_ref(decoded);
+
+        for frame in buf.samples().chunks(channels) {
+            let frame_peak = frame.iter().fold(0f32, |acc, s| acc.max(s.abs()));
+            block_peak = block_peak.max(frame_peak);
+            frames_in_block += 1;
+            if frames_in_block == FRAMES_PER_BLOCK {
+                peaks.push(block_peak);
+                block_peak = 0.0;
+                frames_in_block = 0;
+            }
+        }
+    }
+    if frames_in_block > 0 {
+        peaks.push(block_peak);
+    }
+    Ok(peaks)
+}
+
+/// Merges the given `peaks` into exactly `buckets` values by taking the max of each range.
+fn fold_into_buckets(peaks: &[f32], buckets: usize) -> Vec<f32> {
+    if peaks.is_empty() {
+        return vec![0.0; buckets];
+    }
+    (0..buckets)
+        .map(|i| {
+            let start = i * peaks.len() / buckets;
+            // make sure every bucket covers at least one peak when there are fewer peaks than buckets.
+            let end = ((i + 1) * peaks.len() / buckets).max(start + 1);
+            peaks[start.min(peaks.len() - 1)..end.min(peaks.len())]
+                .iter()
+                .fold(0f32, |acc, p| acc.max(*p))
+        })
+        .collect()
+}
+
+/// Scales the given `peaks` so the loudest one equals `1.0`.
+fn normalize(peaks: Vec<f32>) -> Vec<f32> {
+    let max = peaks.iter().fold(0f32, |acc, p| acc.max(*p));
+    if max <= f32::EPSILON {
+        return peaks;
+    }
+    peaks.into_iter().map(|p| (p / max).min(1.0)).collect()
+}
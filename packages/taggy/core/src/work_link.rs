@@ -0,0 +1,16 @@
+/// The place of a file in a continuous work, e.g. a movement of a symphony or a track of a live set,
+/// see [link_continuous_work](crate::api::link_continuous_work).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct WorkLink {
+    /// The name of the work, e.g. `Symphony No. 9`.
+    pub work: String,
+    /// The position of the file in the work, starting at `1`.
+    pub movement_number: Option<u32>,
+    /// The number of files of the work.
+    pub movement_total: Option<u32>,
+    /// Whether the file should be played without a gap after the previous one.
+    pub gapless: bool,
+    /// Whether players should show the work and movement instead of the track title.
+    pub show_movement: bool,
+}
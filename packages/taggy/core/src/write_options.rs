@@ -0,0 +1,144 @@
+use crate::date_context::DateContext;
+use crate::tag::TagType;
+
+/// Extra options for [write_all_with_options](crate::api::write_all_with_options)
+/// and [write_primary_with_options](crate::api::write_primary_with_options).
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct WriteOptions {
+    /// Refuse to overwrite the fields which already have a value in the file,
+    /// so only the missing ones are filled.
+    pub protect_existing: bool,
+    /// When `protect_existing` is set, keep the existing values of the protected fields
+    /// and write the others, instead of failing the whole write.
+    pub skip_protected: bool,
+    /// Overwrite the fields even when `protect_existing` is set.
+    pub force: bool,
+    /// Remove all the pictures already in the file, in the same save as the new tags.
+    ///
+    /// The pictures of the written tags are still written.
+    pub clear_pictures: bool,
+    /// What to do with the tags whose type isn't supported by the file format,
+    /// e.g. a Vorbis comments tag written to an MP3 file.
+    pub unsupported_tag_policy: UnsupportedTagPolicy,
+    /// Write the tags to a sidecar file (e.g. `track.flac.taggy.json`) when the file is read-only,
+    /// instead of failing. Read them back with [read_all_with_sidecar](crate::api::read_all_with_sidecar).
+    pub sidecar_fallback: bool,
+    /// How to cut the values exceeding the length limit of the tag format,
+    /// each cut value is reported in the write warnings.
+    pub truncation: TruncationStrategy,
+    /// The rules the written values are checked against, see [LintProfile].
+    pub lint_profile: LintProfile,
+    /// Also write the fields of the primary tag to an ID3v1 tag, for the players which only read
+    /// ID3v1. Only applies to the formats supporting ID3v1, and when no ID3v1 tag is written along.
+    pub sync_id3v1: bool,
+    /// Drop the pictures which aren't JPEG, each dropped picture is reported in the write warnings.
+    pub jpeg_pictures_only: bool,
+    /// Drop the pictures whose width or height is larger than this many pixels,
+    /// each dropped picture is reported in the write warnings. `None` for no limit.
+    pub max_picture_size: Option<u32>,
+    /// Normalize the written dates according to the user's locale and time zone, e.g. for
+    /// dates typed as `03/04/2001`, see [DateContext]. `None` to write the dates as they are.
+    pub date_context: Option<DateContext>,
+    /// The maximum sizes of the written tags by type, e.g. to keep the ID3v2 tags small enough
+    /// for the players refusing to load files with large ones. The write fails with
+    /// [LimitError::TagTooLarge](crate::limits::LimitError::TagTooLarge) when a tag exceeds it.
+    pub max_tag_sizes: Vec<TagSizeLimit>,
+}
+
+impl WriteOptions {
+    /// Whether the existing fields should be checked before writing.
+    pub(crate) fn protects_existing(&self) -> bool {
+        self.protect_existing && !self.force
+    }
+}
+
+/// The maximum size of the written tags of a type, see [WriteOptions]'s `max_tag_sizes`.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct TagSizeLimit {
+    pub tag_type: TagType,
+    /// The maximum size of the encoded tag in bytes, headers and pictures included.
+    pub max_size: u64,
+}
+
+/// What to do when writing a tag whose type isn't supported by the file format.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum UnsupportedTagPolicy {
+    /// Don't write the tag, and report it in the write warnings.
+    #[default]
+    Skip,
+    /// Write the tag fields to the file primary tag instead, without overwriting
+    /// the fields of a primary tag written along.
+    ConvertToSupported,
+    /// Fail the whole write.
+    Error,
+}
+
+/// How to cut the values exceeding the length limit of a tag format, e.g. the 30 characters
+/// of an ID3v1 title.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum TruncationStrategy {
+    /// Keep the values as they are, only the fixed size fields of ID3v1 are still cut
+    /// when saving.
+    #[default]
+    Off,
+    /// Keep as many characters as fit.
+    HardCut,
+    /// Keep as many characters as fit with a trailing `...`.
+    Ellipsis,
+    /// Keep the whole words which fit, or cut hard when the first word doesn't.
+    WordBoundary,
+}
+
+/// The rules the written values are checked against, each issue is reported as a warning.
+#[derive(Debug, Copy, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum LintProfile {
+    /// Don't check the values.
+    #[default]
+    Off,
+    /// Warn about values longer than most players show (255 characters)
+    /// and about control characters.
+    Standard,
+    /// [LintProfile::Standard], and warn about titles, artists and albums longer than
+    /// 64 characters, emojis, and invisible characters, which car head units can't display.
+    CarPlaySafe,
+}
+
+/// Named sets of [WriteOptions] for the devices the files are written for,
+/// see [write_options_for_profile](crate::api::write_options_for_profile).
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum WriteProfile {
+    /// For old car stereos and portable players: an ID3v1 copy of the primary tag,
+    /// JPEG only pictures of at most 500 pixels, values cut at word boundaries
+    /// and checked with [LintProfile::CarPlaySafe].
+    OldCarStereo,
+    /// For archiving: the values and pictures are written as they are,
+    /// and checked with [LintProfile::Standard].
+    Archival,
+}
+
+impl WriteProfile {
+    /// The [WriteOptions] of this profile.
+    pub fn options(self) -> WriteOptions {
+        match self {
+            WriteProfile::OldCarStereo => WriteOptions {
+                truncation: TruncationStrategy::WordBoundary,
+                lint_profile: LintProfile::CarPlaySafe,
+                sync_id3v1: true,
+                jpeg_pictures_only: true,
+                max_picture_size: Some(500),
+                ..WriteOptions::default()
+            },
+            WriteProfile::Archival => WriteOptions {
+                truncation: TruncationStrategy::Off,
+                lint_profile: LintProfile::Standard,
+                ..WriteOptions::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::tag::TagType;
+use crate::taggy_file::TaggyFile;
+
+/// The outcome of a write, see [write_all_with_report](crate::api::write_all_with_report).
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct WriteReport {
+    /// The file after the write.
+    pub file: TaggyFile,
+    /// What happened to each of the tags to write, in the same order.
+    pub tags: Vec<TagWriteResult>,
+    /// The issues which didn't prevent the write, e.g. a skipped unsupported tag.
+    pub warnings: Vec<String>,
+    /// Whether the written tags were the same as the existing ones,
+    /// in which case the file wasn't saved.
+    pub unchanged: bool,
+}
+
+/// What happened to a single tag of a write.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct TagWriteResult {
+    /// The type of the tag as it was requested.
+    pub requested_type: TagType,
+    /// The type of the tag as it was written, `None` when it was skipped.
+    pub written_type: Option<TagType>,
+    pub action: TagWriteAction,
+}
+
+impl TagWriteResult {
+    pub(crate) fn new(
+        requested_type: TagType,
+        written_type: Option<TagType>,
+        action: TagWriteAction,
+    ) -> Self {
+        Self {
+            requested_type,
+            written_type,
+            action,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Enum))]
+pub enum TagWriteAction {
+    /// The file didn't have a tag of this type.
+    Inserted,
+    /// The existing tag of this type was replaced.
+    Replaced,
+    /// The tag type isn't supported by the file, so it wasn't written.
+    Skipped,
+    /// The tag type isn't supported by the file, so its fields were written to the primary tag.
+    Converted,
+}
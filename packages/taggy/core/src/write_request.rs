@@ -0,0 +1,13 @@
+use crate::tag::Tag;
+
+/// The tags to write to a single file, see [write_transaction](crate::api::write_transaction).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct WriteRequest {
+    /// The path of the file to write to.
+    pub path: String,
+    /// The tags to write, same as the `tags` of [write_all](crate::api::write_all).
+    pub tags: Vec<Tag>,
+    /// Same as the `override_existent` of [write_all](crate::api::write_all).
+    pub override_existent: bool,
+}
@@ -0,0 +1,12 @@
+/// A release year found in the folder or file name of a file missing it,
+/// see [suggest_years](crate::api::suggest_years).
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "uniffi", derive(uniffi::Record))]
+pub struct YearSuggestion {
+    /// The path of the file missing the year.
+    pub path: String,
+    /// The year to write.
+    pub year: u32,
+    /// The folder or file name the year was found in, e.g. `Artist - Album (1997)`.
+    pub source: String,
+}
@@ -0,0 +1,154 @@
+use crate::batch_result::BatchReadResult;
+use crate::error::TaggyError;
+use crate::limits::LimitError;
+use crate::tag::Tag;
+use crate::utils::buffer::get_tagged_file_from_bytes;
+use crate::utils::file_id::generate_uuid;
+use lofty::FileType;
+use std::fs::{self, File};
+use std::io::{BufReader, Read, Seek, Write};
+use std::path::Path;
+use zip::read::ZipFile;
+use zip::result::ZipError;
+use zip::write::FileOptions;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+
+/// The largest decompressed entry read, as it's held in memory whole, in bytes.
+pub(crate) const MAX_ENTRY_SIZE: u64 = 1024 * 1024 * 1024;
+
+/// Reads all the tags of each audio file of the `.zip` archive at `zip_path`, one entry at a
+/// time, so the archive is never extracted as a whole.
+///
+/// The entries are the audio files by their extension, the folders and the macOS resource forks
+/// are skipped. The entries decompressing to more than `max_entry_size` bytes, or
+/// [MAX_ENTRY_SIZE], fail with [LimitError::FileTooLarge].
+pub(crate) fn read_zip_entries(
+    zip_path: &str,
+    max_entry_size: Option<u64>,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    let max_size = entry_size_limit(max_entry_size);
+    let mut archive = open_archive(zip_path)?;
+    let mut results = vec![];
+    for index in 0..archive.len() {
+        let (name, is_dir) = {
+            let entry = archive.by_index_raw(index)?;
+            (entry.name().to_string(), entry.is_dir())
+        };
+        if is_dir || name.starts_with("__MACOSX/") || !is_audio(&name) {
+            continue;
+        }
+        // e.g. an encrypted entry only fails on its own.
+        let result = archive
+            .by_index(index)
+            .map_err(anyhow::Error::from)
+            .and_then(|entry| read_entry(entry, max_size))
+            .and_then(crate::api::read_all_from_buffer);
+        results.push(BatchReadResult::new(name, result));
+    }
+    Ok(results)
+}
+
+/// Writes the `tags` to the `entry_name` audio file of the `.zip` archive at `zip_path`.
+///
+/// The archive is rebuilt next to it, with the other entries copied without being decompressed,
+/// and only replaces it once it's complete, so a failure leaves it as it was. The entry is
+/// limited in size as with [read_zip_entries].
+pub(crate) fn write_zip_entry(
+    zip_path: &str,
+    entry_name: &str,
+    tags: Vec<Tag>,
+    override_existent: bool,
+    max_entry_size: Option<u64>,
+) -> anyhow::Result<()> {
+    let mut archive = open_archive(zip_path)?;
+    let bytes = match archive.by_name(entry_name) {
+        Err(ZipError::FileNotFound) => return Err(TaggyError::FileNotFound.into()),
+        entry => read_entry(entry?, entry_size_limit(max_entry_size))?,
+    };
+    let tagged = crate::api::write_all_to_buffer(bytes, tags, override_existent)?;
+    // checked before the archive is touched.
+    get_tagged_file_from_bytes(&tagged)?;
+
+    let path = Path::new(zip_path);
+    let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+    let copy_path = path.with_file_name(format!(".{file_name}.taggy-{}", generate_uuid()));
+    let result = rebuild(archive, &copy_path, entry_name, &tagged).and_then(|_| {
+        fs::set_permissions(&copy_path, fs::metadata(path)?.permissions())?;
+        fs::rename(&copy_path, path)?;
+        Ok(())
+    });
+    if result.is_err() {
+        let _ = fs::remove_file(&copy_path);
+    }
+    result
+}
+
+fn open_archive(zip_path: &str) -> anyhow::Result<ZipArchive<BufReader<File>>> {
+    let file = File::open(zip_path).map_err(|_| TaggyError::FileNotFound)?;
+    // e.g. not a zip archive, or a multi-disk one.
+    ZipArchive::new(BufReader::new(file)).map_err(|_| TaggyError::UnsupportedFormat.into())
+}
+
+/// Writes the entries of the `archive` to a new archive at `copy_path`, with the `entry_name`
+/// one holding the `bytes`, then closes both so the copy can replace the archive.
+fn rebuild<R: Read + Seek>(
+    mut archive: ZipArchive<R>,
+    copy_path: &Path,
+    entry_name: &str,
+    bytes: &[u8],
+) -> anyhow::Result<()> {
+    let mut writer = ZipWriter::new(File::create(copy_path)?);
+    writer.set_comment(String::from_utf8_lossy(archive.comment()));
+    for index in 0..archive.len() {
+        let entry = archive.by_index_raw(index)?;
+        if entry.name() != entry_name {
+            writer.raw_copy_file(entry)?;
+            continue;
+        }
+        // the audio files are often stored, they barely compress.
+        let compression = match entry.compression() {
+            CompressionMethod::Stored => CompressionMethod::Stored,
+            _ => CompressionMethod::Deflated,
+        };
+        let mut options = FileOptions::default()
+            .compression_method(compression)
+            .last_modified_time(entry.last_modified())
+            .large_file(bytes.len() as u64 >= u32::MAX as u64);
+        if let Some(mode) = entry.unix_mode() {
+            options = options.unix_permissions(mode);
+        }
+        writer.start_file(entry_name, options)?;
+        writer.write_all(bytes)?;
+    }
+    writer.finish()?.sync_all()?;
+    Ok(())
+}
+
+/// The decompressed bytes of the `entry`, the only part of the archive held in memory.
+///
+/// The size the archive declares for the entry can be forged, so nothing is allocated from it
+/// and the decompression stops past `max_size`.
+fn read_entry(mut entry: ZipFile, max_size: u64) -> anyhow::Result<Vec<u8>> {
+    let mut bytes = vec![];
+    (&mut entry).take(max_size + 1).read_to_end(&mut bytes)?;
+    if bytes.len() as u64 > max_size {
+        return Err(LimitError::FileTooLarge {
+            size: entry.size().max(bytes.len() as u64),
+            max_file_size: max_size,
+        }
+        .into());
+    }
+    Ok(bytes)
+}
+
+/// The lowest of `max_entry_size` and [MAX_ENTRY_SIZE].
+fn entry_size_limit(max_entry_size: Option<u64>) -> u64 {
+    max_entry_size.map_or(MAX_ENTRY_SIZE, |max| max.min(MAX_ENTRY_SIZE))
+}
+
+fn is_audio(name: &str) -> bool {
+    Path::new(name)
+        .extension()
+        .and_then(FileType::from_ext)
+        .is_some()
+}
@@ -1,401 +1,2318 @@
-use crate::tag::{Tag, TagType};
-use crate::taggy_file::TaggyFile;
-use crate::utils::lofty_froms::*;
+//! The API exposed to Dart through `flutter_rust_bridge`.
+//!
+//! All the tag logic lives in `taggy-core`, this file only forwards to it and
+//! declares the mirrors of its types so the bridge can generate their Dart counterparts.
 use anyhow::anyhow;
-use lofty::{BoundTaggedFile, ParseOptions, Probe, TaggedFile, TaggedFileExt};
-use std::fs::OpenOptions;
+use flutter_rust_bridge::frb;
+#[cfg(not(target_arch = "wasm32"))]
+use flutter_rust_bridge::{RustOpaque, StreamSink};
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::Arc;
+pub use taggy_core::album_gaps::AlbumGaps;
+pub use taggy_core::artwork_optimization::{ArtworkOptions, ArtworkSavings};
+pub use taggy_core::audio_info::{AudioDuration, AudioInfo};
+pub use taggy_core::batch_result::BatchReadResult;
+pub use taggy_core::chapter::Chapter;
+#[cfg(not(target_arch = "wasm32"))]
+pub use taggy_core::client::{CacheStats, ClientOptions, TaggyClient};
+pub use taggy_core::comment::Comment;
+pub use taggy_core::custom_item::CustomItem;
+pub use taggy_core::date_context::{DateContext, DateOrder};
+pub use taggy_core::disc_set::{Disc, DiscSet};
+pub use taggy_core::dj_markers::{CuePoint, DjLoop, DjMarkers};
+pub use taggy_core::duplicate_policy::DuplicatePolicy;
+pub use taggy_core::effective_tag::{EffectiveTag, FieldSource};
+use taggy_core::error::TaggyError;
+pub use taggy_core::featuring::{FeaturingChange, FeaturingStyle};
+pub use taggy_core::field_class::FieldClass;
+pub use taggy_core::field_value_count::FieldValueCount;
+#[cfg(not(target_arch = "wasm32"))]
+pub use taggy_core::hooks::{OperationStats, TaggyHooks};
+pub use taggy_core::item_key::ItemKey;
+pub use taggy_core::lyrics_match::LyricsMatch;
+pub use taggy_core::merge_suggestion::MergeSuggestion;
+pub use taggy_core::palette_color::PaletteColor;
+pub use taggy_core::parse_config::{ParseConfig, ParsingMode};
+pub use taggy_core::picture::{
+    MimeType, Picture, PictureChanges, PictureOrder, PictureStorage, PictureType,
+};
+pub use taggy_core::raw_frame::RawFrame;
+pub use taggy_core::recovery_report::RecoveryReport;
+pub use taggy_core::replay_gain::ReplayGain;
+pub use taggy_core::scan_result::{QuarantinedFile, ScanResult, ScannedFile};
+#[cfg(not(target_arch = "wasm32"))]
+pub use taggy_core::scanner::{ScanOptions, SymlinkPolicy};
+pub use taggy_core::synced_lyrics::{SyncedLine, SyncedLyrics};
+pub use taggy_core::tag::{Tag, TagType};
+pub use taggy_core::tag_date::TagDate;
+pub use taggy_core::taggy_file::{FileType, TaggyFile};
+pub use taggy_core::work_link::WorkLink;
+pub use taggy_core::write_options::{
+    LintProfile, TagSizeLimit, TruncationStrategy, UnsupportedTagPolicy, WriteOptions, WriteProfile,
+};
+pub use taggy_core::write_report::{TagWriteAction, TagWriteResult, WriteReport};
+pub use taggy_core::write_request::WriteRequest;
+pub use taggy_core::year_suggestion::YearSuggestion;
 
 /// Read all audio tags from the file at given `path`.
+///
+/// See [taggy_core::api::read_all].
 pub fn read_all(path: String) -> anyhow::Result<TaggyFile> {
-    let tagged = get_tagged_file(path.as_ref())?;
-    Ok(taggy_from_tagged(&tagged, &path))
+    coded(taggy_core::api::read_all(path))
 }
 
 /// Read only the primary audio tag from the file at given `path`.
 ///
-/// **Note**: If the primary tag does not exist,
-/// this will return a [TaggyFile] with no tags.
-///
-/// Throws an **exception** when:
-/// - path doesn't exists
+/// See [taggy_core::api::read_primary].
 pub fn read_primary(path: String) -> anyhow::Result<TaggyFile> {
-    let tagged = get_tagged_file(path.as_ref())?;
-
-    Ok(TaggyFile {
-        tags: get_primary_tag_from_tagged_file(&tagged),
-        ..taggy_from_tagged(&tagged, &path)
-    })
+    coded(taggy_core::api::read_primary(path))
 }
 
 /// Read any audio tag from the file at the given `path`.
 ///
-/// **Note**: If the file has no tags,
-/// this will return a [TaggyFile] with an empty tags.
-///
-/// Throws an **exception** when:
-/// - path doesn't exists
+/// See [taggy_core::api::read_any].
 pub fn read_any(path: String) -> anyhow::Result<TaggyFile> {
-    let tagged = get_tagged_file(path.as_ref())?;
+    coded(taggy_core::api::read_any(path))
+}
 
-    Ok(TaggyFile {
-        tags: get_any_tag_from_tagged_file(&tagged),
-        ..taggy_from_tagged(&tagged, &path)
-    })
+/// Same as [read_all], parsing the file with the given `config`.
+///
+/// See [taggy_core::api::read_all_with_config].
+pub fn read_all_with_config(path: String, config: ParseConfig) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::read_all_with_config(path, config))
 }
 
-/// A helper function to get a [`TaggedFile`] from the given path.
-/// the returned file will be used for reading properties only.
-fn get_tagged_file(path: &str) -> anyhow::Result<TaggedFile> {
-    match Probe::open(path) {
-        Err(_) => Err(anyhow!("The file path does not exist!")),
-        Ok(file) => match file.read() {
-            Ok(tf) => Ok(tf),
-            Err(e) => Err(anyhow!(e)),
-        },
-    }
+/// Same as [read_primary], parsing the file with the given `config`.
+///
+/// See [taggy_core::api::read_primary_with_config].
+pub fn read_primary_with_config(path: String, config: ParseConfig) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::read_primary_with_config(path, config))
 }
 
-/// Write all provided `tags` to the file at given `path`.
+/// Same as [read_any], parsing the file with the given `config`.
+///
+/// See [taggy_core::api::read_any_with_config].
+pub fn read_any_with_config(path: String, config: ParseConfig) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::read_any_with_config(path, config))
+}
+
+/// Read all audio tags from each file of the given `paths`, in a single call.
+///
+/// See [taggy_core::api::read_all_batch].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read_all_batch(paths: Vec<String>, concurrency: u32) -> Vec<BatchReadResult> {
+    taggy_core::api::read_all_batch(paths, concurrency)
+}
+
+/// Read all audio tags from the audio files in the directory at given `dir` path,
+/// and in its subfolders when `recursive` is set.
+///
+/// See [taggy_core::api::scan_directory].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    coded(taggy_core::api::scan_directory(
+        dir,
+        recursive,
+        extensions,
+        concurrency,
+    ))
+}
+
+/// Counts the distinct values of the given `field` across the audio files in the directory
+/// at given `dir` path and its subfolders, e.g. for autocompletion or for spotting the
+/// different spellings of a genre.
+///
+/// See [taggy_core::api::field_values].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn field_values(dir: String, field: String) -> anyhow::Result<Vec<FieldValueCount>> {
+    coded(taggy_core::api::field_values(dir, field))
+}
+
+/// Groups the near-duplicate `values` of a field, e.g. `Hip Hop`, `Hip-Hop` and `hiphop`,
+/// as returned by [field_values].
+///
+/// See [taggy_core::api::suggest_merges].
+pub fn suggest_merges(values: Vec<FieldValueCount>) -> Vec<MergeSuggestion> {
+    taggy_core::api::suggest_merges(values)
+}
+
+/// Finds the audio files in the directory at given `dir` path and its subfolders whose
+/// lyrics contain the `query`, e.g. to find a song from a line of it.
+///
+/// See [taggy_core::api::search_lyrics].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn search_lyrics(dir: String, query: String) -> anyhow::Result<Vec<LyricsMatch>> {
+    coded(taggy_core::api::search_lyrics(dir, query))
+}
+
+/// Finds the albums in the directory at given `dir` path and its subfolders with missing
+/// or duplicated track numbers, e.g. to spot the incomplete albums of a library.
+///
+/// See [taggy_core::api::find_gaps].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn find_gaps(dir: String) -> anyhow::Result<Vec<AlbumGaps>> {
+    coded(taggy_core::api::find_gaps(dir))
+}
+
+/// Same as [scan_directory], with all the [ScanOptions]: e.g. following the symbolic links,
+/// or reading the hidden and system files, which are skipped by default.
+///
+/// See [taggy_core::api::scan_directory_with_options].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory_with_options(
+    dir: String,
+    options: ScanOptions,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    coded(taggy_core::api::scan_directory_with_options(dir, options))
+}
+
+/// Same as [scan_directory], but the symbolic links (and the junctions on Windows) are handled
+/// according to `symlinks`: skipped, followed with each file read once, or followed with each
+/// path read as a distinct entry.
+///
+/// See [taggy_core::api::scan_directory_with_symlinks].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory_with_symlinks(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+    symlinks: SymlinkPolicy,
+) -> anyhow::Result<Vec<BatchReadResult>> {
+    coded(taggy_core::api::scan_directory_with_symlinks(
+        dir,
+        recursive,
+        extensions,
+        concurrency,
+        symlinks,
+    ))
+}
+
+/// Same as [scan_directory], but the files which can't be read, e.g. corrupt or truncated ones,
+/// are collected into a separate quarantine list with the reason of each failure, so they can
+/// be reviewed afterwards. The subfolders which can't be listed are quarantined too.
+///
+/// See [taggy_core::api::scan_directory_with_quarantine].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory_with_quarantine(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+) -> anyhow::Result<ScanResult> {
+    coded(taggy_core::api::scan_directory_with_quarantine(
+        dir,
+        recursive,
+        extensions,
+        concurrency,
+    ))
+}
+
+/// Same as [scan_directory], but streams each file as soon as it's read, along with
+/// periodic progress events, so large libraries can be shown incrementally.
+///
+/// See [taggy_core::scanner::scan_directory_stream].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn scan_directory_stream(
+    dir: String,
+    recursive: bool,
+    extensions: Vec<String>,
+    concurrency: u32,
+    sink: StreamSink<ScanEvent>,
+) -> anyhow::Result<()> {
+    use taggy_core::scanner::{self, ScanEvent as CoreScanEvent};
+
+    let result =
+        scanner::scan_directory_stream(&dir, recursive, &extensions, concurrency, |event| {
+            sink.add(match event {
+                CoreScanEvent::File(result) => ScanEvent::File { result: *result },
+                CoreScanEvent::Progress { done, total } => ScanEvent::Progress { done, total },
+            });
+        });
+    sink.close();
+    coded(result)
+}
+
+/// An event of [scan_directory_stream].
+#[cfg(not(target_arch = "wasm32"))]
+pub enum ScanEvent {
+    /// A file was read, or failed to be read.
+    File { result: BatchReadResult },
+    /// `done` files out of the `total` found were read.
+    ///
+    /// Emitted once the files are found, then periodically, and after the last file.
+    Progress { done: u32, total: u32 },
+}
+
+/// Read all audio tags from the file at the given `path`, merged into a single [EffectiveTag].
 ///
-/// when `override_existent` is set to `true`, this will remove all existing tags.
-/// Otherwise, it will add or update any existing ones.
+/// See [taggy_core::api::read_effective].
+pub fn read_effective(path: String) -> anyhow::Result<EffectiveTag> {
+    coded(taggy_core::api::read_effective(path))
+}
+
+/// Read all audio tags from the file at given `path`, overlaid with the tags of its sidecar
+/// (e.g. `track.flac.taggy.json`) if it has one.
+///
+/// See [taggy_core::api::read_all_with_sidecar].
+pub fn read_all_with_sidecar(path: String) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::read_all_with_sidecar(path))
+}
+
+/// Read all audio tags from the given audio file `bytes`.
+///
+/// See [taggy_core::api::read_all_from_buffer].
+pub fn read_all_from_buffer(bytes: Vec<u8>) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::read_all_from_buffer(bytes))
+}
+
+/// Read only the primary audio tag from the given audio file `bytes`.
+///
+/// See [taggy_core::api::read_primary_from_buffer].
+pub fn read_primary_from_buffer(bytes: Vec<u8>) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::read_primary_from_buffer(bytes))
+}
+
+/// Read any audio tag from the given audio file `bytes`.
 ///
-/// Throws an **exception** when:
-/// - path doesn't exists
+/// See [taggy_core::api::read_any_from_buffer].
+pub fn read_any_from_buffer(bytes: Vec<u8>) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::read_any_from_buffer(bytes))
+}
+
+/// Read the pictures of all the tags of the file at given `path`, the primary tag ones first,
+/// without the rest of the tags.
+///
+/// See [taggy_core::api::read_pictures].
+pub fn read_pictures(path: String) -> anyhow::Result<Vec<Picture>> {
+    coded(taggy_core::api::read_pictures(path))
+}
+
+/// Read the front cover of the file at given `path`, searching all of its tags,
+/// the primary one first.
+///
+/// See [taggy_core::api::read_front_cover].
+pub fn read_front_cover(path: String) -> anyhow::Result<Option<Picture>> {
+    coded(taggy_core::api::read_front_cover(path))
+}
+
+/// Same as [read_pictures], with the pictures sorted in the given `order`.
+///
+/// See [taggy_core::api::read_pictures_with_order].
+pub fn read_pictures_with_order(path: String, order: PictureOrder) -> anyhow::Result<Vec<Picture>> {
+    coded(taggy_core::api::read_pictures_with_order(path, order))
+}
+
+/// Read the first picture of the file at given `path` in the given `order`, e.g. the one
+/// to show as its cover. Returns `None` when the file has no pictures.
+///
+/// See [taggy_core::api::read_primary_picture].
+pub fn read_primary_picture(path: String, order: PictureOrder) -> anyhow::Result<Option<Picture>> {
+    coded(taggy_core::api::read_primary_picture(path, order))
+}
+
+/// Writes the data of the first `picture_type` picture of the file at given `path`
+/// to the file at `out_path` as is, e.g. for filling a thumbnails cache without copying
+/// the picture through the bridge.
+///
+/// See [taggy_core::api::export_picture].
+pub fn export_picture(
+    path: String,
+    out_path: String,
+    picture_type: PictureType,
+) -> anyhow::Result<bool> {
+    coded(taggy_core::api::export_picture(
+        path,
+        out_path,
+        picture_type,
+    ))
+}
+
+/// Read the value of the field with the given `key`, e.g. [ItemKey::Composer], from
+/// the file at given `path`, including the fields [Tag] doesn't model.
+///
+/// See [taggy_core::api::read_field].
+pub fn read_field(path: String, key: ItemKey) -> anyhow::Result<Option<String>> {
+    coded(taggy_core::api::read_field(path, key))
+}
+
+/// The type of the primary tag of the file at given `path`, e.g. [TagType::VorbisComments]
+/// for a FLAC file, which is the type to give the tags written with [write_primary].
+///
+/// See [taggy_core::api::read_primary_tag_type].
+pub fn read_primary_tag_type(path: String) -> anyhow::Result<TagType> {
+    coded(taggy_core::api::read_primary_tag_type(path))
+}
+
+/// Read only the duration of the file at given `path` in **microseconds**.
+///
+/// See [taggy_core::api::read_duration].
+pub fn read_duration(path: String) -> anyhow::Result<u64> {
+    coded(taggy_core::api::read_duration(path))
+}
+
+/// Read only the audio properties (duration, bitrate, sample rate, channels...)
+/// of the file at given `path`, without its tags.
+///
+/// See [taggy_core::api::read_properties].
+pub fn read_properties(path: String) -> anyhow::Result<AudioInfo> {
+    coded(taggy_core::api::read_properties(path))
+}
+
+/// Write all provided `tags` to the file at given `path`.
+///
+/// See [taggy_core::api::write_all].
 pub fn write_all(
     path: String,
     tags: Vec<Tag>,
     override_existent: bool,
 ) -> anyhow::Result<TaggyFile> {
-    let mut tagged_file = get_bound_tagged_file(&path)?;
+    coded(taggy_core::api::write_all(path, tags, override_existent))
+}
 
-    if override_existent {
-        tagged_file.clear();
-    }
+/// Write all provided `tags` to the given audio file `bytes`, and return the tagged bytes.
+///
+/// See [taggy_core::api::write_all_to_buffer].
+pub fn write_all_to_buffer(
+    bytes: Vec<u8>,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> anyhow::Result<Vec<u8>> {
+    coded(taggy_core::api::write_all_to_buffer(
+        bytes,
+        tags,
+        override_existent,
+    ))
+}
 
-    // convert the provided tags to lofty's Tag
-    let lofty_tags = tags.iter().map(|t| t.to_lofty()).collect();
+/// Write the provided `tag` as the primary tag for the file at given `path`.
+///
+/// See [taggy_core::api::write_primary].
+pub fn write_primary(path: String, tag: Tag, keep_others: bool) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::write_primary(path, tag, keep_others))
+}
 
-    // add tags to file
-    insert_tags(&mut tagged_file, &lofty_tags);
+/// Same as [write_all], with the extra write `options`.
+///
+/// See [taggy_core::api::write_all_with_options].
+pub fn write_all_with_options(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+    options: WriteOptions,
+) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::write_all_with_options(
+        path,
+        tags,
+        override_existent,
+        options,
+    ))
+}
 
-    match tagged_file.save() {
-        Ok(_) => Ok(taggy_from_bound_tagged(&tagged_file, &path)),
-        Err(e) => Err(anyhow!(e)),
-    }
+/// The [WriteOptions] of the given device `profile`, to pass to [write_all_with_options]
+/// or [write_primary_with_options]. They can be adjusted before writing.
+pub fn write_options_for_profile(profile: WriteProfile) -> WriteOptions {
+    profile.options()
 }
 
-fn insert_tags(file: &mut BoundTaggedFile, tags: &Vec<lofty::Tag>) {
-    for tag in tags {
-        if file.supports_tag_type(tag.tag_type()) {
-            file.insert_tag(tag.clone());
-        } else {
-            println!(
-                "taggy: The tag type '{:?}' is not supported for the file type '{:?}'",
-                tag.tag_type(),
-                file.file_type()
-            );
-        }
-    }
+/// Same as [write_all_with_options], but returns a [WriteReport] telling which of
+/// the `tags` were inserted, replaced, skipped or converted, along with the issues
+/// which didn't prevent the write.
+///
+/// See [taggy_core::api::write_all_with_report].
+pub fn write_all_with_report(
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+    options: WriteOptions,
+) -> anyhow::Result<WriteReport> {
+    coded(taggy_core::api::write_all_with_report(
+        path,
+        tags,
+        override_existent,
+        options,
+    ))
 }
 
-/// Write the provided `tag` as the primary tag for the file at given `path`.
+/// Same as [write_primary], with the extra write `options`, applied as with
+/// [write_all_with_options].
+///
+/// See [taggy_core::api::write_primary_with_options].
+pub fn write_primary_with_options(
+    path: String,
+    tag: Tag,
+    keep_others: bool,
+    options: WriteOptions,
+) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::write_primary_with_options(
+        path,
+        tag,
+        keep_others,
+        options,
+    ))
+}
+
+/// Write the given `picture` as the `picture_type` picture of the primary tag of the file
+/// at given `path`, replacing the existing pictures of this type.
 ///
-/// If `keep_others` is set to `false`, this will remove any existing tags from the file.
+/// See [taggy_core::api::write_picture].
+pub fn write_picture(
+    path: String,
+    picture: Picture,
+    picture_type: PictureType,
+) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::write_picture(path, picture, picture_type))
+}
+
+/// Reads the image file at given `image_path` as a `picture_type` picture, e.g. to pass it
+/// to [write_all] along with other changes.
 ///
-/// **Note**: the `tag_type` of the give tag will be overridden with the file primary tag type,
-/// so you can set it to any or use [TagType.FilePrimaryType].
+/// See [taggy_core::api::picture_from_path].
+pub fn picture_from_path(image_path: String, picture_type: PictureType) -> anyhow::Result<Picture> {
+    coded(taggy_core::api::picture_from_path(image_path, picture_type))
+}
+
+/// Same as [write_picture], but the picture is read from the image file at given `image_path`,
+/// see [picture_from_path].
 ///
-/// Throws an **exception** when:
-/// - path doesn't exists
-pub fn write_primary(path: String, tag: Tag, keep_others: bool) -> anyhow::Result<TaggyFile> {
-    let mut tagged_file = get_bound_tagged_file(&path)?;
+/// See [taggy_core::api::write_picture_from_path].
+pub fn write_picture_from_path(
+    path: String,
+    image_path: String,
+    picture_type: PictureType,
+) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::write_picture_from_path(
+        path,
+        image_path,
+        picture_type,
+    ))
+}
 
-    if !keep_others {
-        tagged_file.clear();
-    }
+/// Removes the pictures of the given `picture_type` from all the tags of the file
+/// at given `path`, or all of its pictures when `None`, then saves the file.
+///
+/// See [taggy_core::api::remove_pictures].
+pub fn remove_pictures(
+    path: String,
+    picture_type: Option<PictureType>,
+) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::remove_pictures(path, picture_type))
+}
+
+/// Applies the `changes` to the type, description or mimetype of the picture at `index`
+/// in the pictures of the file at given `path`, as returned by [read_pictures], without
+/// sending its data again, e.g. to mark as the front cover a picture written as `Other`.
+///
+/// See [taggy_core::api::update_picture_meta].
+pub fn update_picture_meta(
+    path: String,
+    index: u32,
+    changes: PictureChanges,
+) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::update_picture_meta(path, index, changes))
+}
+
+/// Write the `value` of the field with the given `key` to the primary tag of the file
+/// at given `path`, or remove the field when `None`, e.g. for updating only the rating.
+///
+/// See [taggy_core::api::write_field].
+pub fn write_field(path: String, key: ItemKey, value: Option<String>) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::write_field(path, key, value))
+}
+
+/// Read the loudness normalization gains and peaks of the file at given `path`.
+///
+/// See [taggy_core::api::read_replay_gain].
+pub fn read_replay_gain(path: String) -> anyhow::Result<ReplayGain> {
+    coded(taggy_core::api::read_replay_gain(path))
+}
+
+/// Write the loudness normalization `gain` to the primary tag of the file at given `path`,
+/// see [read_replay_gain]. The gains and peaks which are `None` are removed.
+///
+/// See [taggy_core::api::write_replay_gain].
+pub fn write_replay_gain(path: String, gain: ReplayGain) -> anyhow::Result<()> {
+    coded(taggy_core::api::write_replay_gain(path, gain))
+}
 
-    let lofty_tag_type = tagged_file.file_type().primary_tag_type();
+/// Applies all the write `requests`, or none of them.
+///
+/// See [taggy_core::api::write_transaction].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_transaction(requests: Vec<WriteRequest>) -> anyhow::Result<Vec<TaggyFile>> {
+    coded(taggy_core::api::write_transaction(requests))
+}
+
+/// Same as [write_transaction], but the copies of the files are created in the folder
+/// at given `temp_dir` path instead of next to the files, e.g. the app cache folder for
+/// the Android scoped storage locations which don't allow creating other files.
+///
+/// See [taggy_core::api::write_transaction_with_temp_dir].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write_transaction_with_temp_dir(
+    requests: Vec<WriteRequest>,
+    temp_dir: String,
+) -> anyhow::Result<Vec<TaggyFile>> {
+    coded(taggy_core::api::write_transaction_with_temp_dir(
+        requests, temp_dir,
+    ))
+}
+
+/// Recovers the [write_transaction]s interrupted in the folder at given `dir` path,
+/// e.g. because the app was killed while committing them, and removes their leftover
+/// `.taggy-*` copies of the files.
+///
+/// See [taggy_core::api::recover].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn recover(dir: String) -> anyhow::Result<RecoveryReport> {
+    coded(taggy_core::api::recover(dir))
+}
+
+/// The [DateContext] of the given `locale`, e.g. `en-US` or `fr_FR.UTF-8`, with the user's
+/// `utc_offset_minutes`, to pass in the [ParseConfig] or [WriteOptions] `date_context`.
+///
+/// See [taggy_core::api::date_context_for_locale].
+pub fn date_context_for_locale(locale: String, utc_offset_minutes: i32) -> DateContext {
+    taggy_core::api::date_context_for_locale(locale, utc_offset_minutes)
+}
+
+/// Validates the given `isrc` and returns its normalized 12 characters form,
+/// e.g. `us-rc1-76-07839` becomes `USRC17607839`.
+///
+/// See [taggy_core::api::normalize_isrc].
+pub fn normalize_isrc(isrc: String) -> anyhow::Result<String> {
+    coded(taggy_core::api::normalize_isrc(isrc))
+}
+
+/// Generates a new random UUID, suitable for [write_file_id].
+///
+/// See [taggy_core::api::generate_file_id].
+pub fn generate_file_id() -> String {
+    taggy_core::api::generate_file_id()
+}
+
+/// Read the unique identifier stored by the application `scope` in the file at given `path`,
+/// `null` if the file has no identifier for this `scope`.
+///
+/// See [taggy_core::api::read_file_id].
+pub fn read_file_id(path: String, scope: String) -> anyhow::Result<Option<String>> {
+    coded(taggy_core::api::read_file_id(path, scope))
+}
 
-    // override the tag's type with the file's primary tag type
-    let updated_tag = Tag {
-        tag_type: TagType::from(lofty_tag_type),
-        ..tag
-    };
+/// Write the unique identifier `id` of the application `scope` to the file at given `path`,
+/// replacing any previous identifier of this `scope`.
+///
+/// See [taggy_core::api::write_file_id].
+pub fn write_file_id(path: String, scope: String, id: String) -> anyhow::Result<()> {
+    coded(taggy_core::api::write_file_id(path, scope, id))
+}
 
-    // add tags to file
-    tagged_file.insert_tag(updated_tag.to_lofty());
-    tagged_file.save()?;
+/// Returns the identifier of the application `scope` of the file at given `path`,
+/// after writing a newly generated one if the file doesn't have any.
+///
+/// See [taggy_core::api::ensure_file_id].
+pub fn ensure_file_id(path: String, scope: String) -> anyhow::Result<String> {
+    coded(taggy_core::api::ensure_file_id(path, scope))
+}
+
+/// Read the synchronized lyrics of the file at given `path`, i.e. the lyrics with the time
+/// each line is sung at, `None` if the file has none.
+///
+/// See [taggy_core::api::read_synced_lyrics].
+pub fn read_synced_lyrics(path: String) -> anyhow::Result<Option<SyncedLyrics>> {
+    coded(taggy_core::api::read_synced_lyrics(path))
+}
+
+/// Write the synchronized `lyrics` to the file at given `path` as an ID3v2 `SYLT` frame,
+/// replacing the existing ones. Lyrics without lines remove them.
+///
+/// See [taggy_core::api::write_synced_lyrics].
+pub fn write_synced_lyrics(path: String, lyrics: SyncedLyrics) -> anyhow::Result<()> {
+    coded(taggy_core::api::write_synced_lyrics(path, lyrics))
+}
+
+/// Parse the `lrc` text, made of `[mm:ss.xx]` prefixed lines, to [SyncedLyrics],
+/// e.g. to import a `.lrc` file before writing it with [write_synced_lyrics].
+///
+/// See [taggy_core::api::parse_lrc].
+pub fn parse_lrc(lrc: String) -> anyhow::Result<SyncedLyrics> {
+    coded(taggy_core::api::parse_lrc(lrc))
+}
+
+/// Format the `lyrics` as LRC text, e.g. to export them to a `.lrc` file.
+///
+/// See [taggy_core::api::to_lrc].
+pub fn to_lrc(lyrics: SyncedLyrics) -> String {
+    taggy_core::api::to_lrc(lyrics)
+}
+
+/// Read the chapters of the file at given `path`, sorted by start time, e.g. of an audiobook
+/// or a podcast episode, none if the file has none.
+///
+/// See [taggy_core::api::read_chapters].
+pub fn read_chapters(path: String) -> anyhow::Result<Vec<Chapter>> {
+    coded(taggy_core::api::read_chapters(path))
+}
+
+/// Write the `chapters` to the file at given `path`, replacing the existing ones.
+/// No chapters remove them.
+///
+/// See [taggy_core::api::write_chapters].
+pub fn write_chapters(path: String, chapters: Vec<Chapter>) -> anyhow::Result<()> {
+    coded(taggy_core::api::write_chapters(path, chapters))
+}
+
+/// Read the frames of the ID3v2 tag of the file at given `path` as they're stored, e.g. the
+/// `GEOB`, `PRIV` or proprietary frames [Tag] doesn't model, none if the file has no ID3v2 tag.
+///
+/// See [taggy_core::api::read_raw_frames].
+pub fn read_raw_frames(path: String) -> anyhow::Result<Vec<RawFrame>> {
+    coded(taggy_core::api::read_raw_frames(path))
+}
+
+/// Write the `frame` to the ID3v2 tag of the file at given `path`, e.g. to store a binary blob
+/// in a `PRIV` frame, see [read_raw_frames].
+///
+/// See [taggy_core::api::write_raw_frame].
+pub fn write_raw_frame(path: String, frame: RawFrame) -> anyhow::Result<()> {
+    coded(taggy_core::api::write_raw_frame(path, frame))
+}
+
+/// Reads the bytes of the tag of given `tag_type` of the file at given `path` exactly as
+/// they're stored, e.g. to copy a tag to another file with [inject_raw_tag].
+///
+/// See [taggy_core::api::dump_raw_tag].
+pub fn dump_raw_tag(path: String, tag_type: TagType) -> anyhow::Result<Vec<u8>> {
+    coded(taggy_core::api::dump_raw_tag(path, tag_type))
+}
+
+/// Writes the given `bytes` as is to the file at given `path`, in place of its tag of the same
+/// type, see [dump_raw_tag].
+///
+/// See [taggy_core::api::inject_raw_tag].
+pub fn inject_raw_tag(path: String, bytes: Vec<u8>) -> anyhow::Result<()> {
+    coded(taggy_core::api::inject_raw_tag(path, bytes))
+}
+
+/// Reads the hot cues, saved loops and track color Serato DJ stored in the ID3v2 tag of the file
+/// at given `path`.
+///
+/// See [taggy_core::api::read_dj_markers].
+pub fn read_dj_markers(path: String) -> anyhow::Result<Option<DjMarkers>> {
+    coded(taggy_core::api::read_dj_markers(path))
+}
+
+/// Finds the audio files of the album in the directory at given `dir` path, grouped by disc.
+///
+/// See [taggy_core::api::detect_disc_set].
+pub fn detect_disc_set(dir: String) -> anyhow::Result<DiscSet> {
+    coded(taggy_core::api::detect_disc_set(dir))
+}
+
+/// Writes consistent `disc_number`s and `disc_total`s to all the files of the album
+/// in the directory at given `dir` path, see [detect_disc_set].
+///
+/// See [taggy_core::api::fix_disc_numbers].
+pub fn fix_disc_numbers(dir: String, fold_into_track_number: bool) -> anyhow::Result<DiscSet> {
+    coded(taggy_core::api::fix_disc_numbers(
+        dir,
+        fold_into_track_number,
+    ))
+}
+
+/// Embeds the same front cover into all the files of the album in the directory at given
+/// `dir` path, see [detect_disc_set].
+///
+/// See [taggy_core::api::unify_album_art].
+pub fn unify_album_art(dir: String, source: Option<Picture>) -> anyhow::Result<Vec<String>> {
+    coded(taggy_core::api::unify_album_art(dir, source))
+}
+
+/// Looks for the release year of the audio files missing one in the directory at given
+/// `dir` path and its subfolders, from their folder names like `Artist - Album (1997)`,
+/// then from their file names.
+///
+/// See [taggy_core::api::suggest_years].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn suggest_years(dir: String) -> anyhow::Result<Vec<YearSuggestion>> {
+    coded(taggy_core::api::suggest_years(dir))
+}
+
+/// Writes the year of each of the given `suggestions` to the primary tag of its file,
+/// see [suggest_years].
+///
+/// See [taggy_core::api::apply_years].
+pub fn apply_years(suggestions: Vec<YearSuggestion>) -> anyhow::Result<Vec<TaggyFile>> {
+    coded(taggy_core::api::apply_years(suggestions))
+}
+
+/// Looks for the audio files in the directory at given `dir` path and its subfolders whose
+/// featured artists aren't credited according to the `style`, e.g. `Song` by `A feat. X`
+/// for [FeaturingStyle::InTitle], since libraries mixing both break the search by artist.
+///
+/// See [taggy_core::api::suggest_featuring].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn suggest_featuring(
+    dir: String,
+    style: FeaturingStyle,
+) -> anyhow::Result<Vec<FeaturingChange>> {
+    coded(taggy_core::api::suggest_featuring(dir, style))
+}
+
+/// Writes the title and artist of each of the given `changes` to the primary tag of its file,
+/// see [suggest_featuring].
+///
+/// See [taggy_core::api::apply_featuring].
+pub fn apply_featuring(changes: Vec<FeaturingChange>) -> anyhow::Result<Vec<TaggyFile>> {
+    coded(taggy_core::api::apply_featuring(changes))
+}
+
+/// Marks the files at given `paths` as the consecutive movements of the continuous `work`,
+/// e.g. a symphony or a live set, so players can group them and play them without gaps.
+///
+/// See [taggy_core::api::link_continuous_work].
+pub fn link_continuous_work(
+    paths: Vec<String>,
+    work: String,
+    show_movement: bool,
+) -> anyhow::Result<()> {
+    coded(taggy_core::api::link_continuous_work(
+        paths,
+        work,
+        show_movement,
+    ))
+}
+
+/// Read the place of the file at given `path` in a continuous work,
+/// see [link_continuous_work].
+///
+/// See [taggy_core::api::read_work_link].
+pub fn read_work_link(path: String) -> anyhow::Result<Option<WorkLink>> {
+    coded(taggy_core::api::read_work_link(path))
+}
+
+/// Checks the text fields of the given `tag` against the rules of the `profile`,
+/// e.g. before writing it.
+///
+/// See [taggy_core::api::lint_tag].
+pub fn lint_tag(tag: Tag, profile: LintProfile) -> Vec<String> {
+    taggy_core::api::lint_tag(tag, profile)
+}
+
+/// Take a snapshot of the complete metadata of the file at given `path`, as JSON.
+///
+/// See [taggy_core::api::snapshot].
+pub fn snapshot(path: String) -> anyhow::Result<String> {
+    coded(taggy_core::api::snapshot(path))
+}
 
-    Ok(taggy_from_bound_tagged(&tagged_file, &path))
+/// Replace all the tags of the file at given `path` with the ones of the given `snapshot`,
+/// taken with [snapshot].
+///
+/// See [taggy_core::api::restore].
+pub fn restore(path: String, snapshot: String) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::restore(path, snapshot))
 }
 
 /// Delete all tags from file at given `path`.
 ///
-/// Throws an **exception** when:
-/// - path doesn't exists
+/// See [taggy_core::api::remove_all].
 pub fn remove_all(path: String) -> anyhow::Result<()> {
-    let mut tagged = get_bound_tagged_file(&path)?;
-    // save the file tags types
-    let tags_types = tagged
-        .tags()
-        .iter()
-        .map(|e| e.tag_type())
-        .collect::<Vec<lofty::TagType>>();
-
-    let _ = &tagged.clear();
-
-    // foreach type we insert an empty tag to override its data
-    for tag_type in tags_types {
-        insert_empty_tag(&mut tagged, TagType::from(tag_type));
-    }
-    tagged
-        .save()
-        .map_err(|_| anyhow!("Failed to remove file tags"))
+    coded(taggy_core::api::remove_all(path))
 }
 
 /// Deletes the `tag` with `TagType` equals to `tag_type` from file at the given `path`.
 ///
-/// If the file doesn't have any tag with the given `tag_type`,
-/// **no** errors will be returned.
-///
-/// Throws an **exception** when:
-/// - path doesn't exists
+/// See [taggy_core::api::remove_tag].
 pub fn remove_tag(path: String, tag_type: TagType) -> anyhow::Result<()> {
-    let mut tagged = get_bound_tagged_file(&path)?;
-    if tagged.remove(tag_type.into()).is_none() {
-        // There's no need for saving the file cuz it wasn't changed
-        // since the tag doesn't exist
-        return Ok(());
-    }
-    insert_empty_tag(&mut tagged, tag_type);
-    tagged.save().map_err(|e| anyhow!(e))
+    coded(taggy_core::api::remove_tag(path, tag_type))
 }
 
-/// Adds a tag with empty data to the file.
+/// Converts the tag of the given `from` tag type of the file at given `path` to a tag of the
+/// given `to` tag type, e.g. to move a legacy library from APEv2 to ID3v2 tags.
 ///
-/// This is required to remove the metadata of existing tag with given `tag_type`.
-/// The Reason is still unknown to me but it was found during testing.
-fn insert_empty_tag(file: &mut BoundTaggedFile, tag_type: TagType) {
-    let empty_tag = Tag::new(tag_type);
-    file.insert_tag(empty_tag.to_lofty());
+/// See [taggy_core::api::convert_tag].
+pub fn convert_tag(
+    path: String,
+    from: TagType,
+    to: TagType,
+    strip_source: bool,
+) -> anyhow::Result<TaggyFile> {
+    coded(taggy_core::api::convert_tag(path, from, to, strip_source))
 }
 
-/// A helper function to get a [`BoundTaggedFile`] from the given path
-/// which can be used to read an write tags to the file on disk directly.
-fn get_bound_tagged_file(path: &String) -> anyhow::Result<BoundTaggedFile> {
-    // We'll need to open our file for reading *and* writing
-    let file = OpenOptions::new()
-        .read(true)
-        .write(true)
-        .open(path.clone())?;
+/// Removes the fields of the given `classes` from all the tags of the files at given `paths`,
+/// e.g. the comments, lyrics and private data before sharing a library, and returns the files
+/// as they are afterwards.
+///
+/// See [taggy_core::api::strip_fields].
+pub fn strip_fields(
+    paths: Vec<String>,
+    classes: Vec<FieldClass>,
+) -> anyhow::Result<Vec<TaggyFile>> {
+    coded(taggy_core::api::strip_fields(paths, classes))
+}
 
-    match BoundTaggedFile::read_from(file, ParseOptions::new()) {
-        Ok(file) => Ok(file),
-        Err(e) => Err(anyhow!(e)),
-    }
+/// Computes the waveform of the audio track of the file at given `path`.
+///
+/// See [taggy_core::api::compute_waveform].
+#[cfg(feature = "decode")]
+pub fn compute_waveform(path: String, buckets: u32) -> anyhow::Result<Vec<f32>> {
+    coded(taggy_core::api::compute_waveform(path, buckets))
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::picture::{MimeType, Picture, PictureType};
-    use rand::Rng;
-    use std::fs::{copy, remove_file};
-    use std::path::Path;
-    use std::{env, panic};
+/// Computes the `k` dominant colors of the cover of the file at given `path`, most common
+/// first, e.g. for theming a player UI after it.
+///
+/// See [taggy_core::api::artwork_palette].
+#[cfg(feature = "artwork")]
+pub fn artwork_palette(path: String, k: u32) -> anyhow::Result<Vec<PaletteColor>> {
+    coded(taggy_core::api::artwork_palette(path, k))
+}
 
-    #[test]
-    fn reading_non_existing_file_is_an_error() {
-        let result = read_all(get_fake_path());
-        assert!(result.is_err());
-    }
+/// Encodes the cover of the file at given `path` as a [BlurHash](https://blurha.sh) string,
+/// e.g. for showing a placeholder in lists before the cover itself is loaded.
+///
+/// See [taggy_core::api::artwork_blurhash].
+#[cfg(feature = "artwork")]
+pub fn artwork_blurhash(path: String) -> anyhow::Result<Option<String>> {
+    coded(taggy_core::api::artwork_blurhash(path))
+}
 
-    #[test]
-    fn reading_existing_file_is_ok() {
-        let result = read_all(get_audio_sample_file_path());
-        assert!(result.is_ok());
-    }
+/// Re-encodes the embedded pictures of each file of the given `paths` as JPEGs of the
+/// `options` quality, downscaled to their `max_size`, e.g. to reclaim the space of needlessly
+/// large covers in a library.
+///
+/// See [taggy_core::api::optimize_artwork].
+#[cfg(all(feature = "artwork", not(target_arch = "wasm32")))]
+pub fn optimize_artwork(
+    paths: Vec<String>,
+    options: ArtworkOptions,
+) -> anyhow::Result<Vec<ArtworkSavings>> {
+    coded(taggy_core::api::optimize_artwork(paths, options))
+}
 
-    #[test]
-    fn reading_file_with_no_tags_should_return_with_empty_tags() {
-        let path = get_no_tags_sample_file_path();
-        let taggy = read_all(path).unwrap();
-        assert!(taggy.tags.is_empty());
-    }
+/// Read all audio tags of each audio file inside the `.zip` archive at given `zip_path`,
+/// e.g. to preview a purchased album before unpacking it.
+///
+/// See [taggy_core::api::read_zip_entries].
+#[cfg(feature = "zip")]
+pub fn read_zip_entries(zip_path: String) -> anyhow::Result<Vec<BatchReadResult>> {
+    coded(taggy_core::api::read_zip_entries(zip_path))
+}
 
-    #[test]
-    fn writing_tags_to_non_existing_file_is_an_error() {
-        let result = write_all(get_fake_path(), vec![], true);
-        assert!(result.is_err());
-    }
+/// Write all provided `tags` to the `entry_name` audio file inside the `.zip` archive at given
+/// `zip_path`, see [read_zip_entries].
+///
+/// See [taggy_core::api::write_zip_entry].
+#[cfg(feature = "zip")]
+pub fn write_zip_entry(
+    zip_path: String,
+    entry_name: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> anyhow::Result<()> {
+    coded(taggy_core::api::write_zip_entry(
+        zip_path,
+        entry_name,
+        tags,
+        override_existent,
+    ))
+}
 
-    #[test]
-    fn it_updates_file_primary_tag() {
-        with_duplicate_file(get_audio_sample_file_path(), |path| {
-            let old_tag = read_primary(path.clone()).unwrap().first_tag().unwrap();
-            let new_tag = Tag::builder().with_tag_type(old_tag.tag_type).create();
-            let result = write_primary(path.clone(), new_tag.clone(), false);
-            let tag_after_write = read_primary(path.clone()).unwrap().first_tag().unwrap();
-            assert!(result.is_ok());
-            assert_eq!(tag_after_write, new_tag);
-        });
-    }
+/*
+ * Client
+ */
 
-    #[test]
-    fn it_updates_a_file_with_no_tags_after_writing() {
-        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
-            // `TagType::Id3v2` is the primary tag type for the file so the equality assertion passes for
-            // this property.
-            let tag = Tag::builder().with_tag_type(TagType::Id3v2).create();
-            // act
-            let result = write_primary(path.clone(), tag.clone(), true);
-            // assert
-            let created_tag = result.unwrap().tags.first().unwrap().clone();
-            assert_eq!(created_tag, tag);
-        });
-    }
+/// Creates a [TaggyClient] which caches the files it parses,
+/// up to `options.cache_capacity` files and `options.cache_max_bytes` bytes.
+///
+/// Prefer a client over the free functions when the same file is read several times,
+/// e.g. reading the primary tag and then all tags of the selected file.
+///
+/// The client operations fail when exceeding the `options` limits (`max_file_size`, `timeout_ms`),
+/// which keeps a corrupt file on a slow network share from hanging a scan.
+/// They wait instead when exceeding the IO rate limits (`max_files_per_sec`, `max_bytes_per_sec`).
+///
+/// The client is safe to use from several isolates at once, e.g. the UI one and a background
+/// fetch one: the reads and writes of the same file are serialized. Prefer sharing a client
+/// over creating one per isolate, which would parse and cache the same files again.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn create_client(options: ClientOptions) -> RustOpaque<TaggyClient> {
+    RustOpaque::new(TaggyClient::new(options))
+}
+
+/// Same as [read_all], but served from the `client` cache when the file didn't change.
+///
+/// See [TaggyClient::read_all].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_read_all(client: RustOpaque<TaggyClient>, path: String) -> anyhow::Result<TaggyFile> {
+    coded(client.read_all(path))
+}
+
+/// Same as [read_primary], but served from the `client` cache when the file didn't change.
+///
+/// See [TaggyClient::read_primary].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_read_primary(
+    client: RustOpaque<TaggyClient>,
+    path: String,
+) -> anyhow::Result<TaggyFile> {
+    coded(client.read_primary(path))
+}
+
+/// Same as [read_any], but served from the `client` cache when the file didn't change.
+///
+/// See [TaggyClient::read_any].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_read_any(client: RustOpaque<TaggyClient>, path: String) -> anyhow::Result<TaggyFile> {
+    coded(client.read_any(path))
+}
+
+/// Same as [write_all], and drops the file from the `client` cache.
+///
+/// See [TaggyClient::write_all].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_write_all(
+    client: RustOpaque<TaggyClient>,
+    path: String,
+    tags: Vec<Tag>,
+    override_existent: bool,
+) -> anyhow::Result<TaggyFile> {
+    coded(client.write_all(path, tags, override_existent))
+}
 
-    #[test]
-    fn it_adds_image_to_tag() {
-        with_duplicate_file(get_no_tags_sample_file_path(), |path| {
-            let pic = get_pic_from_asset();
-            let tag = Tag::builder().with_pictures(vec![pic.clone()]).create();
-            // act
-            let taggy = write_primary(path.clone(), tag.clone(), false)
-                .expect("Failed to write primary tag");
-            let tag = taggy.primary_tag().unwrap();
-            let added_picture = tag.pictures.first().unwrap();
-            // assert
-            assert_eq!(added_picture.pic_data, pic.pic_data);
+/// Same as [write_primary], and drops the file from the `client` cache.
+///
+/// See [TaggyClient::write_primary].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_write_primary(
+    client: RustOpaque<TaggyClient>,
+    path: String,
+    tag: Tag,
+    keep_others: bool,
+) -> anyhow::Result<TaggyFile> {
+    coded(client.write_primary(path, tag, keep_others))
+}
+
+/// Same as [remove_all], and drops the file from the `client` cache.
+///
+/// See [TaggyClient::remove_all].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_remove_all(client: RustOpaque<TaggyClient>, path: String) -> anyhow::Result<()> {
+    coded(client.remove_all(path))
+}
+
+/// Same as [remove_tag], and drops the file from the `client` cache.
+///
+/// See [TaggyClient::remove_tag].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_remove_tag(
+    client: RustOpaque<TaggyClient>,
+    path: String,
+    tag_type: TagType,
+) -> anyhow::Result<()> {
+    coded(client.remove_tag(path, tag_type))
+}
+
+/// Drops all the files cached by the `client`.
+///
+/// See [TaggyClient::clear_cache].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_clear_cache(client: RustOpaque<TaggyClient>) {
+    client.clear_cache()
+}
+
+/// The number and size of the files cached by the `client`, and how often the cache
+/// was used since the client was created.
+///
+/// See [TaggyClient::cache_stats].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_cache_stats(client: RustOpaque<TaggyClient>) -> CacheStats {
+    client.cache_stats()
+}
+
+/// Streams the [HookEvent]s of the operations made with the `client`, e.g. to collect
+/// timing metrics for the host application.
+///
+/// Subscribing again replaces the previous stream.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn client_hook_events(
+    client: RustOpaque<TaggyClient>,
+    sink: StreamSink<HookEvent>,
+) -> anyhow::Result<()> {
+    client.set_hooks(Some(Arc::new(SinkHooks(sink))));
+    Ok(())
+}
+
+/// An event emitted by a `TaggyClient` operation.
+#[cfg(not(target_arch = "wasm32"))]
+pub enum HookEvent {
+    /// A file is about to be read.
+    ReadStart { path: String },
+    /// A file was read successfully.
+    ReadEnd { stats: OperationStats },
+    /// A file was written successfully.
+    Write { stats: OperationStats },
+    /// Reading or writing a file failed.
+    Error { path: String, message: String },
+}
+
+/// Forwards the client hooks to a Dart stream.
+#[cfg(not(target_arch = "wasm32"))]
+struct SinkHooks(StreamSink<HookEvent>);
+
+#[cfg(not(target_arch = "wasm32"))]
+impl TaggyHooks for SinkHooks {
+    fn on_read_start(&self, path: &str) {
+        self.0.add(HookEvent::ReadStart {
+            path: path.to_string(),
         });
     }
 
-    #[test]
-    fn it_removes_a_specific_tag_from_file() {
-        with_duplicate_file(get_audio_sample_file_path(), |path| {
-            let taggy = read_primary(path.clone()).expect("Failed to read primary tag");
-            let tag = taggy.primary_tag();
-            // first assert a tag exists
-            assert!(tag.as_ref().is_some());
-            // act
-            let remove_result = remove_tag(path.clone(), tag.unwrap().tag_type);
-            // if remove_result.
-            // assert
-            assert!(remove_result.is_ok());
-            let taggy_after = read_primary(path.clone()).expect("Failed to read primary tag");
-            assert!(taggy_after.primary_tag().is_none());
+    fn on_read_end(&self, stats: &OperationStats) {
+        self.0.add(HookEvent::ReadEnd {
+            stats: stats.clone(),
         });
     }
-    #[test]
-    fn it_removes_all_tags_from_file() {
-        with_duplicate_file(get_audio_sample_file_path(), |path| {
-            let taggy = read_primary(path.clone()).expect("Failed to read primary tag");
-            let tag = taggy.primary_tag();
-            // first assert a tag exists
-            assert!(tag.as_ref().is_some());
-            // act
-            let remove_result = remove_all(path.clone());
-            // if remove_result.
-            // assert
-            assert!(remove_result.is_ok());
-            let taggy_after = read_all(path.clone()).expect("Failed to read primary tag");
-            assert!(taggy_after.tags.is_empty());
+
+    fn on_write(&self, stats: &OperationStats) {
+        self.0.add(HookEvent::Write {
+            stats: stats.clone(),
         });
     }
-    #[test]
-    fn it_removes_only_specified_tag_from_file() {
-        with_duplicate_file(get_audio_sample_file_path(), |path| {
-            // setup
-            let new_tag = Tag::builder().with_tag_type(TagType::Ape).create();
-            let taggy = write_all(path.clone(), vec![new_tag.clone()], false)
-                .expect("Failed to write a new tag");
-            assert_eq!(taggy.tags.len(), 2);
-            // act
-            let remove_result = remove_tag(path.clone(), new_tag.tag_type);
-            // assert
-            assert!(remove_result.is_ok());
-            let taggy_after_remove = read_all(path).expect("Failed to read tags");
-            assert_eq!(taggy_after_remove.tags.len(), 1);
+
+    fn on_error(&self, path: &str, error: &anyhow::Error) {
+        self.0.add(HookEvent::Error {
+            path: path.to_string(),
+            message: error.to_string(),
         });
     }
+}
 
-    /*
-     * Helper Functions
-     */
-    fn get_pic_from_asset() -> Picture {
-        let bytes = std::fs::read(get_image_path()).expect("Failed to read image bytes");
-        Picture {
-            pic_type: PictureType::CoverFront,
-            pic_data: bytes,
-            mime_type: Some(MimeType::Jpeg),
-            width: None,
-            height: None,
-            color_depth: None,
-            num_colors: None,
-        }
-    }
+/*
+ * Mirrors of taggy-core types
+ */
+
+/// Timing and size of a finished `TaggyClient` operation.
+#[cfg(not(target_arch = "wasm32"))]
+#[frb(mirror(OperationStats))]
+pub struct _OperationStats {
+    /// The path of the file the operation was applied to.
+    pub path: String,
+    /// The wall-clock time the operation took, in microseconds.
+    pub elapsed_micros: u64,
+    /// The size of the file after the operation, if it could be read.
+    pub bytes: Option<u64>,
+    /// Whether the result was served from the client cache without reading the file.
+    pub from_cache: bool,
+}
 
-    /// Creates a copy of the file at given `path` and place it in the same directory.
-    /// Then calls [op] with the duplicated file path.
+/// The state of the cache of a `TaggyClient`, see [client_cache_stats].
+#[cfg(not(target_arch = "wasm32"))]
+#[frb(mirror(CacheStats))]
+pub struct _CacheStats {
+    /// The number of cached files.
+    pub entries: u32,
+    /// The estimated memory taken by the cached files, in bytes.
+    pub bytes: u64,
+    /// The number of reads served from the cache.
+    pub hits: u64,
+    /// The number of reads which had to parse the file, because it wasn't cached or changed.
+    pub misses: u64,
+    /// The number of files dropped to stay within the cache limits.
+    pub evictions: u64,
+}
+
+/// The options a `TaggyClient` is created with.
+#[cfg(not(target_arch = "wasm32"))]
+#[frb(mirror(ClientOptions))]
+pub struct _ClientOptions {
+    /// The maximum number of parsed files to keep in memory, `0` disables caching.
+    pub cache_capacity: u32,
+    /// The maximum estimated memory taken by the cached files, mostly by their pictures,
+    /// in bytes. The least recently used files are dropped beyond it, `None` for no limit.
+    pub cache_max_bytes: Option<u64>,
+    /// Files larger than this many bytes are rejected, `None` for no limit.
+    pub max_file_size: Option<u64>,
+    /// Reads taking longer than this many milliseconds fail, `None` for no limit.
+    pub timeout_ms: Option<u64>,
+    /// The maximum number of files read from or written to disk per second, `None` for no limit.
     ///
-    /// The duplicated file will be **automatically removed** when [op] is finished.
-    fn with_duplicate_file(path: String, op: fn(d_path: String)) {
-        let rand = rand::thread_rng().gen_range(0..=100);
-        let file_name = format!("test_copy_{:?}.mp3", rand);
-        let path = Path::new(&path);
-        let copy_path = path.with_file_name(file_name);
-        copy(path, copy_path.as_path()).expect("Failed to copy the test sample file");
-        let duplicate_path = copy_path.to_str().unwrap().to_string();
-
-        // Execute the operation within a Result context
-        let result = panic::catch_unwind(|| op(duplicate_path.clone()));
-
-        // Remove the duplicate file regardless of the operation result
-        remove_duplicate(&duplicate_path);
-
-        // Check if the operation panicked and Re-throw the error
-        if let Err(panic) = result {
-            panic::resume_unwind(panic);
-        }
-    }
-    fn remove_duplicate(path: &str) {
-        remove_file(path).expect("Failed to remove test asset");
-    }
-    fn get_image_path() -> String {
-        let current_dir = env::current_dir().expect("Failed to get current directory");
-        current_dir
-            .parent()
-            .unwrap()
-            .join("test_samples\\image.jpg")
-            .to_str()
-            .unwrap()
-            .to_string()
-    }
+    /// Reads served from the cache aren't throttled.
+    pub max_files_per_sec: Option<u32>,
+    /// The maximum number of file bytes read from or written to disk per second, `None` for no limit.
+    ///
+    /// A file is accounted with its whole size, even though only its tags are usually read.
+    pub max_bytes_per_sec: Option<u64>,
+    /// Refuse all the writes with a `permission_denied` error, before opening the files.
+    ///
+    /// The reads never request write access either way, this guarantees the files
+    /// are never opened for writing, e.g. on media where that triggers sync clients.
+    pub read_only: bool,
+}
 
-    fn get_audio_sample_file_path() -> String {
-        let current_dir = env::current_dir().expect("Failed to get current directory");
-        current_dir
-            .parent()
-            .unwrap()
-            .join("test_samples\\sample.mp3")
-            .to_str()
-            .unwrap()
-            .to_string()
-    }
+/// How the files are parsed, see [read_all_with_config].
+#[frb(mirror(ParseConfig))]
+pub struct _ParseConfig {
+    /// The maximum number of bytes to skip while looking for the tags or the audio stream.
+    pub max_junk_bytes: u32,
+    /// Read the audio properties (duration, bitrate...), which takes extra time.
+    pub read_properties: bool,
+    /// How to handle the invalid parts of the file.
+    pub parsing_mode: ParsingMode,
+    /// Return the pictures data along with their metadata (type, mime type, size...).
+    ///
+    /// When disabled, the [Picture]s `pic_data` is empty, and writing such pictures back
+    /// keeps the data already in the file.
+    pub read_picture_data: bool,
+    /// Normalize the dates of the read tags according to the user's locale and time zone,
+    /// see [DateContext]. `None` to return the dates as they are written.
+    pub date_context: Option<DateContext>,
+    /// Which value is read for the fields a tag holds several times, e.g. two ID3v2 `TIT2`
+    /// frames. Defaults to the first one.
+    pub duplicate_policy: DuplicatePolicy,
+}
 
-    fn get_no_tags_sample_file_path() -> String {
-        let current_dir = env::current_dir().expect("Failed to get current directory");
-        current_dir
-            .parent()
-            .unwrap()
-            .join("test_samples\\no_tags.mp3")
-            .to_str()
-            .unwrap()
-            .to_string()
-    }
+#[frb(mirror(ParsingMode))]
+pub enum _ParsingMode {
+    /// Fail on any invalid part.
+    Strict,
+    /// Skip the invalid parts when possible, and only fail when the file can't be read at all.
+    BestAttempt,
+}
 
-    fn get_fake_path() -> String {
-        String::from("fake/path/file.mp3")
-    }
+/// Which value is read for a field a tag holds several times, see [ParseConfig].
+///
+/// Whatever the policy, each duplicated field is reported in the warnings of the read file.
+#[frb(mirror(DuplicatePolicy))]
+pub enum _DuplicatePolicy {
+    /// Read the first value.
+    First,
+    /// Read the last value.
+    Last,
+    /// Read all the values, joined with `; `.
+    Concatenate,
+    /// Read the first value, and list all the values in the warnings.
+    ReportAll,
+}
+
+/// What a scan walks and reads, see [scan_directory_with_options].
+#[cfg(not(target_arch = "wasm32"))]
+#[frb(mirror(ScanOptions))]
+pub struct _ScanOptions {
+    /// Walk the subfolders too.
+    pub recursive: bool,
+    /// Only read the files with one of these extensions (e.g. `mp3`),
+    /// or the files of any supported format when empty.
+    pub extensions: Vec<String>,
+    /// The number of files read at the same time, `0` for one per CPU core.
+    pub concurrency: u32,
+    /// How the symbolic links are handled.
+    pub symlinks: SymlinkPolicy,
+    /// Skip the hidden files and folders.
+    pub skip_hidden: bool,
+    /// Skip the AppleDouble files, the `__MACOSX` folders and the recycle bins.
+    pub skip_system_files: bool,
+}
+
+/// How a scan handles the symbolic links, see [scan_directory_with_symlinks].
+#[cfg(not(target_arch = "wasm32"))]
+#[frb(mirror(SymlinkPolicy))]
+pub enum _SymlinkPolicy {
+    /// The links are ignored.
+    Skip,
+    /// The links are followed, and each file is read once, under the first path it's found at.
+    Follow,
+    /// The links are followed, and each path a file is found at is read as a distinct entry.
+    Distinct,
+}
+
+#[frb(mirror(WriteOptions))]
+pub struct _WriteOptions {
+    /// Refuse to overwrite the fields which already have a value in the file,
+    /// so only the missing ones are filled.
+    pub protect_existing: bool,
+    /// When `protect_existing` is set, keep the existing values of the protected fields
+    /// and write the others, instead of failing the whole write.
+    pub skip_protected: bool,
+    /// Overwrite the fields even when `protect_existing` is set.
+    pub force: bool,
+    /// Remove all the pictures already in the file, in the same save as the new tags.
+    ///
+    /// The pictures of the written tags are still written.
+    pub clear_pictures: bool,
+    /// What to do with the tags whose type isn't supported by the file format,
+    /// e.g. a Vorbis comments tag written to an MP3 file.
+    pub unsupported_tag_policy: UnsupportedTagPolicy,
+    /// Write the tags to a sidecar file (e.g. `track.flac.taggy.json`) when the file is read-only,
+    /// instead of failing. Read them back with [read_all_with_sidecar].
+    pub sidecar_fallback: bool,
+    /// How to cut the values exceeding the length limit of the tag format,
+    /// each cut value is reported in the write warnings.
+    pub truncation: TruncationStrategy,
+    /// The rules the written values are checked against, see [LintProfile].
+    pub lint_profile: LintProfile,
+    /// Also write the fields of the primary tag to an ID3v1 tag, for the players which only read
+    /// ID3v1. Only applies to the formats supporting ID3v1, and when no ID3v1 tag is written along.
+    pub sync_id3v1: bool,
+    /// Drop the pictures which aren't JPEG, each dropped picture is reported in the write warnings.
+    pub jpeg_pictures_only: bool,
+    /// Drop the pictures whose width or height is larger than this many pixels,
+    /// each dropped picture is reported in the write warnings. `None` for no limit.
+    pub max_picture_size: Option<u32>,
+    /// Normalize the written dates according to the user's locale and time zone,
+    /// see [DateContext]. `None` to write the dates as they are.
+    pub date_context: Option<DateContext>,
+    /// The maximum sizes of the written tags by type, e.g. to keep the ID3v2 tags small enough
+    /// for the players refusing to load files with large ones. The write fails when a tag
+    /// exceeds it.
+    pub max_tag_sizes: Vec<TagSizeLimit>,
+}
+
+#[frb(mirror(TagSizeLimit))]
+pub struct _TagSizeLimit {
+    pub tag_type: TagType,
+    /// The maximum size of the encoded tag in bytes, headers and pictures included.
+    pub max_size: u64,
+}
+
+/// The user's locale and time zone settings, used to interpret the dates of the tags consistently.
+///
+/// With a context, the `recording_date` and `original_release_date` are normalized to ISO 8601:
+/// the ambiguous numeric dates like `03/04/2001` are read in the `date_order`, and the timestamps
+/// with a time zone are converted to the `utc_offset_minutes` local time.
+#[frb(mirror(DateContext))]
+pub struct _DateContext {
+    /// The order of the day and the month in the numeric dates.
+    pub date_order: DateOrder,
+    /// The user's offset from UTC in minutes, e.g. `120` for UTC+2.
+    pub utc_offset_minutes: i32,
+}
+
+/// The order of the day and the month in numeric dates, see [DateContext].
+#[frb(mirror(DateOrder))]
+pub enum _DateOrder {
+    /// e.g. `31/12/2001`, used by most locales.
+    DayMonth,
+    /// e.g. `12/31/2001`, used in the United States.
+    MonthDay,
+}
+
+/// What to do when writing a tag whose type isn't supported by the file format.
+#[frb(mirror(UnsupportedTagPolicy))]
+pub enum _UnsupportedTagPolicy {
+    /// Don't write the tag, and report it in the write warnings.
+    Skip,
+    /// Write the tag fields to the file primary tag instead, without overwriting
+    /// the fields of a primary tag written along.
+    ConvertToSupported,
+    /// Fail the whole write.
+    Error,
+}
+
+/// How to cut the values exceeding the length limit of a tag format, e.g. the 30 characters
+/// of an ID3v1 title.
+#[frb(mirror(TruncationStrategy))]
+pub enum _TruncationStrategy {
+    /// Keep the values as they are, only the fixed size fields of ID3v1 are still cut
+    /// when saving.
+    Off,
+    /// Keep as many characters as fit.
+    HardCut,
+    /// Keep as many characters as fit with a trailing `...`.
+    Ellipsis,
+    /// Keep the whole words which fit, or cut hard when the first word doesn't.
+    WordBoundary,
+}
+
+/// The groups of fields removed by [strip_fields], e.g. to sanitize a library before sharing it.
+#[frb(mirror(FieldClass))]
+pub enum _FieldClass {
+    /// The comments, i.e. the ID3v2 `COMM` frames, the MP4 `©cmt` atom
+    /// and the `COMMENT` Vorbis comments.
+    Comments,
+    /// The unsynchronized and synchronized lyrics, i.e. the ID3v2 `USLT` and `SYLT` frames,
+    /// the MP4 `©lyr` atom and the `LYRICS` and `UNSYNCEDLYRICS` Vorbis comments.
+    Lyrics,
+    /// The ratings and play counters, i.e. the ID3v2 `POPM` and `PCNT` frames, the MP4 `rate`
+    /// atom and the `RATING` Vorbis comment.
+    Ratings,
+    /// The data applications store for themselves, i.e. the ID3v2 `PRIV` and `GEOB` frames,
+    /// e.g. the Serato cue points, and the binary items of the other tags.
+    PrivateData,
+    /// The links, i.e. the ID3v2 `W***` frames, e.g. `WXXX`, and the URL fields of the
+    /// other tags.
+    Urls,
+}
+
+/// The rules the written values are checked against, each issue is reported as a warning.
+#[frb(mirror(LintProfile))]
+pub enum _LintProfile {
+    /// Don't check the values.
+    Off,
+    /// Warn about values longer than most players show (255 characters)
+    /// and about control characters.
+    Standard,
+    /// [LintProfile::Standard], and warn about titles, artists and albums longer than
+    /// 64 characters, emojis, and invisible characters, which car head units can't display.
+    CarPlaySafe,
+}
+
+/// Named sets of [WriteOptions] for the devices the files are written for,
+/// see [write_options_for_profile].
+#[frb(mirror(WriteProfile))]
+pub enum _WriteProfile {
+    /// For old car stereos and portable players: an ID3v1 copy of the primary tag,
+    /// JPEG only pictures of at most 500 pixels, values cut at word boundaries
+    /// and checked with [LintProfile::CarPlaySafe].
+    OldCarStereo,
+    /// For archiving: the values and pictures are written as they are,
+    /// and checked with [LintProfile::Standard].
+    Archival,
+}
+
+/// What happened to a single tag of a write.
+#[frb(mirror(TagWriteResult))]
+pub struct _TagWriteResult {
+    /// The type of the tag as it was requested.
+    pub requested_type: TagType,
+    /// The type of the tag as it was written, `null` when it was skipped.
+    pub written_type: Option<TagType>,
+    pub action: TagWriteAction,
+}
+
+#[frb(mirror(TagWriteAction))]
+pub enum _TagWriteAction {
+    /// The file didn't have a tag of this type.
+    Inserted,
+    /// The existing tag of this type was replaced.
+    Replaced,
+    /// The tag type isn't supported by the file, so it wasn't written.
+    Skipped,
+    /// The tag type isn't supported by the file, so its fields were written to the primary tag.
+    Converted,
+}
+
+/// The outcome of a write, see [write_all_with_report].
+/// The outcome of reading a single file of a batch, see [read_all_batch].
+///
+/// Exactly one of `file` and `error` is set.
+#[frb(mirror(BatchReadResult))]
+pub struct _BatchReadResult {
+    /// The path of the file, as it was requested.
+    pub path: String,
+    /// The file tags, when it was read successfully.
+    pub file: Option<TaggyFile>,
+    /// Why the file couldn't be read.
+    pub error: Option<String>,
+}
+
+/// The outcome of recovering the interrupted transactions of a folder, see [recover].
+#[frb(mirror(RecoveryReport))]
+pub struct _RecoveryReport {
+    /// The files put back as they were before a transaction which didn't fully commit.
+    pub rolled_back: Vec<String>,
+    /// The files of a transaction which committed, but was interrupted while cleaning up.
+    pub completed: Vec<String>,
+    /// The files whose leftover copies were removed, the files themselves weren't changed.
+    pub discarded: Vec<String>,
+}
+
+/// The outcome of a scan, see [scan_directory_with_quarantine].
+#[frb(mirror(ScanResult))]
+pub struct _ScanResult {
+    /// The files read successfully, sorted by path.
+    pub files: Vec<ScannedFile>,
+    /// The files and folders which couldn't be read, sorted by path, to be reviewed afterwards.
+    pub quarantine: Vec<QuarantinedFile>,
+}
+
+/// A file read successfully by a scan.
+#[frb(mirror(ScannedFile))]
+pub struct _ScannedFile {
+    pub path: String,
+    pub file: TaggyFile,
+}
+
+/// A file, or a folder, which a scan couldn't read.
+#[frb(mirror(QuarantinedFile))]
+pub struct _QuarantinedFile {
+    pub path: String,
+    /// The stable code of the failure, e.g. `corrupt_tag` or `empty_or_truncated`.
+    pub code: String,
+    /// Why the file couldn't be read.
+    pub reason: String,
+}
+
+/// The place of a file in a continuous work, e.g. a movement of a symphony or a track of a live set,
+/// see [link_continuous_work].
+#[frb(mirror(WorkLink))]
+pub struct _WorkLink {
+    /// The name of the work, e.g. `Symphony No. 9`.
+    pub work: String,
+    /// The position of the file in the work, starting at `1`.
+    pub movement_number: Option<u32>,
+    /// The number of files of the work.
+    pub movement_total: Option<u32>,
+    /// Whether the file should be played without a gap after the previous one.
+    pub gapless: bool,
+    /// Whether players should show the work and movement instead of the track title.
+    pub show_movement: bool,
+}
+
+/// Lyrics with the time each line is sung at, e.g. for karaoke-style highlighting,
+/// see [read_synced_lyrics] and [parse_lrc].
+#[frb(mirror(SyncedLyrics))]
+pub struct _SyncedLyrics {
+    /// The ISO 639-2 language of the lyrics, e.g. `eng`.
+    pub language: Option<String>,
+    /// The description of the lyrics, e.g. `Live version`.
+    pub description: Option<String>,
+    /// The lines, sorted by time.
+    pub lines: Vec<SyncedLine>,
+}
+
+/// A line of [SyncedLyrics].
+#[frb(mirror(SyncedLine))]
+pub struct _SyncedLine {
+    /// The time the line starts at, in milliseconds from the start of the track.
+    pub timestamp_ms: u32,
+    pub text: String,
+}
+
+/// A chapter of an audiobook or podcast episode, see [read_chapters] and [write_chapters].
+#[frb(mirror(Chapter))]
+pub struct _Chapter {
+    /// The time the chapter starts at, in milliseconds from the start of the track.
+    pub start_ms: u32,
+    /// The time the chapter ends at, in milliseconds from the start of the track.
+    pub end_ms: u32,
+    /// The title of the chapter, e.g. `Chapter 1: The Boy Who Lived`.
+    pub title: Option<String>,
+    /// A link to more about the chapter, e.g. the show notes of a podcast episode.
+    ///
+    /// Only stored in ID3v2 tags.
+    pub url: Option<String>,
+    /// The picture shown during the chapter, e.g. a slide.
+    ///
+    /// Only stored in ID3v2 tags.
+    pub picture: Option<Picture>,
+}
+
+/// A frame of an ID3v2 tag as stored in the file, see [read_raw_frames] and [write_raw_frame].
+#[frb(mirror(RawFrame))]
+pub struct _RawFrame {
+    /// The frame ID, e.g. `GEOB`, of 3 characters in the ID3v2.2 tags.
+    pub id: String,
+    /// The frame flags, in the ID3v2.4 layout whatever the tag version, e.g. `0x1000`
+    /// for a read-only frame.
+    pub flags: u16,
+    /// The frame content, without its header.
+    ///
+    /// The unsynchronization and the data length indicator are removed, but the compressed or
+    /// encrypted content is kept as is.
+    pub data: Vec<u8>,
+}
+
+/// The cue points, loops and color a DJ software set on a track, e.g. in Serato DJ,
+/// see [read_dj_markers].
+#[frb(mirror(DjMarkers))]
+pub struct _DjMarkers {
+    /// The hot cues, sorted by index.
+    pub cues: Vec<CuePoint>,
+    /// The saved loops, sorted by index.
+    pub loops: Vec<DjLoop>,
+    /// The color of the track in the library, as `0xRRGGBB`.
+    pub track_color: Option<u32>,
+    /// Whether the BPM of the track is locked, so it isn't analyzed again.
+    pub is_bpm_locked: bool,
+}
+
+/// A hot cue of [DjMarkers].
+#[frb(mirror(CuePoint))]
+pub struct _CuePoint {
+    /// The pad the cue is on, starting at `0`.
+    pub index: u8,
+    /// The time of the cue, in milliseconds from the start of the track.
+    pub position_ms: u32,
+    /// The color of the cue, as `0xRRGGBB`.
+    pub color: u32,
+    pub name: Option<String>,
+}
+
+/// A saved loop of [DjMarkers].
+#[frb(mirror(DjLoop))]
+pub struct _DjLoop {
+    /// The slot the loop is in, starting at `0`.
+    pub index: u8,
+    /// The time the loop starts at, in milliseconds from the start of the track.
+    pub start_ms: u32,
+    /// The time the loop ends at, in milliseconds from the start of the track.
+    pub end_ms: u32,
+    pub name: Option<String>,
+    /// Whether the loop can't be moved or deleted.
+    pub is_locked: bool,
+}
+
+/// The loudness normalization gains of a file, see [read_replay_gain].
+///
+/// The gains are in dB relative to the ReplayGain reference of -18 LUFS whatever the format,
+/// the R128 gains of the Opus files being converted from their -23 LUFS reference.
+#[frb(mirror(ReplayGain))]
+pub struct _ReplayGain {
+    /// The gain to apply to play the track at the reference loudness, e.g. `-6.5`.
+    pub track_gain_db: Option<f64>,
+    /// The peak sample of the track, `1.0` being full scale. Not stored by the Opus files.
+    pub track_peak: Option<f64>,
+    /// The gain to apply to play the album at the reference loudness.
+    pub album_gain_db: Option<f64>,
+    /// The peak sample of the album, `1.0` being full scale. Not stored by the Opus files.
+    pub album_peak: Option<f64>,
+    /// The output gain of the Opus header, which every decoder applies before the other gains,
+    /// `None` for the other formats.
+    pub opus_output_gain_db: Option<f64>,
+}
+
+/// An album with missing or duplicated track numbers, see [find_gaps].
+#[frb(mirror(AlbumGaps))]
+pub struct _AlbumGaps {
+    /// The album name.
+    pub album: String,
+    /// The album artist, the files without one are grouped together.
+    pub album_artist: Option<String>,
+    /// The disc whose tracks are checked, `1` for the files without a `disc_number`.
+    pub disc_number: u32,
+    /// The expected number of tracks, the highest `track_total` of the files,
+    /// or their highest track number when none has a total.
+    pub track_total: u32,
+    /// The track numbers from `1` to the `track_total` no file has, sorted.
+    pub missing: Vec<u32>,
+    /// The track numbers more than one file has, sorted.
+    pub duplicated: Vec<u32>,
+    /// The paths of the files of the album disc, sorted.
+    pub files: Vec<String>,
+}
+
+/// A file whose lyrics contain the query of [search_lyrics].
+///
+/// The offsets and lengths are in UTF-16 code units, like the indexes of the Dart strings.
+#[frb(mirror(LyricsMatch))]
+pub struct _LyricsMatch {
+    /// The path of the file.
+    pub path: String,
+    /// The lines of the lyrics holding the first occurrence of the query.
+    pub snippet: String,
+    /// Where the first occurrence starts in the `snippet`.
+    pub snippet_offset: u32,
+    /// The length of the first occurrence in the `snippet`.
+    pub snippet_length: u32,
+    /// Where each occurrence starts in the whole lyrics.
+    pub offsets: Vec<u32>,
+}
+
+/// How many files have a given value of a field, see [field_values].
+#[frb(mirror(FieldValueCount))]
+pub struct _FieldValueCount {
+    /// The value, as written in the files.
+    pub value: String,
+    /// The number of files having the value.
+    pub count: u32,
+}
+
+/// One of the dominant colors of a picture, see [artwork_palette].
+#[frb(mirror(PaletteColor))]
+pub struct _PaletteColor {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    /// The share of the picture pixels closest to this color, from `0.0` to `1.0`.
+    pub proportion: f32,
+}
+
+/// How [optimize_artwork] re-encodes the embedded pictures.
+#[frb(mirror(ArtworkOptions))]
+pub struct _ArtworkOptions {
+    /// The JPEG quality of the re-encoded pictures, from `1` to `100`, `85` by default.
+    pub quality: u8,
+    /// The largest width or height of the pictures in pixels, the larger ones are downscaled to
+    /// it, keeping their aspect ratio.
+    pub max_size: Option<u32>,
+    /// Compute the savings without writing the files.
+    pub dry_run: bool,
+    /// The number of files optimized at the same time, `0` for one per CPU core.
+    pub concurrency: u32,
+}
+
+/// The outcome of optimizing the pictures of a single file, see [optimize_artwork].
+#[frb(mirror(ArtworkSavings))]
+pub struct _ArtworkSavings {
+    /// The path of the file, as it was requested.
+    pub path: String,
+    /// The number of pictures replaced with a smaller re-encoded one.
+    pub pictures_optimized: u32,
+    /// The size of the data of all the pictures of the file before, in bytes.
+    pub bytes_before: u64,
+    /// The size of the data of all the pictures of the file after, in bytes.
+    pub bytes_after: u64,
+    /// Why the file couldn't be optimized, it's left untouched then.
+    pub error: Option<String>,
+}
+
+/// A release year found in the folder or file name of a file missing it, see [suggest_years].
+#[frb(mirror(YearSuggestion))]
+pub struct _YearSuggestion {
+    /// The path of the file missing the year.
+    pub path: String,
+    /// The year to write.
+    pub year: u32,
+    /// The folder or file name the year was found in, e.g. `Artist - Album (1997)`.
+    pub source: String,
+}
+
+/// Where the featured artists of a track are credited, e.g. `Song (feat. X)` by `A`,
+/// or `Song` by `A feat. X`, see [suggest_featuring].
+#[frb(mirror(FeaturingStyle))]
+pub enum _FeaturingStyle {
+    /// In the track title, e.g. `Song (feat. X)`.
+    InTitle,
+    /// In the track artist, e.g. `A feat. X`.
+    InArtist,
+}
+
+/// The new title and artist of a file whose featured artists are moved, see [suggest_featuring].
+#[frb(mirror(FeaturingChange))]
+pub struct _FeaturingChange {
+    /// The path of the file.
+    pub path: String,
+    /// The title to write, e.g. `Song (feat. X)`.
+    pub track_title: String,
+    /// The artist to write, e.g. `A`.
+    pub track_artist: String,
+    /// The title in the file, e.g. `Song`.
+    pub previous_track_title: String,
+    /// The artist in the file, e.g. `A feat. X`.
+    pub previous_track_artist: String,
+}
+
+/// A group of near-duplicate values of a field, see [suggest_merges].
+#[frb(mirror(MergeSuggestion))]
+pub struct _MergeSuggestion {
+    /// The value to keep, the most common one of the group.
+    pub canonical: String,
+    /// The other values of the group, to be replaced with the `canonical` one.
+    pub variants: Vec<String>,
+    /// The number of files having any value of the group.
+    pub count: u32,
+}
+
+#[frb(mirror(WriteReport))]
+pub struct _WriteReport {
+    /// The file after the write.
+    pub file: TaggyFile,
+    /// What happened to each of the tags to write, in the same order.
+    pub tags: Vec<TagWriteResult>,
+    /// The issues which didn't prevent the write, e.g. a skipped unsupported tag.
+    pub warnings: Vec<String>,
+    /// Whether the written tags were the same as the existing ones,
+    /// in which case the file wasn't saved.
+    pub unchanged: bool,
+}
+
+/// The tags to write to a single file, see [write_transaction].
+#[frb(mirror(WriteRequest))]
+pub struct _WriteRequest {
+    /// The path of the file to write to.
+    pub path: String,
+    /// The tags to write, same as the `tags` of [write_all].
+    pub tags: Vec<Tag>,
+    /// Same as the `override_existent` of [write_all].
+    pub override_existent: bool,
+}
+
+/// The discs of an album found in a folder, see [detect_disc_set].
+#[frb(mirror(DiscSet))]
+pub struct _DiscSet {
+    /// The discs sorted by their number.
+    pub discs: Vec<Disc>,
+}
+
+/// A single disc of a [DiscSet].
+#[frb(mirror(Disc))]
+pub struct _Disc {
+    /// The disc number, from the `disc_number` tag or the `Disc N` folder name.
+    pub number: u32,
+    /// The paths of the audio files of this disc, sorted.
+    pub files: Vec<String>,
+}
+
+/// The values of all the tags of a file merged into a single tag, see [read_effective].
+#[frb(mirror(EffectiveTag))]
+pub struct _EffectiveTag {
+    /// The merged values, its `tag_type` is the file primary tag type.
+    pub tag: Tag,
+    /// Which tag each of the `tag` fields with a value came from.
+    pub sources: Vec<FieldSource>,
+}
+
+/// The origin of a field value of an [EffectiveTag].
+#[frb(mirror(FieldSource))]
+pub struct _FieldSource {
+    /// The name of the field, same as the [Tag] property, e.g. `track_title`.
+    pub field: String,
+    /// The type of the tag the value was read from.
+    pub tag_type: TagType,
+}
+
+/// A generic representation of an audio file
+///
+/// Holds information about a file and its audio tags.
+#[frb(mirror(TaggyFile))]
+pub struct _TaggyFile {
+    /// The Type of this file
+    pub file_type: Option<FileType>,
+    /// The Size of this file
+    pub size: Option<u64>,
+    /// The properties of this file audio track.
+    pub audio: AudioInfo,
+    /// The tags included with this file.
+    pub tags: Vec<Tag>,
+    pub primary_tag_type: TagType,
+    /// The absolute path of this file with all the symbolic links resolved,
+    /// `None` when read from an in-memory buffer.
+    pub canonical_path: Option<String>,
+    /// When this file was created, in milliseconds since the Unix epoch,
+    /// `None` when the file system doesn't record it or when read from an in-memory buffer.
+    pub created_at_ms: Option<i64>,
+    /// When this file was last modified, in milliseconds since the Unix epoch,
+    /// `None` when read from an in-memory buffer.
+    pub modified_at_ms: Option<i64>,
+    /// When this file was added to the library, in milliseconds since the Unix epoch, as written
+    /// by some players to a `DATE_ADDED` or `ADDED` custom item of its tags.
+    pub date_added_ms: Option<i64>,
+    /// The chapters of this file, sorted by start time, see [Chapter].
+    pub chapters: Vec<Chapter>,
+    /// The issues found while reading this file, e.g. the fields its tags hold several times,
+    /// see [DuplicatePolicy].
+    pub warnings: Vec<String>,
+}
+
+/// The information of an audio track
+#[frb(mirror(AudioInfo))]
+pub struct _AudioInfo {
+    /// The duration in seconds.
+    pub duration_sec: Option<u64>,
+    /// The duration in milliseconds.
+    pub duration_ms: Option<u64>,
+    /// The duration split into hours, minutes, seconds and milliseconds.
+    pub duration: Option<AudioDuration>,
+    pub overall_bitrate: Option<u32>,
+    pub audio_bitrate: Option<u32>,
+    pub sample_rate: Option<u32>,
+    pub bit_depth: Option<u8>,
+    pub channels: Option<u8>,
+    pub channel_mask: Option<u32>,
+    /// The number of samples per audio frame, for the formats where it's fixed
+    /// (`1` for uncompressed PCM).
+    pub frame_length: Option<u32>,
+    /// The duration of an audio frame in microseconds, the finest position players can
+    /// seek to without decoding, when the `frame_length` is known.
+    pub seek_granularity_us: Option<u32>,
+}
+
+/// A duration split into its components, so it can be shown as e.g. `1:02:03.456`
+/// without any conversion.
+#[frb(mirror(AudioDuration))]
+pub struct _AudioDuration {
+    pub hours: u32,
+    /// `0` to `59`.
+    pub minutes: u32,
+    /// `0` to `59`.
+    pub seconds: u32,
+    /// `0` to `999`.
+    pub millis: u32,
+}
+
+#[frb(mirror(Tag))]
+pub struct _Tag {
+    pub tag_type: TagType,
+    pub pictures: Vec<Picture>,
+    pub track_title: Option<String>,
+    pub track_artist: Option<String>,
+    pub album: Option<String>,
+    pub album_artist: Option<String>,
+    pub producer: Option<String>,
+    pub track_number: Option<u32>,
+    /// Total track count of this track's disc
+    pub track_total: Option<u32>,
+    pub disc_number: Option<u32>,
+    pub disc_total: Option<u32>,
+    pub year: Option<u32>,
+    pub recording_date: Option<String>,
+    pub original_release_date: Option<String>,
+    /// The release date, as precise as the tag stores it, i.e. the ID3v2.4 `TDRC` frame, the ID3v2.3
+    /// `TYER` and `TDAT` frames, the MP4 `©day` atom and the `DATE` Vorbis comment.
+    ///
+    /// Read from the same item as `recording_date`, and written instead of it when they differ.
+    /// The ID3v2 tags are written as ID3v2.4, so the `TYER` and `TDAT` frames become a `TDRC` one.
+    pub release_date: Option<TagDate>,
+    /// The original release date, i.e. the ID3v2.4 `TDOR` frame, the ID3v2.3 `TORY` one and
+    /// the `ORIGINALDATE` Vorbis comment, see `release_date`.
+    ///
+    /// Read from the same item as `original_release_date`, and written instead of it when they
+    /// differ.
+    pub original_date: Option<TagDate>,
+    pub language: Option<String>,
+    pub lyrics: Option<String>,
+    pub genre: Option<String>,
+    /// The International Standard Recording Code, e.g. `USRC17607839`.
+    pub isrc: Option<String>,
+    /// The first composer, see `composers`.
+    pub composer: Option<String>,
+    pub conductor: Option<String>,
+    pub remixer: Option<String>,
+    /// The track artist used for sorting, e.g. `Beatles, The`.
+    pub artist_sort: Option<String>,
+    /// The album title used for sorting.
+    pub album_sort: Option<String>,
+    /// The track title used for sorting.
+    pub title_sort: Option<String>,
+    /// The album artist used for sorting.
+    pub album_artist_sort: Option<String>,
+    /// The tempo in beats per minute, rounded to the nearest integer like the ID3v2 `TBPM` frame.
+    pub bpm: Option<u32>,
+    /// The musical key the track starts in, e.g. `Am` or `8A` in the Camelot notation.
+    pub initial_key: Option<String>,
+    /// The mood of the track, e.g. `Energetic`.
+    pub mood: Option<String>,
+    /// The rating from 0 to 100, i.e. 20 per star, see [Tag::rating_stars].
+    ///
+    /// Stored in the ID3v2 `POPM` frame with the Windows Media Player scale, where 0 is
+    /// unrated, the `RATING` Vorbis comment or APE item, and the MP4 `rate` atom.
+    pub rating: Option<u32>,
+    /// The ISO 639-2 language of the `lyrics`, e.g. `eng`. Only stored by the ID3v2 `USLT` frame.
+    pub lyrics_language: Option<String>,
+    /// The description of the `lyrics`, e.g. `Live version`. Only stored by the ID3v2 `USLT` frame.
+    pub lyrics_description: Option<String>,
+    /// The MusicBrainz ID of the recording, i.e. the `MUSICBRAINZ_TRACKID` Vorbis comment
+    /// and the ID3v2 `UFID` frame Picard writes.
+    pub musicbrainz_recording_id: Option<String>,
+    /// The MusicBrainz ID of the release, i.e. the album.
+    pub musicbrainz_release_id: Option<String>,
+    /// The MusicBrainz ID of the release group, gathering all the releases of an album.
+    pub musicbrainz_release_group_id: Option<String>,
+    /// The MusicBrainz ID of the track artist.
+    pub musicbrainz_artist_id: Option<String>,
+    /// The MusicBrainz ID of the album artist.
+    pub musicbrainz_album_artist_id: Option<String>,
+    /// The MusicBrainz ID of the track on the release, unlike `musicbrainz_recording_id`.
+    pub musicbrainz_track_id: Option<String>,
+    /// The AcoustID of the recording, found from its `acoustid_fingerprint`.
+    pub acoustid_id: Option<String>,
+    /// The Chromaprint fingerprint of the audio, as computed by `fpcalc`.
+    pub acoustid_fingerprint: Option<String>,
+    /// The barcode of the release, e.g. its UPC or EAN.
+    pub barcode: Option<String>,
+    /// The catalog number the label gave to the release.
+    pub catalog_number: Option<String>,
+    /// The record label, stored in the ID3v2 `TPUB` publisher frame.
+    pub label: Option<String>,
+    /// The copyright message, e.g. `℗ 2023 Some Label`.
+    pub copyright: Option<String>,
+    /// The grouping of the track, e.g. `Piano Sonatas`, i.e. the ID3v2 `TIT1` frame (and `GRP1`
+    /// one of iTunes), the MP4 `©grp` atom and the `GROUPING` Vorbis comment.
+    pub grouping: Option<String>,
+    /// The subtitle of the track, e.g. `Live at Wembley`, i.e. the ID3v2 `TIT3` frame and
+    /// the `SUBTITLE` Vorbis comment.
+    pub subtitle: Option<String>,
+    /// The classical work the track is part of, e.g. `Symphony No. 9`, i.e. the `WORK` `TXXX`
+    /// frame of ID3v2, the MP4 `©wrk` atom and the `WORK` Vorbis comment.
+    pub work: Option<String>,
+    /// The name of the movement, e.g. `Allegro ma non troppo`, i.e. the ID3v2 `MVNM` frame,
+    /// the MP4 `©mvn` atom and the `MOVEMENTNAME` Vorbis comment.
+    pub movement: Option<String>,
+    /// The number of the movement in the work, i.e. the ID3v2 `MVIN` frame, the MP4 `©mvi`
+    /// atom and the `MOVEMENT` Vorbis comment.
+    pub movement_number: Option<u32>,
+    /// The number of movements of the work, i.e. the total of the ID3v2 `MVIN` frame,
+    /// the MP4 `©mvc` atom and the `MOVEMENTTOTAL` Vorbis comment.
+    pub movement_total: Option<u32>,
+    /// Whether the album is a compilation of various artists, i.e. the ID3v2 `TCMP` frame,
+    /// the MP4 `cpil` atom and the `COMPILATION` Vorbis comment, which iTunes groups the tracks
+    /// of an album by instead of their album artist.
+    pub is_compilation: Option<bool>,
+    /// Whether the file is a podcast episode, i.e. the ID3v2 `PCST` frame, the MP4 `pcst` atom
+    /// and the `PODCAST` Vorbis comment.
+    pub is_podcast: Option<bool>,
+    /// The feed of the podcast, i.e. the ID3v2 `WFED` frame, the MP4 `purl` atom and the
+    /// `PODCASTURL` Vorbis comment.
+    pub podcast_url: Option<String>,
+    /// The globally unique identifier of the podcast episode, i.e. the ID3v2 `TGID` frame,
+    /// the MP4 `egid` atom and the `PODCASTGUID` Vorbis comment.
+    pub episode_id: Option<String>,
+    /// The narrator of the audiobook, i.e. the MP4 `©nrt` atom and the `NARRATOR` `TXXX`
+    /// frame of ID3v2 and Vorbis comment.
+    pub narrator: Option<String>,
+    /// The series the audiobook is part of, e.g. `The Expanse`, i.e. the `SERIES` `TXXX`
+    /// frame of ID3v2, MP4 freeform atom and Vorbis comment.
+    pub series: Option<String>,
+    /// The part of the audiobook in its series, e.g. `1` or `1.5`, i.e. the `SERIES-PART`
+    /// `TXXX` frame of ID3v2, MP4 freeform atom and Vorbis comment.
+    pub series_part: Option<String>,
+    /// The application specific fields, e.g. `MY_APP_PLAYCOUNT`, see [CustomItem].
+    pub custom_items: Vec<CustomItem>,
+    /// All the track artists, e.g. of a collaboration, the first one being `track_artist`.
+    ///
+    /// ID3v2 stores them in a single null-separated frame, Vorbis comments, APE and MP4
+    /// as separate values of the same field and the other tags only keep the first one.
+    /// Setting `track_artist` to another value than the first artist replaces all of them.
+    pub artists: Vec<String>,
+    /// All the genres, the first one being `genre`, stored like `artists`.
+    pub genres: Vec<String>,
+    /// All the composers, the first one being `composer`, stored like `artists`.
+    pub composers: Vec<String>,
+    /// The comments, e.g. the user comment along with the `iTunNORM` one, see [Comment].
+    pub comments: Vec<Comment>,
+}
+
+/// An application specific field of a tag, e.g. `MY_APP_PLAYCOUNT`.
+///
+/// It's stored as a `TXXX` frame by ID3v2, a Vorbis comment, a `----:com.apple.iTunes:<key>`
+/// freeform atom by MP4 and an item by APE.
+#[frb(mirror(CustomItem))]
+pub struct _CustomItem {
+    pub key: String,
+    pub value: String,
+}
+
+/// A comment of a tag, e.g. the user comment or the `iTunNORM` one iTunes writes.
+///
+/// Only ID3v2 stores the `language` and `description` of its `COMM` frames, so several comments
+/// can coexist. The other tags only keep the comments without a description, as `COMMENT`
+/// Vorbis comments, `Comment` APE items and `©cmt` MP4 atoms.
+/// A date of a tag, as precise as the tag stores it, e.g. only the year of the ID3v2.3 `TYER`
+/// frame, see [Tag]'s `release_date`.
+#[frb(mirror(TagDate))]
+pub struct _TagDate {
+    pub year: u32,
+    /// From 1 to 12, `None` when only the year is known.
+    pub month: Option<u32>,
+    /// From 1 to 31, `None` when only the year and the month are known.
+    pub day: Option<u32>,
+}
+
+#[frb(mirror(Comment))]
+pub struct _Comment {
+    /// The ISO 639-2 language, e.g. `eng`, `None` when it's unknown.
+    pub language: Option<String>,
+    /// Tells the comments apart, e.g. `iTunNORM`, `None` for the user comment.
+    pub description: Option<String>,
+    pub text: String,
+}
+
+/// Gives information about a tag's picture.
+///
+/// The `width`, `height`, `color_depth` and `num_colors` are read from the image header, and
+/// written to the formats storing them along with the picture (i.e. FLAC picture blocks).
+#[frb(mirror(Picture))]
+pub struct _Picture {
+    pub pic_type: PictureType,
+    /// The picture's data
+    pub pic_data: Vec<u8>,
+    /// The picture's mimetype
+    pub mime_type: Option<MimeType>,
+    /// The picture's width in pixels
+    pub width: Option<u32>,
+    /// The picture's height in pixels
+    pub height: Option<u32>,
+    /// The picture's color depth in bits per pixel
+    pub color_depth: Option<u32>,
+    /// The number of colors used
+    pub num_colors: Option<u32>,
+    /// The size of the picture's data in bytes, set on read even when the data itself
+    /// was skipped, see [ParseConfig]'s `read_picture_data`.
+    pub data_size: Option<u32>,
+    /// The picture's description, e.g. `Front cover`.
+    pub description: Option<String>,
+    /// How the picture is stored in the file it was read from, `None` for the pictures
+    /// which weren't read from a file. Ignored on write, where it follows the file format.
+    pub storage: Option<PictureStorage>,
+}
+
+#[frb(mirror(TagType))]
+pub enum _TagType {
+    /// This covers both APEv1 and APEv2 as it doesn't matter much
+    Ape,
+    /// Represents an ID3v1 tag
+    Id3v1,
+    /// This covers all ID3v2 versions since they all get upgraded to ID3v2.4
+    Id3v2,
+    /// Represents an MP4 ilst atom
+    Mp4Ilst,
+    /// Represents vorbis comments
+    VorbisComments,
+    /// Represents a RIFF INFO LIST
+    RiffInfo,
+    /// Represents AIFF text chunks
+    AiffText,
+    /// This will be converted to the audio file primary tag type.
+    ///
+    /// **Note**: this is intended for the tag passed to `write_primary()` if you don't
+    /// know the file primary tag type.
+    FilePrimaryType,
+    /// Other type
+    Other,
+}
+
+/// The type of a file
+#[frb(mirror(FileType))]
+pub enum _FileType {
+    Aac,
+    Aiff,
+    Ape,
+    Flac,
+    Mpeg,
+    Mp4,
+    Mpc,
+    Opus,
+    Vorbis,
+    Speex,
+    Wav,
+    WavPack,
+    Other,
+}
+
+#[frb(mirror(PictureType))]
+pub enum _PictureType {
+    Other,
+    Icon,
+    OtherIcon,
+    CoverFront,
+    CoverBack,
+    Leaflet,
+    Media,
+    LeadArtist,
+    Artist,
+    Conductor,
+    Band,
+    Composer,
+    Lyricist,
+    RecordingLocation,
+    DuringRecording,
+    DuringPerformance,
+    ScreenCapture,
+    BrightFish,
+    Illustration,
+    BandLogo,
+    PublisherLogo,
+    Undefined,
+}
+
+/// The key of a single field of a tag, see [read_field] and [write_field].
+///
+/// Covers the fields [Tag] doesn't model, each is mapped to the native key of the tag format.
+/// Not all the keys are supported by all the formats.
+#[frb(mirror(ItemKey))]
+pub enum _ItemKey {
+    /// The album title.
+    AlbumTitle,
+    /// The subtitle of the disc set.
+    SetSubtitle,
+    /// The TV show name.
+    ShowName,
+    /// The content group, e.g. a work of classical music.
+    ContentGroup,
+    /// The track title.
+    TrackTitle,
+    /// The track subtitle.
+    TrackSubtitle,
+    /// The title of the original album of a cover.
+    OriginalAlbumTitle,
+    /// The artist of the original song of a cover.
+    OriginalArtist,
+    /// The lyricist of the original song of a cover.
+    OriginalLyricist,
+    /// The album title used for sorting.
+    AlbumTitleSortOrder,
+    /// The album artist used for sorting.
+    AlbumArtistSortOrder,
+    /// The track title used for sorting.
+    TrackTitleSortOrder,
+    /// The track artist used for sorting.
+    TrackArtistSortOrder,
+    /// The TV show name used for sorting.
+    ShowNameSortOrder,
+    /// The composer used for sorting.
+    ComposerSortOrder,
+    /// The album artist.
+    AlbumArtist,
+    /// The track artist.
+    TrackArtist,
+    Arranger,
+    Writer,
+    Composer,
+    Conductor,
+    Director,
+    Engineer,
+    Lyricist,
+    MixDj,
+    MixEngineer,
+    MusicianCredits,
+    Performer,
+    Producer,
+    Publisher,
+    Label,
+    InternetRadioStationName,
+    InternetRadioStationOwner,
+    Remixer,
+    DiscNumber,
+    DiscTotal,
+    TrackNumber,
+    TrackTotal,
+    /// The rating, as stored by the format, e.g. the ID3v2 `POPM` frame.
+    Popularimeter,
+    ParentalAdvisory,
+    RecordingDate,
+    Year,
+    OriginalReleaseDate,
+    /// The International Standard Recording Code.
+    Isrc,
+    Barcode,
+    CatalogNumber,
+    Work,
+    Movement,
+    MovementNumber,
+    MovementTotal,
+    MusicBrainzRecordingId,
+    MusicBrainzTrackId,
+    MusicBrainzReleaseId,
+    MusicBrainzReleaseGroupId,
+    MusicBrainzArtistId,
+    MusicBrainzReleaseArtistId,
+    MusicBrainzWorkId,
+    /// Whether the album is a compilation, `1` or `0`.
+    FlagCompilation,
+    /// Whether the file is a podcast episode, `1` or `0`.
+    FlagPodcast,
+    FileType,
+    FileOwner,
+    TaggingTime,
+    Length,
+    OriginalFileName,
+    OriginalMediaType,
+    EncodedBy,
+    EncoderSoftware,
+    EncoderSettings,
+    EncodingTime,
+    ReplayGainAlbumGain,
+    ReplayGainAlbumPeak,
+    ReplayGainTrackGain,
+    ReplayGainTrackPeak,
+    /// The track gain of the Opus files, a Q7.8 number relative to -23 LUFS, e.g. `-2560`,
+    /// see [read_replay_gain] for the gain in dB.
+    R128TrackGain,
+    /// The album gain of the Opus files, stored like `R128TrackGain`.
+    R128AlbumGain,
+    AudioFileUrl,
+    AudioSourceUrl,
+    CommercialInformationUrl,
+    CopyrightUrl,
+    TrackArtistUrl,
+    RadioStationUrl,
+    PaymentUrl,
+    PublisherUrl,
+    Genre,
+    InitialKey,
+    Color,
+    Mood,
+    Bpm,
+    CopyrightMessage,
+    License,
+    PodcastDescription,
+    PodcastSeriesCategory,
+    PodcastURL,
+    PodcastReleaseDate,
+    PodcastGlobalUniqueID,
+    PodcastKeywords,
+    Comment,
+    Description,
+    Language,
+    Script,
+    Lyrics,
+    AppleXid,
+    AppleId3v2ContentGroup,
+}
+
+/// The changes to the type, description and mimetype of a picture already in a file,
+/// see [update_picture_meta]. The `None` fields are left as they are.
+#[frb(mirror(PictureChanges))]
+pub struct _PictureChanges {
+    pub pic_type: Option<PictureType>,
+    /// The new description, an empty one removes it.
+    pub description: Option<String>,
+    /// The new mimetype, e.g. for a PNG marked as JPEG. The picture data isn't converted.
+    pub mime_type: Option<MimeType>,
+}
+
+/// Which picture comes first when a file has several, see [read_primary_picture].
+///
+/// The pictures which are equal for the order keep their stored order.
+#[frb(mirror(PictureOrder))]
+pub enum _PictureOrder {
+    /// The order the pictures are stored in.
+    AsStored,
+    /// The front covers first.
+    FrontCoverFirst,
+    /// The front covers, then the back covers, then the other types in their ID3v2 order.
+    ByType,
+    /// The largest pictures first, by pixel count then by data size.
+    BySize,
+}
+
+#[frb(mirror(PictureStorage))]
+pub enum _PictureStorage {
+    /// An ID3v2 `APIC` frame.
+    Id3v2Frame,
+    /// A FLAC `PICTURE` metadata block.
+    FlacBlock,
+    /// A FLAC picture block encoded in base64 in a `METADATA_BLOCK_PICTURE` comment,
+    /// for the Ogg Vorbis, Opus and Speex files.
+    VorbisComment,
+    /// An MP4 `covr` atom.
+    Mp4Atom,
+    /// An APE binary item, e.g. `Cover Art (Front)`.
+    ApeItem,
+}
+
+#[frb(mirror(MimeType))]
+pub enum _MimeType {
+    /// PNG image
+    Png,
+    /// JPEG image
+    Jpeg,
+    /// TIFF image
+    Tiff,
+    /// BMP image
+    Bmp,
+    /// GIF image
+    Gif,
+    /// Unknown mimetype
+    Unknown,
+    /// No mimetype
+    None,
+}
+
+/// Prefixes the message of the `result` error with the stable code of its [TaggyError] kind,
+/// e.g. `[file_not_found] The file path does not exist!`, so the Dart side can branch on
+/// the kind of a `FfiException` without depending on the wording of the message.
+fn coded<T>(result: anyhow::Result<T>) -> anyhow::Result<T> {
+    result.map_err(|e| {
+        let error = TaggyError::from(&e);
+        anyhow!("[{}] {error}", error.code())
+    })
 }
@@ -20,19 +20,25 @@ use std::sync::Arc;
 
 // Section: imports
 
-use crate::audio_info::AudioInfo;
-use crate::picture::MimeType;
-use crate::picture::Picture;
-use crate::picture::PictureType;
-use crate::tag::Tag;
-use crate::tag::TagType;
-use crate::taggy_file::FileType;
-use crate::taggy_file::TaggyFile;
+use taggy_core::audio_info::AudioDuration;
+use taggy_core::audio_info::AudioInfo;
+use taggy_core::chapter::Chapter;
+use taggy_core::comment::Comment;
+use taggy_core::custom_item::CustomItem;
+use taggy_core::picture::MimeType;
+use taggy_core::picture::Picture;
+use taggy_core::picture::PictureStorage;
+use taggy_core::picture::PictureType;
+use taggy_core::tag::Tag;
+use taggy_core::tag::TagType;
+use taggy_core::tag_date::TagDate;
+use taggy_core::taggy_file::FileType;
+use taggy_core::taggy_file::TaggyFile;
 
 // Section: wire functions
 
 fn wire_read_all_impl(port_: MessagePort, path: impl Wire2Api<String> + UnwindSafe) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, TaggyFile, _>(
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, mirror_TaggyFile, _>(
         WrapInfo {
             debug_name: "read_all",
             port: Some(port_),
@@ -45,7 +51,7 @@ fn wire_read_all_impl(port_: MessagePort, path: impl Wire2Api<String> + UnwindSa
     )
 }
 fn wire_read_primary_impl(port_: MessagePort, path: impl Wire2Api<String> + UnwindSafe) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, TaggyFile, _>(
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, mirror_TaggyFile, _>(
         WrapInfo {
             debug_name: "read_primary",
             port: Some(port_),
@@ -58,7 +64,7 @@ fn wire_read_primary_impl(port_: MessagePort, path: impl Wire2Api<String> + Unwi
     )
 }
 fn wire_read_any_impl(port_: MessagePort, path: impl Wire2Api<String> + UnwindSafe) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, TaggyFile, _>(
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, mirror_TaggyFile, _>(
         WrapInfo {
             debug_name: "read_any",
             port: Some(port_),
@@ -76,7 +82,7 @@ fn wire_write_all_impl(
     tags: impl Wire2Api<Vec<Tag>> + UnwindSafe,
     override_existent: impl Wire2Api<bool> + UnwindSafe,
 ) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, TaggyFile, _>(
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, mirror_TaggyFile, _>(
         WrapInfo {
             debug_name: "write_all",
             port: Some(port_),
@@ -96,7 +102,7 @@ fn wire_write_primary_impl(
     tag: impl Wire2Api<Tag> + UnwindSafe,
     keep_others: impl Wire2Api<bool> + UnwindSafe,
 ) {
-    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, TaggyFile, _>(
+    FLUTTER_RUST_BRIDGE_HANDLER.wrap::<_, _, _, mirror_TaggyFile, _>(
         WrapInfo {
             debug_name: "write_primary",
             port: Some(port_),
@@ -143,8 +149,244 @@ fn wire_remove_tag_impl(
 }
 // Section: wrapper structs
 
+pub struct mirror_AudioDuration(AudioDuration);
+
+pub struct mirror_AudioInfo(AudioInfo);
+
+pub struct mirror_Chapter(Chapter);
+
+pub struct mirror_Comment(Comment);
+
+pub struct mirror_CustomItem(CustomItem);
+
+pub struct mirror_FileType(FileType);
+
+pub struct mirror_MimeType(MimeType);
+
+pub struct mirror_Picture(Picture);
+
+pub struct mirror_PictureStorage(PictureStorage);
+
+pub struct mirror_PictureType(PictureType);
+
+pub struct mirror_Tag(Tag);
+
+pub struct mirror_TagDate(TagDate);
+
+pub struct mirror_TagType(TagType);
+
+pub struct mirror_TaggyFile(TaggyFile);
+
 // Section: static checks
 
+const _: fn() = || {
+    {
+        let TaggyFile = None::<TaggyFile>.unwrap();
+        let _: Option<FileType> = TaggyFile.file_type;
+        let _: Option<u64> = TaggyFile.size;
+        let _: AudioInfo = TaggyFile.audio;
+        let _: Vec<Tag> = TaggyFile.tags;
+        let _: TagType = TaggyFile.primary_tag_type;
+        let _: Option<String> = TaggyFile.canonical_path;
+        let _: Option<i64> = TaggyFile.created_at_ms;
+        let _: Option<i64> = TaggyFile.modified_at_ms;
+        let _: Option<i64> = TaggyFile.date_added_ms;
+        let _: Vec<Chapter> = TaggyFile.chapters;
+        let _: Vec<String> = TaggyFile.warnings;
+    }
+    {
+        let AudioInfo = None::<AudioInfo>.unwrap();
+        let _: Option<u64> = AudioInfo.duration_sec;
+        let _: Option<u64> = AudioInfo.duration_ms;
+        let _: Option<AudioDuration> = AudioInfo.duration;
+        let _: Option<u32> = AudioInfo.overall_bitrate;
+        let _: Option<u32> = AudioInfo.audio_bitrate;
+        let _: Option<u32> = AudioInfo.sample_rate;
+        let _: Option<u8> = AudioInfo.bit_depth;
+        let _: Option<u8> = AudioInfo.channels;
+        let _: Option<u32> = AudioInfo.channel_mask;
+        let _: Option<u32> = AudioInfo.frame_length;
+        let _: Option<u32> = AudioInfo.seek_granularity_us;
+    }
+    {
+        let AudioDuration = None::<AudioDuration>.unwrap();
+        let _: u32 = AudioDuration.hours;
+        let _: u32 = AudioDuration.minutes;
+        let _: u32 = AudioDuration.seconds;
+        let _: u32 = AudioDuration.millis;
+    }
+    {
+        let Tag = None::<Tag>.unwrap();
+        let _: TagType = Tag.tag_type;
+        let _: Vec<Picture> = Tag.pictures;
+        let _: Option<String> = Tag.track_title;
+        let _: Option<String> = Tag.track_artist;
+        let _: Option<String> = Tag.album;
+        let _: Option<String> = Tag.album_artist;
+        let _: Option<String> = Tag.producer;
+        let _: Option<u32> = Tag.track_number;
+        let _: Option<u32> = Tag.track_total;
+        let _: Option<u32> = Tag.disc_number;
+        let _: Option<u32> = Tag.disc_total;
+        let _: Option<u32> = Tag.year;
+        let _: Option<String> = Tag.recording_date;
+        let _: Option<String> = Tag.original_release_date;
+        let _: Option<TagDate> = Tag.release_date;
+        let _: Option<TagDate> = Tag.original_date;
+        let _: Option<String> = Tag.language;
+        let _: Option<String> = Tag.lyrics;
+        let _: Option<String> = Tag.genre;
+        let _: Option<String> = Tag.isrc;
+        let _: Option<String> = Tag.composer;
+        let _: Option<String> = Tag.conductor;
+        let _: Option<String> = Tag.remixer;
+        let _: Option<String> = Tag.artist_sort;
+        let _: Option<String> = Tag.album_sort;
+        let _: Option<String> = Tag.title_sort;
+        let _: Option<String> = Tag.album_artist_sort;
+        let _: Option<u32> = Tag.bpm;
+        let _: Option<String> = Tag.initial_key;
+        let _: Option<String> = Tag.mood;
+        let _: Option<u32> = Tag.rating;
+        let _: Option<String> = Tag.lyrics_language;
+        let _: Option<String> = Tag.lyrics_description;
+        let _: Option<String> = Tag.musicbrainz_recording_id;
+        let _: Option<String> = Tag.musicbrainz_release_id;
+        let _: Option<String> = Tag.musicbrainz_release_group_id;
+        let _: Option<String> = Tag.musicbrainz_artist_id;
+        let _: Option<String> = Tag.musicbrainz_album_artist_id;
+        let _: Option<String> = Tag.musicbrainz_track_id;
+        let _: Option<String> = Tag.acoustid_id;
+        let _: Option<String> = Tag.acoustid_fingerprint;
+        let _: Option<String> = Tag.barcode;
+        let _: Option<String> = Tag.catalog_number;
+        let _: Option<String> = Tag.label;
+        let _: Option<String> = Tag.copyright;
+        let _: Option<String> = Tag.grouping;
+        let _: Option<String> = Tag.subtitle;
+        let _: Option<String> = Tag.work;
+        let _: Option<String> = Tag.movement;
+        let _: Option<u32> = Tag.movement_number;
+        let _: Option<u32> = Tag.movement_total;
+        let _: Option<bool> = Tag.is_compilation;
+        let _: Option<bool> = Tag.is_podcast;
+        let _: Option<String> = Tag.podcast_url;
+        let _: Option<String> = Tag.episode_id;
+        let _: Option<String> = Tag.narrator;
+        let _: Option<String> = Tag.series;
+        let _: Option<String> = Tag.series_part;
+        let _: Vec<CustomItem> = Tag.custom_items;
+        let _: Vec<String> = Tag.artists;
+        let _: Vec<String> = Tag.genres;
+        let _: Vec<String> = Tag.composers;
+        let _: Vec<Comment> = Tag.comments;
+    }
+    {
+        let TagDate = None::<TagDate>.unwrap();
+        let _: u32 = TagDate.year;
+        let _: Option<u32> = TagDate.month;
+        let _: Option<u32> = TagDate.day;
+    }
+    {
+        let Comment = None::<Comment>.unwrap();
+        let _: Option<String> = Comment.language;
+        let _: Option<String> = Comment.description;
+        let _: String = Comment.text;
+    }
+    {
+        let CustomItem = None::<CustomItem>.unwrap();
+        let _: String = CustomItem.key;
+        let _: String = CustomItem.value;
+    }
+    {
+        let Picture = None::<Picture>.unwrap();
+        let _: PictureType = Picture.pic_type;
+        let _: Vec<u8> = Picture.pic_data;
+        let _: Option<MimeType> = Picture.mime_type;
+        let _: Option<u32> = Picture.width;
+        let _: Option<u32> = Picture.height;
+        let _: Option<u32> = Picture.color_depth;
+        let _: Option<u32> = Picture.num_colors;
+        let _: Option<u32> = Picture.data_size;
+        let _: Option<String> = Picture.description;
+        let _: Option<PictureStorage> = Picture.storage;
+    }
+    {
+        let Chapter = None::<Chapter>.unwrap();
+        let _: u32 = Chapter.start_ms;
+        let _: u32 = Chapter.end_ms;
+        let _: Option<String> = Chapter.title;
+        let _: Option<String> = Chapter.url;
+        let _: Option<Picture> = Chapter.picture;
+    }
+    match None::<TagType>.unwrap() {
+        TagType::Ape => {}
+        TagType::Id3v1 => {}
+        TagType::Id3v2 => {}
+        TagType::Mp4Ilst => {}
+        TagType::VorbisComments => {}
+        TagType::RiffInfo => {}
+        TagType::AiffText => {}
+        TagType::FilePrimaryType => {}
+        TagType::Other => {}
+    }
+    match None::<FileType>.unwrap() {
+        FileType::Aac => {}
+        FileType::Aiff => {}
+        FileType::Ape => {}
+        FileType::Flac => {}
+        FileType::Mpeg => {}
+        FileType::Mp4 => {}
+        FileType::Mpc => {}
+        FileType::Opus => {}
+        FileType::Vorbis => {}
+        FileType::Speex => {}
+        FileType::Wav => {}
+        FileType::WavPack => {}
+        FileType::Other => {}
+    }
+    match None::<PictureType>.unwrap() {
+        PictureType::Other => {}
+        PictureType::Icon => {}
+        PictureType::OtherIcon => {}
+        PictureType::CoverFront => {}
+        PictureType::CoverBack => {}
+        PictureType::Leaflet => {}
+        PictureType::Media => {}
+        PictureType::LeadArtist => {}
+        PictureType::Artist => {}
+        PictureType::Conductor => {}
+        PictureType::Band => {}
+        PictureType::Composer => {}
+        PictureType::Lyricist => {}
+        PictureType::RecordingLocation => {}
+        PictureType::DuringRecording => {}
+        PictureType::DuringPerformance => {}
+        PictureType::ScreenCapture => {}
+        PictureType::BrightFish => {}
+        PictureType::Illustration => {}
+        PictureType::BandLogo => {}
+        PictureType::PublisherLogo => {}
+        PictureType::Undefined => {}
+    }
+    match None::<MimeType>.unwrap() {
+        MimeType::Png => {}
+        MimeType::Jpeg => {}
+        MimeType::Tiff => {}
+        MimeType::Bmp => {}
+        MimeType::Gif => {}
+        MimeType::Unknown => {}
+        MimeType::None => {}
+    }
+    match None::<PictureStorage>.unwrap() {
+        PictureStorage::Id3v2Frame => {}
+        PictureStorage::FlacBlock => {}
+        PictureStorage::VorbisComment => {}
+        PictureStorage::Mp4Atom => {}
+        PictureStorage::ApeItem => {}
+    }
+};
+
 // Section: allocate functions
 
 // Section: related functions
@@ -191,6 +433,19 @@ impl Wire2Api<MimeType> for i32 {
     }
 }
 
+impl Wire2Api<PictureStorage> for i32 {
+    fn wire2api(self) -> PictureStorage {
+        match self {
+            0 => PictureStorage::Id3v2Frame,
+            1 => PictureStorage::FlacBlock,
+            2 => PictureStorage::VorbisComment,
+            3 => PictureStorage::Mp4Atom,
+            4 => PictureStorage::ApeItem,
+            _ => unreachable!("Invalid variant for PictureStorage: {}", self),
+        }
+    }
+}
+
 impl Wire2Api<PictureType> for i32 {
     fn wire2api(self) -> PictureType {
         match self {
@@ -250,202 +505,367 @@ impl Wire2Api<u8> for u8 {
 
 // Section: impl IntoDart
 
-impl support::IntoDart for AudioInfo {
+impl support::IntoDart for mirror_AudioDuration {
     fn into_dart(self) -> support::DartAbi {
         vec![
-            self.duration_sec.into_dart(),
-            self.overall_bitrate.into_dart(),
-            self.audio_bitrate.into_dart(),
-            self.sample_rate.into_dart(),
-            self.bit_depth.into_dart(),
-            self.channels.into_dart(),
-            self.channel_mask.into_dart(),
+            self.0.hours.into_into_dart().into_dart(),
+            self.0.minutes.into_into_dart().into_dart(),
+            self.0.seconds.into_into_dart().into_dart(),
+            self.0.millis.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
 }
-impl support::IntoDartExceptPrimitive for AudioInfo {}
-impl rust2dart::IntoIntoDart<AudioInfo> for AudioInfo {
-    fn into_into_dart(self) -> Self {
-        self
+impl support::IntoDartExceptPrimitive for mirror_AudioDuration {}
+impl rust2dart::IntoIntoDart<mirror_AudioDuration> for AudioDuration {
+    fn into_into_dart(self) -> mirror_AudioDuration {
+        mirror_AudioDuration(self)
     }
 }
 
-impl support::IntoDart for FileType {
+impl support::IntoDart for mirror_AudioInfo {
     fn into_dart(self) -> support::DartAbi {
-        match self {
-            Self::Aac => 0,
-            Self::Aiff => 1,
-            Self::Ape => 2,
-            Self::Flac => 3,
-            Self::Mpeg => 4,
-            Self::Mp4 => 5,
-            Self::Mpc => 6,
-            Self::Opus => 7,
-            Self::Vorbis => 8,
-            Self::Speex => 9,
-            Self::Wav => 10,
-            Self::WavPack => 11,
-            Self::Other => 12,
+        vec![
+            self.0.duration_sec.into_dart(),
+            self.0.duration_ms.into_dart(),
+            self.0.duration.map(|v| mirror_AudioDuration(v)).into_dart(),
+            self.0.overall_bitrate.into_dart(),
+            self.0.audio_bitrate.into_dart(),
+            self.0.sample_rate.into_dart(),
+            self.0.bit_depth.into_dart(),
+            self.0.channels.into_dart(),
+            self.0.channel_mask.into_dart(),
+            self.0.frame_length.into_dart(),
+            self.0.seek_granularity_us.into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_AudioInfo {}
+impl rust2dart::IntoIntoDart<mirror_AudioInfo> for AudioInfo {
+    fn into_into_dart(self) -> mirror_AudioInfo {
+        mirror_AudioInfo(self)
+    }
+}
+
+impl support::IntoDart for mirror_Chapter {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.start_ms.into_dart(),
+            self.0.end_ms.into_dart(),
+            self.0.title.into_dart(),
+            self.0.url.into_dart(),
+            self.0.picture.map(|v| mirror_Picture(v)).into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_Chapter {}
+impl rust2dart::IntoIntoDart<mirror_Chapter> for Chapter {
+    fn into_into_dart(self) -> mirror_Chapter {
+        mirror_Chapter(self)
+    }
+}
+
+impl support::IntoDart for mirror_Comment {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.language.into_dart(),
+            self.0.description.into_dart(),
+            self.0.text.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_Comment {}
+impl rust2dart::IntoIntoDart<mirror_Comment> for Comment {
+    fn into_into_dart(self) -> mirror_Comment {
+        mirror_Comment(self)
+    }
+}
+
+impl support::IntoDart for mirror_CustomItem {
+    fn into_dart(self) -> support::DartAbi {
+        vec![
+            self.0.key.into_into_dart().into_dart(),
+            self.0.value.into_into_dart().into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_CustomItem {}
+impl rust2dart::IntoIntoDart<mirror_CustomItem> for CustomItem {
+    fn into_into_dart(self) -> mirror_CustomItem {
+        mirror_CustomItem(self)
+    }
+}
+
+impl support::IntoDart for mirror_FileType {
+    fn into_dart(self) -> support::DartAbi {
+        match self.0 {
+            FileType::Aac => 0,
+            FileType::Aiff => 1,
+            FileType::Ape => 2,
+            FileType::Flac => 3,
+            FileType::Mpeg => 4,
+            FileType::Mp4 => 5,
+            FileType::Mpc => 6,
+            FileType::Opus => 7,
+            FileType::Vorbis => 8,
+            FileType::Speex => 9,
+            FileType::Wav => 10,
+            FileType::WavPack => 11,
+            FileType::Other => 12,
         }
         .into_dart()
     }
 }
-impl support::IntoDartExceptPrimitive for FileType {}
-impl rust2dart::IntoIntoDart<FileType> for FileType {
-    fn into_into_dart(self) -> Self {
-        self
+impl support::IntoDartExceptPrimitive for mirror_FileType {}
+impl rust2dart::IntoIntoDart<mirror_FileType> for FileType {
+    fn into_into_dart(self) -> mirror_FileType {
+        mirror_FileType(self)
     }
 }
 
-impl support::IntoDart for MimeType {
+impl support::IntoDart for mirror_MimeType {
     fn into_dart(self) -> support::DartAbi {
-        match self {
-            Self::Png => 0,
-            Self::Jpeg => 1,
-            Self::Tiff => 2,
-            Self::Bmp => 3,
-            Self::Gif => 4,
-            Self::Unknown => 5,
-            Self::None => 6,
+        match self.0 {
+            MimeType::Png => 0,
+            MimeType::Jpeg => 1,
+            MimeType::Tiff => 2,
+            MimeType::Bmp => 3,
+            MimeType::Gif => 4,
+            MimeType::Unknown => 5,
+            MimeType::None => 6,
         }
         .into_dart()
     }
 }
-impl support::IntoDartExceptPrimitive for MimeType {}
-impl rust2dart::IntoIntoDart<MimeType> for MimeType {
-    fn into_into_dart(self) -> Self {
-        self
+impl support::IntoDartExceptPrimitive for mirror_MimeType {}
+impl rust2dart::IntoIntoDart<mirror_MimeType> for MimeType {
+    fn into_into_dart(self) -> mirror_MimeType {
+        mirror_MimeType(self)
     }
 }
 
-impl support::IntoDart for Picture {
+impl support::IntoDart for mirror_Picture {
     fn into_dart(self) -> support::DartAbi {
         vec![
-            self.pic_type.into_into_dart().into_dart(),
-            self.pic_data.into_into_dart().into_dart(),
-            self.mime_type.into_dart(),
-            self.width.into_dart(),
-            self.height.into_dart(),
-            self.color_depth.into_dart(),
-            self.num_colors.into_dart(),
+            self.0.pic_type.into_into_dart().into_dart(),
+            self.0.pic_data.into_into_dart().into_dart(),
+            self.0.mime_type.map(|v| mirror_MimeType(v)).into_dart(),
+            self.0.width.into_dart(),
+            self.0.height.into_dart(),
+            self.0.color_depth.into_dart(),
+            self.0.num_colors.into_dart(),
+            self.0.data_size.into_dart(),
+            self.0.description.into_dart(),
+            self.0.storage.map(|v| mirror_PictureStorage(v)).into_dart(),
         ]
         .into_dart()
     }
 }
-impl support::IntoDartExceptPrimitive for Picture {}
-impl rust2dart::IntoIntoDart<Picture> for Picture {
-    fn into_into_dart(self) -> Self {
-        self
+impl support::IntoDartExceptPrimitive for mirror_Picture {}
+impl rust2dart::IntoIntoDart<mirror_Picture> for Picture {
+    fn into_into_dart(self) -> mirror_Picture {
+        mirror_Picture(self)
     }
 }
 
-impl support::IntoDart for PictureType {
+impl support::IntoDart for mirror_PictureStorage {
     fn into_dart(self) -> support::DartAbi {
-        match self {
-            Self::Other => 0,
-            Self::Icon => 1,
-            Self::OtherIcon => 2,
-            Self::CoverFront => 3,
-            Self::CoverBack => 4,
-            Self::Leaflet => 5,
-            Self::Media => 6,
-            Self::LeadArtist => 7,
-            Self::Artist => 8,
-            Self::Conductor => 9,
-            Self::Band => 10,
-            Self::Composer => 11,
-            Self::Lyricist => 12,
-            Self::RecordingLocation => 13,
-            Self::DuringRecording => 14,
-            Self::DuringPerformance => 15,
-            Self::ScreenCapture => 16,
-            Self::BrightFish => 17,
-            Self::Illustration => 18,
-            Self::BandLogo => 19,
-            Self::PublisherLogo => 20,
-            Self::Undefined => 21,
+        match self.0 {
+            PictureStorage::Id3v2Frame => 0,
+            PictureStorage::FlacBlock => 1,
+            PictureStorage::VorbisComment => 2,
+            PictureStorage::Mp4Atom => 3,
+            PictureStorage::ApeItem => 4,
         }
         .into_dart()
     }
 }
-impl support::IntoDartExceptPrimitive for PictureType {}
-impl rust2dart::IntoIntoDart<PictureType> for PictureType {
-    fn into_into_dart(self) -> Self {
-        self
+impl support::IntoDartExceptPrimitive for mirror_PictureStorage {}
+impl rust2dart::IntoIntoDart<mirror_PictureStorage> for PictureStorage {
+    fn into_into_dart(self) -> mirror_PictureStorage {
+        mirror_PictureStorage(self)
     }
 }
 
-impl support::IntoDart for Tag {
+impl support::IntoDart for mirror_PictureType {
+    fn into_dart(self) -> support::DartAbi {
+        match self.0 {
+            PictureType::Other => 0,
+            PictureType::Icon => 1,
+            PictureType::OtherIcon => 2,
+            PictureType::CoverFront => 3,
+            PictureType::CoverBack => 4,
+            PictureType::Leaflet => 5,
+            PictureType::Media => 6,
+            PictureType::LeadArtist => 7,
+            PictureType::Artist => 8,
+            PictureType::Conductor => 9,
+            PictureType::Band => 10,
+            PictureType::Composer => 11,
+            PictureType::Lyricist => 12,
+            PictureType::RecordingLocation => 13,
+            PictureType::DuringRecording => 14,
+            PictureType::DuringPerformance => 15,
+            PictureType::ScreenCapture => 16,
+            PictureType::BrightFish => 17,
+            PictureType::Illustration => 18,
+            PictureType::BandLogo => 19,
+            PictureType::PublisherLogo => 20,
+            PictureType::Undefined => 21,
+        }
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_PictureType {}
+impl rust2dart::IntoIntoDart<mirror_PictureType> for PictureType {
+    fn into_into_dart(self) -> mirror_PictureType {
+        mirror_PictureType(self)
+    }
+}
+
+impl support::IntoDart for mirror_Tag {
     fn into_dart(self) -> support::DartAbi {
         vec![
-            self.tag_type.into_into_dart().into_dart(),
-            self.pictures.into_into_dart().into_dart(),
-            self.track_title.into_dart(),
-            self.track_artist.into_dart(),
-            self.album.into_dart(),
-            self.album_artist.into_dart(),
-            self.producer.into_dart(),
-            self.track_number.into_dart(),
-            self.track_total.into_dart(),
-            self.disc_number.into_dart(),
-            self.disc_total.into_dart(),
-            self.year.into_dart(),
-            self.recording_date.into_dart(),
-            self.original_release_date.into_dart(),
-            self.language.into_dart(),
-            self.lyrics.into_dart(),
-            self.genre.into_dart(),
+            self.0.tag_type.into_into_dart().into_dart(),
+            self.0.pictures.into_into_dart().into_dart(),
+            self.0.track_title.into_dart(),
+            self.0.track_artist.into_dart(),
+            self.0.album.into_dart(),
+            self.0.album_artist.into_dart(),
+            self.0.producer.into_dart(),
+            self.0.track_number.into_dart(),
+            self.0.track_total.into_dart(),
+            self.0.disc_number.into_dart(),
+            self.0.disc_total.into_dart(),
+            self.0.year.into_dart(),
+            self.0.recording_date.into_dart(),
+            self.0.original_release_date.into_dart(),
+            self.0.release_date.map(|v| mirror_TagDate(v)).into_dart(),
+            self.0.original_date.map(|v| mirror_TagDate(v)).into_dart(),
+            self.0.language.into_dart(),
+            self.0.lyrics.into_dart(),
+            self.0.genre.into_dart(),
+            self.0.isrc.into_dart(),
+            self.0.composer.into_dart(),
+            self.0.conductor.into_dart(),
+            self.0.remixer.into_dart(),
+            self.0.artist_sort.into_dart(),
+            self.0.album_sort.into_dart(),
+            self.0.title_sort.into_dart(),
+            self.0.album_artist_sort.into_dart(),
+            self.0.bpm.into_dart(),
+            self.0.initial_key.into_dart(),
+            self.0.mood.into_dart(),
+            self.0.rating.into_dart(),
+            self.0.lyrics_language.into_dart(),
+            self.0.lyrics_description.into_dart(),
+            self.0.musicbrainz_recording_id.into_dart(),
+            self.0.musicbrainz_release_id.into_dart(),
+            self.0.musicbrainz_release_group_id.into_dart(),
+            self.0.musicbrainz_artist_id.into_dart(),
+            self.0.musicbrainz_album_artist_id.into_dart(),
+            self.0.musicbrainz_track_id.into_dart(),
+            self.0.acoustid_id.into_dart(),
+            self.0.acoustid_fingerprint.into_dart(),
+            self.0.barcode.into_dart(),
+            self.0.catalog_number.into_dart(),
+            self.0.label.into_dart(),
+            self.0.copyright.into_dart(),
+            self.0.grouping.into_dart(),
+            self.0.subtitle.into_dart(),
+            self.0.work.into_dart(),
+            self.0.movement.into_dart(),
+            self.0.movement_number.into_dart(),
+            self.0.movement_total.into_dart(),
+            self.0.is_compilation.into_dart(),
+            self.0.is_podcast.into_dart(),
+            self.0.podcast_url.into_dart(),
+            self.0.episode_id.into_dart(),
+            self.0.narrator.into_dart(),
+            self.0.series.into_dart(),
+            self.0.series_part.into_dart(),
+            self.0.custom_items.into_into_dart().into_dart(),
+            self.0.artists.into_into_dart().into_dart(),
+            self.0.genres.into_into_dart().into_dart(),
+            self.0.composers.into_into_dart().into_dart(),
+            self.0.comments.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
 }
-impl support::IntoDartExceptPrimitive for Tag {}
-impl rust2dart::IntoIntoDart<Tag> for Tag {
-    fn into_into_dart(self) -> Self {
-        self
+impl support::IntoDartExceptPrimitive for mirror_Tag {}
+impl rust2dart::IntoIntoDart<mirror_Tag> for Tag {
+    fn into_into_dart(self) -> mirror_Tag {
+        mirror_Tag(self)
     }
 }
 
-impl support::IntoDart for TagType {
+impl support::IntoDart for mirror_TagDate {
     fn into_dart(self) -> support::DartAbi {
-        match self {
-            Self::Ape => 0,
-            Self::Id3v1 => 1,
-            Self::Id3v2 => 2,
-            Self::Mp4Ilst => 3,
-            Self::VorbisComments => 4,
-            Self::RiffInfo => 5,
-            Self::AiffText => 6,
-            Self::FilePrimaryType => 7,
-            Self::Other => 8,
+        vec![
+            self.0.year.into_into_dart().into_dart(),
+            self.0.month.into_dart(),
+            self.0.day.into_dart(),
+        ]
+        .into_dart()
+    }
+}
+impl support::IntoDartExceptPrimitive for mirror_TagDate {}
+impl rust2dart::IntoIntoDart<mirror_TagDate> for TagDate {
+    fn into_into_dart(self) -> mirror_TagDate {
+        mirror_TagDate(self)
+    }
+}
+
+impl support::IntoDart for mirror_TagType {
+    fn into_dart(self) -> support::DartAbi {
+        match self.0 {
+            TagType::Ape => 0,
+            TagType::Id3v1 => 1,
+            TagType::Id3v2 => 2,
+            TagType::Mp4Ilst => 3,
+            TagType::VorbisComments => 4,
+            TagType::RiffInfo => 5,
+            TagType::AiffText => 6,
+            TagType::FilePrimaryType => 7,
+            TagType::Other => 8,
         }
         .into_dart()
     }
 }
-impl support::IntoDartExceptPrimitive for TagType {}
-impl rust2dart::IntoIntoDart<TagType> for TagType {
-    fn into_into_dart(self) -> Self {
-        self
+impl support::IntoDartExceptPrimitive for mirror_TagType {}
+impl rust2dart::IntoIntoDart<mirror_TagType> for TagType {
+    fn into_into_dart(self) -> mirror_TagType {
+        mirror_TagType(self)
     }
 }
 
-impl support::IntoDart for TaggyFile {
+impl support::IntoDart for mirror_TaggyFile {
     fn into_dart(self) -> support::DartAbi {
         vec![
-            self.file_type.into_dart(),
-            self.size.into_dart(),
-            self.audio.into_into_dart().into_dart(),
-            self.tags.into_into_dart().into_dart(),
-            self.primary_tag_type.into_into_dart().into_dart(),
+            self.0.file_type.map(|v| mirror_FileType(v)).into_dart(),
+            self.0.size.into_dart(),
+            self.0.audio.into_into_dart().into_dart(),
+            self.0.tags.into_into_dart().into_dart(),
+            self.0.primary_tag_type.into_into_dart().into_dart(),
+            self.0.canonical_path.into_dart(),
+            self.0.created_at_ms.into_dart(),
+            self.0.modified_at_ms.into_dart(),
+            self.0.date_added_ms.into_dart(),
+            self.0.chapters.into_into_dart().into_dart(),
+            self.0.warnings.into_into_dart().into_dart(),
         ]
         .into_dart()
     }
 }
-impl support::IntoDartExceptPrimitive for TaggyFile {}
-impl rust2dart::IntoIntoDart<TaggyFile> for TaggyFile {
-    fn into_into_dart(self) -> Self {
-        self
+impl support::IntoDartExceptPrimitive for mirror_TaggyFile {}
+impl rust2dart::IntoIntoDart<mirror_TaggyFile> for TaggyFile {
+    fn into_into_dart(self) -> mirror_TaggyFile {
+        mirror_TaggyFile(self)
     }
 }
 
@@ -513,6 +933,67 @@ mod web {
         }
     }
 
+    impl Wire2Api<Vec<String>> for JsValue {
+        fn wire2api(self) -> Vec<String> {
+            self.dyn_into::<JsArray>()
+                .unwrap()
+                .iter()
+                .map(Wire2Api::wire2api)
+                .collect()
+        }
+    }
+
+    impl Wire2Api<Comment> for JsValue {
+        fn wire2api(self) -> Comment {
+            let self_ = self.dyn_into::<JsArray>().unwrap();
+            assert_eq!(
+                self_.length(),
+                3,
+                "Expected 3 elements, got {}",
+                self_.length()
+            );
+            Comment {
+                language: self_.get(0).wire2api(),
+                description: self_.get(1).wire2api(),
+                text: self_.get(2).wire2api(),
+            }
+        }
+    }
+
+    impl Wire2Api<CustomItem> for JsValue {
+        fn wire2api(self) -> CustomItem {
+            let self_ = self.dyn_into::<JsArray>().unwrap();
+            assert_eq!(
+                self_.length(),
+                2,
+                "Expected 2 elements, got {}",
+                self_.length()
+            );
+            CustomItem {
+                key: self_.get(0).wire2api(),
+                value: self_.get(1).wire2api(),
+            }
+        }
+    }
+
+    impl Wire2Api<Vec<Comment>> for JsValue {
+        fn wire2api(self) -> Vec<Comment> {
+            self.dyn_into::<JsArray>()
+                .unwrap()
+                .iter()
+                .map(Wire2Api::wire2api)
+                .collect()
+        }
+    }
+    impl Wire2Api<Vec<CustomItem>> for JsValue {
+        fn wire2api(self) -> Vec<CustomItem> {
+            self.dyn_into::<JsArray>()
+                .unwrap()
+                .iter()
+                .map(Wire2Api::wire2api)
+                .collect()
+        }
+    }
     impl Wire2Api<Vec<Picture>> for JsValue {
         fn wire2api(self) -> Vec<Picture> {
             self.dyn_into::<JsArray>()
@@ -543,8 +1024,8 @@ mod web {
             let self_ = self.dyn_into::<JsArray>().unwrap();
             assert_eq!(
                 self_.length(),
-                7,
-                "Expected 7 elements, got {}",
+                10,
+                "Expected 10 elements, got {}",
                 self_.length()
             );
             Picture {
@@ -555,6 +1036,9 @@ mod web {
                 height: self_.get(4).wire2api(),
                 color_depth: self_.get(5).wire2api(),
                 num_colors: self_.get(6).wire2api(),
+                data_size: self_.get(7).wire2api(),
+                description: self_.get(8).wire2api(),
+                storage: self_.get(9).wire2api(),
             }
         }
     }
@@ -564,8 +1048,8 @@ mod web {
             let self_ = self.dyn_into::<JsArray>().unwrap();
             assert_eq!(
                 self_.length(),
-                17,
-                "Expected 17 elements, got {}",
+                63,
+                "Expected 63 elements, got {}",
                 self_.length()
             );
             Tag {
@@ -583,9 +1067,71 @@ mod web {
                 year: self_.get(11).wire2api(),
                 recording_date: self_.get(12).wire2api(),
                 original_release_date: self_.get(13).wire2api(),
-                language: self_.get(14).wire2api(),
-                lyrics: self_.get(15).wire2api(),
-                genre: self_.get(16).wire2api(),
+                release_date: self_.get(14).wire2api(),
+                original_date: self_.get(15).wire2api(),
+                language: self_.get(16).wire2api(),
+                lyrics: self_.get(17).wire2api(),
+                genre: self_.get(18).wire2api(),
+                isrc: self_.get(19).wire2api(),
+                composer: self_.get(20).wire2api(),
+                conductor: self_.get(21).wire2api(),
+                remixer: self_.get(22).wire2api(),
+                artist_sort: self_.get(23).wire2api(),
+                album_sort: self_.get(24).wire2api(),
+                title_sort: self_.get(25).wire2api(),
+                album_artist_sort: self_.get(26).wire2api(),
+                bpm: self_.get(27).wire2api(),
+                initial_key: self_.get(28).wire2api(),
+                mood: self_.get(29).wire2api(),
+                rating: self_.get(30).wire2api(),
+                lyrics_language: self_.get(31).wire2api(),
+                lyrics_description: self_.get(32).wire2api(),
+                musicbrainz_recording_id: self_.get(33).wire2api(),
+                musicbrainz_release_id: self_.get(34).wire2api(),
+                musicbrainz_release_group_id: self_.get(35).wire2api(),
+                musicbrainz_artist_id: self_.get(36).wire2api(),
+                musicbrainz_album_artist_id: self_.get(37).wire2api(),
+                musicbrainz_track_id: self_.get(38).wire2api(),
+                acoustid_id: self_.get(39).wire2api(),
+                acoustid_fingerprint: self_.get(40).wire2api(),
+                barcode: self_.get(41).wire2api(),
+                catalog_number: self_.get(42).wire2api(),
+                label: self_.get(43).wire2api(),
+                copyright: self_.get(44).wire2api(),
+                grouping: self_.get(45).wire2api(),
+                subtitle: self_.get(46).wire2api(),
+                work: self_.get(47).wire2api(),
+                movement: self_.get(48).wire2api(),
+                movement_number: self_.get(49).wire2api(),
+                movement_total: self_.get(50).wire2api(),
+                is_compilation: self_.get(51).wire2api(),
+                is_podcast: self_.get(52).wire2api(),
+                podcast_url: self_.get(53).wire2api(),
+                episode_id: self_.get(54).wire2api(),
+                narrator: self_.get(55).wire2api(),
+                series: self_.get(56).wire2api(),
+                series_part: self_.get(57).wire2api(),
+                custom_items: self_.get(58).wire2api(),
+                artists: self_.get(59).wire2api(),
+                genres: self_.get(60).wire2api(),
+                composers: self_.get(61).wire2api(),
+                comments: self_.get(62).wire2api(),
+            }
+        }
+    }
+    impl Wire2Api<TagDate> for JsValue {
+        fn wire2api(self) -> TagDate {
+            let self_ = self.dyn_into::<JsArray>().unwrap();
+            assert_eq!(
+                self_.length(),
+                3,
+                "Expected 3 elements, got {}",
+                self_.length()
+            );
+            TagDate {
+                year: self_.get(0).wire2api(),
+                month: self_.get(1).wire2api(),
+                day: self_.get(2).wire2api(),
             }
         }
     }
@@ -622,16 +1168,36 @@ mod web {
             (!self.is_undefined() && !self.is_null()).then(|| self.wire2api())
         }
     }
+    impl Wire2Api<Option<bool>> for JsValue {
+        fn wire2api(self) -> Option<bool> {
+            (!self.is_undefined() && !self.is_null()).then(|| self.wire2api())
+        }
+    }
     impl Wire2Api<Option<MimeType>> for JsValue {
         fn wire2api(self) -> Option<MimeType> {
             (!self.is_undefined() && !self.is_null()).then(|| self.wire2api())
         }
     }
+    impl Wire2Api<Option<PictureStorage>> for JsValue {
+        fn wire2api(self) -> Option<PictureStorage> {
+            (!self.is_undefined() && !self.is_null()).then(|| self.wire2api())
+        }
+    }
+    impl Wire2Api<Option<TagDate>> for JsValue {
+        fn wire2api(self) -> Option<TagDate> {
+            (!self.is_undefined() && !self.is_null()).then(|| self.wire2api())
+        }
+    }
     impl Wire2Api<Option<u32>> for JsValue {
         fn wire2api(self) -> Option<u32> {
             (!self.is_undefined() && !self.is_null()).then(|| self.wire2api())
         }
     }
+    impl Wire2Api<PictureStorage> for JsValue {
+        fn wire2api(self) -> PictureStorage {
+            (self.unchecked_into_f64() as i32).wire2api()
+        }
+    }
     impl Wire2Api<PictureType> for JsValue {
         fn wire2api(self) -> PictureType {
             (self.unchecked_into_f64() as i32).wire2api()
@@ -713,21 +1279,63 @@ mod io {
 
     // Section: allocate functions
 
+    #[no_mangle]
+    pub extern "C" fn new_StringList_0(len: i32) -> *mut wire_StringList {
+        let wrap = wire_StringList {
+            ptr: support::new_leak_vec_ptr(<*mut wire_uint_8_list>::new_with_null_ptr(), len),
+            len,
+        };
+        support::new_leak_box_ptr(wrap)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn new_box_autoadd_bool_0(value: bool) -> *mut bool {
+        support::new_leak_box_ptr(value)
+    }
+
     #[no_mangle]
     pub extern "C" fn new_box_autoadd_mime_type_0(value: i32) -> *mut i32 {
         support::new_leak_box_ptr(value)
     }
 
+    #[no_mangle]
+    pub extern "C" fn new_box_autoadd_picture_storage_0(value: i32) -> *mut i32 {
+        support::new_leak_box_ptr(value)
+    }
+
     #[no_mangle]
     pub extern "C" fn new_box_autoadd_tag_0() -> *mut wire_Tag {
         support::new_leak_box_ptr(wire_Tag::new_with_null_ptr())
     }
 
+    #[no_mangle]
+    pub extern "C" fn new_box_autoadd_tag_date_0() -> *mut wire_TagDate {
+        support::new_leak_box_ptr(wire_TagDate::new_with_null_ptr())
+    }
+
     #[no_mangle]
     pub extern "C" fn new_box_autoadd_u32_0(value: u32) -> *mut u32 {
         support::new_leak_box_ptr(value)
     }
 
+    #[no_mangle]
+    pub extern "C" fn new_list_comment_0(len: i32) -> *mut wire_list_comment {
+        let wrap = wire_list_comment {
+            ptr: support::new_leak_vec_ptr(<wire_Comment>::new_with_null_ptr(), len),
+            len,
+        };
+        support::new_leak_box_ptr(wrap)
+    }
+
+    #[no_mangle]
+    pub extern "C" fn new_list_custom_item_0(len: i32) -> *mut wire_list_custom_item {
+        let wrap = wire_list_custom_item {
+            ptr: support::new_leak_vec_ptr(<wire_CustomItem>::new_with_null_ptr(), len),
+            len,
+        };
+        support::new_leak_box_ptr(wrap)
+    }
+
     #[no_mangle]
     pub extern "C" fn new_list_picture_0(len: i32) -> *mut wire_list_picture {
         let wrap = wire_list_picture {
@@ -766,24 +1374,69 @@ mod io {
         }
     }
 
+    impl Wire2Api<Vec<String>> for *mut wire_StringList {
+        fn wire2api(self) -> Vec<String> {
+            let vec = unsafe {
+                let wrap = support::box_from_leak_ptr(self);
+                support::vec_from_leak_ptr(wrap.ptr, wrap.len)
+            };
+            vec.into_iter().map(Wire2Api::wire2api).collect()
+        }
+    }
+
+    impl Wire2Api<bool> for *mut bool {
+        fn wire2api(self) -> bool {
+            unsafe { *support::box_from_leak_ptr(self) }
+        }
+    }
     impl Wire2Api<MimeType> for *mut i32 {
         fn wire2api(self) -> MimeType {
             let wrap = unsafe { support::box_from_leak_ptr(self) };
             Wire2Api::<MimeType>::wire2api(*wrap).into()
         }
     }
+    impl Wire2Api<PictureStorage> for *mut i32 {
+        fn wire2api(self) -> PictureStorage {
+            let wrap = unsafe { support::box_from_leak_ptr(self) };
+            Wire2Api::<PictureStorage>::wire2api(*wrap).into()
+        }
+    }
     impl Wire2Api<Tag> for *mut wire_Tag {
         fn wire2api(self) -> Tag {
             let wrap = unsafe { support::box_from_leak_ptr(self) };
             Wire2Api::<Tag>::wire2api(*wrap).into()
         }
     }
+    impl Wire2Api<TagDate> for *mut wire_TagDate {
+        fn wire2api(self) -> TagDate {
+            let wrap = unsafe { support::box_from_leak_ptr(self) };
+            Wire2Api::<TagDate>::wire2api(*wrap).into()
+        }
+    }
     impl Wire2Api<u32> for *mut u32 {
         fn wire2api(self) -> u32 {
             unsafe { *support::box_from_leak_ptr(self) }
         }
     }
 
+    impl Wire2Api<Vec<Comment>> for *mut wire_list_comment {
+        fn wire2api(self) -> Vec<Comment> {
+            let vec = unsafe {
+                let wrap = support::box_from_leak_ptr(self);
+                support::vec_from_leak_ptr(wrap.ptr, wrap.len)
+            };
+            vec.into_iter().map(Wire2Api::wire2api).collect()
+        }
+    }
+    impl Wire2Api<Vec<CustomItem>> for *mut wire_list_custom_item {
+        fn wire2api(self) -> Vec<CustomItem> {
+            let vec = unsafe {
+                let wrap = support::box_from_leak_ptr(self);
+                support::vec_from_leak_ptr(wrap.ptr, wrap.len)
+            };
+            vec.into_iter().map(Wire2Api::wire2api).collect()
+        }
+    }
     impl Wire2Api<Vec<Picture>> for *mut wire_list_picture {
         fn wire2api(self) -> Vec<Picture> {
             let vec = unsafe {
@@ -803,6 +1456,25 @@ mod io {
         }
     }
 
+    impl Wire2Api<Comment> for wire_Comment {
+        fn wire2api(self) -> Comment {
+            Comment {
+                language: self.language.wire2api(),
+                description: self.description.wire2api(),
+                text: self.text.wire2api(),
+            }
+        }
+    }
+
+    impl Wire2Api<CustomItem> for wire_CustomItem {
+        fn wire2api(self) -> CustomItem {
+            CustomItem {
+                key: self.key.wire2api(),
+                value: self.value.wire2api(),
+            }
+        }
+    }
+
     impl Wire2Api<Picture> for wire_Picture {
         fn wire2api(self) -> Picture {
             Picture {
@@ -813,6 +1485,9 @@ mod io {
                 height: self.height.wire2api(),
                 color_depth: self.color_depth.wire2api(),
                 num_colors: self.num_colors.wire2api(),
+                data_size: self.data_size.wire2api(),
+                description: self.description.wire2api(),
+                storage: self.storage.wire2api(),
             }
         }
     }
@@ -834,9 +1509,64 @@ mod io {
                 year: self.year.wire2api(),
                 recording_date: self.recording_date.wire2api(),
                 original_release_date: self.original_release_date.wire2api(),
+                release_date: self.release_date.wire2api(),
+                original_date: self.original_date.wire2api(),
                 language: self.language.wire2api(),
                 lyrics: self.lyrics.wire2api(),
                 genre: self.genre.wire2api(),
+                isrc: self.isrc.wire2api(),
+                composer: self.composer.wire2api(),
+                conductor: self.conductor.wire2api(),
+                remixer: self.remixer.wire2api(),
+                artist_sort: self.artist_sort.wire2api(),
+                album_sort: self.album_sort.wire2api(),
+                title_sort: self.title_sort.wire2api(),
+                album_artist_sort: self.album_artist_sort.wire2api(),
+                bpm: self.bpm.wire2api(),
+                initial_key: self.initial_key.wire2api(),
+                mood: self.mood.wire2api(),
+                rating: self.rating.wire2api(),
+                lyrics_language: self.lyrics_language.wire2api(),
+                lyrics_description: self.lyrics_description.wire2api(),
+                musicbrainz_recording_id: self.musicbrainz_recording_id.wire2api(),
+                musicbrainz_release_id: self.musicbrainz_release_id.wire2api(),
+                musicbrainz_release_group_id: self.musicbrainz_release_group_id.wire2api(),
+                musicbrainz_artist_id: self.musicbrainz_artist_id.wire2api(),
+                musicbrainz_album_artist_id: self.musicbrainz_album_artist_id.wire2api(),
+                musicbrainz_track_id: self.musicbrainz_track_id.wire2api(),
+                acoustid_id: self.acoustid_id.wire2api(),
+                acoustid_fingerprint: self.acoustid_fingerprint.wire2api(),
+                barcode: self.barcode.wire2api(),
+                catalog_number: self.catalog_number.wire2api(),
+                label: self.label.wire2api(),
+                copyright: self.copyright.wire2api(),
+                grouping: self.grouping.wire2api(),
+                subtitle: self.subtitle.wire2api(),
+                work: self.work.wire2api(),
+                movement: self.movement.wire2api(),
+                movement_number: self.movement_number.wire2api(),
+                movement_total: self.movement_total.wire2api(),
+                is_compilation: self.is_compilation.wire2api(),
+                is_podcast: self.is_podcast.wire2api(),
+                podcast_url: self.podcast_url.wire2api(),
+                episode_id: self.episode_id.wire2api(),
+                narrator: self.narrator.wire2api(),
+                series: self.series.wire2api(),
+                series_part: self.series_part.wire2api(),
+                custom_items: self.custom_items.wire2api(),
+                artists: self.artists.wire2api(),
+                genres: self.genres.wire2api(),
+                composers: self.composers.wire2api(),
+                comments: self.comments.wire2api(),
+            }
+        }
+    }
+    impl Wire2Api<TagDate> for wire_TagDate {
+        fn wire2api(self) -> TagDate {
+            TagDate {
+                year: self.year.wire2api(),
+                month: self.month.wire2api(),
+                day: self.day.wire2api(),
             }
         }
     }
@@ -851,6 +1581,27 @@ mod io {
     }
     // Section: wire structs
 
+    #[repr(C)]
+    #[derive(Clone)]
+    pub struct wire_StringList {
+        ptr: *mut *mut wire_uint_8_list,
+        len: i32,
+    }
+
+    #[repr(C)]
+    #[derive(Clone)]
+    pub struct wire_list_comment {
+        ptr: *mut wire_Comment,
+        len: i32,
+    }
+
+    #[repr(C)]
+    #[derive(Clone)]
+    pub struct wire_list_custom_item {
+        ptr: *mut wire_CustomItem,
+        len: i32,
+    }
+
     #[repr(C)]
     #[derive(Clone)]
     pub struct wire_list_picture {
@@ -865,6 +1616,21 @@ mod io {
         len: i32,
     }
 
+    #[repr(C)]
+    #[derive(Clone)]
+    pub struct wire_Comment {
+        language: *mut wire_uint_8_list,
+        description: *mut wire_uint_8_list,
+        text: *mut wire_uint_8_list,
+    }
+
+    #[repr(C)]
+    #[derive(Clone)]
+    pub struct wire_CustomItem {
+        key: *mut wire_uint_8_list,
+        value: *mut wire_uint_8_list,
+    }
+
     #[repr(C)]
     #[derive(Clone)]
     pub struct wire_Picture {
@@ -875,6 +1641,9 @@ mod io {
         height: *mut u32,
         color_depth: *mut u32,
         num_colors: *mut u32,
+        data_size: *mut u32,
+        description: *mut wire_uint_8_list,
+        storage: *mut i32,
     }
 
     #[repr(C)]
@@ -894,9 +1663,63 @@ mod io {
         year: *mut u32,
         recording_date: *mut wire_uint_8_list,
         original_release_date: *mut wire_uint_8_list,
+        release_date: *mut wire_TagDate,
+        original_date: *mut wire_TagDate,
         language: *mut wire_uint_8_list,
         lyrics: *mut wire_uint_8_list,
         genre: *mut wire_uint_8_list,
+        isrc: *mut wire_uint_8_list,
+        composer: *mut wire_uint_8_list,
+        conductor: *mut wire_uint_8_list,
+        remixer: *mut wire_uint_8_list,
+        artist_sort: *mut wire_uint_8_list,
+        album_sort: *mut wire_uint_8_list,
+        title_sort: *mut wire_uint_8_list,
+        album_artist_sort: *mut wire_uint_8_list,
+        bpm: *mut u32,
+        initial_key: *mut wire_uint_8_list,
+        mood: *mut wire_uint_8_list,
+        rating: *mut u32,
+        lyrics_language: *mut wire_uint_8_list,
+        lyrics_description: *mut wire_uint_8_list,
+        musicbrainz_recording_id: *mut wire_uint_8_list,
+        musicbrainz_release_id: *mut wire_uint_8_list,
+        musicbrainz_release_group_id: *mut wire_uint_8_list,
+        musicbrainz_artist_id: *mut wire_uint_8_list,
+        musicbrainz_album_artist_id: *mut wire_uint_8_list,
+        musicbrainz_track_id: *mut wire_uint_8_list,
+        acoustid_id: *mut wire_uint_8_list,
+        acoustid_fingerprint: *mut wire_uint_8_list,
+        barcode: *mut wire_uint_8_list,
+        catalog_number: *mut wire_uint_8_list,
+        label: *mut wire_uint_8_list,
+        copyright: *mut wire_uint_8_list,
+        grouping: *mut wire_uint_8_list,
+        subtitle: *mut wire_uint_8_list,
+        work: *mut wire_uint_8_list,
+        movement: *mut wire_uint_8_list,
+        movement_number: *mut u32,
+        movement_total: *mut u32,
+        is_compilation: *mut bool,
+        is_podcast: *mut bool,
+        podcast_url: *mut wire_uint_8_list,
+        episode_id: *mut wire_uint_8_list,
+        narrator: *mut wire_uint_8_list,
+        series: *mut wire_uint_8_list,
+        series_part: *mut wire_uint_8_list,
+        custom_items: *mut wire_list_custom_item,
+        artists: *mut wire_StringList,
+        genres: *mut wire_StringList,
+        composers: *mut wire_StringList,
+        comments: *mut wire_list_comment,
+    }
+
+    #[repr(C)]
+    #[derive(Clone)]
+    pub struct wire_TagDate {
+        year: u32,
+        month: *mut u32,
+        day: *mut u32,
     }
 
     #[repr(C)]
@@ -918,6 +1741,37 @@ mod io {
         }
     }
 
+    impl NewWithNullPtr for wire_Comment {
+        fn new_with_null_ptr() -> Self {
+            Self {
+                language: core::ptr::null_mut(),
+                description: core::ptr::null_mut(),
+                text: core::ptr::null_mut(),
+            }
+        }
+    }
+
+    impl Default for wire_Comment {
+        fn default() -> Self {
+            Self::new_with_null_ptr()
+        }
+    }
+
+    impl NewWithNullPtr for wire_CustomItem {
+        fn new_with_null_ptr() -> Self {
+            Self {
+                key: core::ptr::null_mut(),
+                value: core::ptr::null_mut(),
+            }
+        }
+    }
+
+    impl Default for wire_CustomItem {
+        fn default() -> Self {
+            Self::new_with_null_ptr()
+        }
+    }
+
     impl NewWithNullPtr for wire_Picture {
         fn new_with_null_ptr() -> Self {
             Self {
@@ -928,6 +1782,9 @@ mod io {
                 height: core::ptr::null_mut(),
                 color_depth: core::ptr::null_mut(),
                 num_colors: core::ptr::null_mut(),
+                data_size: core::ptr::null_mut(),
+                description: core::ptr::null_mut(),
+                storage: core::ptr::null_mut(),
             }
         }
     }
@@ -955,9 +1812,55 @@ mod io {
                 year: core::ptr::null_mut(),
                 recording_date: core::ptr::null_mut(),
                 original_release_date: core::ptr::null_mut(),
+                release_date: core::ptr::null_mut(),
+                original_date: core::ptr::null_mut(),
                 language: core::ptr::null_mut(),
                 lyrics: core::ptr::null_mut(),
                 genre: core::ptr::null_mut(),
+                isrc: core::ptr::null_mut(),
+                composer: core::ptr::null_mut(),
+                conductor: core::ptr::null_mut(),
+                remixer: core::ptr::null_mut(),
+                artist_sort: core::ptr::null_mut(),
+                album_sort: core::ptr::null_mut(),
+                title_sort: core::ptr::null_mut(),
+                album_artist_sort: core::ptr::null_mut(),
+                bpm: core::ptr::null_mut(),
+                initial_key: core::ptr::null_mut(),
+                mood: core::ptr::null_mut(),
+                rating: core::ptr::null_mut(),
+                lyrics_language: core::ptr::null_mut(),
+                lyrics_description: core::ptr::null_mut(),
+                musicbrainz_recording_id: core::ptr::null_mut(),
+                musicbrainz_release_id: core::ptr::null_mut(),
+                musicbrainz_release_group_id: core::ptr::null_mut(),
+                musicbrainz_artist_id: core::ptr::null_mut(),
+                musicbrainz_album_artist_id: core::ptr::null_mut(),
+                musicbrainz_track_id: core::ptr::null_mut(),
+                acoustid_id: core::ptr::null_mut(),
+                acoustid_fingerprint: core::ptr::null_mut(),
+                barcode: core::ptr::null_mut(),
+                catalog_number: core::ptr::null_mut(),
+                label: core::ptr::null_mut(),
+                copyright: core::ptr::null_mut(),
+                grouping: core::ptr::null_mut(),
+                subtitle: core::ptr::null_mut(),
+                work: core::ptr::null_mut(),
+                movement: core::ptr::null_mut(),
+                movement_number: core::ptr::null_mut(),
+                movement_total: core::ptr::null_mut(),
+                is_compilation: core::ptr::null_mut(),
+                is_podcast: core::ptr::null_mut(),
+                podcast_url: core::ptr::null_mut(),
+                episode_id: core::ptr::null_mut(),
+                narrator: core::ptr::null_mut(),
+                series: core::ptr::null_mut(),
+                series_part: core::ptr::null_mut(),
+                custom_items: core::ptr::null_mut(),
+                artists: core::ptr::null_mut(),
+                genres: core::ptr::null_mut(),
+                composers: core::ptr::null_mut(),
+                comments: core::ptr::null_mut(),
             }
         }
     }
@@ -968,6 +1871,22 @@ mod io {
         }
     }
 
+    impl NewWithNullPtr for wire_TagDate {
+        fn new_with_null_ptr() -> Self {
+            Self {
+                year: Default::default(),
+                month: core::ptr::null_mut(),
+                day: core::ptr::null_mut(),
+            }
+        }
+    }
+
+    impl Default for wire_TagDate {
+        fn default() -> Self {
+            Self::new_with_null_ptr()
+        }
+    }
+
     // Section: sync execution mode utility
 
     #[no_mangle]
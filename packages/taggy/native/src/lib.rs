@@ -1,15 +1,3 @@
 #[allow(dead_code)]
 mod api;
-#[allow(dead_code)]
-mod audio_info;
 mod bridge_generated;
-#[allow(dead_code)]
-mod builders;
-#[allow(dead_code)]
-mod picture;
-#[allow(dead_code)]
-mod tag;
-#[allow(dead_code)]
-mod taggy_file;
-#[allow(dead_code)]
-mod utils;
@@ -1,3 +0,0 @@
-pub(crate) mod file_utils;
-pub(crate) mod lofty_froms;
-pub(crate) mod lofty_intos;
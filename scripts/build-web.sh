@@ -0,0 +1,26 @@
+#!/bin/bash
+
+# Setup
+BUILD_DIR=platform-build
+mkdir $BUILD_DIR
+cd $BUILD_DIR || exit
+
+# Install build dependencies
+rustup toolchain install nightly
+rustup +nightly target add wasm32-unknown-unknown
+rustup +nightly component add rust-src
+cargo install wasm-bindgen-cli
+
+# Build the wasm module, flutter_rust_bridge requires shared memory support for its web workers.
+RUSTFLAGS="-C target-feature=+atomics,+bulk-memory,+mutable-globals" \
+  cargo +nightly build -p taggy --features wasm \
+  --target wasm32-unknown-unknown -r -Z build-std=std,panic_abort
+mkdir web
+wasm-bindgen ../target/wasm32-unknown-unknown/release/taggy.wasm \
+  --out-dir web --no-typescript --target no-modules
+
+# Archive the wasm module
+tar -czvf web.tar.gz web
+
+# Cleanup
+rm -rf web
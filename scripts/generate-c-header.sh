@@ -0,0 +1,7 @@
+#!/bin/bash
+
+# Regenerates the C header of the `capi` feature.
+cargo install cbindgen
+
+cd packages/taggy/core || exit
+cbindgen --config cbindgen.toml --output include/taggy.h src/capi.rs
@@ -0,0 +1,15 @@
+#!/bin/bash
+
+# Generates the Kotlin and Swift bindings of the `uniffi` feature.
+OUT_DIR=platform-build/uniffi
+mkdir -p $OUT_DIR
+
+cargo build -p taggy-core --features uniffi -r
+for LANGUAGE in kotlin swift
+do
+    cargo run -p taggy-core --features uniffi --bin uniffi-bindgen -- generate \
+        --library target/release/libtaggy_core.so \
+        --config packages/taggy/core/uniffi.toml \
+        --language $LANGUAGE \
+        --out-dir $OUT_DIR/$LANGUAGE
+done